@@ -4,18 +4,28 @@
 use std::sync::Arc;
 use std::{marker::PhantomData, time::Duration};
 
-use super::inner::ClientInner;
-#[cfg(any(feature = "grpc", feature = "rest"))]
-use crate::transport::AnyTransport;
+use super::{cache::DecisionCache, inner::ClientInner};
+#[cfg(feature = "tracing")]
+use crate::tracing_support::{Metrics, MetricsConfig, TracePropagation};
 #[cfg(feature = "grpc")]
 use crate::transport::GrpcTransport;
 #[cfg(feature = "rest")]
 use crate::transport::RestTransport;
+#[cfg(all(feature = "tracing", any(feature = "grpc", feature = "rest")))]
+use crate::transport::metered::MeteredTransport;
+#[cfg(any(feature = "grpc", feature = "rest"))]
+use crate::transport::{
+    AnyTransport, CassetteMode, balancer::BalancedTransport, cassette::CassetteTransport,
+    faults::FaultTransport,
+};
 use crate::{
     Client, Error,
     auth::Credentials,
-    config::{CacheConfig, DegradationConfig, RetryConfig, TlsConfig},
-    transport::{PoolConfig, TransportStrategy},
+    config::{
+        CacheConfig, DegradationConfig, LoadBalancingStrategy, OutlierDetectionConfig, RetryConfig,
+        TlsConfig,
+    },
+    transport::{Cassette, FaultInjector, PoolConfig, TransportStrategy},
 };
 
 /// Marker type: URL not yet provided.
@@ -48,7 +58,7 @@ pub struct HasCredentials;
 ///
 /// # Required Configuration
 ///
-/// - `url()`: The InferaDB API endpoint
+/// - `url()` or `endpoints()`: The InferaDB API endpoint(s)
 /// - `credentials()`: Authentication credentials
 ///
 /// # Optional Configuration
@@ -58,6 +68,12 @@ pub struct HasCredentials;
 /// - `tls_config()`: Custom TLS settings
 /// - `degradation_config()`: Graceful degradation behavior
 /// - `timeout()`: Request timeout
+/// - `load_balancing()`: Endpoint selection when multiple endpoints are set
+/// - `outlier_detection()`: Ejection of failing endpoints
+/// - `cassette()`: Record engine calls to a file, or replay them
+/// - `fault_injection()`: Injected latency and failures for chaos testing
+/// - `trace_propagation()`: Trace context wire format (`tracing` feature)
+/// - `metrics()`: Request metrics collection (`tracing` feature)
 ///
 /// # Example
 ///
@@ -86,6 +102,15 @@ pub struct ClientBuilder<UrlState, CredentialsState> {
     timeout: Option<Duration>,
     transport_strategy: TransportStrategy,
     pool_config: PoolConfig,
+    endpoints: Vec<String>,
+    load_balancing: LoadBalancingStrategy,
+    outlier_detection: OutlierDetectionConfig,
+    cassette: Option<Cassette>,
+    fault_injection: Option<FaultInjector>,
+    #[cfg(feature = "tracing")]
+    trace_propagation: TracePropagation,
+    #[cfg(feature = "tracing")]
+    metrics: Option<Metrics>,
     _url_state: PhantomData<UrlState>,
     _credentials_state: PhantomData<CredentialsState>,
 }
@@ -103,6 +128,15 @@ impl ClientBuilder<NoUrl, NoCredentials> {
             timeout: None,
             transport_strategy: TransportStrategy::default(),
             pool_config: PoolConfig::default(),
+            endpoints: Vec::new(),
+            load_balancing: LoadBalancingStrategy::default(),
+            outlier_detection: OutlierDetectionConfig::default(),
+            cassette: None,
+            fault_injection: None,
+            #[cfg(feature = "tracing")]
+            trace_propagation: TracePropagation::default(),
+            #[cfg(feature = "tracing")]
+            metrics: None,
             _url_state: PhantomData,
             _credentials_state: PhantomData,
         }
@@ -139,6 +173,70 @@ impl<C> ClientBuilder<NoUrl, C> {
             timeout: self.timeout,
             transport_strategy: self.transport_strategy,
             pool_config: self.pool_config,
+            endpoints: Vec::new(),
+            load_balancing: self.load_balancing,
+            outlier_detection: self.outlier_detection,
+            cassette: self.cassette,
+            fault_injection: self.fault_injection,
+            #[cfg(feature = "tracing")]
+            trace_propagation: self.trace_propagation,
+            #[cfg(feature = "tracing")]
+            metrics: self.metrics,
+            _url_state: PhantomData,
+            _credentials_state: PhantomData,
+        }
+    }
+
+    /// Sets multiple InferaDB API endpoints for client-side load balancing.
+    ///
+    /// Engine requests (checks, writes, lookups) are distributed across all
+    /// endpoints using the configured [`LoadBalancingStrategy`] and fail over
+    /// to the next endpoint when one is unavailable. Writes without an
+    /// idempotency key and deletes only fail over on connection errors, since
+    /// the first endpoint may already have applied them. Control plane
+    /// requests use the first endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - The API endpoints (e.g., one per region)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::LoadBalancingStrategy;
+    ///
+    /// let builder = Client::builder()
+    ///     .endpoints([
+    ///         "https://us-east.api.inferadb.com",
+    ///         "https://eu-west.api.inferadb.com",
+    ///     ])
+    ///     .load_balancing(LoadBalancingStrategy::LatencyWeighted);
+    /// ```
+    pub fn endpoints<I, S>(self, endpoints: I) -> ClientBuilder<HasUrl, C>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let endpoints: Vec<String> = endpoints.into_iter().map(Into::into).collect();
+        ClientBuilder {
+            url: endpoints.first().cloned(),
+            credentials: self.credentials,
+            retry_config: self.retry_config,
+            cache_config: self.cache_config,
+            tls_config: self.tls_config,
+            degradation_config: self.degradation_config,
+            timeout: self.timeout,
+            transport_strategy: self.transport_strategy,
+            pool_config: self.pool_config,
+            endpoints,
+            load_balancing: self.load_balancing,
+            outlier_detection: self.outlier_detection,
+            cassette: self.cassette,
+            fault_injection: self.fault_injection,
+            #[cfg(feature = "tracing")]
+            trace_propagation: self.trace_propagation,
+            #[cfg(feature = "tracing")]
+            metrics: self.metrics,
             _url_state: PhantomData,
             _credentials_state: PhantomData,
         }
@@ -178,6 +276,15 @@ impl<U> ClientBuilder<U, NoCredentials> {
             timeout: self.timeout,
             transport_strategy: self.transport_strategy,
             pool_config: self.pool_config,
+            endpoints: self.endpoints,
+            load_balancing: self.load_balancing,
+            outlier_detection: self.outlier_detection,
+            cassette: self.cassette,
+            fault_injection: self.fault_injection,
+            #[cfg(feature = "tracing")]
+            trace_propagation: self.trace_propagation,
+            #[cfg(feature = "tracing")]
+            metrics: self.metrics,
             _url_state: PhantomData,
             _credentials_state: PhantomData,
         }
@@ -337,18 +444,233 @@ impl<U, C> ClientBuilder<U, C> {
         self.pool_config = config;
         self
     }
+
+    /// Sets how requests are distributed across multiple endpoints.
+    ///
+    /// Only applies when more than one endpoint is configured via
+    /// [`endpoints()`](ClientBuilder::endpoints).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::LoadBalancingStrategy;
+    ///
+    /// let builder = builder.load_balancing(LoadBalancingStrategy::LeastOutstanding);
+    /// ```
+    #[must_use]
+    pub fn load_balancing(mut self, strategy: LoadBalancingStrategy) -> Self {
+        self.load_balancing = strategy;
+        self
+    }
+
+    /// Sets the outlier detection configuration for multiple endpoints.
+    ///
+    /// Controls when failing endpoints are ejected from rotation and how
+    /// long they stay out before being readmitted.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::OutlierDetectionConfig;
+    /// use std::time::Duration;
+    ///
+    /// let builder = builder.outlier_detection(
+    ///     OutlierDetectionConfig::builder()
+    ///         .consecutive_failures(3)
+    ///         .base_ejection_time(Duration::from_secs(10))
+    ///         .build()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn outlier_detection(mut self, config: OutlierDetectionConfig) -> Self {
+        self.outlier_detection = config;
+        self
+    }
+
+    /// Records engine calls to a cassette file, or replays them from one.
+    ///
+    /// In record mode every engine call goes to the server as usual and is
+    /// appended to the file. In replay mode calls are answered from the file
+    /// and no engine connection is made, which makes tests deterministic and
+    /// runnable offline. Control-plane calls are not recorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::testing::{Cassette, MatchRules};
+    ///
+    /// let cassette = if std::env::var("RECORD").is_ok() {
+    ///     Cassette::record("tests/cassettes/sharing.json")
+    /// } else {
+    ///     Cassette::replay("tests/cassettes/sharing.json")
+    ///         .with_rules(MatchRules::new().ignore_idempotency_keys())
+    /// };
+    /// let builder = builder.cassette(cassette);
+    /// ```
+    #[must_use]
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Injects latency and failures into engine calls, for chaos testing.
+    ///
+    /// Faults are applied to each endpoint separately, below load balancing,
+    /// so injected failures count towards outlier detection. The REST
+    /// transport's own retries run inside the injected faults; an injected
+    /// failure surfaces to the caller unless the balancer fails over.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::ErrorKind;
+    /// use inferadb::testing::{Fault, FaultInjector};
+    ///
+    /// let builder = builder.fault_injection(
+    ///     FaultInjector::new(42).with(Fault::error(ErrorKind::Unavailable, 0.1)),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn fault_injection(mut self, injector: FaultInjector) -> Self {
+        self.fault_injection = Some(injector);
+        self
+    }
+
+    /// Sets the format used to propagate trace context to the server.
+    ///
+    /// Defaults to W3C Trace Context (`traceparent` / `tracestate`). Only
+    /// requests given a context with `with_trace_context()` carry headers.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::tracing_support::TracePropagation;
+    ///
+    /// let builder = builder.trace_propagation(TracePropagation::B3);
+    /// ```
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn trace_propagation(mut self, propagation: TracePropagation) -> Self {
+        self.trace_propagation = propagation;
+        self
+    }
+
+    /// Enables metrics collection.
+    ///
+    /// Every engine call is recorded with its operation, transport and
+    /// outcome, together with retries, endpoint ejections and the configured
+    /// pool size. Read the collector back with [`Client::metrics`] to render
+    /// it for Prometheus; with the `metrics` feature each sample is also
+    /// forwarded to the `metrics` facade.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::tracing_support::MetricsConfig;
+    ///
+    /// let builder = builder.metrics(MetricsConfig::default().with_label("service", "docs"));
+    /// ```
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn metrics(mut self, config: MetricsConfig) -> Self {
+        self.metrics = Some(Metrics::new(config));
+        self
+    }
 }
 
 impl<U, C> ClientBuilder<U, C> {
     /// Creates the transport based on the configured strategy.
+    ///
+    /// When multiple endpoints are configured, one transport is created per
+    /// endpoint and wrapped in a load-balancing transport.
     #[cfg(any(feature = "grpc", feature = "rest"))]
-    #[allow(unused_variables)]
     async fn create_transport(
         &self,
         url: &url::Url,
         timeout: Duration,
         initial_token: Option<&String>,
     ) -> Result<Option<Arc<AnyTransport>>, Error> {
+        if let Some(cassette) = self.cassette.as_ref().filter(|c| c.mode() == CassetteMode::Replay)
+        {
+            let replay = CassetteTransport::replay(cassette.clone())?;
+            return Ok(Some(self.meter(self.inject(replay.into_any(), 0))));
+        }
+
+        if self.endpoints.len() <= 1 {
+            let transport = self.create_endpoint_transport(url, timeout, initial_token).await?;
+            return Ok(Some(self.meter(self.inject(transport, 0))));
+        }
+
+        let mut endpoints = Vec::with_capacity(self.endpoints.len());
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            let parsed = url::Url::parse(endpoint).map_err(|e| {
+                Error::configuration(format!("invalid endpoint URL '{}': {}", endpoint, e))
+            })?;
+            if parsed.scheme() != "https" && !self.tls_config.skip_verification {
+                return Err(Error::configuration(
+                    "HTTPS is required. Use .insecure() for development with HTTP.",
+                ));
+            }
+            let transport = self.create_endpoint_transport(&parsed, timeout, initial_token).await?;
+            endpoints.push((endpoint.clone(), self.inject(transport, index)));
+        }
+
+        let balanced =
+            BalancedTransport::new(endpoints, self.load_balancing, self.outlier_detection.clone())?;
+        Ok(Some(self.meter(AnyTransport::Balanced(balanced))))
+    }
+
+    /// Wraps the transport so that it records metrics and cassette
+    /// interactions, when enabled.
+    #[cfg(any(feature = "grpc", feature = "rest"))]
+    fn meter(&self, transport: AnyTransport) -> Arc<AnyTransport> {
+        #[cfg(feature = "tracing")]
+        if let Some(metrics) = &self.metrics {
+            let mut transport = transport;
+            transport.attach_metrics(metrics);
+            let endpoints = self.endpoints.len().max(1) as u64;
+            metrics
+                .set_connection_pool_limit(u64::from(self.pool_config.max_connections) * endpoints);
+            return Arc::new(AnyTransport::Metered(MeteredTransport::new(
+                Arc::new(self.record(transport)),
+                metrics.clone(),
+            )));
+        }
+        Arc::new(self.record(transport))
+    }
+
+    /// Wraps the transport of the endpoint at `index` so that it injects
+    /// faults, when enabled.
+    #[cfg(any(feature = "grpc", feature = "rest"))]
+    fn inject(&self, transport: AnyTransport, index: usize) -> AnyTransport {
+        match &self.fault_injection {
+            Some(injector) => {
+                FaultTransport::new(injector.for_endpoint(index), Arc::new(transport)).into_any()
+            },
+            None => transport,
+        }
+    }
+
+    /// Wraps the transport so that its calls are recorded, in record mode.
+    #[cfg(any(feature = "grpc", feature = "rest"))]
+    fn record(&self, transport: AnyTransport) -> AnyTransport {
+        match &self.cassette {
+            Some(cassette) if cassette.mode() == CassetteMode::Record => {
+                CassetteTransport::record(cassette.clone(), Arc::new(transport)).into_any()
+            },
+            _ => transport,
+        }
+    }
+
+    /// Creates the transport for a single endpoint based on the configured strategy.
+    #[cfg(any(feature = "grpc", feature = "rest"))]
+    #[allow(unused_variables)]
+    async fn create_endpoint_transport(
+        &self,
+        url: &url::Url,
+        timeout: Duration,
+        initial_token: Option<&String>,
+    ) -> Result<AnyTransport, Error> {
         match &self.transport_strategy {
             #[cfg(feature = "grpc")]
             TransportStrategy::GrpcOnly => {
@@ -360,7 +682,7 @@ impl<U, C> ClientBuilder<U, C> {
                     timeout,
                 )
                 .await?;
-                Ok(Some(Arc::new(AnyTransport::Grpc(grpc))))
+                Ok(AnyTransport::Grpc(grpc))
             },
             #[cfg(not(feature = "grpc"))]
             TransportStrategy::GrpcOnly => Err(Error::configuration(
@@ -378,7 +700,7 @@ impl<U, C> ClientBuilder<U, C> {
                 if let Some(token) = initial_token {
                     rest.set_auth_token(token.clone());
                 }
-                Ok(Some(Arc::new(AnyTransport::Rest(rest))))
+                Ok(AnyTransport::Rest(rest))
             },
             #[cfg(not(feature = "rest"))]
             TransportStrategy::RestOnly => Err(Error::configuration(
@@ -396,7 +718,20 @@ impl<U, C> ClientBuilder<U, C> {
                 )
                 .await
                 {
-                    Ok(grpc) => Ok(Some(Arc::new(AnyTransport::Grpc(grpc)))),
+                    Ok(grpc) => {
+                        // Simulations the gRPC API cannot express go over REST
+                        let rest = RestTransport::new(
+                            url.clone(),
+                            &self.tls_config,
+                            &self.pool_config,
+                            self.retry_config.clone(),
+                            timeout,
+                        )?;
+                        if let Some(token) = initial_token {
+                            rest.set_auth_token(token.clone());
+                        }
+                        Ok(AnyTransport::Grpc(grpc.with_rest(rest)))
+                    },
                     Err(_) => {
                         // Fall back to REST
                         let rest = RestTransport::new(
@@ -409,7 +744,7 @@ impl<U, C> ClientBuilder<U, C> {
                         if let Some(token) = initial_token {
                             rest.set_auth_token(token.clone());
                         }
-                        Ok(Some(Arc::new(AnyTransport::Rest(rest))))
+                        Ok(AnyTransport::Rest(rest))
                     },
                 }
             },
@@ -423,7 +758,7 @@ impl<U, C> ClientBuilder<U, C> {
                     timeout,
                 )
                 .await?;
-                Ok(Some(Arc::new(AnyTransport::Grpc(grpc))))
+                Ok(AnyTransport::Grpc(grpc))
             },
             #[cfg(all(not(feature = "grpc"), feature = "rest"))]
             TransportStrategy::PreferGrpc { .. } => {
@@ -438,7 +773,7 @@ impl<U, C> ClientBuilder<U, C> {
                 if let Some(token) = initial_token {
                     rest.set_auth_token(token.clone());
                 }
-                Ok(Some(Arc::new(AnyTransport::Rest(rest))))
+                Ok(AnyTransport::Rest(rest))
             },
             #[cfg(all(feature = "grpc", feature = "rest"))]
             TransportStrategy::PreferRest { .. } => {
@@ -454,7 +789,7 @@ impl<U, C> ClientBuilder<U, C> {
                         if let Some(token) = initial_token {
                             rest.set_auth_token(token.clone());
                         }
-                        Ok(Some(Arc::new(AnyTransport::Rest(rest))))
+                        Ok(AnyTransport::Rest(rest))
                     },
                     Err(_) => {
                         // Fall back to gRPC
@@ -466,7 +801,7 @@ impl<U, C> ClientBuilder<U, C> {
                             timeout,
                         )
                         .await?;
-                        Ok(Some(Arc::new(AnyTransport::Grpc(grpc))))
+                        Ok(AnyTransport::Grpc(grpc))
                     },
                 }
             },
@@ -482,7 +817,7 @@ impl<U, C> ClientBuilder<U, C> {
                 if let Some(token) = initial_token {
                     rest.set_auth_token(token.clone());
                 }
-                Ok(Some(Arc::new(AnyTransport::Rest(rest))))
+                Ok(AnyTransport::Rest(rest))
             },
             #[cfg(all(not(feature = "rest"), feature = "grpc"))]
             TransportStrategy::PreferRest { .. } => {
@@ -495,7 +830,7 @@ impl<U, C> ClientBuilder<U, C> {
                     timeout,
                 )
                 .await?;
-                Ok(Some(Arc::new(AnyTransport::Grpc(grpc))))
+                Ok(AnyTransport::Grpc(grpc))
             },
         }
     }
@@ -526,11 +861,40 @@ impl ClientBuilder<HasUrl, HasCredentials> {
 
         let timeout = self.timeout.unwrap_or(Duration::from_secs(30));
 
+        let transport = match &self.cassette {
+            Some(cassette) if cassette.mode() == CassetteMode::Replay => {
+                Arc::new(CassetteTransport::replay(cassette.clone())?.into_any())
+            },
+            _ => transport,
+        };
+
+        let transport = match &self.fault_injection {
+            Some(injector) => {
+                Arc::new(FaultTransport::new(injector.for_endpoint(0), transport).into_any())
+            },
+            None => transport,
+        };
+
+        let transport = match &self.cassette {
+            Some(cassette) if cassette.mode() == CassetteMode::Record => {
+                Arc::new(CassetteTransport::record(cassette.clone(), transport).into_any())
+            },
+            _ => transport,
+        };
+
+        #[cfg(feature = "tracing")]
+        let transport = match &self.metrics {
+            Some(metrics) => {
+                Arc::new(AnyTransport::Metered(MeteredTransport::new(transport, metrics.clone())))
+            },
+            None => transport,
+        };
+
         let inner = ClientInner {
             url,
-            credentials,
+            credentials: parking_lot::RwLock::new(credentials),
             retry_config: self.retry_config,
-            cache_config: self.cache_config,
+            decision_cache: DecisionCache::new(self.cache_config),
             tls_config: self.tls_config,
             degradation_config: self.degradation_config,
             timeout,
@@ -540,6 +904,10 @@ impl ClientBuilder<HasUrl, HasCredentials> {
             http_client: None,
             #[cfg(feature = "rest")]
             auth_token: parking_lot::RwLock::new(None),
+            #[cfg(feature = "tracing")]
+            trace_propagation: self.trace_propagation,
+            #[cfg(feature = "tracing")]
+            metrics: self.metrics,
             shutdown_guard: None,
         };
 
@@ -623,9 +991,9 @@ impl ClientBuilder<HasUrl, HasCredentials> {
 
         let inner = ClientInner {
             url,
-            credentials,
+            credentials: parking_lot::RwLock::new(credentials),
             retry_config: self.retry_config,
-            cache_config: self.cache_config,
+            decision_cache: DecisionCache::new(self.cache_config),
             tls_config: self.tls_config,
             degradation_config: self.degradation_config,
             timeout,
@@ -635,6 +1003,10 @@ impl ClientBuilder<HasUrl, HasCredentials> {
             http_client,
             #[cfg(feature = "rest")]
             auth_token: parking_lot::RwLock::new(initial_token),
+            #[cfg(feature = "tracing")]
+            trace_propagation: self.trace_propagation,
+            #[cfg(feature = "tracing")]
+            metrics: self.metrics,
             shutdown_guard: None,
         };
 
@@ -726,9 +1098,9 @@ impl ClientBuilder<HasUrl, HasCredentials> {
 
         let inner = ClientInner {
             url,
-            credentials,
+            credentials: parking_lot::RwLock::new(credentials),
             retry_config: self.retry_config,
-            cache_config: self.cache_config,
+            decision_cache: DecisionCache::new(self.cache_config),
             tls_config: self.tls_config,
             degradation_config: self.degradation_config,
             timeout,
@@ -738,6 +1110,10 @@ impl ClientBuilder<HasUrl, HasCredentials> {
             http_client,
             #[cfg(feature = "rest")]
             auth_token: parking_lot::RwLock::new(initial_token),
+            #[cfg(feature = "tracing")]
+            trace_propagation: self.trace_propagation,
+            #[cfg(feature = "tracing")]
+            metrics: self.metrics,
             shutdown_guard: Some(shutdown_guard),
         };
 
@@ -902,6 +1278,50 @@ mod tests {
         assert_eq!(builder.pool_config.max_connections, 100);
     }
 
+    #[test]
+    fn test_builder_endpoints() {
+        let builder = ClientBuilder::new()
+            .endpoints(["https://us.example.com", "https://eu.example.com"])
+            .credentials(BearerCredentialsConfig::new("token"))
+            .load_balancing(LoadBalancingStrategy::LeastOutstanding)
+            .outlier_detection(OutlierDetectionConfig::disabled());
+
+        assert_eq!(builder.url.as_deref(), Some("https://us.example.com"));
+        assert_eq!(builder.endpoints.len(), 2);
+        assert_eq!(builder.load_balancing, LoadBalancingStrategy::LeastOutstanding);
+        assert!(!builder.outlier_detection.is_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_build_endpoints_requires_https() {
+        let result = ClientBuilder::new()
+            .endpoints(["https://us.example.com", "http://eu.example.com"])
+            .credentials(BearerCredentialsConfig::new("token"))
+            .build()
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("HTTPS"));
+    }
+
+    #[cfg(feature = "rest")]
+    #[tokio::test]
+    async fn test_build_endpoints_balances_transport() {
+        let client = ClientBuilder::new()
+            .endpoints(["https://us.example.com", "https://eu.example.com"])
+            .credentials(BearerCredentialsConfig::new("token"))
+            .transport_strategy(TransportStrategy::RestOnly)
+            .build()
+            .await
+            .unwrap();
+
+        let stats = client.transport_stats().unwrap();
+        assert_eq!(stats.endpoints.len(), 2);
+        assert_eq!(stats.endpoints[0].url, "https://us.example.com");
+        assert_eq!(stats.endpoints[1].url, "https://eu.example.com");
+        assert!(stats.endpoints.iter().all(|e| e.healthy));
+        assert_eq!(client.url(), "https://us.example.com");
+    }
+
     #[test]
     fn test_builder_default() {
         let builder: ClientBuilder<NoUrl, NoCredentials> = ClientBuilder::default();
@@ -0,0 +1,235 @@
+//! Local cache of authorization decisions.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::{ConsistencyRequirement, config::CacheConfig};
+
+/// Identifies a cached decision.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    organization_id: String,
+    vault_id: String,
+    subject: String,
+    permission: String,
+    resource: String,
+}
+
+impl CacheKey {
+    pub(crate) fn new(
+        organization_id: &str,
+        vault_id: &str,
+        subject: &str,
+        permission: &str,
+        resource: &str,
+    ) -> Self {
+        Self {
+            organization_id: organization_id.to_owned(),
+            vault_id: vault_id.to_owned(),
+            subject: subject.to_owned(),
+            permission: permission.to_owned(),
+            resource: resource.to_owned(),
+        }
+    }
+}
+
+/// Decisions cached under a [`CacheConfig`].
+///
+/// Only eventual checks without ABAC context are cached: context is
+/// evaluated at request time, and stricter consistency requirements must
+/// reach the server (see [`ConsistencyRequirement::allows_cached`]). Writes
+/// and deletes made through the client drop the vault's entries.
+pub(crate) struct DecisionCache {
+    config: CacheConfig,
+    entries: Mutex<HashMap<CacheKey, Entry>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    allowed: bool,
+    expires_at: Instant,
+}
+
+impl DecisionCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        Self { config, entries: Mutex::new(HashMap::new()) }
+    }
+
+    /// Returns `true` if a check with this consistency and context may be
+    /// served from, and stored in, the cache.
+    pub(crate) fn accepts(&self, consistency: &ConsistencyRequirement, has_context: bool) -> bool {
+        self.config.enabled && consistency.allows_cached() && !has_context
+    }
+
+    /// Returns the cached decision for `key`, if it has not expired.
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<bool> {
+        let mut entries = self.entries.lock();
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.allowed),
+            Some(_) => {
+                entries.remove(key);
+                None
+            },
+            None => None,
+        }
+    }
+
+    /// Caches a decision, unless it is a denial and negative caching is off.
+    pub(crate) fn insert(&self, key: CacheKey, allowed: bool) {
+        let ttl = if allowed {
+            self.config.ttl
+        } else if self.config.negative_caching {
+            self.config.effective_negative_ttl()
+        } else {
+            return;
+        };
+        if ttl.is_zero() || self.config.max_entries == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut entries = self.entries.lock();
+        if entries.len() >= self.config.max_entries && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= self.config.max_entries {
+                evict_soonest(&mut entries, self.config.max_entries / 10 + 1);
+            }
+        }
+        entries.insert(key, Entry { allowed, expires_at: now + ttl.min(MAX_TTL) });
+    }
+
+    /// Drops every decision cached for a vault.
+    pub(crate) fn invalidate_vault(&self, organization_id: &str, vault_id: &str) {
+        if !self.config.enabled {
+            return;
+        }
+        self.entries
+            .lock()
+            .retain(|key, _| key.organization_id != organization_id || key.vault_id != vault_id);
+    }
+
+    /// Returns the number of cached decisions, including expired ones not
+    /// yet dropped.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+}
+
+/// Longest time a decision is kept, so a huge TTL cannot overflow `Instant`.
+const MAX_TTL: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Removes the `count` entries closest to expiring.
+fn evict_soonest(entries: &mut HashMap<CacheKey, Entry>, count: usize) {
+    let mut expiries: Vec<Instant> = entries.values().map(|entry| entry.expires_at).collect();
+    let count = count.min(expiries.len());
+    if count == 0 {
+        return;
+    }
+    let (_, cutoff, _) = expiries.select_nth_unstable(count - 1);
+    let cutoff = *cutoff;
+    let mut removed = 0;
+    entries.retain(|_, entry| {
+        if removed < count && entry.expires_at <= cutoff {
+            removed += 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::ConsistencyToken;
+
+    fn key(resource: &str) -> CacheKey {
+        CacheKey::new("org", "vault", "user:alice", "view", resource)
+    }
+
+    fn cache_with_ttl(ttl: Duration) -> DecisionCache {
+        DecisionCache::new(CacheConfig::builder().enabled(true).ttl(ttl).build())
+    }
+
+    #[test]
+    fn test_accepts_only_eventual_checks_without_context() {
+        let cache = DecisionCache::new(CacheConfig::enabled_config());
+        assert!(cache.accepts(&ConsistencyRequirement::Eventual, false));
+        assert!(!cache.accepts(&ConsistencyRequirement::Eventual, true));
+        assert!(!cache.accepts(&ConsistencyRequirement::Full, false));
+        let token = ConsistencyRequirement::AtLeastAsFresh(ConsistencyToken::new("1"));
+        assert!(!cache.accepts(&token, false));
+
+        let disabled = DecisionCache::new(CacheConfig::default());
+        assert!(!disabled.accepts(&ConsistencyRequirement::Eventual, false));
+    }
+
+    #[test]
+    fn test_entries_expire() {
+        let cache = DecisionCache::new(
+            CacheConfig::builder()
+                .enabled(true)
+                .ttl(Duration::from_secs(60))
+                .negative_ttl(Duration::ZERO)
+                .build(),
+        );
+        cache.insert(key("doc:1"), true);
+        cache.insert(key("doc:2"), false);
+        assert_eq!(cache.get(&key("doc:1")), Some(true));
+        assert_eq!(cache.get(&key("doc:2")), None);
+
+        let cache = cache_with_ttl(Duration::from_nanos(1));
+        cache.insert(key("doc:1"), true);
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(cache.get(&key("doc:1")), None);
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_negative_caching_can_be_disabled() {
+        let cache = DecisionCache::new(
+            CacheConfig::builder().enabled(true).negative_caching(false).build(),
+        );
+        cache.insert(key("doc:1"), false);
+        assert_eq!(cache.get(&key("doc:1")), None);
+    }
+
+    #[test]
+    fn test_full_cache_evicts_soonest_to_expire() {
+        let cache = DecisionCache::new(
+            CacheConfig::builder()
+                .enabled(true)
+                .max_entries(2)
+                .negative_ttl(Duration::from_secs(10))
+                .build(),
+        );
+        cache.insert(key("doc:1"), false);
+        cache.insert(key("doc:2"), true);
+        cache.insert(key("doc:3"), true);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key("doc:1")), None);
+        assert_eq!(cache.get(&key("doc:3")), Some(true));
+    }
+
+    #[test]
+    fn test_invalidate_vault() {
+        let cache = DecisionCache::new(CacheConfig::enabled_config());
+        cache.insert(key("doc:1"), true);
+        cache.insert(CacheKey::new("org", "other", "user:alice", "view", "doc:1"), true);
+        cache.invalidate_vault("org", "vault");
+        assert_eq!(cache.get(&key("doc:1")), None);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_huge_ttl_does_not_overflow() {
+        let cache = cache_with_ttl(Duration::MAX);
+        cache.insert(key("doc:1"), true);
+        assert_eq!(cache.get(&key("doc:1")), Some(true));
+    }
+}
@@ -5,32 +5,34 @@ use std::sync::Arc;
 use std::time::Duration;
 
 #[cfg(feature = "rest")]
-use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::header::{ACCEPT, AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE, HeaderMap, HeaderValue};
 #[cfg(feature = "rest")]
 use serde::{Serialize, de::DeserializeOwned};
 
-use super::health::ShutdownGuard;
+use super::{cache::DecisionCache, health::ShutdownGuard};
 #[cfg(feature = "rest")]
 use crate::error::{Error, ErrorKind};
+#[cfg(feature = "tracing")]
+use crate::tracing_support::{Metrics, TracePropagation};
 #[cfg(any(feature = "grpc", feature = "rest"))]
 use crate::transport::AnyTransport;
 use crate::{
     auth::Credentials,
-    config::{CacheConfig, DegradationConfig, RetryConfig, TlsConfig},
+    config::{DegradationConfig, RetryConfig, TlsConfig},
 };
 
 pub(crate) struct ClientInner {
     /// The InferaDB API URL.
     pub url: String,
 
-    /// Authentication credentials.
-    pub credentials: Credentials,
+    /// Authentication credentials, replaceable when keys are rotated.
+    pub credentials: parking_lot::RwLock<Credentials>,
 
     /// Retry configuration.
     pub retry_config: RetryConfig,
 
-    /// Cache configuration.
-    pub cache_config: CacheConfig,
+    /// Local cache of authorization decisions.
+    pub decision_cache: DecisionCache,
 
     /// TLS configuration.
     pub tls_config: TlsConfig,
@@ -53,6 +55,14 @@ pub(crate) struct ClientInner {
     #[cfg(feature = "rest")]
     pub auth_token: parking_lot::RwLock<Option<String>>,
 
+    /// Wire format for trace context propagation.
+    #[cfg(feature = "tracing")]
+    pub trace_propagation: TracePropagation,
+
+    /// Metrics collector, when metrics are enabled.
+    #[cfg(feature = "tracing")]
+    pub metrics: Option<Metrics>,
+
     /// Shutdown guard for graceful shutdown tracking.
     pub shutdown_guard: Option<ShutdownGuard>,
 }
@@ -84,6 +94,9 @@ impl ClientInner {
             );
         }
 
+        #[cfg(feature = "tracing")]
+        crate::tracing_support::inject_current(&mut headers);
+
         Ok(headers)
     }
 
@@ -99,19 +112,30 @@ impl ClientInner {
     where
         R: DeserializeOwned,
     {
-        let url = self.build_url(path)?;
-        let headers = self.build_headers()?;
-
-        let response = self
-            .http_client()?
-            .get(url)
-            .headers(headers)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::new(ErrorKind::Transport, format!("Request failed: {}", e)))?;
+        self.traced("GET", path, async {
+            let request = self.http_client()?.get(self.build_url(path)?);
+            let response = self.send(request).await?;
+            self.handle_response(response).await
+        })
+        .await
+    }
 
-        self.handle_response(response).await
+    /// Makes a GET request to the Control API, also returning how long the
+    /// response may be cached according to its `Cache-Control` header.
+    pub(crate) async fn control_get_cacheable<R>(
+        &self,
+        path: &str,
+    ) -> Result<(R, Option<Duration>), Error>
+    where
+        R: DeserializeOwned,
+    {
+        self.traced("GET", path, async {
+            let request = self.http_client()?.get(self.build_url(path)?);
+            let response = self.send(request).await?;
+            let max_age = cache_max_age(response.headers());
+            Ok((self.handle_response(response).await?, max_age))
+        })
+        .await
     }
 
     /// Makes a POST request to the Control API.
@@ -120,20 +144,12 @@ impl ClientInner {
         T: Serialize,
         R: DeserializeOwned,
     {
-        let url = self.build_url(path)?;
-        let headers = self.build_headers()?;
-
-        let response = self
-            .http_client()?
-            .post(url)
-            .headers(headers)
-            .json(body)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::new(ErrorKind::Transport, format!("Request failed: {}", e)))?;
-
-        self.handle_response(response).await
+        self.traced("POST", path, async {
+            let request = self.http_client()?.post(self.build_url(path)?).json(body);
+            let response = self.send(request).await?;
+            self.handle_response(response).await
+        })
+        .await
     }
 
     /// Makes a POST request to the Control API without a body.
@@ -141,19 +157,12 @@ impl ClientInner {
     where
         R: DeserializeOwned,
     {
-        let url = self.build_url(path)?;
-        let headers = self.build_headers()?;
-
-        let response = self
-            .http_client()?
-            .post(url)
-            .headers(headers)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::new(ErrorKind::Transport, format!("Request failed: {}", e)))?;
-
-        self.handle_response(response).await
+        self.traced("POST", path, async {
+            let request = self.http_client()?.post(self.build_url(path)?);
+            let response = self.send(request).await?;
+            self.handle_response(response).await
+        })
+        .await
     }
 
     /// Makes a PATCH request to the Control API.
@@ -162,43 +171,61 @@ impl ClientInner {
         T: Serialize,
         R: DeserializeOwned,
     {
-        let url = self.build_url(path)?;
-        let headers = self.build_headers()?;
-
-        let response = self
-            .http_client()?
-            .patch(url)
-            .headers(headers)
-            .json(body)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::new(ErrorKind::Transport, format!("Request failed: {}", e)))?;
-
-        self.handle_response(response).await
+        self.traced("PATCH", path, async {
+            let request = self.http_client()?.patch(self.build_url(path)?).json(body);
+            let response = self.send(request).await?;
+            self.handle_response(response).await
+        })
+        .await
     }
 
     /// Makes a DELETE request to the Control API.
     pub(crate) async fn control_delete(&self, path: &str) -> Result<(), Error> {
-        let url = self.build_url(path)?;
-        let headers = self.build_headers()?;
+        self.traced("DELETE", path, async {
+            let request = self.http_client()?.delete(self.build_url(path)?);
+            let response = self.send(request).await?;
+
+            let status = response.status();
+            if status.is_success() {
+                Ok(())
+            } else {
+                let body = response.text().await.unwrap_or_default();
+                Err(self.map_status_error(status, &body))
+            }
+        })
+        .await
+    }
 
-        let response = self
-            .http_client()?
-            .delete(url)
-            .headers(headers)
+    /// Sends a Control API request with the standard headers and timeout.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, Error> {
+        let response = request
+            .headers(self.build_headers()?)
             .timeout(self.timeout)
             .send()
             .await
             .map_err(|e| Error::new(ErrorKind::Transport, format!("Request failed: {}", e)))?;
 
-        let status = response.status();
-        if status.is_success() {
-            Ok(())
-        } else {
-            let body = response.text().await.unwrap_or_default();
-            Err(self.map_status_error(status, &body))
-        }
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("http.response.status_code", response.status().as_u16());
+
+        Ok(response)
+    }
+
+    /// Runs a Control API call, inside a span when the `tracing` feature is
+    /// enabled.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    async fn traced<T>(
+        &self,
+        method: &'static str,
+        path: &str,
+        call: impl Future<Output = Result<T, Error>>,
+    ) -> Result<T, Error> {
+        #[cfg(feature = "tracing")]
+        let call = crate::tracing_support::instrument(
+            crate::tracing_support::control_span(method, path),
+            call,
+        );
+        call.await
     }
 
     /// Handles an HTTP response.
@@ -232,27 +259,49 @@ impl ClientInner {
     }
 }
 
+/// Returns how long a response may be cached according to its
+/// `Cache-Control` header; `no-store` and `no-cache` allow no caching.
+#[cfg(feature = "rest")]
+fn cache_max_age(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(CACHE_CONTROL)?.to_str().ok()?;
+    value.split(',').map(str::trim).find_map(|directive| {
+        if directive.eq_ignore_ascii_case("no-store") || directive.eq_ignore_ascii_case("no-cache")
+        {
+            return Some(Duration::ZERO);
+        }
+        let (name, seconds) = directive.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("max-age")
+            .then(|| seconds.trim().trim_matches('"').parse().ok().map(Duration::from_secs))
+            .flatten()
+    })
+}
+
 #[cfg(all(test, feature = "rest"))]
 #[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
     use reqwest::StatusCode;
 
     use super::*;
-    use crate::auth::BearerCredentialsConfig;
+    use crate::{auth::BearerCredentialsConfig, config::CacheConfig};
 
     fn create_test_inner() -> ClientInner {
         let token = "test_token";
         ClientInner {
             url: "https://api.example.com".to_string(),
-            credentials: BearerCredentialsConfig::new(token).into(),
+            credentials: parking_lot::RwLock::new(BearerCredentialsConfig::new(token).into()),
             retry_config: RetryConfig::default(),
-            cache_config: CacheConfig::default(),
+            decision_cache: DecisionCache::new(CacheConfig::default()),
             tls_config: TlsConfig::default(),
             degradation_config: DegradationConfig::default(),
             timeout: Duration::from_secs(30),
             transport: None,
             http_client: Some(reqwest::Client::new()),
             auth_token: parking_lot::RwLock::new(Some(token.to_string())),
+            #[cfg(feature = "tracing")]
+            trace_propagation: TracePropagation::default(),
+            #[cfg(feature = "tracing")]
+            metrics: None,
             shutdown_guard: None,
         }
     }
@@ -260,15 +309,19 @@ mod tests {
     fn create_test_inner_no_token() -> ClientInner {
         ClientInner {
             url: "https://api.example.com".to_string(),
-            credentials: BearerCredentialsConfig::new("test").into(),
+            credentials: parking_lot::RwLock::new(BearerCredentialsConfig::new("test").into()),
             retry_config: RetryConfig::default(),
-            cache_config: CacheConfig::default(),
+            decision_cache: DecisionCache::new(CacheConfig::default()),
             tls_config: TlsConfig::default(),
             degradation_config: DegradationConfig::default(),
             timeout: Duration::from_secs(30),
             transport: None,
             http_client: Some(reqwest::Client::new()),
             auth_token: parking_lot::RwLock::new(None),
+            #[cfg(feature = "tracing")]
+            trace_propagation: TracePropagation::default(),
+            #[cfg(feature = "tracing")]
+            metrics: None,
             shutdown_guard: None,
         }
     }
@@ -276,15 +329,19 @@ mod tests {
     fn create_test_inner_no_http_client() -> ClientInner {
         ClientInner {
             url: "https://api.example.com".to_string(),
-            credentials: BearerCredentialsConfig::new("test").into(),
+            credentials: parking_lot::RwLock::new(BearerCredentialsConfig::new("test").into()),
             retry_config: RetryConfig::default(),
-            cache_config: CacheConfig::default(),
+            decision_cache: DecisionCache::new(CacheConfig::default()),
             tls_config: TlsConfig::default(),
             degradation_config: DegradationConfig::default(),
             timeout: Duration::from_secs(30),
             transport: None,
             http_client: None,
             auth_token: parking_lot::RwLock::new(None),
+            #[cfg(feature = "tracing")]
+            trace_propagation: TracePropagation::default(),
+            #[cfg(feature = "tracing")]
+            metrics: None,
             shutdown_guard: None,
         }
     }
@@ -392,4 +449,17 @@ mod tests {
         assert!(matches!(error.kind(), ErrorKind::Transport));
         assert!(error.to_string().contains("418"));
     }
+
+    #[test]
+    fn test_cache_max_age() {
+        let max_age = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(CACHE_CONTROL, HeaderValue::from_static(value));
+            cache_max_age(&headers)
+        };
+        assert_eq!(max_age("public, max-age=300"), Some(Duration::from_secs(300)));
+        assert_eq!(max_age("no-store"), Some(Duration::ZERO));
+        assert_eq!(max_age("public"), None);
+        assert_eq!(cache_max_age(&HeaderMap::new()), None);
+    }
 }
@@ -28,17 +28,22 @@
 #![allow(dead_code)]
 
 mod builder;
+mod cache;
 mod health;
 mod inner;
+mod trace_scope;
 
 use std::sync::Arc;
 #[cfg(not(feature = "rest"))]
 use std::time::Duration;
 
 pub use builder::ClientBuilder;
+#[cfg(feature = "rest")]
+pub(crate) use cache::CacheKey;
 pub use health::{
     ComponentHealth, HealthResponse, HealthStatus, ReadinessCriteria, ShutdownGuard, ShutdownHandle,
 };
+pub(crate) use trace_scope::TraceScope;
 
 use crate::{
     control::{
@@ -126,6 +131,113 @@ impl Client {
         &self.inner.url
     }
 
+    /// Returns the API client ID, when authenticating with client
+    /// credentials.
+    pub fn client_id(&self) -> Option<String> {
+        self.inner.credentials.read().as_client_credentials().map(|c| c.client_id.clone())
+    }
+
+    /// Returns the ID of the certificate the signing key belongs to, when
+    /// authenticating with client credentials and one was configured.
+    pub fn certificate_id(&self) -> Option<String> {
+        self.inner.credentials.read().as_client_credentials().and_then(|c| c.certificate_id.clone())
+    }
+
+    /// Replaces the signing key of the client credentials in place.
+    ///
+    /// Every clone of this client, and every organization and vault client
+    /// derived from it, signs with the new key from then on. Used by
+    /// [`KeyRotator`](crate::control::KeyRotator) after registering a new
+    /// certificate.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Configuration` error if the client authenticates with a
+    /// bearer token.
+    pub fn replace_signing_key(
+        &self,
+        private_key: crate::Ed25519PrivateKey,
+        certificate_id: Option<String>,
+    ) -> Result<(), crate::Error> {
+        let mut credentials = self.inner.credentials.write();
+        match &mut *credentials {
+            crate::Credentials::ClientCredentials(config) => {
+                config.private_key = private_key;
+                config.certificate_id = certificate_id;
+                Ok(())
+            },
+            crate::Credentials::Bearer(_) => Err(crate::Error::configuration(
+                "signing keys can only be replaced for client credentials",
+            )),
+        }
+    }
+
+    /// Returns the public key of the current signing key, when
+    /// authenticating with client credentials.
+    pub(crate) fn signing_public_key(&self) -> Option<[u8; 32]> {
+        self.inner
+            .credentials
+            .read()
+            .as_client_credentials()
+            .map(|c| c.private_key.public_key_bytes())
+    }
+
+    /// Returns the metrics collector, if metrics were enabled with
+    /// [`ClientBuilder::metrics`](crate::ClientBuilder::metrics).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if let Some(metrics) = client.metrics() {
+    ///     let body = metrics.render_prometheus();
+    /// }
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub fn metrics(&self) -> Option<&crate::tracing_support::Metrics> {
+        self.inner.metrics.as_ref()
+    }
+
+    /// Writes the engine calls recorded so far to the cassette file.
+    ///
+    /// Recordings are also written when the client is dropped, but write
+    /// errors can only be reported from here. Does nothing unless the client
+    /// records a [`Cassette`](crate::testing::Cassette).
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the cassette file cannot be written.
+    #[cfg(any(feature = "grpc", feature = "rest"))]
+    pub async fn flush_cassette(&self) -> Result<(), crate::Error> {
+        match self.transport().and_then(|t| t.cassette()) {
+            Some(cassette) => cassette.flush().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Runs `future` with every SDK call inside it parented to `context`.
+    ///
+    /// Use this for calls whose builders have no `with_trace_context()`,
+    /// such as control-plane operations, or to group several calls under
+    /// one parent.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let vaults = client
+    ///     .with_trace_context(trace_ctx, async {
+    ///         client.organization("org_123").vaults().list().await
+    ///     })
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub async fn with_trace_context<F: std::future::Future>(
+        &self,
+        context: crate::tracing_support::TraceContext,
+        future: F,
+    ) -> F::Output {
+        TraceScope::new(self, context).run(future).await
+    }
+
     /// Creates a client from the inner implementation.
     pub(crate) fn from_inner(inner: inner::ClientInner) -> Self {
         Self { inner: Arc::new(inner) }
@@ -137,7 +249,7 @@ impl Client {
     }
 
     /// Returns the transport client, if available.
-    #[cfg(feature = "rest")]
+    #[cfg(any(feature = "grpc", feature = "rest"))]
     pub(crate) fn transport(&self) -> Option<&std::sync::Arc<crate::transport::AnyTransport>> {
         self.inner.transport.as_ref()
     }
@@ -269,23 +381,27 @@ impl Client {
                         })
                         .collect();
 
-                    Ok(HealthResponse {
+                    let mut response = HealthResponse {
                         status,
                         version: server_health.version.unwrap_or_else(|| "unknown".to_string()),
                         latency,
                         components,
                         timestamp: chrono::Utc::now(),
-                    })
+                    };
+                    self.add_endpoint_health(&mut response).await;
+                    Ok(response)
                 },
                 Err(_) => {
                     // Fall back to simple health check
-                    Ok(HealthResponse {
+                    let mut response = HealthResponse {
                         status: HealthStatus::Unhealthy,
                         version: "unknown".to_string(),
                         latency: start.elapsed(),
                         components: HashMap::new(),
                         timestamp: chrono::Utc::now(),
-                    })
+                    };
+                    self.add_endpoint_health(&mut response).await;
+                    Ok(response)
                 },
             }
         }
@@ -300,6 +416,64 @@ impl Client {
         })
     }
 
+    /// Probes every endpoint of a multi-endpoint client and records the
+    /// results as `endpoint:<url>` components.
+    ///
+    /// Probing also readmits recovered endpoints and ejects failing ones.
+    /// The overall status is downgraded to degraded when only some
+    /// endpoints are healthy.
+    #[cfg(feature = "rest")]
+    async fn add_endpoint_health(&self, response: &mut HealthResponse) {
+        let Some(balanced) = self.inner.transport.as_deref().and_then(|t| t.balanced()) else {
+            return;
+        };
+
+        let probes = balanced.probe().await;
+        let healthy = probes.iter().filter(|probe| probe.error.is_none()).count();
+
+        for probe in probes {
+            let status =
+                if probe.error.is_none() { HealthStatus::Healthy } else { HealthStatus::Unhealthy };
+            response.components.insert(
+                format!("endpoint:{}", probe.url),
+                ComponentHealth {
+                    status,
+                    message: probe.error,
+                    latency: Some(probe.latency),
+                    last_check: chrono::Utc::now(),
+                },
+            );
+        }
+
+        let total = balanced.len();
+        if healthy == 0 {
+            response.status = HealthStatus::Unhealthy;
+        } else if healthy < total || response.status == HealthStatus::Unhealthy {
+            response.status = HealthStatus::Degraded;
+        }
+    }
+
+    /// Returns transport statistics.
+    ///
+    /// When the client was built with multiple
+    /// [`endpoints`](ClientBuilder::endpoints), the stats include
+    /// per-endpoint health, in-flight requests, and latency. Returns `None`
+    /// if no transport is configured.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if let Some(stats) = client.transport_stats() {
+    ///     for endpoint in &stats.endpoints {
+    ///         println!("{}: healthy={}", endpoint.url, endpoint.healthy);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(any(feature = "grpc", feature = "rest"))]
+    pub fn transport_stats(&self) -> Option<crate::TransportStats> {
+        self.inner.transport.as_ref().map(|transport| transport.stats())
+    }
+
     /// Waits for the service to become ready.
     ///
     /// This is useful during application startup to ensure the
@@ -550,6 +724,44 @@ mod tests {
             .unwrap()
     }
 
+    #[cfg(all(feature = "tracing", feature = "rest"))]
+    #[tokio::test]
+    async fn test_client_records_metrics() {
+        use crate::{Relationship, tracing_support::MetricsConfig};
+
+        let mock = MockTransport::new();
+        mock.add_relationship(Relationship::new("doc:1", "view", "user:alice"));
+        let client = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .metrics(MetricsConfig::default().with_label("service", "api"))
+            .build_with_transport(Arc::new(mock.into_any()))
+            .await
+            .unwrap();
+
+        let vault = client.organization("org_test").vault("vlt_test");
+        assert!(vault.check("user:alice", "view", "doc:1").await.unwrap());
+        assert!(!vault.check("user:bob", "view", "doc:1").await.unwrap());
+        vault.relationships().write(Relationship::new("doc:2", "view", "user:bob")).await.unwrap();
+
+        let metrics = client.metrics().unwrap();
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.check_total, 2);
+        assert_eq!(snapshot.check_allowed, 1);
+        assert_eq!(snapshot.relationship_writes, 1);
+
+        let text = metrics.render_prometheus();
+        for outcome in ["allow", "deny"] {
+            assert!(text.contains(&format!(
+                r#"inferadb_requests_total{{service="api",operation="check",outcome="{}",transport="mock"}} 1"#,
+                outcome
+            )));
+        }
+        assert!(text.contains(
+            r#"inferadb_requests_total{service="api",operation="write",outcome="success",transport="mock"} 1"#
+        ));
+    }
+
     #[tokio::test]
     async fn test_client_url() {
         let client = create_test_client().await;
@@ -802,4 +1014,45 @@ mod wiremock_tests {
         let result = client.wait_ready(std::time::Duration::from_secs(1)).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_health_reports_endpoints() {
+        let healthy = MockServer::start().await;
+        let unhealthy = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/healthz"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"status": "healthy"})),
+            )
+            .mount(&healthy)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/healthz"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&unhealthy)
+            .await;
+
+        let client = Client::builder()
+            .endpoints([healthy.uri(), unhealthy.uri()])
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test_token"))
+            .transport_strategy(crate::TransportStrategy::RestOnly)
+            .build()
+            .await
+            .unwrap();
+
+        let health = client.health().await.unwrap();
+        assert_eq!(health.status, HealthStatus::Degraded);
+
+        let up = &health.components[&format!("endpoint:{}", healthy.uri())];
+        assert_eq!(up.status, HealthStatus::Healthy);
+        let down = &health.components[&format!("endpoint:{}", unhealthy.uri())];
+        assert_eq!(down.status, HealthStatus::Unhealthy);
+        assert!(down.message.is_some());
+
+        let stats = client.transport_stats().unwrap();
+        assert!(stats.endpoints[0].healthy);
+        assert!(!stats.endpoints[1].healthy);
+    }
 }
@@ -0,0 +1,37 @@
+//! Caller-supplied trace context carried by request builders.
+
+use std::future::Future;
+
+#[cfg(feature = "tracing")]
+use crate::{
+    Client,
+    tracing_support::{TraceContext, TracePropagation},
+};
+
+/// The trace context a request builder runs under.
+///
+/// Builders hold one of these unconditionally so only their
+/// `with_trace_context()` methods need a `tracing` feature gate; without the
+/// feature it is empty and [`run`](Self::run) simply awaits the future.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TraceScope {
+    #[cfg(feature = "tracing")]
+    parent: Option<(TraceContext, TracePropagation)>,
+}
+
+impl TraceScope {
+    /// Creates a scope parented to `context`, propagated in `client`'s format.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn new(client: &Client, context: TraceContext) -> Self {
+        Self { parent: Some((context, client.inner().trace_propagation)) }
+    }
+
+    /// Runs `future` as a child of the caller's trace context, if one was set.
+    pub(crate) async fn run<F: Future>(self, future: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        if let Some((parent, propagation)) = self.parent {
+            return crate::tracing_support::with_context(parent.child(), propagation, future).await;
+        }
+        future.await
+    }
+}
@@ -0,0 +1,213 @@
+//! Client-side load balancing across multiple endpoints.
+//!
+//! When a client is configured with more than one endpoint via
+//! [`ClientBuilder::endpoints`](crate::ClientBuilder::endpoints), requests are
+//! spread across them using a [`LoadBalancingStrategy`]. Endpoints that keep
+//! failing are temporarily ejected according to [`OutlierDetectionConfig`]
+//! and readmitted once their ejection period expires or a health probe
+//! succeeds.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inferadb::{LoadBalancingStrategy, OutlierDetectionConfig};
+//! use std::time::Duration;
+//!
+//! let strategy = LoadBalancingStrategy::LatencyWeighted;
+//!
+//! let outliers = OutlierDetectionConfig::builder()
+//!     .consecutive_failures(3)                  // Eject after 3 failures in a row
+//!     .base_ejection_time(Duration::from_secs(10))
+//!     .max_ejection_percent(50)                 // Never eject more than half
+//!     .build();
+//! ```
+
+use std::time::Duration;
+
+use crate::ErrorKind;
+
+/// How requests are distributed across healthy endpoints.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::LoadBalancingStrategy;
+///
+/// assert_eq!(LoadBalancingStrategy::default(), LoadBalancingStrategy::RoundRobin);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadBalancingStrategy {
+    /// Cycle through healthy endpoints in order (default).
+    #[default]
+    RoundRobin,
+    /// Send each request to the endpoint with the fewest in-flight requests.
+    LeastOutstanding,
+    /// Prefer endpoints with the lowest exponentially weighted moving
+    /// average (EWMA) of observed latency, scaled by in-flight requests.
+    LatencyWeighted,
+}
+
+impl std::fmt::Display for LoadBalancingStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadBalancingStrategy::RoundRobin => write!(f, "round-robin"),
+            LoadBalancingStrategy::LeastOutstanding => write!(f, "least-outstanding"),
+            LoadBalancingStrategy::LatencyWeighted => write!(f, "latency-weighted"),
+        }
+    }
+}
+
+/// Outlier detection configuration for multi-endpoint clients.
+///
+/// An endpoint is ejected after `consecutive_failures` failed requests in a
+/// row. The ejection lasts `base_ejection_time` multiplied by the number of
+/// times the endpoint has been ejected, capped at `max_ejection_time`. Once
+/// the ejection expires the endpoint is readmitted and receives traffic again.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::OutlierDetectionConfig;
+/// use std::time::Duration;
+///
+/// let config = OutlierDetectionConfig::builder()
+///     .consecutive_failures(5)
+///     .base_ejection_time(Duration::from_secs(30))
+///     .max_ejection_time(Duration::from_secs(300))
+///     .build();
+/// ```
+#[derive(Debug, Clone, bon::Builder)]
+pub struct OutlierDetectionConfig {
+    /// Number of consecutive failures before an endpoint is ejected.
+    #[builder(default = 5)]
+    consecutive_failures: u32,
+
+    /// Base duration an ejected endpoint is kept out of rotation.
+    #[builder(default = Duration::from_secs(30))]
+    base_ejection_time: Duration,
+
+    /// Upper bound on the ejection duration for repeatedly failing endpoints.
+    #[builder(default = Duration::from_secs(300))]
+    max_ejection_time: Duration,
+
+    /// Maximum percentage of endpoints that may be ejected at once.
+    ///
+    /// At least one endpoint always remains in rotation.
+    #[builder(default = 50)]
+    max_ejection_percent: u8,
+}
+
+impl Default for OutlierDetectionConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl OutlierDetectionConfig {
+    /// Creates a configuration that never ejects endpoints.
+    pub fn disabled() -> Self {
+        Self::builder().consecutive_failures(0).max_ejection_percent(0).build()
+    }
+
+    /// Returns the consecutive failure threshold.
+    pub fn get_consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Returns the base ejection time.
+    pub fn get_base_ejection_time(&self) -> Duration {
+        self.base_ejection_time
+    }
+
+    /// Returns the maximum ejection time.
+    pub fn get_max_ejection_time(&self) -> Duration {
+        self.max_ejection_time
+    }
+
+    /// Returns the maximum percentage of endpoints that may be ejected.
+    pub fn get_max_ejection_percent(&self) -> u8 {
+        self.max_ejection_percent
+    }
+
+    /// Returns `true` if outlier detection is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.consecutive_failures > 0 && self.max_ejection_percent > 0
+    }
+
+    /// Returns the ejection duration for an endpoint ejected `ejections` times.
+    pub fn ejection_time(&self, ejections: u32) -> Duration {
+        self.base_ejection_time.saturating_mul(ejections.max(1)).min(self.max_ejection_time)
+    }
+
+    /// Returns whether an error of this kind counts against an endpoint.
+    ///
+    /// Only errors that indicate the endpoint itself is unhealthy count;
+    /// application errors such as `NotFound` or `Forbidden` do not.
+    pub fn is_endpoint_failure(&self, kind: ErrorKind) -> bool {
+        matches!(
+            kind,
+            ErrorKind::Unavailable
+                | ErrorKind::Timeout
+                | ErrorKind::Connection
+                | ErrorKind::Protocol
+                | ErrorKind::Transport
+                | ErrorKind::Internal
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy_default() {
+        assert_eq!(LoadBalancingStrategy::default(), LoadBalancingStrategy::RoundRobin);
+    }
+
+    #[test]
+    fn test_strategy_display() {
+        assert_eq!(LoadBalancingStrategy::RoundRobin.to_string(), "round-robin");
+        assert_eq!(LoadBalancingStrategy::LeastOutstanding.to_string(), "least-outstanding");
+        assert_eq!(LoadBalancingStrategy::LatencyWeighted.to_string(), "latency-weighted");
+    }
+
+    #[test]
+    fn test_outlier_default() {
+        let config = OutlierDetectionConfig::default();
+        assert_eq!(config.get_consecutive_failures(), 5);
+        assert_eq!(config.get_base_ejection_time(), Duration::from_secs(30));
+        assert_eq!(config.get_max_ejection_time(), Duration::from_secs(300));
+        assert_eq!(config.get_max_ejection_percent(), 50);
+        assert!(config.is_enabled());
+    }
+
+    #[test]
+    fn test_outlier_disabled() {
+        assert!(!OutlierDetectionConfig::disabled().is_enabled());
+    }
+
+    #[test]
+    fn test_ejection_time_backoff() {
+        let config = OutlierDetectionConfig::builder()
+            .base_ejection_time(Duration::from_secs(10))
+            .max_ejection_time(Duration::from_secs(25))
+            .build();
+
+        assert_eq!(config.ejection_time(0), Duration::from_secs(10));
+        assert_eq!(config.ejection_time(1), Duration::from_secs(10));
+        assert_eq!(config.ejection_time(2), Duration::from_secs(20));
+        assert_eq!(config.ejection_time(3), Duration::from_secs(25));
+    }
+
+    #[test]
+    fn test_endpoint_failure_kinds() {
+        let config = OutlierDetectionConfig::default();
+        assert!(config.is_endpoint_failure(ErrorKind::Unavailable));
+        assert!(config.is_endpoint_failure(ErrorKind::Connection));
+        assert!(config.is_endpoint_failure(ErrorKind::Timeout));
+        assert!(!config.is_endpoint_failure(ErrorKind::NotFound));
+        assert!(!config.is_endpoint_failure(ErrorKind::Forbidden));
+    }
+}
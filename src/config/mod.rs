@@ -6,10 +6,12 @@
 //! - [`TlsConfig`]: TLS/SSL settings
 //! - [`DegradationConfig`]: Graceful degradation behavior
 //! - [`CircuitBreakerConfig`]: Circuit breaker for resilience
+//! - [`OutlierDetectionConfig`]: Endpoint ejection for multi-endpoint clients
 
 mod cache;
 mod circuit_breaker;
 mod degradation;
+mod load_balancing;
 mod retry;
 mod tls;
 
@@ -18,5 +20,6 @@ pub use circuit_breaker::{
     CircuitBreakerConfig, CircuitEvent, CircuitState, CircuitStats, FailurePredicate,
 };
 pub use degradation::{DegradationConfig, FailureMode};
+pub use load_balancing::{LoadBalancingStrategy, OutlierDetectionConfig};
 pub use retry::RetryConfig;
 pub use tls::TlsConfig;
@@ -1,11 +1,16 @@
 //! Audit log management for the control plane.
 
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     Error,
     client::Client,
-    control::{Page, SortOrder},
+    control::{
+        Page, SortOrder,
+        audit_follow::FollowAuditLogsRequest,
+        pagination::{self, Paginated},
+    },
 };
 
 /// Client for querying audit logs.
@@ -101,6 +106,21 @@ impl AuditLogsClient {
         Err(Error::configuration("REST feature is required for control API"))
     }
 
+    /// Follows new audit log events as they are written.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// let mut events = org.audit().follow().resume_from(saved_checkpoint).stream();
+    /// while let Some(event) = events.try_next().await? {
+    ///     forward(&event).await?;
+    ///     save(events.checkpoint())?;
+    /// }
+    /// ```
+    pub fn follow(&self) -> FollowAuditLogsRequest {
+        FollowAuditLogsRequest::new(self.clone())
+    }
+
     /// Exports audit logs to a file or stream.
     ///
     /// ## Example
@@ -361,6 +381,7 @@ pub enum ExportFormat {
 }
 
 /// Request to list audit log events.
+#[derive(Clone)]
 pub struct ListAuditLogsRequest {
     client: Client,
     organization_id: String,
@@ -397,6 +418,29 @@ impl ListAuditLogsRequest {
         self
     }
 
+    /// Returns a stream over the audit events of all pages.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<AuditEvent, Error>> + Send + 'static {
+        pagination::items(self)
+    }
+
+    /// Returns a stream of pages, following cursors to the last page.
+    pub fn pages(self) -> impl Stream<Item = Result<Page<AuditEvent>, Error>> + Send + 'static {
+        pagination::pages(self)
+    }
+
+    /// Collects the audit events of all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if there are more than `max_items`
+    /// audit events, or the error of the first page that fails.
+    pub async fn collect_all(self, max_items: usize) -> Result<Vec<AuditEvent>, Error> {
+        pagination::collect_all(self, max_items).await
+    }
+
     /// Sets the sort order.
     #[must_use]
     pub fn sort(mut self, order: SortOrder) -> Self {
@@ -451,25 +495,25 @@ impl ListAuditLogsRequest {
             query_params.push(format!("limit={}", limit));
         }
         if let Some(ref cursor) = self.cursor {
-            query_params.push(format!("cursor={}", cursor));
+            query_params.push(format!("cursor={}", urlencoding::encode(cursor)));
         }
         if let Some(ref sort) = self.sort {
             query_params.push(format!("sort={}", sort.as_str()));
         }
         if let Some(ref actor) = self.actor {
-            query_params.push(format!("actor={}", actor));
+            query_params.push(format!("actor={}", urlencoding::encode(actor)));
         }
         if let Some(ref action) = self.action {
             query_params.push(format!("action={}", action));
         }
         if let Some(ref resource) = self.resource {
-            query_params.push(format!("resource={}", resource));
+            query_params.push(format!("resource={}", urlencoding::encode(resource)));
         }
         if let Some(ref after) = self.after {
-            query_params.push(format!("after={}", after.to_rfc3339()));
+            query_params.push(format!("after={}", urlencoding::encode(&after.to_rfc3339())));
         }
         if let Some(ref before) = self.before {
-            query_params.push(format!("before={}", before.to_rfc3339()));
+            query_params.push(format!("before={}", urlencoding::encode(&before.to_rfc3339())));
         }
 
         if !query_params.is_empty() {
@@ -495,6 +539,14 @@ impl std::future::IntoFuture for ListAuditLogsRequest {
     }
 }
 
+impl Paginated for ListAuditLogsRequest {
+    type Item = AuditEvent;
+
+    fn with_cursor(self, cursor: String) -> Self {
+        self.cursor(cursor)
+    }
+}
+
 /// Request to export audit logs.
 pub struct ExportAuditLogsRequest {
     client: Client,
@@ -620,75 +672,25 @@ impl ExportAuditLogsRequest {
         Err(Error::configuration("REST feature is required for control API"))
     }
 
-    /// Returns a stream of events.
-    #[cfg(feature = "rest")]
-    pub fn stream(self) -> impl futures::Stream<Item = Result<AuditEvent, Error>> + Send + 'static {
-        use futures::StreamExt;
-
-        let client = self.client.clone();
-        let organization_id = self.organization_id.clone();
-        let vault_id = self.vault_id.clone();
-        let after = self.after;
-        let before = self.before;
-
-        futures::stream::unfold(
-            (client, organization_id, vault_id, after, before, None::<String>, false),
-            |(client, org_id, vault_id, after, before, cursor, done)| async move {
-                if done {
-                    return None;
-                }
-
-                let mut path = format!("/control/v1/organizations/{}/audit-logs", org_id);
-                let mut query_params = Vec::new();
-
-                if let Some(ref vault_id) = vault_id {
-                    query_params.push(format!("vault_id={}", vault_id));
-                }
-                if let Some(ref after) = after {
-                    query_params.push(format!("after={}", after.to_rfc3339()));
-                }
-                if let Some(ref before) = before {
-                    query_params.push(format!("before={}", before.to_rfc3339()));
-                }
-                if let Some(ref cursor) = cursor {
-                    query_params.push(format!("cursor={}", cursor));
-                }
-
-                if !query_params.is_empty() {
-                    path.push('?');
-                    path.push_str(&query_params.join("&"));
-                }
-
-                let result: Result<Page<AuditEvent>, Error> =
-                    client.inner().control_get(&path).await;
-
-                match result {
-                    Ok(page) => {
-                        let next_cursor = page.next_cursor().map(|s| s.to_string());
-                        let is_done = next_cursor.is_none();
-                        let events: Vec<Result<AuditEvent, Error>> =
-                            page.items.into_iter().map(Ok).collect();
-                        Some((
-                            futures::stream::iter(events),
-                            (client, org_id, vault_id, after, before, next_cursor, is_done),
-                        ))
-                    },
-                    Err(e) => Some((
-                        futures::stream::iter(vec![Err(e)]),
-                        (client, org_id, vault_id, after, before, None, true),
-                    )),
-                }
-            },
-        )
-        .flatten()
-    }
-
-    /// Returns a stream of events.
-    #[cfg(not(feature = "rest"))]
-    pub fn stream(self) -> impl futures::Stream<Item = Result<AuditEvent, Error>> + Send + 'static {
-        futures::stream::once(async {
-            Err(Error::configuration("REST feature is required for control API"))
-        })
+    /// Returns a stream of events, following cursors to the last page.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<AuditEvent, Error>> + Send + 'static {
+        let request = ListAuditLogsRequest {
+            client: self.client,
+            organization_id: self.organization_id,
+            vault_id: self.vault_id,
+            limit: None,
+            cursor: None,
+            sort: None,
+            actor: None,
+            action: None,
+            resource: None,
+            after: self.after,
+            before: self.before,
+        };
+        request.stream()
     }
 }
 
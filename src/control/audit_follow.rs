@@ -0,0 +1,392 @@
+//! Tailing audit logs as they are written.
+
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::Stream;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, ErrorKind,
+    control::{AuditAction, AuditEvent, AuditLogsClient, SortOrder},
+};
+
+/// Number of recent event IDs remembered for deduplication.
+const RECENT_IDS: usize = 256;
+
+/// Position of an [`AuditFollowStream`], for resuming after a restart.
+///
+/// The checkpoint only covers events the stream has yielded, so an event
+/// that was fetched but not yet consumed is delivered again on resume.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCheckpoint {
+    /// Cursor to continue from, if the server returned one.
+    pub cursor: Option<String>,
+    /// Timestamp of the last yielded event.
+    pub last_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// IDs of the most recently yielded events, oldest first.
+    #[serde(default)]
+    pub recent_ids: Vec<String>,
+}
+
+impl AuditCheckpoint {
+    fn has_seen(&self, event: &AuditEvent) -> bool {
+        self.last_timestamp.is_some_and(|last| event.timestamp < last)
+            || self.recent_ids.contains(&event.id)
+    }
+
+    fn record(&mut self, event: &AuditEvent) {
+        self.last_timestamp =
+            Some(self.last_timestamp.map_or(event.timestamp, |last| last.max(event.timestamp)));
+        if self.recent_ids.len() == RECENT_IDS {
+            self.recent_ids.remove(0);
+        }
+        self.recent_ids.push(event.id.clone());
+    }
+}
+
+/// Request to follow new audit log events.
+///
+/// Created by [`AuditLogsClient::follow`].
+pub struct FollowAuditLogsRequest {
+    audit: AuditLogsClient,
+    vault_id: Option<String>,
+    actor: Option<String>,
+    action: Option<AuditAction>,
+    limit: Option<usize>,
+    poll_interval: Duration,
+    checkpoint: AuditCheckpoint,
+}
+
+impl FollowAuditLogsRequest {
+    pub(crate) fn new(audit: AuditLogsClient) -> Self {
+        Self {
+            audit,
+            vault_id: None,
+            actor: None,
+            action: None,
+            limit: None,
+            poll_interval: Duration::from_secs(5),
+            checkpoint: AuditCheckpoint::default(),
+        }
+    }
+
+    /// Filters by vault ID.
+    #[must_use]
+    pub fn vault(mut self, vault_id: impl Into<String>) -> Self {
+        self.vault_id = Some(vault_id.into());
+        self
+    }
+
+    /// Filters by actor ID.
+    #[must_use]
+    pub fn actor(mut self, actor_id: impl Into<String>) -> Self {
+        self.actor = Some(actor_id.into());
+        self
+    }
+
+    /// Filters by action type.
+    #[must_use]
+    pub fn action(mut self, action: AuditAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    /// Sets the page size of each poll.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets how long to wait between polls once caught up (default: 5s).
+    #[must_use]
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Resumes from a checkpoint saved by an earlier stream.
+    #[must_use]
+    pub fn resume_from(mut self, checkpoint: AuditCheckpoint) -> Self {
+        self.checkpoint = checkpoint;
+        self
+    }
+
+    /// Starts following.
+    ///
+    /// The stream never ends on its own: errors are yielded and polling
+    /// continues after the poll interval, except for authentication,
+    /// permission and invalid-request errors, which end the stream.
+    pub fn stream(self) -> AuditFollowStream {
+        let checkpoint = Arc::new(Mutex::new(self.checkpoint.clone()));
+        let state = FollowState {
+            cursor: self.checkpoint.cursor.clone(),
+            request: self,
+            buffer: VecDeque::new(),
+            checkpoint: Arc::clone(&checkpoint),
+            wait: false,
+            ended: false,
+        };
+        let inner = futures::stream::unfold(state, |mut state| async move {
+            let item = state.next().await?;
+            Some((item, state))
+        });
+        AuditFollowStream { inner: Box::pin(inner), checkpoint }
+    }
+}
+
+impl std::fmt::Debug for FollowAuditLogsRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FollowAuditLogsRequest")
+            .field("organization_id", &self.audit.organization_id())
+            .field("vault_id", &self.vault_id)
+            .field("poll_interval", &self.poll_interval)
+            .field("checkpoint", &self.checkpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+struct FollowState {
+    request: FollowAuditLogsRequest,
+    /// Cursor for the next poll, ahead of the checkpoint while the buffer
+    /// holds fetched events.
+    cursor: Option<String>,
+    buffer: VecDeque<AuditEvent>,
+    checkpoint: Arc<Mutex<AuditCheckpoint>>,
+    /// Whether to wait for the poll interval before the next poll.
+    wait: bool,
+    ended: bool,
+}
+
+impl FollowState {
+    async fn next(&mut self) -> Option<Result<AuditEvent, Error>> {
+        loop {
+            if let Some(event) = self.buffer.pop_front() {
+                let mut checkpoint = self.checkpoint.lock();
+                checkpoint.record(&event);
+                if self.buffer.is_empty() {
+                    checkpoint.cursor = self.cursor.clone();
+                }
+                return Some(Ok(event));
+            }
+            if self.ended {
+                return None;
+            }
+            if self.wait {
+                tokio::time::sleep(self.request.poll_interval).await;
+            }
+            if let Err(e) = self.poll().await {
+                self.wait = true;
+                self.ended = matches!(
+                    e.kind(),
+                    ErrorKind::Unauthorized
+                        | ErrorKind::Forbidden
+                        | ErrorKind::NotFound
+                        | ErrorKind::InvalidArgument
+                );
+                return Some(Err(e));
+            }
+        }
+    }
+
+    /// Fetches one page of events newer than the checkpoint.
+    async fn poll(&mut self) -> Result<(), Error> {
+        let request = &self.request;
+        let mut list = request.audit.list().sort(SortOrder::Ascending);
+        if let Some(vault_id) = &request.vault_id {
+            list = list.vault(vault_id);
+        }
+        if let Some(actor) = &request.actor {
+            list = list.actor(actor);
+        }
+        if let Some(action) = request.action {
+            list = list.action(action);
+        }
+        if let Some(limit) = request.limit {
+            list = list.limit(limit);
+        }
+        // A cursor pins the position exactly; the timestamp is the fallback
+        // for servers that stop returning cursors at the end of the log
+        let last_timestamp = self.checkpoint.lock().last_timestamp;
+        list = match (&self.cursor, last_timestamp) {
+            (Some(cursor), _) => list.cursor(cursor),
+            (None, Some(after)) => list.after(after),
+            (None, None) => list,
+        };
+
+        let page = list.await?;
+        let checkpoint = self.checkpoint.lock();
+        self.buffer.extend(page.items.into_iter().filter(|e| !checkpoint.has_seen(e)));
+        self.wait = !page.page_info.has_next;
+        if let Some(cursor) = page.page_info.next_cursor {
+            self.cursor = Some(cursor);
+        } else if !page.page_info.has_next {
+            self.cursor = None;
+        }
+        drop(checkpoint);
+        if self.buffer.is_empty() {
+            self.checkpoint.lock().cursor = self.cursor.clone();
+        }
+        Ok(())
+    }
+}
+
+/// A never-ending stream of new audit log events.
+///
+/// Events are deduplicated by ID. Save [`checkpoint`](Self::checkpoint)
+/// after handling each event and pass it to
+/// [`FollowAuditLogsRequest::resume_from`] to continue after a restart.
+pub struct AuditFollowStream {
+    inner: Pin<Box<dyn Stream<Item = Result<AuditEvent, Error>> + Send>>,
+    checkpoint: Arc<Mutex<AuditCheckpoint>>,
+}
+
+impl AuditFollowStream {
+    /// Returns the position after the last yielded event.
+    pub fn checkpoint(&self) -> AuditCheckpoint {
+        self.checkpoint.lock().clone()
+    }
+}
+
+impl Stream for AuditFollowStream {
+    type Item = Result<AuditEvent, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl std::fmt::Debug for AuditFollowStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditFollowStream")
+            .field("checkpoint", &self.checkpoint())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "rest"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod wiremock_tests {
+    use futures::StreamExt;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path, query_param},
+    };
+
+    use super::*;
+    use crate::{Client, auth::BearerCredentialsConfig};
+
+    const AUDIT_LOGS: &str = "/control/v1/organizations/org_123/audit-logs";
+
+    async fn follow(server: &MockServer) -> FollowAuditLogsRequest {
+        let client = Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test_token"))
+            .build()
+            .await
+            .unwrap();
+        AuditLogsClient::new(client, "org_123").follow().poll_interval(Duration::from_millis(10))
+    }
+
+    fn event(id: &str, second: u32) -> serde_json::Value {
+        json!({
+            "id": id,
+            "organization_id": "org_123",
+            "timestamp": format!("2024-01-01T00:00:{:02}Z", second),
+            "actor": { "id": "user_123", "actor_type": "user" },
+            "action": "relationship_write",
+            "outcome": "success"
+        })
+    }
+
+    fn page(items: serde_json::Value, next_cursor: Option<&str>) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "items": items,
+            "page_info": { "has_next": false, "next_cursor": next_cursor }
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_follow_polls_with_cursor_and_deduplicates() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(AUDIT_LOGS))
+            .and(query_param("cursor", "tail_1"))
+            .respond_with(page(json!([event("evt_2", 2), event("evt_3", 3)]), Some("tail_2")))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(AUDIT_LOGS))
+            .and(query_param("cursor", "tail_2"))
+            .respond_with(page(json!([]), Some("tail_2")))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(AUDIT_LOGS))
+            .and(query_param("sort", "asc"))
+            .respond_with(page(json!([event("evt_1", 1), event("evt_2", 2)]), Some("tail_1")))
+            .mount(&server)
+            .await;
+
+        let mut stream = follow(&server).await.stream();
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            ids.push(stream.next().await.unwrap().unwrap().id);
+        }
+        assert_eq!(ids, ["evt_1", "evt_2", "evt_3"]);
+
+        let checkpoint = stream.checkpoint();
+        assert_eq!(checkpoint.cursor.as_deref(), Some("tail_2"));
+        assert_eq!(checkpoint.last_timestamp.unwrap().to_rfc3339(), "2024-01-01T00:00:03+00:00");
+        assert_eq!(checkpoint.recent_ids, ["evt_1", "evt_2", "evt_3"]);
+    }
+
+    #[tokio::test]
+    async fn test_follow_resumes_from_checkpoint() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(AUDIT_LOGS))
+            .and(query_param("after", "2024-01-01T00:00:02+00:00"))
+            .respond_with(page(json!([event("evt_2", 2), event("evt_3", 3)]), None))
+            .mount(&server)
+            .await;
+
+        let checkpoint = AuditCheckpoint {
+            cursor: None,
+            last_timestamp: Some("2024-01-01T00:00:02Z".parse().unwrap()),
+            recent_ids: vec!["evt_2".into()],
+        };
+        let mut stream = follow(&server).await.resume_from(checkpoint).stream();
+        assert_eq!(stream.next().await.unwrap().unwrap().id, "evt_3");
+    }
+
+    #[tokio::test]
+    async fn test_follow_retries_server_errors_and_stops_on_auth_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(AUDIT_LOGS))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(AUDIT_LOGS))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let mut stream = follow(&server).await.stream();
+        assert_eq!(stream.next().await.unwrap().unwrap_err().kind(), ErrorKind::Internal);
+        assert_eq!(stream.next().await.unwrap().unwrap_err().kind(), ErrorKind::Unauthorized);
+        assert!(stream.next().await.is_none());
+    }
+}
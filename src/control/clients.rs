@@ -3,12 +3,16 @@
 //! Provides operations for managing API clients (service accounts)
 //! and their certificates for programmatic access.
 
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     Error,
     client::Client,
-    control::{Page, SortOrder},
+    control::{
+        Page, SortOrder,
+        pagination::{self, Paginated},
+    },
 };
 
 /// Client for managing API clients within an organization.
@@ -385,6 +389,7 @@ impl UpdateApiClientRequest {
 }
 
 /// Request to list API clients.
+#[derive(Clone)]
 pub struct ListApiClientsRequest {
     client: Client,
     organization_id: String,
@@ -409,6 +414,29 @@ impl ListApiClientsRequest {
         self
     }
 
+    /// Returns a stream over the API clients of all pages.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<ApiClient, Error>> + Send + 'static {
+        pagination::items(self)
+    }
+
+    /// Returns a stream of pages, following cursors to the last page.
+    pub fn pages(self) -> impl Stream<Item = Result<Page<ApiClient>, Error>> + Send + 'static {
+        pagination::pages(self)
+    }
+
+    /// Collects the API clients of all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if there are more than `max_items`
+    /// API clients, or the error of the first page that fails.
+    pub async fn collect_all(self, max_items: usize) -> Result<Vec<ApiClient>, Error> {
+        pagination::collect_all(self, max_items).await
+    }
+
     /// Sets the sort order.
     #[must_use]
     pub fn sort(mut self, order: SortOrder) -> Self {
@@ -468,6 +496,14 @@ impl std::future::IntoFuture for ListApiClientsRequest {
     }
 }
 
+impl Paginated for ListApiClientsRequest {
+    type Item = ApiClient;
+
+    fn with_cursor(self, cursor: String) -> Self {
+        self.cursor(cursor)
+    }
+}
+
 /// A certificate associated with an API client.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClientCertificate {
@@ -0,0 +1,520 @@
+//! Reference documentation and graphs generated from a schema.
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt::{self, Write as _},
+};
+
+use super::{Expr, Schema, TypeDef};
+
+/// A relation or permission of a type, such as `document#view`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemberRef {
+    /// The type name.
+    pub type_name: String,
+    /// The relation or permission name.
+    pub name: String,
+}
+
+impl MemberRef {
+    fn new(type_name: &str, name: &str) -> Self {
+        Self { type_name: type_name.to_owned(), name: name.to_owned() }
+    }
+}
+
+impl fmt::Display for MemberRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.type_name, self.name)
+    }
+}
+
+/// Output format for [`Schema::type_graph`] and [`Schema::permission_graph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphFormat {
+    /// A Mermaid flowchart, which GitHub and most wikis render inline.
+    #[default]
+    Mermaid,
+    /// A Graphviz `digraph`.
+    Dot,
+}
+
+/// A member that another member is computed from.
+struct Dependency {
+    from: MemberRef,
+    to: MemberRef,
+    /// Whether `to` is excluded (`a - b`) rather than granting `from`.
+    excluded: bool,
+}
+
+impl Schema {
+    /// Returns the relations that grant `type_name#permission`, following
+    /// permissions, arrows and subject sets across types.
+    ///
+    /// Excluded operands (`- banned`) do not grant access and are not
+    /// included. Relations that are only part of an intersection are.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inferadb::control::ipl::Schema;
+    ///
+    /// let schema = Schema::parse(
+    ///     "type user {}
+    ///      type team { relation member: user }
+    ///      type project {
+    ///          relation owner: user
+    ///          relation admin: user | team#member
+    ///          permission manage = owner | admin
+    ///      }",
+    /// )?;
+    ///
+    /// let includes: Vec<String> =
+    ///     schema.includes("project", "manage").iter().map(ToString::to_string).collect();
+    /// assert_eq!(includes, ["project#admin", "project#owner", "team#member"]);
+    /// # Ok::<(), inferadb::Error>(())
+    /// ```
+    pub fn includes(&self, type_name: &str, permission: &str) -> Vec<MemberRef> {
+        let dependencies = self.dependencies();
+        let mut seen = HashSet::new();
+        let mut stack = vec![MemberRef::new(type_name, permission)];
+        let mut relations = BTreeSet::new();
+        while let Some(member) = stack.pop() {
+            if !seen.insert(member.clone()) {
+                continue;
+            }
+            for dependency in dependencies.iter().filter(|d| d.from == member && !d.excluded) {
+                if self.is_relation(&dependency.to) {
+                    relations.insert(dependency.to.clone());
+                }
+                stack.push(dependency.to.clone());
+            }
+        }
+        relations.into_iter().collect()
+    }
+
+    /// Returns the permissions that `type_name#relation` grants, directly or
+    /// through other permissions, arrows and subject sets. This is the
+    /// inverse of [`includes`](Self::includes).
+    pub fn used_by(&self, type_name: &str, relation: &str) -> Vec<MemberRef> {
+        let dependencies = self.dependencies();
+        let mut seen = HashSet::new();
+        let mut stack = vec![MemberRef::new(type_name, relation)];
+        let mut permissions = BTreeSet::new();
+        while let Some(member) = stack.pop() {
+            if !seen.insert(member.clone()) {
+                continue;
+            }
+            for dependency in dependencies.iter().filter(|d| d.to == member && !d.excluded) {
+                if !self.is_relation(&dependency.from) {
+                    permissions.insert(dependency.from.clone());
+                }
+                stack.push(dependency.from.clone());
+            }
+        }
+        permissions.into_iter().collect()
+    }
+
+    /// Renders Markdown reference documentation: one section per type with
+    /// its `///` documentation, its relations with the subject types they
+    /// accept and the permissions they grant, and its permissions with their
+    /// expressions and the relations they include.
+    ///
+    /// Sections start at `##` so the output can follow a title of your own.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for (i, def) in self.types.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            self.type_markdown(def, &mut out);
+        }
+        out
+    }
+
+    /// Renders the types as nodes, with an edge for each relation that a
+    /// permission follows with `->`, labelled with the relation name.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inferadb::control::ipl::{GraphFormat, Schema};
+    ///
+    /// let schema = Schema::parse(
+    ///     "type folder { relation viewer: user  permission view = viewer }
+    ///      type document { relation parent: folder  permission view = parent->view }",
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     schema.type_graph(GraphFormat::Mermaid),
+    ///     "flowchart LR\n    folder\n    document\n    document -->|parent| folder\n"
+    /// );
+    /// # Ok::<(), inferadb::Error>(())
+    /// ```
+    pub fn type_graph(&self, format: GraphFormat) -> String {
+        let mut edges = Vec::new();
+        for def in &self.types {
+            for permission in &def.permissions {
+                permission.expr.visit(&mut |expr| {
+                    let Expr::Arrow { relation, .. } = expr else {
+                        return;
+                    };
+                    for subject in def.relation(relation).iter().flat_map(|r| &r.subjects) {
+                        let edge =
+                            (def.name.as_str(), relation.as_str(), subject.type_name.as_str());
+                        if !edges.contains(&edge) {
+                            edges.push(edge);
+                        }
+                    }
+                });
+            }
+        }
+
+        let mut out = String::new();
+        match format {
+            GraphFormat::Mermaid => {
+                out.push_str("flowchart LR\n");
+                for def in &self.types {
+                    let _ = writeln!(out, "    {}", def.name);
+                }
+                for (from, label, to) in edges {
+                    let _ = writeln!(out, "    {} -->|{}| {}", from, label, to);
+                }
+            },
+            GraphFormat::Dot => {
+                out.push_str("digraph schema {\n    rankdir=LR;\n    node [shape=box];\n");
+                for def in &self.types {
+                    let _ = writeln!(out, "    \"{}\";", def.name);
+                }
+                for (from, label, to) in edges {
+                    let _ = writeln!(out, "    \"{}\" -> \"{}\" [label=\"{}\"];", from, to, label);
+                }
+                out.push_str("}\n");
+            },
+        }
+        out
+    }
+
+    /// Renders every relation and permission as a node, grouped by type,
+    /// with an edge from each member to the permissions computed from it.
+    /// Relations are drawn as boxes and permissions as rounded nodes;
+    /// excluded operands are drawn with dashed edges.
+    pub fn permission_graph(&self, format: GraphFormat) -> String {
+        let dependencies = self.dependencies();
+        let mut out = String::new();
+        match format {
+            GraphFormat::Mermaid => {
+                out.push_str("flowchart LR\n");
+                for def in &self.types {
+                    let _ = writeln!(out, "    subgraph {}", def.name);
+                    for relation in &def.relations {
+                        let _ = writeln!(
+                            out,
+                            "        {}[\"{}\"]",
+                            mermaid_id(&def.name, &relation.name),
+                            relation.name
+                        );
+                    }
+                    for permission in &def.permissions {
+                        let _ = writeln!(
+                            out,
+                            "        {}(\"{}\")",
+                            mermaid_id(&def.name, &permission.name),
+                            permission.name
+                        );
+                    }
+                    out.push_str("    end\n");
+                }
+                for dependency in &dependencies {
+                    let arrow = if dependency.excluded { "-.->|excluded|" } else { "-->" };
+                    let _ = writeln!(
+                        out,
+                        "    {} {} {}",
+                        mermaid_id(&dependency.to.type_name, &dependency.to.name),
+                        arrow,
+                        mermaid_id(&dependency.from.type_name, &dependency.from.name)
+                    );
+                }
+            },
+            GraphFormat::Dot => {
+                out.push_str("digraph permissions {\n    rankdir=LR;\n");
+                for def in &self.types {
+                    let _ = writeln!(out, "    subgraph \"cluster_{}\" {{", def.name);
+                    let _ = writeln!(out, "        label=\"{}\";", def.name);
+                    for relation in &def.relations {
+                        let _ = writeln!(
+                            out,
+                            "        \"{}#{}\" [label=\"{}\", shape=box];",
+                            def.name, relation.name, relation.name
+                        );
+                    }
+                    for permission in &def.permissions {
+                        let _ = writeln!(
+                            out,
+                            "        \"{}#{}\" [label=\"{}\", shape=ellipse];",
+                            def.name, permission.name, permission.name
+                        );
+                    }
+                    out.push_str("    }\n");
+                }
+                for dependency in &dependencies {
+                    let style = if dependency.excluded {
+                        " [style=dashed, label=\"excluded\"]"
+                    } else {
+                        ""
+                    };
+                    let _ = writeln!(
+                        out,
+                        "    \"{}\" -> \"{}\"{};",
+                        dependency.to, dependency.from, style
+                    );
+                }
+                out.push_str("}\n");
+            },
+        }
+        out
+    }
+
+    fn is_relation(&self, member: &MemberRef) -> bool {
+        self.get_type(&member.type_name).and_then(|t| t.relation(&member.name)).is_some()
+    }
+
+    fn exists(&self, type_name: &str, name: &str) -> bool {
+        self.get_type(type_name)
+            .is_some_and(|t| t.relation(name).is_some() || t.permission(name).is_some())
+    }
+
+    /// Returns the direct dependencies of every member, in source order.
+    /// References to undefined members are skipped.
+    fn dependencies(&self) -> Vec<Dependency> {
+        let mut dependencies: Vec<Dependency> = Vec::new();
+        let mut push = |from: MemberRef, to: MemberRef, excluded: bool| {
+            if !dependencies.iter().any(|d| d.from == from && d.to == to && d.excluded == excluded)
+            {
+                dependencies.push(Dependency { from, to, excluded });
+            }
+        };
+
+        for def in &self.types {
+            for relation in &def.relations {
+                for subject in &relation.subjects {
+                    if let Some(set) = &subject.relation
+                        && self.exists(&subject.type_name, set)
+                    {
+                        push(
+                            MemberRef::new(&def.name, &relation.name),
+                            MemberRef::new(&subject.type_name, set),
+                            false,
+                        );
+                    }
+                }
+            }
+            for permission in &def.permissions {
+                let mut references = Vec::new();
+                operands(&permission.expr, false, &mut references);
+                for (expr, excluded) in references {
+                    let from = MemberRef::new(&def.name, &permission.name);
+                    match expr {
+                        Expr::Ref(name) if self.exists(&def.name, name) => {
+                            push(from, MemberRef::new(&def.name, name), excluded);
+                        },
+                        Expr::Arrow { relation, permission: target } => {
+                            let Some(relation) = def.relation(relation) else {
+                                continue;
+                            };
+                            push(from.clone(), MemberRef::new(&def.name, &relation.name), excluded);
+                            for subject in &relation.subjects {
+                                if self.exists(&subject.type_name, target) {
+                                    push(
+                                        from.clone(),
+                                        MemberRef::new(&subject.type_name, target),
+                                        excluded,
+                                    );
+                                }
+                            }
+                        },
+                        _ => {},
+                    }
+                }
+            }
+        }
+        dependencies
+    }
+
+    fn type_markdown(&self, def: &TypeDef, out: &mut String) {
+        let _ = writeln!(out, "## `{}`\n", def.name);
+        if let Some(doc) = def.doc() {
+            let _ = writeln!(out, "{}\n", doc);
+        }
+        // Members of this type first, by their short name
+        let list = |mut members: Vec<MemberRef>| {
+            members.sort_by_key(|m| m.type_name != def.name);
+            let names: Vec<String> = members
+                .iter()
+                .map(|m| {
+                    if m.type_name == def.name {
+                        format!("`{}`", m.name)
+                    } else {
+                        format!("`{}`", m)
+                    }
+                })
+                .collect();
+            if names.is_empty() { "nothing".to_owned() } else { names.join(", ") }
+        };
+
+        if !def.relations.is_empty() {
+            out.push_str("### Relations\n\n");
+            for relation in &def.relations {
+                let _ = writeln!(out, "#### `{}`\n", relation.name);
+                if let Some(doc) = relation.doc() {
+                    let _ = writeln!(out, "{}\n", doc);
+                }
+                let subjects: Vec<String> =
+                    relation.subjects.iter().map(|s| format!("`{}`", s)).collect();
+                let _ = writeln!(out, "- Subjects: {}", subjects.join(", "));
+                let _ =
+                    writeln!(out, "- Grants: {}\n", list(self.used_by(&def.name, &relation.name)));
+            }
+        }
+
+        if !def.permissions.is_empty() {
+            out.push_str("### Permissions\n\n");
+            for permission in &def.permissions {
+                let _ = writeln!(out, "#### `{}`\n", permission.name);
+                if let Some(doc) = permission.doc() {
+                    let _ = writeln!(out, "{}\n", doc);
+                }
+                let _ = writeln!(out, "- Expression: `{}`", permission.expr);
+                let _ = writeln!(
+                    out,
+                    "- Includes: {}\n",
+                    list(self.includes(&def.name, &permission.name))
+                );
+            }
+        }
+
+        // Drop the blank line after the last list
+        if out.ends_with("\n\n") {
+            out.pop();
+        }
+    }
+}
+
+/// Collects the references in `expr`, marking those on the excluded side of
+/// an exclusion.
+fn operands<'a>(expr: &'a Expr, excluded: bool, out: &mut Vec<(&'a Expr, bool)>) {
+    match expr {
+        Expr::Union(operands) | Expr::Intersection(operands) => {
+            operands.iter().for_each(|operand| self::operands(operand, excluded, out));
+        },
+        Expr::Exclusion(base, subtracted) => {
+            operands(base, excluded, out);
+            operands(subtracted, true, out);
+        },
+        Expr::Ref(_) | Expr::Arrow { .. } => out.push((expr, excluded)),
+        Expr::Context(_) => {},
+    }
+}
+
+/// Mermaid node IDs can't contain `#`.
+fn mermaid_id(type_name: &str, name: &str) -> String {
+    format!("{}__{}", type_name, name)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = "
+        /// A person.
+        type user {}
+
+        type team {
+            relation member: user
+        }
+
+        /// A project and its documents' permissions.
+        type project {
+            relation team: team
+            /// Can do anything.
+            relation owner: user
+            relation admin: user | team#member
+            relation banned: user
+            /// Full control.
+            permission manage = owner | admin - banned
+        }
+
+        type document {
+            relation project: project
+            relation viewer: user
+            permission view = viewer | project->manage
+        }";
+
+    fn names(members: Vec<MemberRef>) -> Vec<String> {
+        members.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_includes_and_used_by_cross_types() {
+        let schema = Schema::parse(SCHEMA).unwrap();
+
+        assert_eq!(
+            names(schema.includes("document", "view")),
+            [
+                "document#project",
+                "document#viewer",
+                "project#admin",
+                "project#owner",
+                "team#member",
+            ]
+        );
+        assert_eq!(names(schema.used_by("team", "member")), ["document#view", "project#manage"]);
+        assert!(schema.used_by("project", "banned").is_empty());
+        assert!(schema.used_by("project", "team").is_empty());
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = Schema::parse(SCHEMA).unwrap().to_markdown();
+
+        assert!(markdown.starts_with("## `user`\n\nA person.\n\n## `team`\n\n### Relations\n"));
+        assert!(markdown.contains(
+            "#### `owner`\n\nCan do anything.\n\n- Subjects: `user`\n- Grants: `manage`, `document#view`\n"
+        ));
+        assert!(markdown.contains(
+            "#### `manage`\n\nFull control.\n\n- Expression: `owner | admin - banned`\n- Includes: `admin`, `owner`, `team#member`\n"
+        ));
+        assert!(markdown.contains("#### `banned`\n\n- Subjects: `user`\n- Grants: nothing\n"));
+        assert!(markdown.ends_with(
+            "- Includes: `project`, `viewer`, `project#admin`, `project#owner`, `team#member`\n"
+        ));
+    }
+
+    #[test]
+    fn test_type_graph_dot() {
+        let schema = Schema::parse(SCHEMA).unwrap();
+        assert_eq!(
+            schema.type_graph(GraphFormat::Dot),
+            "digraph schema {\n    rankdir=LR;\n    node [shape=box];\n    \"user\";\n    \"team\";\n    \"project\";\n    \"document\";\n    \"document\" -> \"project\" [label=\"project\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_permission_graph() {
+        let schema = Schema::parse(SCHEMA).unwrap();
+
+        let mermaid = schema.permission_graph(GraphFormat::Mermaid);
+        assert!(mermaid.contains("    subgraph project\n        project__team[\"team\"]\n"));
+        assert!(mermaid.contains("        project__manage(\"manage\")\n    end\n"));
+        assert!(mermaid.contains("    team__member --> project__admin\n"));
+        assert!(mermaid.contains("    project__banned -.->|excluded| project__manage\n"));
+        assert!(mermaid.contains("    project__manage --> document__view\n"));
+
+        let dot = schema.permission_graph(GraphFormat::Dot);
+        assert!(dot.contains("    subgraph \"cluster_team\" {\n        label=\"team\";\n        \"team#member\" [label=\"member\", shape=box];\n    }\n"));
+        assert!(dot.contains(
+            "    \"project#banned\" -> \"project#manage\" [style=dashed, label=\"excluded\"];\n"
+        ));
+        assert!(dot.contains("    \"document#project\" -> \"document#view\";\n"));
+    }
+}
@@ -0,0 +1,334 @@
+//! Canonical formatting of IPL source.
+
+use std::fmt::{self, Write as _};
+
+use super::{PermissionDef, RelationDef, Schema, TypeDef};
+use crate::{Error, ErrorKind};
+
+/// Formats IPL source.
+///
+/// The output uses the `type` spelling, one definition per line, relations
+/// before permissions, and canonical spacing and operators (`|`, `&`, `-`,
+/// with parentheses only where precedence needs them). Comments are kept
+/// with the definition they precede or follow; `/* */` comments are
+/// rewritten as `//` comments, one per line. Formatting is idempotent.
+///
+/// # Errors
+///
+/// Returns a [`SchemaViolation`](ErrorKind::SchemaViolation) error if the
+/// source fails to parse.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::control::ipl::{FormatConfig, format};
+///
+/// let formatted = format(
+///     "entity document{relations{viewer:user|group#member}permissions{view:(viewer)|parent->view}}",
+///     &FormatConfig::default(),
+/// )?;
+///
+/// assert_eq!(
+///     formatted,
+///     "type document {\n    relation viewer: user | group#member\n\n    permission view = viewer | parent->view\n}\n"
+/// );
+/// # Ok::<(), inferadb::Error>(())
+/// ```
+pub fn format(source: &str, config: &FormatConfig) -> Result<String, Error> {
+    Ok(Schema::parse(source)?.to_ipl(config))
+}
+
+/// Checks that IPL source is already formatted, for enforcing formatting in
+/// CI.
+///
+/// # Errors
+///
+/// Returns a [`SchemaViolation`](ErrorKind::SchemaViolation) error if the
+/// source fails to parse, or if it differs from [`format()`]'s output. The
+/// message names the first line that differs.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::control::ipl::{FormatConfig, check};
+///
+/// let config = FormatConfig::default();
+/// assert!(check("type user {}\n", &config).is_ok());
+///
+/// let err = check("type user {\n}\n", &config).unwrap_err();
+/// assert!(err.to_string().contains("line 1 is not formatted"));
+/// ```
+pub fn check(source: &str, config: &FormatConfig) -> Result<(), Error> {
+    let formatted = format(source, config)?;
+    if formatted == source {
+        return Ok(());
+    }
+    let line = formatted.lines().zip(source.lines()).take_while(|(e, f)| e == f).count();
+    let message = match (formatted.lines().nth(line), source.lines().nth(line)) {
+        (None, None) => {
+            "schema is not formatted: line endings or the final newline differ".to_owned()
+        },
+        (expected, found) => format!(
+            "line {} is not formatted: expected {}, found {}",
+            line + 1,
+            describe(expected),
+            describe(found)
+        ),
+    };
+    Err(Error::new(ErrorKind::SchemaViolation, message))
+}
+
+fn describe(line: Option<&str>) -> String {
+    match line {
+        Some(line) => format!("`{}`", line),
+        None => "end of file".to_owned(),
+    }
+}
+
+/// Configuration for [`format()`].
+///
+/// Definitions keep their source order by default. Sorting makes the output
+/// independent of where people added definitions, at the cost of a larger
+/// diff the first time it is applied.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::control::ipl::FormatConfig;
+///
+/// let config = FormatConfig::builder().sort_types(true).sort_relations(true).indent(2).build();
+/// ```
+#[derive(Debug, Clone, bon::Builder)]
+pub struct FormatConfig {
+    /// Sort types by name.
+    #[builder(default)]
+    pub sort_types: bool,
+
+    /// Sort relations by name within each type.
+    #[builder(default)]
+    pub sort_relations: bool,
+
+    /// Sort permissions by name within each type.
+    #[builder(default)]
+    pub sort_permissions: bool,
+
+    /// Spaces per indentation level.
+    #[builder(default = 4)]
+    pub indent: usize,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Schema {
+    /// Renders the schema as canonical IPL source. See [`format()`].
+    pub fn to_ipl(&self, config: &FormatConfig) -> String {
+        let mut types: Vec<&TypeDef> = self.types.iter().collect();
+        if config.sort_types {
+            types.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        let indent = " ".repeat(config.indent);
+        let mut out = String::new();
+        for (i, def) in types.into_iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            write_type(&mut out, def, config, &indent);
+        }
+        if !self.end_comments.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            write_comments(&mut out, &self.end_comments, "");
+        }
+        out
+    }
+}
+
+/// Renders the schema with the default [`FormatConfig`].
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_ipl(&FormatConfig::default()))
+    }
+}
+
+fn write_type(out: &mut String, def: &TypeDef, config: &FormatConfig, indent: &str) {
+    write_comments(out, &def.comments, "");
+    if def.relations.is_empty() && def.permissions.is_empty() && def.end_comments.is_empty() {
+        let _ = writeln!(out, "type {} {{}}", def.name);
+        return;
+    }
+    let _ = writeln!(out, "type {} {{", def.name);
+
+    let mut relations: Vec<&RelationDef> = def.relations.iter().collect();
+    if config.sort_relations {
+        relations.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    for relation in &relations {
+        write_comments(out, &relation.comments, indent);
+        let subjects: Vec<String> = relation.subjects.iter().map(ToString::to_string).collect();
+        let _ = write!(out, "{}relation {}: {}", indent, relation.name, subjects.join(" | "));
+        write_trailing_comment(out, relation.trailing_comment.as_deref());
+    }
+
+    let mut permissions: Vec<&PermissionDef> = def.permissions.iter().collect();
+    if config.sort_permissions {
+        permissions.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    if !relations.is_empty() && !permissions.is_empty() {
+        out.push('\n');
+    }
+    for permission in &permissions {
+        write_comments(out, &permission.comments, indent);
+        let _ = write!(out, "{}permission {} = {}", indent, permission.name, permission.expr);
+        write_trailing_comment(out, permission.trailing_comment.as_deref());
+    }
+
+    if !def.end_comments.is_empty() {
+        if !relations.is_empty() || !permissions.is_empty() {
+            out.push('\n');
+        }
+        write_comments(out, &def.end_comments, indent);
+    }
+    out.push_str("}\n");
+}
+
+fn write_comments(out: &mut String, comments: &[String], indent: &str) {
+    for comment in comments {
+        let _ = writeln!(out, "{}//{}", indent, comment.trim_end());
+    }
+}
+
+fn write_trailing_comment(out: &mut String, comment: Option<&str>) {
+    if let Some(comment) = comment {
+        let _ = write!(out, " //{}", comment.trim_end());
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    const MESSY: &str = "
+// Identity
+entity user{}
+
+/// A document.
+entity document {
+  // Who can see it
+  relations { viewer : user|group#member ,   owner:user:* ; }
+  permissions {
+      edit: owner&(context.mfa)   // requires MFA
+      view : ((viewer|edit)) - banned|blocked
+  }
+  // TODO: sharing
+}
+type group { relation member: user }
+// end of schema
+";
+
+    const FORMATTED: &str = "\
+// Identity
+type user {}
+
+/// A document.
+type document {
+    // Who can see it
+    relation viewer: user | group#member
+    relation owner: user:*
+
+    permission edit = owner & context.mfa // requires MFA
+    permission view = viewer | edit - banned | blocked
+
+    // TODO: sharing
+}
+
+type group {
+    relation member: user
+}
+
+// end of schema
+";
+
+    #[test]
+    fn test_format_normalizes_and_keeps_comments() {
+        let config = FormatConfig::default();
+        let formatted = format(MESSY, &config).unwrap();
+        assert_eq!(formatted, FORMATTED);
+        assert_eq!(format(&formatted, &config).unwrap(), formatted);
+        assert_eq!(Schema::parse(MESSY).unwrap().to_string(), FORMATTED);
+    }
+
+    #[test]
+    fn test_format_preserves_meaning() {
+        let formatted = format(MESSY, &FormatConfig::default()).unwrap();
+        let diff = crate::control::SchemaDiff::between(MESSY, &formatted).unwrap();
+        assert!(diff.changes.is_empty(), "{}", diff);
+    }
+
+    #[test]
+    fn test_format_sorting_and_indent() {
+        let config = FormatConfig::builder()
+            .sort_types(true)
+            .sort_relations(true)
+            .sort_permissions(true)
+            .indent(2)
+            .build();
+        let formatted = format(
+            "type user {}
+             type document {
+                 relation viewer: user
+                 relation editor: user
+                 permission view = viewer | editor
+                 permission edit = editor
+             }",
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            formatted,
+            "type document {\n  relation editor: user\n  relation viewer: user\n\n  permission edit = editor\n  permission view = viewer | editor\n}\n\ntype user {}\n"
+        );
+    }
+
+    #[test]
+    fn test_check_reports_first_difference() {
+        let config = FormatConfig::default();
+        assert!(check(FORMATTED, &config).is_ok());
+
+        let err =
+            check(&FORMATTED.replace("owner & context", "owner&context"), &config).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SchemaViolation);
+        assert!(err.to_string().contains(
+            "line 10 is not formatted: expected `    permission edit = owner & context.mfa // requires MFA`, found `    permission edit = owner&context.mfa // requires MFA`"
+        ));
+
+        let err =
+            check(&FORMATTED.replace("type group {\n", "type group { "), &config).unwrap_err();
+        assert!(err.to_string().contains("line 16 is not formatted"));
+
+        let err = check(FORMATTED.trim_end(), &config).unwrap_err();
+        assert!(err.to_string().contains("the final newline differ"));
+
+        assert!(check("type {", &config).is_err());
+    }
+
+    #[test]
+    fn test_format_rewrites_block_comments() {
+        let formatted = format(
+            "/* Users */ type user {}\ntype document {\n  /*\n   * Readers\n   */\n  relation viewer: user /* public */\n}",
+            &FormatConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            formatted,
+            "// Users\ntype user {}\n\ntype document {\n    // Readers\n    relation viewer: user // public\n}\n"
+        );
+    }
+}
@@ -0,0 +1,717 @@
+//! Schema linting against the schema design guidance.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use super::{Expr, PermissionDef, Schema, TypeDef};
+use crate::{
+    Error,
+    control::{ValidationIssue, ValidationResult},
+};
+
+/// Lints IPL source.
+///
+/// # Errors
+///
+/// Returns a [`SchemaViolation`](crate::ErrorKind::SchemaViolation) error if
+/// the source fails to parse.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::control::ipl::{LintConfig, LintRule, lint};
+///
+/// let report = lint(
+///     "type user {}
+///      type document {
+///          relation owner: user
+///          relation archived_by: user
+///          permission edit = owner
+///      }",
+///     &LintConfig::default(),
+/// )?;
+///
+/// assert_eq!(report.issues.len(), 1);
+/// assert_eq!(report.issues[0].rule, LintRule::UnusedRelation);
+/// assert_eq!(report.issues[0].location, "document#archived_by");
+/// # Ok::<(), inferadb::Error>(())
+/// ```
+pub fn lint(source: &str, config: &LintConfig) -> Result<LintReport, Error> {
+    Ok(Schema::parse(source)?.lint(config))
+}
+
+/// A lint rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// A permission refers to a relation, permission or type that does not
+    /// exist.
+    UndefinedReference,
+    /// A permission follows more arrows than
+    /// [`LintConfig::max_hierarchy_depth`]. Recursive relations are not
+    /// counted, since their depth depends on the data.
+    DeepHierarchy,
+    /// A permission unions more operands than
+    /// [`LintConfig::max_union_width`].
+    WideUnion,
+    /// Every branch of a permission is an intersection of relations, so no
+    /// single relationship grants it.
+    IntersectionOnly,
+    /// A relation is not referenced by any permission, arrow or subject set.
+    UnusedRelation,
+    /// A relation is only used as a subject set (`group#member`) and no
+    /// permission grants access through it, so callers must check the
+    /// relation directly.
+    RelationWithoutPermission,
+}
+
+impl LintRule {
+    /// Every rule.
+    pub const ALL: [LintRule; 6] = [
+        LintRule::UndefinedReference,
+        LintRule::DeepHierarchy,
+        LintRule::WideUnion,
+        LintRule::IntersectionOnly,
+        LintRule::UnusedRelation,
+        LintRule::RelationWithoutPermission,
+    ];
+
+    /// Returns the rule ID, such as `wide_union`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            LintRule::UndefinedReference => "undefined_reference",
+            LintRule::DeepHierarchy => "deep_hierarchy",
+            LintRule::WideUnion => "wide_union",
+            LintRule::IntersectionOnly => "intersection_only",
+            LintRule::UnusedRelation => "unused_relation",
+            LintRule::RelationWithoutPermission => "relation_without_permission",
+        }
+    }
+
+    /// Returns the severity the rule reports at unless overridden.
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            LintRule::UndefinedReference => Severity::Error,
+            LintRule::RelationWithoutPermission => Severity::Info,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// Severity of a lint issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Worth knowing; usually intentional.
+    Info,
+    /// Likely a design problem.
+    Warning,
+    /// The schema is broken.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Configuration for [`lint`].
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::control::ipl::{LintConfig, LintRule, Severity};
+///
+/// let config = LintConfig::builder()
+///     .disabled([LintRule::RelationWithoutPermission].into())
+///     .severities([(LintRule::WideUnion, Severity::Error)].into())
+///     .max_union_width(6)
+///     .build();
+///
+/// assert_eq!(config.severity(LintRule::WideUnion), Some(Severity::Error));
+/// assert_eq!(config.severity(LintRule::RelationWithoutPermission), None);
+/// ```
+#[derive(Debug, Clone, bon::Builder)]
+pub struct LintConfig {
+    /// Rules that are not run.
+    #[builder(default)]
+    pub disabled: HashSet<LintRule>,
+
+    /// Severities that replace a rule's default severity.
+    #[builder(default)]
+    pub severities: HashMap<LintRule, Severity>,
+
+    /// Most arrows a permission may follow before [`LintRule::DeepHierarchy`]
+    /// reports it.
+    #[builder(default = 4)]
+    pub max_hierarchy_depth: usize,
+
+    /// Most operands a permission's union may have before
+    /// [`LintRule::WideUnion`] reports it.
+    #[builder(default = 8)]
+    pub max_union_width: usize,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl LintConfig {
+    /// Returns the severity `rule` reports at, or `None` if it is disabled.
+    pub fn severity(&self, rule: LintRule) -> Option<Severity> {
+        if self.disabled.contains(&rule) {
+            return None;
+        }
+        Some(self.severities.get(&rule).copied().unwrap_or_else(|| rule.default_severity()))
+    }
+}
+
+/// A problem found by [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// The rule that found the issue.
+    pub rule: LintRule,
+    /// The issue's severity.
+    pub severity: Severity,
+    /// Line of the definition (1-indexed).
+    pub line: u32,
+    /// The definition, such as `document` or `document#view`.
+    pub location: String,
+    /// What is wrong.
+    pub message: String,
+    /// How to fix it.
+    pub hint: String,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}[{}]: {} (line {}, {})",
+            self.severity, self.rule, self.message, self.line, self.location
+        )?;
+        write!(f, "  = hint: {}", self.hint)
+    }
+}
+
+impl From<LintIssue> for ValidationIssue {
+    fn from(issue: LintIssue) -> Self {
+        ValidationIssue {
+            line: issue.line,
+            column: 1,
+            message: format!("{}: {}", issue.message, issue.hint),
+            code: issue.rule.id().to_owned(),
+        }
+    }
+}
+
+/// The issues found by [`lint`], in source order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LintReport {
+    /// Every issue found.
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    /// Returns `true` if no issues were found.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns `true` if any issue is an error.
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+
+    /// Returns the issues of at least `severity`.
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &LintIssue> {
+        self.issues.iter().filter(move |i| i.severity >= severity)
+    }
+
+    /// Returns the issues found by `rule`.
+    pub fn by_rule(&self, rule: LintRule) -> impl Iterator<Item = &LintIssue> {
+        self.issues.iter().filter(move |i| i.rule == rule)
+    }
+}
+
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        let count = |severity| self.issues.iter().filter(|i| i.severity == severity).count();
+        write!(
+            f,
+            "{} error(s), {} warning(s), {} info",
+            count(Severity::Error),
+            count(Severity::Warning),
+            count(Severity::Info)
+        )
+    }
+}
+
+/// Errors become validation errors; warnings and infos become warnings.
+impl From<LintReport> for ValidationResult {
+    fn from(report: LintReport) -> Self {
+        let (errors, warnings): (Vec<_>, Vec<_>) =
+            report.issues.into_iter().partition(|i| i.severity == Severity::Error);
+        ValidationResult {
+            is_valid: errors.is_empty(),
+            errors: errors.into_iter().map(Into::into).collect(),
+            warnings: warnings.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Schema {
+    /// Lints the schema. See [`lint`].
+    pub fn lint(&self, config: &LintConfig) -> LintReport {
+        let mut linter = Linter { schema: self, config, issues: Vec::new() };
+        linter.run();
+        let mut issues = linter.issues;
+        issues.sort_by_key(|i| i.line);
+        LintReport { issues }
+    }
+}
+
+struct Linter<'a> {
+    schema: &'a Schema,
+    config: &'a LintConfig,
+    issues: Vec<LintIssue>,
+}
+
+impl Linter<'_> {
+    fn report(
+        &mut self,
+        rule: LintRule,
+        line: u32,
+        location: String,
+        message: String,
+        hint: impl Into<String>,
+    ) {
+        if let Some(severity) = self.config.severity(rule) {
+            self.issues.push(LintIssue {
+                rule,
+                severity,
+                line,
+                location,
+                message,
+                hint: hint.into(),
+            });
+        }
+    }
+
+    fn run(&mut self) {
+        for def in &self.schema.types {
+            self.undefined_references(def);
+            for permission in &def.permissions {
+                self.deep_hierarchy(def, permission);
+                self.wide_union(def, permission);
+                self.intersection_only(def, permission);
+            }
+        }
+        self.unused_relations();
+    }
+
+    fn undefined_references(&mut self, def: &TypeDef) {
+        let schema = self.schema;
+        for relation in &def.relations {
+            for subject in &relation.subjects {
+                let message = match (schema.get_type(&subject.type_name), &subject.relation) {
+                    (None, _) => format!("subject type `{}` is not defined", subject.type_name),
+                    (Some(target), Some(set))
+                        if target.relation(set).is_none() && target.permission(set).is_none() =>
+                    {
+                        format!("type `{}` has no relation `{}`", target.name, set)
+                    },
+                    _ => continue,
+                };
+                self.report(
+                    LintRule::UndefinedReference,
+                    relation.line,
+                    format!("{}#{}", def.name, relation.name),
+                    message,
+                    "define it or fix the name",
+                );
+            }
+        }
+
+        for permission in &def.permissions {
+            let mut messages = Vec::new();
+            permission.expr.visit(&mut |expr| match expr {
+                Expr::Ref(name)
+                    if def.relation(name).is_none() && def.permission(name).is_none() =>
+                {
+                    messages.push(format!(
+                        "`{}` is not a relation or permission of `{}`",
+                        name, def.name
+                    ));
+                },
+                Expr::Arrow { relation, permission: target } => match def.relation(relation) {
+                    None => {
+                        messages.push(format!("`{}` is not a relation of `{}`", relation, def.name))
+                    },
+                    Some(relation) => {
+                        for subject in &relation.subjects {
+                            if let Some(subject_type) = schema.get_type(&subject.type_name)
+                                && subject_type.permission(target).is_none()
+                                && subject_type.relation(target).is_none()
+                            {
+                                messages.push(format!(
+                                    "`{}->{}` reaches `{}`, which has no `{}`",
+                                    relation.name, target, subject_type.name, target
+                                ));
+                            }
+                        }
+                    },
+                },
+                _ => {},
+            });
+            for message in messages {
+                self.report(
+                    LintRule::UndefinedReference,
+                    permission.line,
+                    format!("{}#{}", def.name, permission.name),
+                    message,
+                    "define it or fix the name",
+                );
+            }
+        }
+    }
+
+    fn deep_hierarchy(&mut self, def: &TypeDef, permission: &PermissionDef) {
+        let path = longest_arrow_path(self.schema, def, &permission.name, &mut Vec::new());
+        if path.len() <= self.config.max_hierarchy_depth {
+            return;
+        }
+        let chain: Vec<&str> =
+            std::iter::once(def.name.as_str()).chain(path.iter().map(String::as_str)).collect();
+        self.report(
+            LintRule::DeepHierarchy,
+            permission.line,
+            format!("{}#{}", def.name, permission.name),
+            format!(
+                "permission follows {} arrows ({}), more than the limit of {}",
+                path.len(),
+                chain.join(" -> "),
+                self.config.max_hierarchy_depth
+            ),
+            "keep hierarchies to 2-3 levels; grant access at an intermediate level or add a direct relation for hot paths",
+        );
+    }
+
+    fn wide_union(&mut self, def: &TypeDef, permission: &PermissionDef) {
+        let width = permission.expr.branches().len();
+        if width <= self.config.max_union_width {
+            return;
+        }
+        self.report(
+            LintRule::WideUnion,
+            permission.line,
+            format!("{}#{}", def.name, permission.name),
+            format!(
+                "permission unions {} operands, more than the limit of {}",
+                width, self.config.max_union_width
+            ),
+            "replace per-permission relations with a few roles and derive permissions from them",
+        );
+    }
+
+    fn intersection_only(&mut self, def: &TypeDef, permission: &PermissionDef) {
+        let mut base = &permission.expr;
+        while let Expr::Exclusion(inner, _) = base {
+            base = inner;
+        }
+        // Intersections with a context condition are ABAC gates, not a design smell
+        let only_intersections = base.branches().iter().all(|branch| {
+            matches!(branch, Expr::Intersection(operands)
+                if !operands.iter().any(|o| matches!(o, Expr::Context(_))))
+        });
+        if !only_intersections {
+            return;
+        }
+        self.report(
+            LintRule::IntersectionOnly,
+            permission.line,
+            format!("{}#{}", def.name, permission.name),
+            format!("permission `{}` is only granted through intersections", permission.expr),
+            "every grant needs several relationships; union in a relation that grants access on its own",
+        );
+    }
+
+    fn unused_relations(&mut self) {
+        // (type, relation) pairs used by permissions and arrows, or as subject sets
+        let mut by_permission: HashSet<(&str, &str)> = HashSet::new();
+        let mut by_subject_set: HashSet<(&str, &str)> = HashSet::new();
+        for def in &self.schema.types {
+            for permission in &def.permissions {
+                permission.expr.visit(&mut |expr| match expr {
+                    Expr::Ref(name) => {
+                        by_permission.insert((&def.name, name));
+                    },
+                    Expr::Arrow { relation, permission: target } => {
+                        by_permission.insert((&def.name, relation));
+                        for subject in def.relation(relation).iter().flat_map(|r| &r.subjects) {
+                            by_permission.insert((&subject.type_name, target));
+                        }
+                    },
+                    _ => {},
+                });
+            }
+            for subject in def.relations.iter().flat_map(|r| &r.subjects) {
+                if let Some(set) = &subject.relation {
+                    by_subject_set.insert((&subject.type_name, set));
+                }
+            }
+        }
+
+        for def in &self.schema.types {
+            for relation in &def.relations {
+                let key = (def.name.as_str(), relation.name.as_str());
+                if by_permission.contains(&key) {
+                    continue;
+                }
+                let location = format!("{}#{}", def.name, relation.name);
+                if by_subject_set.contains(&key) {
+                    self.report(
+                        LintRule::RelationWithoutPermission,
+                        relation.line,
+                        location,
+                        format!("relation `{}` is used as a subject set but no permission grants through it", relation.name),
+                        format!("add a permission such as `permission is_{0} = {0}` and check that instead", relation.name),
+                    );
+                } else {
+                    self.report(
+                        LintRule::UnusedRelation,
+                        relation.line,
+                        location,
+                        format!("relation `{}` is not used by any permission", relation.name),
+                        "reference it from a permission or remove it",
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Returns the types reached by the longest chain of arrows from `name` on
+/// `def`, skipping arrows back into a permission already on the chain.
+fn longest_arrow_path(
+    schema: &Schema,
+    def: &TypeDef,
+    name: &str,
+    visiting: &mut Vec<(String, String)>,
+) -> Vec<String> {
+    let key = (def.name.clone(), name.to_owned());
+    let Some(permission) = def.permission(name) else {
+        return Vec::new();
+    };
+    if visiting.contains(&key) {
+        return Vec::new();
+    }
+    visiting.push(key);
+
+    let mut longest = Vec::new();
+    let mut consider = |path: Vec<String>| {
+        if path.len() > longest.len() {
+            longest = path;
+        }
+    };
+    let mut steps = Vec::new();
+    permission.expr.visit(&mut |expr| match expr {
+        Expr::Ref(name) => steps.push((None, name.as_str())),
+        Expr::Arrow { relation, permission } => {
+            for subject in def.relation(relation).iter().flat_map(|r| &r.subjects) {
+                steps.push((Some(subject.type_name.as_str()), permission.as_str()));
+            }
+        },
+        _ => {},
+    });
+    for (target, name) in steps {
+        match target {
+            None => consider(longest_arrow_path(schema, def, name, visiting)),
+            Some(target) => {
+                let Some(target_def) = schema.get_type(target) else {
+                    continue;
+                };
+                if visiting.iter().any(|(t, n)| t == target && n == name) {
+                    continue;
+                }
+                let mut path = vec![target.to_owned()];
+                path.extend(longest_arrow_path(schema, target_def, name, visiting));
+                consider(path);
+            },
+        }
+    }
+
+    visiting.pop();
+    longest
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    fn rules(source: &str) -> Vec<(LintRule, String)> {
+        lint(source, &LintConfig::default())
+            .unwrap()
+            .issues
+            .into_iter()
+            .map(|i| (i.rule, i.location))
+            .collect()
+    }
+
+    #[test]
+    fn test_documented_patterns_are_clean() {
+        let report = lint(
+            "type user {}
+             type folder {
+                 relation parent: folder
+                 relation viewer: user
+                 permission view = viewer | parent->view
+             }
+             type document {
+                 relation parent: folder
+                 relation viewer: user
+                 relation confidential_viewer: user
+                 permission view = viewer | parent->view
+                 permission view_confidential = confidential_viewer & context.ip_in_allowlist
+             }",
+            &LintConfig::default(),
+        )
+        .unwrap();
+        assert!(report.is_clean(), "{}", report);
+    }
+
+    #[test]
+    fn test_undefined_references() {
+        assert_eq!(
+            rules(
+                "type user {}
+                 type folder { relation viewer: user  permission view = viewer }
+                 type document {
+                     relation parent: folder
+                     relation viewer: user | team#member
+                     permission view = viewer | owner | parent->edit | org->view
+                 }"
+            ),
+            [
+                (LintRule::UndefinedReference, "document#viewer".to_owned()),
+                (LintRule::UndefinedReference, "document#view".to_owned()),
+                (LintRule::UndefinedReference, "document#view".to_owned()),
+                (LintRule::UndefinedReference, "document#view".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deep_hierarchy_ignores_recursion() {
+        let source = "type user {}
+             type org { relation member: user  permission view = member }
+             type division { relation org: org  permission view = org->view }
+             type team { relation division: division  permission view = division->view }
+             type folder {
+                 relation team: team
+                 relation parent: folder
+                 permission view = team->view | parent->view
+             }
+             type document { relation folder: folder  permission view = folder->view }";
+
+        let config = LintConfig::builder().max_hierarchy_depth(3).build();
+        let report = lint(source, &config).unwrap();
+        let deep: Vec<_> = report.by_rule(LintRule::DeepHierarchy).collect();
+        assert_eq!(deep.len(), 1);
+        assert_eq!(deep[0].location, "document#view");
+        assert!(
+            deep[0].message.contains("4 arrows (document -> folder -> team -> division -> org)")
+        );
+
+        assert!(lint(source, &LintConfig::default()).unwrap().is_clean());
+    }
+
+    #[test]
+    fn test_wide_union_and_intersection_only() {
+        let source = "type user {}
+             type document {
+                 relation a: user
+                 relation b: user
+                 relation c: user
+                 permission view = a | b | c
+                 permission edit = a & b
+                 permission share = (a & c) | (b & c) - a
+             }";
+        let config = LintConfig::builder().max_union_width(2).build();
+        let found: Vec<_> = lint(source, &config)
+            .unwrap()
+            .issues
+            .into_iter()
+            .map(|i| (i.rule, i.location))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (LintRule::WideUnion, "document#view".to_owned()),
+                (LintRule::IntersectionOnly, "document#edit".to_owned()),
+                (LintRule::IntersectionOnly, "document#share".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_relations() {
+        assert_eq!(
+            rules(
+                "type user {}
+                 type group { relation member: user  relation admin: user }
+                 type document {
+                     relation viewer: user | group#member
+                     relation can_share: user
+                     permission view = viewer
+                 }"
+            ),
+            [
+                (LintRule::RelationWithoutPermission, "group#member".to_owned()),
+                (LintRule::UnusedRelation, "group#admin".to_owned()),
+                (LintRule::UnusedRelation, "document#can_share".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_overrides_and_report_conversion() {
+        let source = "type user {}
+             type group { relation member: user  relation admin: user }
+             type document { relation viewer: user | group#member  permission view = viewer | missing }";
+        let config = LintConfig::builder()
+            .disabled([LintRule::RelationWithoutPermission].into())
+            .severities([(LintRule::UnusedRelation, Severity::Error)].into())
+            .build();
+        let report = lint(source, &config).unwrap();
+
+        assert!(report.has_errors());
+        assert_eq!(report.at_least(Severity::Error).count(), 2);
+        assert!(report.to_string().starts_with(
+            "error[unused_relation]: relation `admin` is not used by any permission (line 2, group#admin)\n  = hint: "
+        ));
+        assert!(report.to_string().ends_with("2 error(s), 0 warning(s), 0 info"));
+
+        let validation = ValidationResult::from(report);
+        assert!(!validation.is_valid());
+        assert_eq!(validation.errors[1].code, "undefined_reference");
+        assert_eq!(validation.errors[1].line, 3);
+    }
+}
@@ -0,0 +1,885 @@
+//! Syntax tree and parser for IPL, the InferaDB policy language.
+//!
+//! [`Schema::parse`] reads schema source for offline tooling such as
+//! [`SchemaDiff::between`](super::SchemaDiff::between), [`lint()`], [`format()`]
+//! and the documentation generator ([`Schema::to_markdown`],
+//! [`Schema::permission_graph`]), without a server.
+//! Both spellings of IPL are accepted and parse to the same tree:
+//!
+//! ```text
+//! type document {
+//!     relation viewer: user | group#member
+//!     permission view = viewer | parent->view
+//! }
+//!
+//! entity Document {
+//!     relations { viewer: User | Group#member }
+//!     permissions { view: viewer | parent.view }
+//! }
+//! ```
+//!
+//! Entity syntax may also write arrows as `parent->view`.
+//!
+//! In permission expressions `&` binds tighter than `|`, which binds
+//! tighter than `-`, so `viewer | editor - banned` excludes `banned` from
+//! both relations.
+
+use std::fmt;
+
+use crate::{Error, ErrorKind, control::ValidationIssue};
+
+mod docs;
+mod format;
+mod lint;
+
+pub use docs::{GraphFormat, MemberRef};
+pub use format::{FormatConfig, check, format};
+pub use lint::{LintConfig, LintIssue, LintReport, LintRule, Severity, lint};
+
+/// A parsed schema.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Schema {
+    /// Type definitions, in source order.
+    pub types: Vec<TypeDef>,
+    /// Comments after the last type.
+    pub end_comments: Vec<String>,
+}
+
+impl Schema {
+    /// Parses IPL source.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ErrorKind::SchemaViolation`] error with the line and
+    /// column of the first syntax error.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inferadb::control::ipl::Schema;
+    ///
+    /// let schema = Schema::parse(
+    ///     "type document {
+    ///         relation viewer: user
+    ///         permission view = viewer
+    ///     }",
+    /// )?;
+    /// assert!(schema.get_type("document").unwrap().relation("viewer").is_some());
+    /// # Ok::<(), inferadb::Error>(())
+    /// ```
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        parse(source).map_err(|issue| {
+            Error::new(
+                ErrorKind::SchemaViolation,
+                format!("{}:{}: {}", issue.line, issue.column, issue.message),
+            )
+        })
+    }
+
+    /// Returns the type with the given name.
+    pub fn get_type(&self, name: &str) -> Option<&TypeDef> {
+        self.types.iter().find(|t| t.name == name)
+    }
+}
+
+/// A `type` (or `entity`) definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDef {
+    /// The type name.
+    pub name: String,
+    /// Relations, in source order.
+    pub relations: Vec<RelationDef>,
+    /// Permissions, in source order.
+    pub permissions: Vec<PermissionDef>,
+    /// Comments on the lines before the definition.
+    pub comments: Vec<String>,
+    /// Comments before the closing brace.
+    pub end_comments: Vec<String>,
+    /// Line of the type name (1-indexed).
+    pub line: u32,
+}
+
+impl TypeDef {
+    /// Returns the relation with the given name.
+    pub fn relation(&self, name: &str) -> Option<&RelationDef> {
+        self.relations.iter().find(|r| r.name == name)
+    }
+
+    /// Returns the permission with the given name.
+    pub fn permission(&self, name: &str) -> Option<&PermissionDef> {
+        self.permissions.iter().find(|p| p.name == name)
+    }
+
+    /// Returns the `///` documentation of the type.
+    pub fn doc(&self) -> Option<String> {
+        doc(&self.comments)
+    }
+}
+
+/// A relation and the subject types it accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelationDef {
+    /// The relation name.
+    pub name: String,
+    /// Accepted subject types.
+    pub subjects: Vec<SubjectType>,
+    /// Comments on the lines before the definition.
+    pub comments: Vec<String>,
+    /// Comment after the definition on the same line.
+    pub trailing_comment: Option<String>,
+    /// Line of the relation name (1-indexed).
+    pub line: u32,
+}
+
+impl RelationDef {
+    /// Returns the `///` documentation of the relation.
+    pub fn doc(&self) -> Option<String> {
+        doc(&self.comments)
+    }
+}
+
+/// A subject type accepted by a relation: `user`, `group#member` or `user:*`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SubjectType {
+    /// The subject's type.
+    pub type_name: String,
+    /// The subject set relation, for `group#member`.
+    pub relation: Option<String>,
+    /// Whether every subject of the type is accepted, for `user:*`.
+    pub wildcard: bool,
+}
+
+impl fmt::Display for SubjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.type_name)?;
+        if let Some(relation) = &self.relation {
+            write!(f, "#{}", relation)?;
+        }
+        if self.wildcard {
+            write!(f, ":*")?;
+        }
+        Ok(())
+    }
+}
+
+/// A permission and the expression that computes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionDef {
+    /// The permission name.
+    pub name: String,
+    /// The permission expression.
+    pub expr: Expr,
+    /// Comments on the lines before the definition.
+    pub comments: Vec<String>,
+    /// Comment after the definition on the same line.
+    pub trailing_comment: Option<String>,
+    /// Line of the permission name (1-indexed).
+    pub line: u32,
+}
+
+impl PermissionDef {
+    /// Returns the `///` documentation of the permission.
+    pub fn doc(&self) -> Option<String> {
+        doc(&self.comments)
+    }
+}
+
+/// A permission expression.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expr {
+    /// A relation or permission of the same type: `viewer`.
+    Ref(String),
+    /// A permission reached through a relation: `parent->view`.
+    Arrow {
+        /// The relation to follow.
+        relation: String,
+        /// The permission on the related object.
+        permission: String,
+    },
+    /// A boolean context attribute: `context.ip_in_allowlist`.
+    Context(String),
+    /// Any of the operands: `a | b`.
+    Union(Vec<Expr>),
+    /// All of the operands: `a & b`.
+    Intersection(Vec<Expr>),
+    /// The base minus the excluded subjects: `a - b`.
+    Exclusion(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Returns the operands of a top-level union, or the expression itself.
+    pub fn branches(&self) -> Vec<&Expr> {
+        match self {
+            Expr::Union(operands) => operands.iter().collect(),
+            expr => vec![expr],
+        }
+    }
+
+    /// Returns every relation or permission reference in the expression,
+    /// with arrows contributing their relation.
+    pub fn references(&self) -> Vec<&str> {
+        let mut references = Vec::new();
+        self.visit(&mut |expr| match expr {
+            Expr::Ref(name) => references.push(name.as_str()),
+            Expr::Arrow { relation, .. } => references.push(relation.as_str()),
+            _ => {},
+        });
+        references
+    }
+
+    /// Calls `f` on this expression and every subexpression.
+    pub fn visit<'a>(&'a self, f: &mut impl FnMut(&'a Expr)) {
+        f(self);
+        match self {
+            Expr::Union(operands) | Expr::Intersection(operands) => {
+                operands.iter().for_each(|operand| operand.visit(f));
+            },
+            Expr::Exclusion(base, excluded) => {
+                base.visit(f);
+                excluded.visit(f);
+            },
+            Expr::Ref(_) | Expr::Arrow { .. } | Expr::Context(_) => {},
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Exclusion(..) => 0,
+            Expr::Union(_) => 1,
+            Expr::Intersection(_) => 2,
+            Expr::Ref(_) | Expr::Arrow { .. } | Expr::Context(_) => 3,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, min_precedence: u8) -> fmt::Result {
+        if self.precedence() < min_precedence {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Ref(name) => write!(f, "{}", name),
+            Expr::Arrow { relation, permission } => write!(f, "{}->{}", relation, permission),
+            Expr::Context(path) => write!(f, "context.{}", path),
+            Expr::Union(operands) | Expr::Intersection(operands) => {
+                let (operator, precedence) =
+                    if matches!(self, Expr::Union(_)) { (" | ", 2) } else { (" & ", 3) };
+                for (i, operand) in operands.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(operator)?;
+                    }
+                    operand.fmt_operand(f, precedence)?;
+                }
+                Ok(())
+            },
+            Expr::Exclusion(base, excluded) => {
+                base.fmt_operand(f, 0)?;
+                f.write_str(" - ")?;
+                excluded.fmt_operand(f, 1)
+            },
+        }
+    }
+}
+
+fn doc(comments: &[String]) -> Option<String> {
+    let lines: Vec<&str> = comments
+        .iter()
+        .filter_map(|c| c.strip_prefix('/'))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect();
+    if lines.is_empty() { None } else { Some(lines.join("\n")) }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Tok {
+    Ident(String),
+    Punct(char),
+    Arrow,
+    /// Text after `//`, or one line of a `/* */` comment.
+    Comment(String),
+    Eof,
+}
+
+impl fmt::Display for Tok {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tok::Ident(name) => write!(f, "`{}`", name),
+            Tok::Punct(c) => write!(f, "`{}`", c),
+            Tok::Arrow => write!(f, "`->`"),
+            Tok::Comment(_) => write!(f, "comment"),
+            Tok::Eof => write!(f, "end of schema"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    line: u32,
+    column: u32,
+}
+
+fn issue(line: u32, column: u32, message: impl Into<String>) -> ValidationIssue {
+    ValidationIssue { line, column, message: message.into(), code: "syntax_error".to_owned() }
+}
+
+fn lex(source: &str) -> Result<Vec<Token>, ValidationIssue> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let (mut line, mut column) = (1u32, 1u32);
+
+    while let Some(&c) = chars.peek() {
+        let (start_line, start_column) = (line, column);
+        let mut advance = |chars: &mut std::iter::Peekable<std::str::Chars<'_>>| {
+            let c = chars.next();
+            if c == Some('\n') {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+            c
+        };
+
+        let tok = match c {
+            c if c.is_whitespace() => {
+                advance(&mut chars);
+                continue;
+            },
+            '/' => {
+                advance(&mut chars);
+                if chars.peek() == Some(&'*') {
+                    advance(&mut chars);
+                    let mut text = String::new();
+                    loop {
+                        match advance(&mut chars) {
+                            Some('*') if chars.peek() == Some(&'/') => break,
+                            Some(c) => text.push(c),
+                            None => {
+                                return Err(issue(
+                                    start_line,
+                                    start_column,
+                                    "unterminated block comment",
+                                ));
+                            },
+                        }
+                    }
+                    advance(&mut chars);
+                    for (offset, text) in block_comment_lines(&text).into_iter().enumerate() {
+                        let line = start_line + offset as u32;
+                        tokens.push(Token { tok: Tok::Comment(text), line, column: start_column });
+                    }
+                    continue;
+                }
+                if chars.peek() != Some(&'/') {
+                    return Err(issue(start_line, start_column, "unexpected `/`"));
+                }
+                advance(&mut chars);
+                let mut text = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    text.push(c);
+                    advance(&mut chars);
+                }
+                Tok::Comment(text.trim_end().to_owned())
+            },
+            '-' => {
+                advance(&mut chars);
+                if chars.peek() == Some(&'>') {
+                    advance(&mut chars);
+                    Tok::Arrow
+                } else {
+                    Tok::Punct('-')
+                }
+            },
+            '{' | '}' | '(' | ')' | ':' | '=' | '|' | '&' | '#' | '*' | ',' | ';' | '.' => {
+                advance(&mut chars);
+                Tok::Punct(c)
+            },
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    name.push(c);
+                    advance(&mut chars);
+                }
+                Tok::Ident(name)
+            },
+            c => return Err(issue(start_line, start_column, format!("unexpected `{}`", c))),
+        };
+        tokens.push(Token { tok, line: start_line, column: start_column });
+    }
+
+    tokens.push(Token { tok: Tok::Eof, line, column });
+    Ok(tokens)
+}
+
+/// Splits the text of a `/* */` comment into line comment texts, dropping
+/// the blank first and last lines and the `*` that starts each line of
+/// C-style blocks.
+fn block_comment_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    if lines.len() > 1 && lines[0].trim().is_empty() {
+        lines.remove(0);
+    }
+    lines
+        .into_iter()
+        .map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix('*').unwrap_or(line).trim();
+            if line.is_empty() { String::new() } else { format!(" {}", line) }
+        })
+        .collect()
+}
+
+/// Parses IPL source, reporting the first syntax error as a validation issue.
+pub(crate) fn parse(source: &str) -> Result<Schema, ValidationIssue> {
+    let mut parser = Parser { tokens: lex(source)?, pos: 0, last_line: 0, entity: false };
+    parser.schema()
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    last_line: u32,
+    /// Whether the current type uses `entity` syntax, where arrows may be
+    /// written `parent.view`.
+    entity: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        // lex() always ends the tokens with Eof, which is never consumed
+        &self.tokens[self.pos.min(self.tokens.len() - 1)]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.peek().clone();
+        if token.tok != Tok::Eof {
+            self.pos += 1;
+        }
+        self.last_line = token.line;
+        token
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        self.peek().tok == Tok::Punct(c)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.peek().tok, Tok::Ident(name) if name == keyword)
+    }
+
+    /// Returns `true` if the next tokens are `keyword {`.
+    fn is_block(&self, keyword: &str) -> bool {
+        self.is_keyword(keyword)
+            && self.tokens.get(self.pos + 1).is_some_and(|t| t.tok == Tok::Punct('{'))
+    }
+
+    fn unexpected(&self, expected: &str) -> ValidationIssue {
+        let token = self.peek();
+        issue(token.line, token.column, format!("expected {}, found {}", expected, token.tok))
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<(), ValidationIssue> {
+        if self.is_punct(c) {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", c)))
+        }
+    }
+
+    fn ident(&mut self, expected: &str) -> Result<(String, u32), ValidationIssue> {
+        match &self.peek().tok {
+            Tok::Ident(name) => {
+                let name = name.clone();
+                let line = self.next().line;
+                Ok((name, line))
+            },
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+
+    fn comments(&mut self) -> Vec<String> {
+        let mut comments = Vec::new();
+        while let Tok::Comment(text) = &self.peek().tok {
+            comments.push(text.clone());
+            self.next();
+        }
+        comments
+    }
+
+    /// Takes a comment on the same line as the last token.
+    fn trailing_comment(&mut self) -> Option<String> {
+        match &self.peek().tok {
+            Tok::Comment(text) if self.peek().line == self.last_line => {
+                let text = text.clone();
+                self.next();
+                Some(text)
+            },
+            _ => None,
+        }
+    }
+
+    /// Skips an optional `,` or `;` after a definition.
+    fn separator(&mut self) {
+        if self.is_punct(',') || self.is_punct(';') {
+            self.next();
+        }
+    }
+
+    fn schema(&mut self) -> Result<Schema, ValidationIssue> {
+        let mut schema = Schema::default();
+        loop {
+            let comments = self.comments();
+            if self.peek().tok == Tok::Eof {
+                schema.end_comments = comments;
+                return Ok(schema);
+            }
+            if !(self.is_keyword("type") || self.is_keyword("entity")) {
+                return Err(self.unexpected("`type` or `entity`"));
+            }
+            self.entity = self.is_keyword("entity");
+            self.next();
+            let (name, line) = self.ident("a type name")?;
+            if schema.get_type(&name).is_some() {
+                return Err(issue(line, 1, format!("type `{}` is defined twice", name)));
+            }
+            let mut def = TypeDef {
+                name,
+                relations: Vec::new(),
+                permissions: Vec::new(),
+                comments,
+                end_comments: Vec::new(),
+                line,
+            };
+            self.expect_punct('{')?;
+            self.type_body(&mut def)?;
+            schema.types.push(def);
+        }
+    }
+
+    fn type_body(&mut self, def: &mut TypeDef) -> Result<(), ValidationIssue> {
+        loop {
+            let comments = self.comments();
+            if self.is_punct('}') {
+                self.next();
+                def.end_comments.extend(comments);
+                return Ok(());
+            } else if self.is_block("relations") || self.is_block("permissions") {
+                let relations = self.is_keyword("relations");
+                // Comments before the block belong to its first definition
+                let mut pending = comments;
+                self.next();
+                self.next();
+                loop {
+                    let mut comments = std::mem::take(&mut pending);
+                    comments.extend(self.comments());
+                    if self.is_punct('}') {
+                        self.next();
+                        def.end_comments.extend(comments);
+                        break;
+                    }
+                    if relations {
+                        self.relation(def, comments)?;
+                    } else {
+                        self.permission(def, comments)?;
+                    }
+                }
+            } else if self.is_keyword("relation") {
+                self.next();
+                self.relation(def, comments)?;
+            } else if self.is_keyword("permission") {
+                self.next();
+                self.permission(def, comments)?;
+            } else {
+                return Err(self.unexpected("`relation`, `permission` or `}`"));
+            }
+        }
+    }
+
+    fn check_unique(&self, def: &TypeDef, name: &str, line: u32) -> Result<(), ValidationIssue> {
+        if def.relation(name).is_some() || def.permission(name).is_some() {
+            return Err(issue(
+                line,
+                1,
+                format!("`{}` is defined twice in type `{}`", name, def.name),
+            ));
+        }
+        Ok(())
+    }
+
+    fn relation(
+        &mut self,
+        def: &mut TypeDef,
+        comments: Vec<String>,
+    ) -> Result<(), ValidationIssue> {
+        let (name, line) = self.ident("a relation name")?;
+        self.check_unique(def, &name, line)?;
+        self.expect_punct(':')?;
+        let mut subjects = vec![self.subject_type()?];
+        while self.is_punct('|') {
+            self.next();
+            subjects.push(self.subject_type()?);
+        }
+        let mut trailing_comment = self.trailing_comment();
+        self.separator();
+        if trailing_comment.is_none() {
+            trailing_comment = self.trailing_comment();
+        }
+        def.relations.push(RelationDef { name, subjects, comments, trailing_comment, line });
+        Ok(())
+    }
+
+    fn subject_type(&mut self) -> Result<SubjectType, ValidationIssue> {
+        let (type_name, _) = self.ident("a subject type")?;
+        let mut subject = SubjectType { type_name, relation: None, wildcard: false };
+        if self.is_punct('#') {
+            self.next();
+            subject.relation = Some(self.ident("a relation name")?.0);
+        } else if self.is_punct(':') {
+            self.next();
+            self.expect_punct('*')?;
+            subject.wildcard = true;
+        }
+        Ok(subject)
+    }
+
+    fn permission(
+        &mut self,
+        def: &mut TypeDef,
+        comments: Vec<String>,
+    ) -> Result<(), ValidationIssue> {
+        let (name, line) = self.ident("a permission name")?;
+        self.check_unique(def, &name, line)?;
+        if self.is_punct('=') || self.is_punct(':') {
+            self.next();
+        } else {
+            return Err(self.unexpected("`=`"));
+        }
+        let expr = self.exclusion()?;
+        let mut trailing_comment = self.trailing_comment();
+        self.separator();
+        if trailing_comment.is_none() {
+            trailing_comment = self.trailing_comment();
+        }
+        def.permissions.push(PermissionDef { name, expr, comments, trailing_comment, line });
+        Ok(())
+    }
+
+    fn exclusion(&mut self) -> Result<Expr, ValidationIssue> {
+        let mut expr = self.union()?;
+        while self.is_punct('-') {
+            self.next();
+            expr = Expr::Exclusion(Box::new(expr), Box::new(self.union()?));
+        }
+        Ok(expr)
+    }
+
+    fn union(&mut self) -> Result<Expr, ValidationIssue> {
+        let mut operands = vec![self.intersection()?];
+        while self.is_punct('|') {
+            self.next();
+            match self.intersection()? {
+                Expr::Union(nested) => operands.extend(nested),
+                operand => operands.push(operand),
+            }
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { Expr::Union(operands) })
+    }
+
+    fn intersection(&mut self) -> Result<Expr, ValidationIssue> {
+        let mut operands = vec![self.atom()?];
+        while self.is_punct('&') {
+            self.next();
+            match self.atom()? {
+                Expr::Intersection(nested) => operands.extend(nested),
+                operand => operands.push(operand),
+            }
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { Expr::Intersection(operands) })
+    }
+
+    fn atom(&mut self) -> Result<Expr, ValidationIssue> {
+        if self.is_punct('(') {
+            self.next();
+            let expr = self.exclusion()?;
+            self.expect_punct(')')?;
+            // Keep nested operators of the same kind flat, as if unparenthesized
+            return Ok(expr);
+        }
+        let (name, _) = self.ident("a relation, permission or `(`")?;
+        if name == "context" && self.is_punct('.') {
+            let mut path = Vec::new();
+            while self.is_punct('.') {
+                self.next();
+                path.push(self.ident("a context attribute")?.0);
+            }
+            return Ok(Expr::Context(path.join(".")));
+        }
+        if self.peek().tok == Tok::Arrow || (self.entity && self.is_punct('.')) {
+            self.next();
+            let (permission, _) = self.ident("a permission name")?;
+            return Ok(Expr::Arrow { relation: name, permission });
+        }
+        Ok(Expr::Ref(name))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_type_syntax() {
+        let schema = parse(
+            "// Users\n\
+             type user {}\n\
+             \n\
+             /// A document in a folder.\n\
+             type document {\n\
+                 relation parent: folder\n\
+                 relation viewer: user | group#member | user:* // public links\n\
+                 permission view = viewer | parent->view\n\
+                 permission edit = (owner | editor) & context.ip_allowed - banned\n\
+             }\n",
+        )
+        .unwrap();
+
+        assert_eq!(schema.types.len(), 2);
+        assert_eq!(schema.types[0].comments, [" Users"]);
+        let document = schema.get_type("document").unwrap();
+        assert_eq!(document.line, 5);
+        assert_eq!(document.doc().as_deref(), Some("A document in a folder."));
+
+        let viewer = document.relation("viewer").unwrap();
+        let subjects: Vec<_> = viewer.subjects.iter().map(ToString::to_string).collect();
+        assert_eq!(subjects, ["user", "group#member", "user:*"]);
+        assert_eq!(viewer.trailing_comment.as_deref(), Some(" public links"));
+
+        let view = document.permission("view").unwrap();
+        assert_eq!(
+            view.expr,
+            Expr::Union(vec![
+                Expr::Ref("viewer".into()),
+                Expr::Arrow { relation: "parent".into(), permission: "view".into() },
+            ])
+        );
+        let edit = document.permission("edit").unwrap();
+        assert!(matches!(edit.expr, Expr::Exclusion(..)));
+        assert_eq!(edit.expr.to_string(), "(owner | editor) & context.ip_allowed - banned");
+        assert_eq!(edit.expr.references(), ["owner", "editor", "banned"]);
+    }
+
+    #[test]
+    fn test_parse_entity_syntax() {
+        let schema = parse(
+            "entity User {}
+             entity Document {
+                 relations { owner: User, viewer: User | Group#member }
+                 permissions { view: viewer | owner, edit: owner }
+             }",
+        )
+        .unwrap();
+
+        let document = schema.get_type("Document").unwrap();
+        assert_eq!(document.relations.len(), 2);
+        assert_eq!(document.permission("view").unwrap().expr.to_string(), "viewer | owner");
+        assert_eq!(document.permission("edit").unwrap().expr, Expr::Ref("owner".into()));
+    }
+
+    #[test]
+    fn test_parse_entity_dot_arrows() {
+        // The schema from docs/guides/testing.md
+        let schema = parse(
+            "entity User {}
+             entity Folder {
+                 relations { owner: User }
+                 permissions { view: owner, delete: owner }
+             }
+             entity Document {
+                 relations { parent: Folder, viewer: User }
+                 permissions { view: viewer | parent.view, delete: parent.delete }
+             }",
+        )
+        .unwrap();
+
+        let document = schema.get_type("Document").unwrap();
+        assert_eq!(
+            document.permission("view").unwrap().expr,
+            Expr::Union(vec![
+                Expr::Ref("viewer".into()),
+                Expr::Arrow { relation: "parent".into(), permission: "view".into() },
+            ])
+        );
+        assert_eq!(document.permission("delete").unwrap().expr.to_string(), "parent->delete");
+
+        let err = parse("type document { permission view = parent.view }").unwrap_err();
+        assert_eq!(err.message, "expected `relation`, `permission` or `}`, found `.`");
+    }
+
+    #[test]
+    fn test_expr_display_parenthesizes_by_precedence() {
+        for source in ["a | b & c", "(a | b) & c", "a - (b - c)", "a - b - c", "(a - b) | c"] {
+            let schema = parse(&format!("type t {{ permission p = {} }}", source)).unwrap();
+            assert_eq!(schema.types[0].permissions[0].expr.to_string(), source);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors_have_positions() {
+        let err = parse("type document {\n    relation viewer user\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 21));
+        assert_eq!(err.message, "expected `:`, found `user`");
+
+        let err = parse("type a {}\ntype a {}").unwrap_err();
+        assert_eq!(err.message, "type `a` is defined twice");
+
+        let err = Schema::parse("type a { permission p = }").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SchemaViolation);
+        assert!(err.to_string().contains("1:25: expected a relation, permission or `(`"));
+    }
+
+    #[test]
+    fn test_parse_block_comments() {
+        let schema = parse(
+            "/*
+              * Users and the documents
+              * they can read.
+              */
+             type user {}
+             type document {
+                 /* Readers */ relation viewer: user /* public */
+                 permission view = viewer
+             }
+",
+        )
+        .unwrap();
+        assert_eq!(schema.types[0].comments, [" Users and the documents", " they can read."]);
+        let viewer = schema.get_type("document").unwrap().relation("viewer").unwrap();
+        assert_eq!(viewer.comments, [" Readers"]);
+        assert_eq!(viewer.trailing_comment.as_deref(), Some(" public"));
+
+        let err = parse(
+            "type user {}
+/* open",
+        )
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "unterminated block comment");
+    }
+}
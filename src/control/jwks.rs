@@ -3,8 +3,11 @@
 //! Provides operations for retrieving JSON Web Key Sets (JWKS) from
 //! InferaDB for verifying tokens issued by the service.
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+use super::{JwtVerifier, JwtVerifierConfig};
 use crate::{Error, client::Client};
 
 /// Client for JWKS operations.
@@ -69,6 +72,40 @@ impl JwksClient {
         Err(Error::configuration("REST feature is required for JWKS"))
     }
 
+    /// Gets the JWKS together with how long it may be cached, according to
+    /// the response's `Cache-Control` header.
+    #[cfg(feature = "rest")]
+    pub(crate) async fn get_cacheable(&self) -> Result<(Jwks, Option<Duration>), Error> {
+        self.client.inner().control_get_cacheable("/control/v1/jwks").await
+    }
+
+    /// Gets the JWKS together with how long it may be cached.
+    #[cfg(not(feature = "rest"))]
+    pub(crate) async fn get_cacheable(&self) -> Result<(Jwks, Option<Duration>), Error> {
+        Err(Error::configuration("REST feature is required for JWKS"))
+    }
+
+    /// Creates a verifier for tokens signed with this organization's keys.
+    ///
+    /// Keys are fetched on first use and cached. See [`JwtVerifier`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::control::JwtVerifierConfig;
+    ///
+    /// let verifier = client.jwks().verifier(
+    ///     JwtVerifierConfig::builder()
+    ///         .issuer("https://api.inferadb.com")
+    ///         .audience("my-service")
+    ///         .build(),
+    /// );
+    /// let claims = verifier.verify(token).await?;
+    /// ```
+    pub fn verifier(&self, config: JwtVerifierConfig) -> JwtVerifier {
+        JwtVerifier::new(self.clone(), config)
+    }
+
     /// Gets a specific key by ID.
     ///
     /// This is a convenience method that fetches the JWKS and finds
@@ -0,0 +1,734 @@
+//! Local verification of InferaDB-issued JWTs.
+//!
+//! [`JwtVerifier`] checks EdDSA (Ed25519) signatures against the keys
+//! published in the organization's JWKS and validates the standard time,
+//! issuer and audience claims, without a round trip per token.
+
+use std::{sync::Arc, time::Duration};
+
+use base64::prelude::*;
+use ed25519_dalek::{Signature, VerifyingKey};
+use parking_lot::RwLock;
+use serde::{Deserialize, Deserializer, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use tokio::time::Instant;
+
+use super::{Jwk, Jwks, JwksClient};
+use crate::Error;
+
+/// Configuration for a [`JwtVerifier`].
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::control::JwtVerifierConfig;
+/// use std::time::Duration;
+///
+/// let config = JwtVerifierConfig::builder()
+///     .issuer("https://api.inferadb.com")
+///     .audience("my-service")
+///     .leeway(Duration::from_secs(30))
+///     .build();
+/// ```
+#[derive(Debug, Clone, bon::Builder)]
+pub struct JwtVerifierConfig {
+    /// Required `iss` claim. Any issuer is accepted when unset.
+    #[builder(into)]
+    pub issuer: Option<String>,
+
+    /// Audience that must appear in the `aud` claim. Any audience is
+    /// accepted when unset.
+    #[builder(into)]
+    pub audience: Option<String>,
+
+    /// Clock skew tolerated when checking `exp` and `nbf`.
+    #[builder(default = Duration::from_secs(60))]
+    pub leeway: Duration,
+
+    /// Whether tokens without an `exp` claim are rejected.
+    #[builder(default = true)]
+    pub require_exp: bool,
+
+    /// How long fetched keys are cached when the JWKS response carries no
+    /// `Cache-Control` max-age, and the longest a max-age may keep them.
+    #[builder(default = Duration::from_secs(300))]
+    pub cache_ttl: Duration,
+
+    /// Minimum time between two key fetches.
+    ///
+    /// Bounds the refreshes triggered by tokens with an unknown `kid`, so
+    /// that forged tokens cannot flood the JWKS endpoint.
+    #[builder(default = Duration::from_secs(30))]
+    pub min_refresh_interval: Duration,
+}
+
+impl Default for JwtVerifierConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Claims of a verified token.
+///
+/// Registered claims are typed; every other claim is kept in `extra`. Use
+/// [`JwtVerifier::verify_as`] to deserialize the claims into your own type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Claims {
+    /// Issuer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+
+    /// Subject.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+
+    /// Audiences; a single string audience is read as one entry.
+    #[serde(default, deserialize_with = "one_or_many", skip_serializing_if = "Vec::is_empty")]
+    pub aud: Vec<String>,
+
+    /// Expiry, in seconds since the Unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+
+    /// Start of validity, in seconds since the Unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+
+    /// Issue time, in seconds since the Unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+
+    /// Token ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+
+    /// All other claims.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+impl Claims {
+    /// Returns the expiry time, if the token has one.
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.exp.and_then(|exp| chrono::DateTime::from_timestamp(exp, 0))
+    }
+
+    /// Returns a claim that is not a registered claim.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.extra.get(name)
+    }
+}
+
+/// Reads `aud` as either a string or an array of strings.
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(audience) => vec![audience],
+        OneOrMany::Many(audiences) => audiences,
+    })
+}
+
+#[derive(Deserialize)]
+struct Header {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+/// A token split into its decoded parts.
+struct Token<'a> {
+    header: Header,
+    claims: Value,
+    signing_input: &'a str,
+    signature: Signature,
+}
+
+impl<'a> Token<'a> {
+    fn parse(token: &'a str) -> Result<Self, Error> {
+        let malformed = || Error::unauthorized("malformed token");
+        let (signing_input, signature) = token.rsplit_once('.').ok_or_else(malformed)?;
+        let (header, claims) = signing_input.split_once('.').ok_or_else(malformed)?;
+
+        let decode = |part: &str| BASE64_URL_SAFE_NO_PAD.decode(part).map_err(|_| malformed());
+        let header = serde_json::from_slice(&decode(header)?).map_err(|_| malformed())?;
+        let claims = serde_json::from_slice(&decode(claims)?).map_err(|_| malformed())?;
+        let signature = Signature::from_slice(&decode(signature)?).map_err(|_| malformed())?;
+        Ok(Self { header, claims, signing_input, signature })
+    }
+}
+
+/// The cached key set and when it was fetched.
+struct KeyCache {
+    jwks: Jwks,
+    fetched_at: Option<Instant>,
+    expires_at: Option<Instant>,
+}
+
+struct VerifierInner {
+    source: Option<JwksClient>,
+    config: JwtVerifierConfig,
+    keys: RwLock<KeyCache>,
+    refresh: tokio::sync::Mutex<()>,
+}
+
+/// Verifies InferaDB-issued JWTs locally against the organization's JWKS.
+///
+/// Keys are fetched on first use and cached for the response's
+/// `Cache-Control` max-age, capped at [`JwtVerifierConfig::cache_ttl`], or
+/// for `cache_ttl` without one.
+/// A token whose `kid` is not cached triggers a refresh, so key rotations
+/// are picked up immediately; refreshes are at most one per
+/// [`JwtVerifierConfig::min_refresh_interval`]. If a refresh fails, the
+/// previously fetched keys stay in use.
+///
+/// Only EdDSA (Ed25519) signatures are accepted. The verifier is cheap to
+/// clone; clones share the key cache.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use inferadb::control::JwtVerifierConfig;
+///
+/// let verifier = client.jwks().verifier(
+///     JwtVerifierConfig::builder().audience("my-service").build(),
+/// );
+///
+/// let claims = verifier.verify(token).await?;
+/// println!("token for {:?}", claims.sub);
+/// ```
+#[derive(Clone)]
+pub struct JwtVerifier {
+    inner: Arc<VerifierInner>,
+}
+
+impl JwtVerifier {
+    /// Creates a verifier that fetches keys with `jwks`.
+    pub fn new(jwks: JwksClient, config: JwtVerifierConfig) -> Self {
+        Self::with_source(Some(jwks), Jwks::new(), config)
+    }
+
+    /// Creates a verifier for a fixed key set that is never refreshed.
+    ///
+    /// Useful for tests and for services that distribute keys themselves.
+    pub fn from_jwks(jwks: Jwks, config: JwtVerifierConfig) -> Self {
+        Self::with_source(None, jwks, config)
+    }
+
+    fn with_source(source: Option<JwksClient>, jwks: Jwks, config: JwtVerifierConfig) -> Self {
+        let keys = KeyCache { jwks, fetched_at: None, expires_at: None };
+        Self {
+            inner: Arc::new(VerifierInner {
+                source,
+                config,
+                keys: RwLock::new(keys),
+                refresh: tokio::sync::Mutex::new(()),
+            }),
+        }
+    }
+
+    /// Returns the configuration.
+    pub fn config(&self) -> &JwtVerifierConfig {
+        &self.inner.config
+    }
+
+    /// Returns the currently cached key set.
+    pub fn keys(&self) -> Jwks {
+        self.inner.keys.read().jwks.clone()
+    }
+
+    /// Verifies `token` and returns its claims.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Unauthorized` error if the token is malformed, not signed
+    /// with EdDSA, signed by an unknown key, has an invalid signature, or
+    /// fails claim validation. Returns the fetch error if no keys could be
+    /// fetched at all.
+    pub async fn verify(&self, token: &str) -> Result<Claims, Error> {
+        let claims = self.verify_token(token).await?;
+        serde_json::from_value(claims)
+            .map_err(|e| Error::unauthorized(format!("token has invalid registered claims: {}", e)))
+    }
+
+    /// Verifies `token` and deserializes its claims into `T`.
+    ///
+    /// The registered claims are validated exactly as by
+    /// [`verify`](Self::verify) before `T` is built.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`verify`](Self::verify), and an
+    /// `Unauthorized` error if the claims do not deserialize into `T`.
+    pub async fn verify_as<T: DeserializeOwned>(&self, token: &str) -> Result<T, Error> {
+        let claims = self.verify_token(token).await?;
+        serde_json::from_value(claims)
+            .map_err(|e| Error::unauthorized(format!("unexpected token claims: {}", e)))
+    }
+
+    /// Fetches the key set now, unless one was fetched within
+    /// [`JwtVerifierConfig::min_refresh_interval`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the fetch error.
+    pub async fn refresh(&self) -> Result<(), Error> {
+        self.try_refresh().await.map(|_| ())
+    }
+
+    async fn verify_token(&self, token: &str) -> Result<Value, Error> {
+        let token = Token::parse(token)?;
+        if token.header.alg != "EdDSA" {
+            return Err(Error::unauthorized(format!(
+                "unsupported token algorithm '{}', expected EdDSA",
+                token.header.alg
+            )));
+        }
+
+        let keys = self.keys_for(token.header.kid.as_deref()).await?;
+        let verified = keys
+            .iter()
+            .any(|key| key.verify_strict(token.signing_input.as_bytes(), &token.signature).is_ok());
+        if !verified {
+            return Err(Error::unauthorized("invalid token signature"));
+        }
+
+        let claims: Claims = serde_json::from_value(token.claims.clone()).map_err(|e| {
+            Error::unauthorized(format!("token has invalid registered claims: {}", e))
+        })?;
+        self.validate(&claims)?;
+        Ok(token.claims)
+    }
+
+    /// Returns the keys that may have signed a token with `kid`, refreshing
+    /// the cache when it has expired or does not know `kid`.
+    async fn keys_for(&self, kid: Option<&str>) -> Result<Vec<VerifyingKey>, Error> {
+        if self.is_expired() {
+            // Stale keys are better than none when the JWKS endpoint is down
+            if let Err(e) = self.try_refresh().await
+                && self.inner.keys.read().jwks.is_empty()
+            {
+                return Err(e);
+            }
+        }
+
+        let keys = self.lookup(kid)?;
+        if !keys.is_empty() {
+            return Ok(keys);
+        }
+        if self.try_refresh().await.unwrap_or(false) {
+            let keys = self.lookup(kid)?;
+            if !keys.is_empty() {
+                return Ok(keys);
+            }
+        }
+        Err(Error::unauthorized(match kid {
+            Some(kid) => format!("no signing key with kid '{}'", kid),
+            None => "no Ed25519 signing key available".to_string(),
+        }))
+    }
+
+    /// Returns the cached Ed25519 keys matching `kid`, or all of them when
+    /// the token names no key.
+    fn lookup(&self, kid: Option<&str>) -> Result<Vec<VerifyingKey>, Error> {
+        let cache = self.inner.keys.read();
+        match kid {
+            Some(kid) => match cache.jwks.find_key(kid) {
+                Some(jwk) => verifying_key(jwk).map(|key| vec![key]),
+                None => Ok(Vec::new()),
+            },
+            None => Ok(cache.jwks.iter().filter_map(|jwk| verifying_key(jwk).ok()).collect()),
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        let cache = self.inner.keys.read();
+        self.inner.source.is_some() && cache.expires_at.is_none_or(|at| Instant::now() >= at)
+    }
+
+    /// Fetches the key set if the source allows it, returning whether a
+    /// fetch happened.
+    async fn try_refresh(&self) -> Result<bool, Error> {
+        let Some(source) = &self.inner.source else {
+            return Ok(false);
+        };
+        let _refreshing = self.inner.refresh.lock().await;
+
+        // A concurrent caller may have refreshed while we waited
+        let recently = self.inner.keys.read().fetched_at.is_some_and(|at| {
+            Instant::now().duration_since(at) < self.inner.config.min_refresh_interval
+        });
+        if recently {
+            return Ok(false);
+        }
+
+        let result = source.get_cacheable().await;
+        let now = Instant::now();
+        let mut cache = self.inner.keys.write();
+        cache.fetched_at = Some(now);
+        let (jwks, max_age) = result?;
+        cache.jwks = jwks;
+        let cache_ttl = self.inner.config.cache_ttl;
+        let ttl = max_age.map_or(cache_ttl, |max_age| max_age.min(cache_ttl));
+        // Keys without an expiry are refetched on next use
+        cache.expires_at = now.checked_add(ttl.min(MAX_CACHE_TTL));
+        Ok(true)
+    }
+
+    fn validate(&self, claims: &Claims) -> Result<(), Error> {
+        let config = &self.inner.config;
+        let now = chrono::Utc::now().timestamp();
+        let leeway = i64::try_from(config.leeway.as_secs()).unwrap_or(i64::MAX);
+
+        match claims.exp {
+            Some(exp) if now > exp.saturating_add(leeway) => {
+                return Err(Error::unauthorized("token has expired"));
+            },
+            None if config.require_exp => {
+                return Err(Error::unauthorized("token has no expiry"));
+            },
+            _ => {},
+        }
+        if let Some(nbf) = claims.nbf
+            && now.saturating_add(leeway) < nbf
+        {
+            return Err(Error::unauthorized("token is not valid yet"));
+        }
+        if let Some(issuer) = &config.issuer
+            && claims.iss.as_ref() != Some(issuer)
+        {
+            return Err(Error::unauthorized(format!(
+                "unexpected token issuer {:?}, expected '{}'",
+                claims.iss, issuer
+            )));
+        }
+        if let Some(audience) = &config.audience
+            && !claims.aud.contains(audience)
+        {
+            return Err(Error::unauthorized(format!(
+                "token audience does not include '{}'",
+                audience
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Longest time fetched keys are cached, whatever the configuration says.
+const MAX_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Decodes the Ed25519 public key of `jwk`.
+fn verifying_key(jwk: &Jwk) -> Result<VerifyingKey, Error> {
+    let kid = jwk.kid.as_deref().unwrap_or_default();
+    if !jwk.is_ed25519() {
+        return Err(Error::unauthorized(format!("signing key '{}' is not an Ed25519 key", kid)));
+    }
+    let invalid = || Error::unauthorized(format!("signing key '{}' is invalid", kid));
+    let x = jwk.x.as_deref().ok_or_else(invalid)?;
+    let bytes: [u8; 32] = BASE64_URL_SAFE_NO_PAD
+        .decode(x)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(invalid)?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| invalid())
+}
+
+impl std::fmt::Debug for JwtVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwtVerifier")
+            .field("config", &self.inner.config)
+            .field("keys", &self.inner.keys.read().jwks.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+pub(crate) mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{ErrorKind, auth::Ed25519PrivateKey};
+
+    /// Returns the JWK publishing `key` under `kid`.
+    pub(crate) fn jwk(key: &Ed25519PrivateKey, kid: &str) -> Jwk {
+        Jwk::ed25519(BASE64_URL_SAFE_NO_PAD.encode(key.public_key_bytes())).with_kid(kid)
+    }
+
+    /// Signs `claims` with `key`, naming `kid` in the header.
+    pub(crate) fn sign(key: &Ed25519PrivateKey, kid: Option<&str>, claims: Value) -> String {
+        let mut header = json!({ "alg": "EdDSA", "typ": "JWT" });
+        if let Some(kid) = kid {
+            header["kid"] = json!(kid);
+        }
+        let encode = |value: &Value| BASE64_URL_SAFE_NO_PAD.encode(value.to_string());
+        let signing_input = format!("{}.{}", encode(&header), encode(&claims));
+        format!("{}.{}", signing_input, key.sign_base64url(signing_input.as_bytes()))
+    }
+
+    fn in_an_hour() -> i64 {
+        chrono::Utc::now().timestamp() + 3600
+    }
+
+    fn verifier(key: &Ed25519PrivateKey, config: JwtVerifierConfig) -> JwtVerifier {
+        JwtVerifier::from_jwks(Jwks::with_keys(vec![jwk(key, "k1")]), config)
+    }
+
+    #[tokio::test]
+    async fn test_verifies_signature_and_returns_claims() {
+        let key = Ed25519PrivateKey::generate();
+        let token = sign(
+            &key,
+            Some("k1"),
+            json!({ "sub": "user:alice", "aud": "svc", "exp": in_an_hour(), "org": "org_1" }),
+        );
+
+        let claims = verifier(&key, JwtVerifierConfig::default()).verify(&token).await.unwrap();
+        assert_eq!(claims.sub.as_deref(), Some("user:alice"));
+        assert_eq!(claims.aud, vec!["svc"]);
+        assert_eq!(claims.get("org"), Some(&json!("org_1")));
+        assert!(claims.expires_at().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_tampered_and_foreign_tokens() {
+        let key = Ed25519PrivateKey::generate();
+        let verifier = verifier(&key, JwtVerifierConfig::default());
+
+        let token = sign(&key, Some("k1"), json!({ "sub": "user:alice", "exp": in_an_hour() }));
+        let forged = sign(&key, Some("k1"), json!({ "sub": "user:mallory", "exp": in_an_hour() }));
+        let (_, payload) = forged.split_once('.').unwrap();
+        let (payload, _) = payload.split_once('.').unwrap();
+        let mut parts: Vec<_> = token.split('.').collect();
+        parts[1] = payload;
+        let err = verifier.verify(&parts.join(".")).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unauthorized);
+
+        let other = Ed25519PrivateKey::generate();
+        let token = sign(&other, Some("k1"), json!({ "exp": in_an_hour() }));
+        assert!(verifier.verify(&token).await.unwrap_err().to_string().contains("signature"));
+
+        let token = sign(&key, Some("k2"), json!({ "exp": in_an_hour() }));
+        assert!(verifier.verify(&token).await.unwrap_err().to_string().contains("kid 'k2'"));
+
+        assert!(verifier.verify("not-a-token").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_other_algorithms() {
+        let key = Ed25519PrivateKey::generate();
+        let encode = |value: Value| BASE64_URL_SAFE_NO_PAD.encode(value.to_string());
+        let token = format!(
+            "{}.{}.{}",
+            encode(json!({ "alg": "none" })),
+            encode(json!({ "exp": in_an_hour() })),
+            BASE64_URL_SAFE_NO_PAD.encode([0u8; 64])
+        );
+
+        let err = verifier(&key, JwtVerifierConfig::default()).verify(&token).await.unwrap_err();
+        assert!(err.to_string().contains("unsupported token algorithm 'none'"));
+    }
+
+    #[tokio::test]
+    async fn test_validates_time_claims_with_leeway() {
+        let key = Ed25519PrivateKey::generate();
+        let now = chrono::Utc::now().timestamp();
+        let config = JwtVerifierConfig::builder().leeway(Duration::from_secs(30)).build();
+        let verifier = verifier(&key, config);
+
+        let slightly_expired = sign(&key, Some("k1"), json!({ "exp": now - 10 }));
+        assert!(verifier.verify(&slightly_expired).await.is_ok());
+
+        let expired = sign(&key, Some("k1"), json!({ "exp": now - 120 }));
+        assert!(verifier.verify(&expired).await.unwrap_err().to_string().contains("expired"));
+
+        let early = sign(&key, Some("k1"), json!({ "exp": now + 600, "nbf": now + 300 }));
+        assert!(verifier.verify(&early).await.unwrap_err().to_string().contains("not valid yet"));
+
+        let no_expiry = sign(&key, Some("k1"), json!({ "sub": "user:alice" }));
+        assert!(verifier.verify(&no_expiry).await.unwrap_err().to_string().contains("no expiry"));
+    }
+
+    #[tokio::test]
+    async fn test_validates_issuer_and_audience() {
+        let key = Ed25519PrivateKey::generate();
+        let config =
+            JwtVerifierConfig::builder().issuer("https://api.inferadb.com").audience("svc").build();
+        let verifier = verifier(&key, config);
+
+        let good = sign(
+            &key,
+            None,
+            json!({ "iss": "https://api.inferadb.com", "aud": ["other", "svc"], "exp": in_an_hour() }),
+        );
+        assert!(verifier.verify(&good).await.is_ok());
+
+        let wrong_issuer =
+            sign(&key, None, json!({ "iss": "https://evil", "aud": "svc", "exp": in_an_hour() }));
+        assert!(verifier.verify(&wrong_issuer).await.unwrap_err().to_string().contains("issuer"));
+
+        let wrong_audience = sign(
+            &key,
+            None,
+            json!({ "iss": "https://api.inferadb.com", "aud": "other", "exp": in_an_hour() }),
+        );
+        assert!(
+            verifier.verify(&wrong_audience).await.unwrap_err().to_string().contains("audience")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_as_custom_claims() {
+        #[derive(Deserialize)]
+        struct ServiceClaims {
+            sub: String,
+            scopes: Vec<String>,
+        }
+
+        let key = Ed25519PrivateKey::generate();
+        let token = sign(
+            &key,
+            Some("k1"),
+            json!({ "sub": "client:ci", "scopes": ["read"], "exp": in_an_hour() }),
+        );
+
+        let claims: ServiceClaims =
+            verifier(&key, JwtVerifierConfig::default()).verify_as(&token).await.unwrap();
+        assert_eq!(claims.sub, "client:ci");
+        assert_eq!(claims.scopes, vec!["read"]);
+    }
+}
+
+#[cfg(all(test, feature = "rest"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod wiremock_tests {
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    use super::{tests::*, *};
+    use crate::{Client, auth::BearerCredentialsConfig, auth::Ed25519PrivateKey};
+
+    async fn jwks_client(server: &MockServer) -> JwksClient {
+        Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test_token"))
+            .build()
+            .await
+            .unwrap()
+            .jwks()
+    }
+
+    fn jwks_response(keys: &[Jwk], cache_control: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200)
+            .insert_header("cache-control", cache_control)
+            .set_body_json(json!({ "keys": keys }))
+    }
+
+    fn claims() -> Value {
+        json!({ "sub": "user:alice", "exp": chrono::Utc::now().timestamp() + 3600 })
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_fetched_once_and_cached() {
+        let server = MockServer::start().await;
+        let key = Ed25519PrivateKey::generate();
+        Mock::given(method("GET"))
+            .and(path("/control/v1/jwks"))
+            .respond_with(jwks_response(&[jwk(&key, "k1")], "max-age=600"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let verifier = jwks_client(&server).await.verifier(JwtVerifierConfig::default());
+        for _ in 0..3 {
+            verifier.verify(&sign(&key, Some("k1"), claims())).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_huge_max_age_is_capped() {
+        let huge_ttl = JwtVerifierConfig::builder().cache_ttl(Duration::MAX).build();
+        for config in [JwtVerifierConfig::default(), huge_ttl] {
+            let server = MockServer::start().await;
+            let key = Ed25519PrivateKey::generate();
+            Mock::given(method("GET"))
+                .and(path("/control/v1/jwks"))
+                .respond_with(jwks_response(&[jwk(&key, "k1")], "max-age=18446744073709551615"))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let verifier = jwks_client(&server).await.verifier(config);
+            for _ in 0..2 {
+                verifier.verify(&sign(&key, Some("k1"), claims())).await.unwrap();
+            }
+            let expires_at = verifier.inner.keys.read().expires_at.unwrap();
+            assert!(expires_at <= Instant::now() + MAX_CACHE_TTL);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_kid_refreshes_after_rotation() {
+        let server = MockServer::start().await;
+        let old = Ed25519PrivateKey::generate();
+        let new = Ed25519PrivateKey::generate();
+        Mock::given(method("GET"))
+            .and(path("/control/v1/jwks"))
+            .respond_with(jwks_response(&[jwk(&old, "k1")], "max-age=600"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/control/v1/jwks"))
+            .respond_with(jwks_response(&[jwk(&old, "k1"), jwk(&new, "k2")], "max-age=600"))
+            .mount(&server)
+            .await;
+
+        let config =
+            JwtVerifierConfig::builder().min_refresh_interval(Duration::from_millis(0)).build();
+        let verifier = jwks_client(&server).await.verifier(config);
+        verifier.verify(&sign(&old, Some("k1"), claims())).await.unwrap();
+        verifier.verify(&sign(&new, Some("k2"), claims())).await.unwrap();
+        assert_eq!(verifier.keys().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_kid_refreshes_are_rate_limited() {
+        let server = MockServer::start().await;
+        let key = Ed25519PrivateKey::generate();
+        Mock::given(method("GET"))
+            .and(path("/control/v1/jwks"))
+            .respond_with(jwks_response(&[jwk(&key, "k1")], "no-cache"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let verifier = jwks_client(&server).await.verifier(JwtVerifierConfig::default());
+        for kid in ["k1", "forged-1", "forged-2"] {
+            let _ = verifier.verify(&sign(&key, Some(kid), claims())).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_failure_without_keys_is_returned() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/control/v1/jwks"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let key = Ed25519PrivateKey::generate();
+        let verifier = jwks_client(&server).await.verifier(JwtVerifierConfig::default());
+        let err = verifier.verify(&sign(&key, Some("k1"), claims())).await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Internal);
+    }
+}
@@ -1,11 +1,15 @@
 //! Member management for the control plane.
 
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     Error,
     client::Client,
-    control::{Page, SortOrder},
+    control::{
+        Page, SortOrder,
+        pagination::{self, Paginated},
+    },
 };
 
 /// Client for organization member management operations.
@@ -483,6 +487,7 @@ impl UpdateMemberRequest {
 }
 
 /// Request to list members.
+#[derive(Clone)]
 pub struct ListMembersRequest {
     client: Client,
     organization_id: String,
@@ -507,6 +512,29 @@ impl ListMembersRequest {
         self
     }
 
+    /// Returns a stream over the members of all pages.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<MemberInfo, Error>> + Send + 'static {
+        pagination::items(self)
+    }
+
+    /// Returns a stream of pages, following cursors to the last page.
+    pub fn pages(self) -> impl Stream<Item = Result<Page<MemberInfo>, Error>> + Send + 'static {
+        pagination::pages(self)
+    }
+
+    /// Collects the members of all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if there are more than `max_items`
+    /// members, or the error of the first page that fails.
+    pub async fn collect_all(self, max_items: usize) -> Result<Vec<MemberInfo>, Error> {
+        pagination::collect_all(self, max_items).await
+    }
+
     /// Sets the sort order.
     #[must_use]
     pub fn sort(mut self, order: SortOrder) -> Self {
@@ -562,7 +590,16 @@ impl std::future::IntoFuture for ListMembersRequest {
     }
 }
 
+impl Paginated for ListMembersRequest {
+    type Item = MemberInfo;
+
+    fn with_cursor(self, cursor: String) -> Self {
+        self.cursor(cursor)
+    }
+}
+
 /// Request to list invitations.
+#[derive(Clone)]
 pub struct ListInvitationsRequest {
     client: Client,
     organization_id: String,
@@ -586,6 +623,29 @@ impl ListInvitationsRequest {
         self
     }
 
+    /// Returns a stream over the invitations of all pages.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<InvitationInfo, Error>> + Send + 'static {
+        pagination::items(self)
+    }
+
+    /// Returns a stream of pages, following cursors to the last page.
+    pub fn pages(self) -> impl Stream<Item = Result<Page<InvitationInfo>, Error>> + Send + 'static {
+        pagination::pages(self)
+    }
+
+    /// Collects the invitations of all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if there are more than `max_items`
+    /// invitations, or the error of the first page that fails.
+    pub async fn collect_all(self, max_items: usize) -> Result<Vec<InvitationInfo>, Error> {
+        pagination::collect_all(self, max_items).await
+    }
+
     /// Filters by status.
     #[must_use]
     pub fn status(mut self, status: InvitationStatus) -> Self {
@@ -631,6 +691,14 @@ impl std::future::IntoFuture for ListInvitationsRequest {
     }
 }
 
+impl Paginated for ListInvitationsRequest {
+    type Item = InvitationInfo;
+
+    fn with_cursor(self, cursor: String) -> Self {
+        self.cursor(cursor)
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
@@ -7,10 +7,11 @@
 //! - Vault management
 //! - Team management
 //! - Member management
-//! - API client management
-//! - JWKS operations
-//! - Schema management
-//! - Audit logs
+//! - API client management and automated key rotation
+//! - JWKS operations and local JWT verification
+//! - Schema management, gated deployment and offline IPL tooling ([`ipl`])
+//! - Audit logs, with live tailing and SIEM export
+//! - Declarative organization configuration with plan/apply reconciliation
 //!
 //! ## API Hierarchy
 //!
@@ -70,13 +71,24 @@
 // Allow dead code for control types not yet integrated
 #![allow(dead_code)]
 
+pub mod ipl;
+pub mod schema;
+
 mod account;
 mod audit;
+mod audit_follow;
 mod clients;
 mod jwks;
+mod jwt;
 mod members;
 mod organizations;
+mod pagination;
+mod reconcile;
+mod rotation;
+mod schema_deploy;
+mod schema_diff;
 mod schemas;
+mod siem;
 mod teams;
 mod types;
 mod vaults;
@@ -90,6 +102,7 @@ pub use account::{
 pub use audit::{
     ActorInfo, ActorType, AuditAction, AuditEvent, AuditLogsClient, AuditOutcome, ExportFormat,
 };
+pub use audit_follow::{AuditCheckpoint, AuditFollowStream, FollowAuditLogsRequest};
 // Re-export API client types
 pub use clients::{
     AddCertificateRequest, ApiClient, ApiClientsClient, CertificatesClient, ClientCertificate,
@@ -97,6 +110,7 @@ pub use clients::{
 };
 // Re-export JWKS types
 pub use jwks::{Jwk, Jwks, JwksClient};
+pub use jwt::{Claims, JwtVerifier, JwtVerifierConfig};
 // Re-export member types
 pub use members::{
     InvitationInfo, InvitationStatus, InvitationsClient, InviteMemberRequest, MemberInfo,
@@ -107,11 +121,20 @@ pub use organizations::{
     CreateOrganizationRequest, OrganizationControlClient, OrganizationInfo, OrganizationsClient,
     UpdateOrganizationRequest,
 };
+pub use reconcile::{
+    ApiClientSpec, ApplyReport, Change, ChangeAction, FailedChange, MemberSpec, OrgSpec, Plan,
+    Reconciler, TeamSpec, VaultSpec,
+};
+pub use rotation::{
+    FileKeyStore, KeyRotator, KeyStore, KeyStoreFuture, Rotation, RotationSchedule,
+};
+pub use schema_deploy::{DeployAssertion, DeploySchemaRequest, SchemaDeployment};
 // Re-export schema types
 pub use schemas::{
     PushSchemaResult, SchemaChange, SchemaChangeType, SchemaDiff, SchemaInfo, SchemaStatus,
     SchemasClient, ValidationIssue, ValidationResult,
 };
+pub use siem::{SiemExporter, SiemFormat, to_cef, to_ocsf};
 // Re-export team types
 pub use teams::{
     CreateTeamRequest, TeamInfo, TeamMemberInfo, TeamRole, TeamsClient, UpdateTeamRequest,
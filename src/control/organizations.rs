@@ -1,5 +1,6 @@
 //! Organization management for the control plane.
 
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -9,6 +10,7 @@ use crate::{
         Page, SortOrder,
         audit::AuditLogsClient,
         members::{InvitationsClient, MembersClient},
+        pagination::{self, Paginated},
         teams::TeamsClient,
         vaults::VaultsClient,
     },
@@ -333,6 +335,7 @@ impl UpdateOrganizationRequest {
 }
 
 /// Request to list organizations.
+#[derive(Clone)]
 pub struct ListOrganizationsRequest {
     client: Client,
     limit: Option<usize>,
@@ -355,6 +358,31 @@ impl ListOrganizationsRequest {
         self
     }
 
+    /// Returns a stream over the organizations of all pages.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<OrganizationInfo, Error>> + Send + 'static {
+        pagination::items(self)
+    }
+
+    /// Returns a stream of pages, following cursors to the last page.
+    pub fn pages(
+        self,
+    ) -> impl Stream<Item = Result<Page<OrganizationInfo>, Error>> + Send + 'static {
+        pagination::pages(self)
+    }
+
+    /// Collects the organizations of all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if there are more than `max_items`
+    /// organizations, or the error of the first page that fails.
+    pub async fn collect_all(self, max_items: usize) -> Result<Vec<OrganizationInfo>, Error> {
+        pagination::collect_all(self, max_items).await
+    }
+
     /// Sets the sort order.
     #[must_use]
     pub fn sort(mut self, order: SortOrder) -> Self {
@@ -401,6 +429,14 @@ impl std::future::IntoFuture for ListOrganizationsRequest {
     }
 }
 
+impl Paginated for ListOrganizationsRequest {
+    type Item = OrganizationInfo;
+
+    fn with_cursor(self, cursor: String) -> Self {
+        self.cursor(cursor)
+    }
+}
+
 /// Request to delete an organization.
 pub struct DeleteOrganizationRequest {
     client: OrganizationControlClient,
@@ -0,0 +1,201 @@
+//! Cursor pagination shared by the control plane list requests.
+//!
+//! Every list request implements [`Paginated`], which gives it the same
+//! `stream()`, `pages()` and `collect_all()` behavior: pages are requested
+//! by following [`PageInfo::next_cursor`](super::PageInfo::next_cursor),
+//! the next page is fetched while the current one is consumed, and the
+//! first error ends the stream.
+
+use std::future::IntoFuture;
+
+use futures::{Stream, StreamExt, TryStreamExt};
+
+use super::Page;
+use crate::Error;
+
+/// A list request that can be re-issued for the following page.
+pub(crate) trait Paginated:
+    IntoFuture<Output = Result<Page<Self::Item>, Error>, IntoFuture: Send> + Clone + Send + 'static
+{
+    /// The listed item.
+    type Item: Send + 'static;
+
+    /// Returns the request for the page at `cursor`.
+    fn with_cursor(self, cursor: String) -> Self;
+}
+
+/// Aborts the prefetch of a page nobody is waiting for anymore.
+struct Prefetch<T>(tokio::task::JoinHandle<Result<Page<T>, Error>>);
+
+impl<T> Drop for Prefetch<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+enum State<R: Paginated> {
+    First(R),
+    Next(R, Prefetch<R::Item>),
+    Done,
+}
+
+/// Streams every page of `request`, prefetching one page ahead.
+pub(crate) fn pages<R: Paginated>(
+    request: R,
+) -> impl Stream<Item = Result<Page<R::Item>, Error>> + Send + 'static {
+    futures::stream::unfold(State::First(request), |state| async move {
+        let (request, result) = match state {
+            State::First(request) => {
+                let result = request.clone().into_future().await;
+                (request, result)
+            },
+            State::Next(request, mut prefetch) => {
+                let result = (&mut prefetch.0).await.unwrap_or_else(|e| {
+                    Err(Error::internal(format!("page prefetch failed: {}", e)))
+                });
+                (request, result)
+            },
+            State::Done => return None,
+        };
+
+        match result {
+            Ok(page) => {
+                let state = match page.next_cursor() {
+                    Some(cursor) if page.has_next() => {
+                        let next = request.with_cursor(cursor.to_owned());
+                        let prefetch = Prefetch(tokio::spawn(next.clone().into_future()));
+                        State::Next(next, prefetch)
+                    },
+                    _ => State::Done,
+                };
+                Some((Ok(page), state))
+            },
+            Err(e) => Some((Err(e), State::Done)),
+        }
+    })
+}
+
+/// Streams the items of every page of `request`.
+pub(crate) fn items<R: Paginated>(
+    request: R,
+) -> impl Stream<Item = Result<R::Item, Error>> + Send + 'static {
+    pages(request)
+        .map_ok(|page| futures::stream::iter(page.items.into_iter().map(Ok)))
+        .try_flatten()
+}
+
+/// Collects the items of every page of `request`, failing once there are
+/// more than `max_items`.
+pub(crate) async fn collect_all<R: Paginated>(
+    request: R,
+    max_items: usize,
+) -> Result<Vec<R::Item>, Error> {
+    let mut pages = std::pin::pin!(pages(request));
+    let mut items = Vec::new();
+    while let Some(page) = pages.next().await {
+        items.extend(page?.items);
+        if items.len() > max_items {
+            return Err(Error::invalid_argument(format!(
+                "listing returned more than {} items; raise the limit or use stream()",
+                max_items
+            )));
+        }
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
+
+    use super::*;
+    use crate::control::PageInfo;
+
+    /// Serves `pages` pages of two numbers each, failing at `fail_at`.
+    #[derive(Clone)]
+    struct Numbers {
+        page: usize,
+        pages: usize,
+        fail_at: Option<usize>,
+        fetched: Arc<AtomicUsize>,
+    }
+
+    impl Numbers {
+        fn new(pages: usize) -> Self {
+            Self { page: 0, pages, fail_at: None, fetched: Arc::default() }
+        }
+    }
+
+    impl IntoFuture for Numbers {
+        type Output = Result<Page<usize>, Error>;
+        type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+        fn into_future(self) -> Self::IntoFuture {
+            Box::pin(async move {
+                self.fetched.fetch_add(1, Ordering::SeqCst);
+                if self.fail_at == Some(self.page) {
+                    return Err(Error::internal("page unavailable"));
+                }
+                let has_next = self.page + 1 < self.pages;
+                Ok(Page {
+                    items: vec![self.page * 2, self.page * 2 + 1],
+                    page_info: PageInfo {
+                        has_next,
+                        next_cursor: has_next.then(|| (self.page + 1).to_string()),
+                        total_count: None,
+                    },
+                })
+            })
+        }
+    }
+
+    impl Paginated for Numbers {
+        type Item = usize;
+
+        fn with_cursor(mut self, cursor: String) -> Self {
+            self.page = cursor.parse().unwrap();
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn test_items_follow_cursors() {
+        let items: Vec<usize> = items(Numbers::new(3)).try_collect().await.unwrap();
+        assert_eq!(items, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_pages_prefetch_next_page() {
+        let request = Numbers::new(3);
+        let fetched = Arc::clone(&request.fetched);
+        let mut pages = std::pin::pin!(pages(request));
+
+        pages.next().await.unwrap().unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(fetched.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_after_error() {
+        let request = Numbers { fail_at: Some(1), ..Numbers::new(3) };
+        let results: Vec<_> = items(request).collect().await;
+        assert_eq!(results.len(), 3);
+        assert!(results[2].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_collect_all_guards_item_count() {
+        assert_eq!(collect_all(Numbers::new(2), 4).await.unwrap().len(), 4);
+
+        let err = collect_all(Numbers::new(3), 4).await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,1471 @@
+//! Declarative organization configuration.
+//!
+//! An [`OrgSpec`] describes the desired vaults, active schemas, members,
+//! teams and API clients of an organization. A [`Reconciler`] compares it
+//! with the live organization, producing a [`Plan`] of ordered changes, and
+//! applies that plan.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use inferadb::control::{OrgSpec, Reconciler};
+//!
+//! let spec = OrgSpec::load("orgs/acme.yaml")?;
+//! let reconciler = Reconciler::new(&client, "org_acme");
+//!
+//! let plan = reconciler.plan(&spec).await?;
+//! println!("{plan}");
+//!
+//! // Deletions and removals require explicit opt-in
+//! let report = reconciler.with_allow_destructive(true).apply(&plan).await?;
+//! ```
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ApiClient, CreateApiClientRequest, CreateTeamRequest, CreateVaultRequest, InvitationStatus,
+    InviteMemberRequest, OrgRole, SchemaDiff, SchemasClient, UpdateApiClientRequest,
+    UpdateMemberRequest, UpdateTeamRequest, UpdateVaultRequest,
+};
+use crate::{Error, ErrorKind, client::Client};
+
+/// Desired state of an organization.
+///
+/// Sections left out of the document are not managed: a missing `teams`
+/// key leaves teams untouched, while `teams: []` deletes every team.
+/// Resources are matched by name, members by email.
+///
+/// The spec is a plain serde type, so any serde format works; JSON and
+/// (with the `yaml` feature) YAML have built-in loaders.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OrgSpec {
+    /// Vaults and their active schemas.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vaults: Option<Vec<VaultSpec>>,
+    /// Organization members and their roles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<MemberSpec>>,
+    /// Teams and their memberships.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teams: Option<Vec<TeamSpec>>,
+    /// API clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clients: Option<Vec<ApiClientSpec>>,
+}
+
+/// Desired state of a vault.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VaultSpec {
+    /// Vault name.
+    pub name: String,
+    /// Display name, if managed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Description, if managed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Active schema content, if managed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+}
+
+/// Desired state of an organization member.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemberSpec {
+    /// Member email address.
+    pub email: String,
+    /// Organization role.
+    #[serde(default)]
+    pub role: OrgRole,
+}
+
+/// Desired state of a team.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TeamSpec {
+    /// Team name.
+    pub name: String,
+    /// Description, if managed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Member emails, if managed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<String>>,
+}
+
+/// Desired state of an API client.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiClientSpec {
+    /// Client name.
+    pub name: String,
+    /// Description, if managed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Permissions, if managed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Vec<String>>,
+    /// Rate limit in requests per second, if managed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<u32>,
+}
+
+impl OrgSpec {
+    /// Parses a spec from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if the document is malformed.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json)
+            .map_err(|e| Error::invalid_argument(format!("invalid organization spec: {}", e)))
+    }
+
+    /// Parses a spec from YAML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if the document is malformed.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(yaml: &str) -> Result<Self, Error> {
+        serde_yaml_ng::from_str(yaml)
+            .map_err(|e| Error::invalid_argument(format!("invalid organization spec: {}", e)))
+    }
+
+    /// Loads a spec from a `.json`, `.yaml` or `.yml` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Configuration` error if the file cannot be read or has an
+    /// unsupported extension, and an `InvalidArgument` error if it is
+    /// malformed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            Error::configuration(format!("cannot read organization spec {}: {}", path.display(), e))
+        })?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+        match extension {
+            "json" => Self::from_json(&contents),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Self::from_yaml(&contents),
+            #[cfg(not(feature = "yaml"))]
+            "yaml" | "yml" => {
+                Err(Error::configuration("YAML organization specs require the `yaml` feature"))
+            },
+            _ => Err(Error::configuration(format!(
+                "unsupported organization spec format: {}",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Kind of a planned [`Change`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeAction {
+    /// Creates a resource or adds a membership.
+    Create,
+    /// Modifies an existing resource.
+    Update,
+    /// Deletes a resource or removes a membership.
+    Delete,
+}
+
+/// A single planned change, in the order it is applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Creates a vault.
+    CreateVault(VaultSpec),
+    /// Updates a vault's display name or description.
+    UpdateVault {
+        /// ID of the vault.
+        id: String,
+        /// Desired state of the vault.
+        spec: VaultSpec,
+    },
+    /// Deletes a vault and all of its data.
+    DeleteVault {
+        /// ID of the vault.
+        id: String,
+        /// Name of the vault.
+        name: String,
+    },
+    /// Pushes and activates a new schema version.
+    DeploySchema {
+        /// Name of the vault.
+        vault: String,
+        /// ID of the vault, unless it is created by the same plan.
+        vault_id: Option<String>,
+        /// Schema content.
+        content: String,
+        /// Breaking changes against the active schema, such as removed
+        /// types, relations or permissions.
+        breaking_changes: Vec<String>,
+    },
+    /// Invites a new member.
+    InviteMember(MemberSpec),
+    /// Changes a member's role.
+    UpdateMemberRole {
+        /// User ID of the member.
+        user_id: String,
+        /// Email of the member.
+        email: String,
+        /// Current role.
+        from: OrgRole,
+        /// Desired role.
+        to: OrgRole,
+    },
+    /// Removes a member from the organization.
+    RemoveMember {
+        /// User ID of the member.
+        user_id: String,
+        /// Email of the member.
+        email: String,
+    },
+    /// Revokes a pending invitation.
+    RevokeInvitation {
+        /// ID of the invitation.
+        id: String,
+        /// Email the invitation was sent to.
+        email: String,
+    },
+    /// Creates a team.
+    CreateTeam {
+        /// Team name.
+        name: String,
+        /// Team description.
+        description: Option<String>,
+    },
+    /// Updates a team's description.
+    UpdateTeam {
+        /// ID of the team.
+        id: String,
+        /// Team name.
+        name: String,
+        /// Desired description.
+        description: Option<String>,
+    },
+    /// Deletes a team.
+    DeleteTeam {
+        /// ID of the team.
+        id: String,
+        /// Team name.
+        name: String,
+    },
+    /// Adds a member to a team.
+    AddTeamMember {
+        /// Team name.
+        team: String,
+        /// ID of the team, unless it is created by the same plan.
+        team_id: Option<String>,
+        /// User ID of the member.
+        user_id: String,
+        /// Email of the member.
+        email: String,
+    },
+    /// Removes a member from a team.
+    RemoveTeamMember {
+        /// Team name.
+        team: String,
+        /// ID of the team.
+        team_id: String,
+        /// User ID of the member.
+        user_id: String,
+        /// Email of the member.
+        email: String,
+    },
+    /// Creates an API client.
+    CreateApiClient(ApiClientSpec),
+    /// Updates an API client.
+    UpdateApiClient {
+        /// ID of the API client.
+        id: String,
+        /// Desired state of the API client.
+        spec: ApiClientSpec,
+        /// Permissions the client currently has that the update removes.
+        removed_permissions: Vec<String>,
+    },
+    /// Deletes an API client.
+    DeleteApiClient {
+        /// ID of the API client.
+        id: String,
+        /// Name of the API client.
+        name: String,
+    },
+}
+
+impl Change {
+    /// Returns the kind of change.
+    pub fn action(&self) -> ChangeAction {
+        match self {
+            Change::CreateVault(_)
+            | Change::InviteMember(_)
+            | Change::CreateTeam { .. }
+            | Change::AddTeamMember { .. }
+            | Change::CreateApiClient(_) => ChangeAction::Create,
+            Change::UpdateVault { .. }
+            | Change::DeploySchema { .. }
+            | Change::UpdateMemberRole { .. }
+            | Change::UpdateTeam { .. }
+            | Change::UpdateApiClient { .. } => ChangeAction::Update,
+            Change::DeleteVault { .. }
+            | Change::RemoveMember { .. }
+            | Change::RevokeInvitation { .. }
+            | Change::DeleteTeam { .. }
+            | Change::RemoveTeamMember { .. }
+            | Change::DeleteApiClient { .. } => ChangeAction::Delete,
+        }
+    }
+
+    /// Returns `true` if the change deletes a resource or revokes access.
+    ///
+    /// Besides deletions, this covers role changes that take permissions
+    /// away from a member, API client updates that remove permissions and
+    /// schema deploys with breaking changes.
+    pub fn is_destructive(&self) -> bool {
+        match self {
+            Change::UpdateMemberRole { from, to, .. } => !role_covers(*to, *from),
+            Change::UpdateApiClient { removed_permissions, .. } => !removed_permissions.is_empty(),
+            Change::DeploySchema { breaking_changes, .. } => !breaking_changes.is_empty(),
+            _ => self.action() == ChangeAction::Delete,
+        }
+    }
+}
+
+/// Returns `true` if `to` grants everything `from` does.
+///
+/// Billing is neither above nor below member and viewer; only the admin
+/// roles cover it.
+fn role_covers(to: OrgRole, from: OrgRole) -> bool {
+    fn rank(role: OrgRole) -> Option<u8> {
+        match role {
+            OrgRole::Owner => Some(3),
+            OrgRole::Admin => Some(2),
+            OrgRole::Member => Some(1),
+            OrgRole::Viewer => Some(0),
+            OrgRole::Billing => None,
+        }
+    }
+    match (rank(to), rank(from)) {
+        (Some(to), Some(from)) => to >= from,
+        (Some(to), None) => to >= 2,
+        (None, from) => from.is_none(),
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self.action() {
+            ChangeAction::Create => '+',
+            ChangeAction::Update => '~',
+            ChangeAction::Delete => '-',
+        };
+        write!(f, "{} ", symbol)?;
+        match self {
+            Change::CreateVault(spec) => write!(f, "create vault {:?}", spec.name),
+            Change::UpdateVault { spec, .. } => write!(f, "update vault {:?}", spec.name),
+            Change::DeleteVault { id, name } => write!(f, "delete vault {:?} ({})", name, id),
+            Change::DeploySchema { vault, breaking_changes, .. } => {
+                write!(f, "deploy schema to vault {:?}", vault)?;
+                if !breaking_changes.is_empty() {
+                    write!(f, " (breaking: {})", breaking_changes.join("; "))?;
+                }
+                Ok(())
+            },
+            Change::InviteMember(spec) => write!(f, "invite {} as {}", spec.email, spec.role),
+            Change::UpdateMemberRole { email, from, to, .. } => {
+                write!(f, "change role of {} from {} to {}", email, from, to)
+            },
+            Change::RemoveMember { email, .. } => write!(f, "remove member {}", email),
+            Change::RevokeInvitation { email, .. } => write!(f, "revoke invitation for {}", email),
+            Change::CreateTeam { name, .. } => write!(f, "create team {:?}", name),
+            Change::UpdateTeam { name, .. } => write!(f, "update team {:?}", name),
+            Change::DeleteTeam { id, name } => write!(f, "delete team {:?} ({})", name, id),
+            Change::AddTeamMember { team, email, .. } => {
+                write!(f, "add {} to team {:?}", email, team)
+            },
+            Change::RemoveTeamMember { team, email, .. } => {
+                write!(f, "remove {} from team {:?}", email, team)
+            },
+            Change::CreateApiClient(spec) => write!(f, "create API client {:?}", spec.name),
+            Change::UpdateApiClient { spec, removed_permissions, .. } => {
+                write!(f, "update API client {:?}", spec.name)?;
+                if !removed_permissions.is_empty() {
+                    write!(f, " (removes {})", removed_permissions.join(", "))?;
+                }
+                Ok(())
+            },
+            Change::DeleteApiClient { id, name } => {
+                write!(f, "delete API client {:?} ({})", name, id)
+            },
+        }
+    }
+}
+
+/// Ordered changes that bring an organization to its [`OrgSpec`].
+///
+/// Creations come first, so that schemas and team memberships can refer to
+/// vaults and teams created by the same plan; removals come last.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Plan {
+    /// The changes, in application order.
+    pub changes: Vec<Change>,
+    /// Parts of the spec that cannot be applied yet, such as team
+    /// memberships of people who have not accepted their invitation.
+    pub warnings: Vec<String>,
+}
+
+impl Plan {
+    /// Returns `true` if the organization already matches the spec.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the changes that delete resources or revoke access.
+    pub fn destructive_changes(&self) -> Vec<&Change> {
+        self.changes.iter().filter(|c| c.is_destructive()).collect()
+    }
+
+    fn count(&self, action: ChangeAction) -> usize {
+        self.changes.iter().filter(|c| c.action() == action).count()
+    }
+}
+
+impl std::fmt::Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "! {}", warning)?;
+        }
+        write!(
+            f,
+            "Plan: {} to add, {} to change, {} to destroy.",
+            self.count(ChangeAction::Create),
+            self.count(ChangeAction::Update),
+            self.count(ChangeAction::Delete)
+        )
+    }
+}
+
+/// A change that failed while applying a plan.
+#[derive(Debug)]
+pub struct FailedChange {
+    /// The change that failed.
+    pub change: Change,
+    /// The error it failed with.
+    pub error: Error,
+}
+
+/// Outcome of [`Reconciler::apply`].
+///
+/// Application stops at the first failing change; the remaining changes
+/// are picked up by the next plan.
+#[derive(Debug)]
+pub struct ApplyReport {
+    /// Changes that were applied, or would be in a dry run.
+    pub applied: Vec<Change>,
+    /// The change that failed, if any.
+    pub failed: Option<FailedChange>,
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+}
+
+impl ApplyReport {
+    /// Returns `true` if every change was applied.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_none()
+    }
+}
+
+/// Plans and applies an [`OrgSpec`] against a live organization.
+///
+/// Applying is guarded: plans that delete resources or revoke access are
+/// refused unless [`with_allow_destructive`](Self::with_allow_destructive)
+/// is set, and [`with_dry_run`](Self::with_dry_run) checks a plan without
+/// changing anything.
+#[derive(Clone)]
+pub struct Reconciler {
+    client: Client,
+    organization_id: String,
+    dry_run: bool,
+    allow_destructive: bool,
+}
+
+impl Reconciler {
+    /// Creates a reconciler for an organization.
+    pub fn new(client: &Client, organization_id: impl Into<String>) -> Self {
+        Self {
+            client: client.clone(),
+            organization_id: organization_id.into(),
+            dry_run: false,
+            allow_destructive: false,
+        }
+    }
+
+    /// Reports the changes `apply` would make without making them.
+    #[must_use]
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Allows plans containing destructive changes to be applied.
+    #[must_use]
+    pub fn with_allow_destructive(mut self, allow: bool) -> Self {
+        self.allow_destructive = allow;
+        self
+    }
+
+    /// Returns the organization ID.
+    pub fn organization_id(&self) -> &str {
+        &self.organization_id
+    }
+
+    /// Computes the changes needed to bring the organization to `spec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the live state cannot be read or `spec` names a
+    /// resource twice.
+    pub async fn plan(&self, spec: &OrgSpec) -> Result<Plan, Error> {
+        let org = self.client.organization(&self.organization_id);
+        let mut plan = Plan::default();
+        let mut removals = Vec::new();
+
+        if let Some(vaults) = &spec.vaults {
+            check_unique("vault", vaults.iter().map(|v| v.name.as_str()), str::to_owned)?;
+            let live = org.vaults().list().collect_all(usize::MAX).await?;
+            for desired in vaults {
+                match live.iter().find(|v| v.name == desired.name) {
+                    Some(vault) => {
+                        let changed = differs(&desired.display_name, &vault.display_name)
+                            || differs(&desired.description, &vault.description);
+                        if changed {
+                            plan.changes.push(Change::UpdateVault {
+                                id: vault.id.clone(),
+                                spec: desired.clone(),
+                            });
+                        }
+                        if let Some(schema) = &desired.schema {
+                            let schemas = SchemasClient::new(
+                                self.client.clone(),
+                                &self.organization_id,
+                                &vault.id,
+                            );
+                            let active = match schemas.get_active().await {
+                                Ok(active) => Some(active.content),
+                                Err(e) if e.kind() == ErrorKind::NotFound => None,
+                                Err(e) => return Err(e),
+                            };
+                            let deploy = match active {
+                                Some(active) => {
+                                    schema_changes(&desired.name, &active, schema, &mut plan)
+                                },
+                                None => Some(Vec::new()),
+                            };
+                            if let Some(breaking_changes) = deploy {
+                                plan.changes.push(Change::DeploySchema {
+                                    vault: desired.name.clone(),
+                                    vault_id: Some(vault.id.clone()),
+                                    content: schema.clone(),
+                                    breaking_changes,
+                                });
+                            }
+                        }
+                    },
+                    None => {
+                        plan.changes.push(Change::CreateVault(desired.clone()));
+                        if let Some(schema) = &desired.schema {
+                            plan.changes.push(Change::DeploySchema {
+                                vault: desired.name.clone(),
+                                vault_id: None,
+                                content: schema.clone(),
+                                breaking_changes: Vec::new(),
+                            });
+                        }
+                    },
+                }
+            }
+            for vault in live.iter().filter(|v| !vaults.iter().any(|d| d.name == v.name)) {
+                removals
+                    .push(Change::DeleteVault { id: vault.id.clone(), name: vault.name.clone() });
+            }
+        }
+
+        // Members are needed to resolve team memberships by email
+        let members = if spec.members.is_some() || spec.teams.is_some() {
+            org.members().list().collect_all(usize::MAX).await?
+        } else {
+            Vec::new()
+        };
+        let mut member_removals = Vec::new();
+
+        if let Some(desired_members) = &spec.members {
+            check_unique(
+                "member",
+                desired_members.iter().map(|m| m.email.as_str()),
+                str::to_ascii_lowercase,
+            )?;
+            let invitations = org.invitations().list().collect_all(usize::MAX).await?;
+            let pending: Vec<_> =
+                invitations.iter().filter(|i| i.status == InvitationStatus::Pending).collect();
+
+            for desired in desired_members {
+                if let Some(member) = members.iter().find(|m| same_email(&m.email, &desired.email))
+                {
+                    if member.role != desired.role {
+                        plan.changes.push(Change::UpdateMemberRole {
+                            user_id: member.user_id.clone(),
+                            email: member.email.clone(),
+                            from: member.role,
+                            to: desired.role,
+                        });
+                    }
+                } else if let Some(invitation) =
+                    pending.iter().find(|i| same_email(&i.email, &desired.email))
+                {
+                    if invitation.role != desired.role {
+                        plan.warnings.push(format!(
+                            "{} was invited as {}; their role can be changed once they join",
+                            invitation.email, invitation.role
+                        ));
+                    }
+                } else {
+                    plan.changes.push(Change::InviteMember(desired.clone()));
+                }
+            }
+            let wanted = |email: &str| desired_members.iter().any(|d| same_email(&d.email, email));
+            for member in members.iter().filter(|m| !wanted(&m.email)) {
+                member_removals.push(Change::RemoveMember {
+                    user_id: member.user_id.clone(),
+                    email: member.email.clone(),
+                });
+            }
+            for invitation in pending.iter().filter(|i| !wanted(&i.email)) {
+                member_removals.push(Change::RevokeInvitation {
+                    id: invitation.id.clone(),
+                    email: invitation.email.clone(),
+                });
+            }
+        }
+
+        if let Some(teams) = &spec.teams {
+            check_unique("team", teams.iter().map(|t| t.name.as_str()), str::to_owned)?;
+            let live = org.teams().list().collect_all(usize::MAX).await?;
+            let mut additions = Vec::new();
+            for desired in teams {
+                let existing = live.iter().find(|t| t.name == desired.name);
+                let current = match existing {
+                    Some(team) => {
+                        if differs(&desired.description, &team.description) {
+                            plan.changes.push(Change::UpdateTeam {
+                                id: team.id.clone(),
+                                name: team.name.clone(),
+                                description: desired.description.clone(),
+                            });
+                        }
+                        if desired.members.is_some() {
+                            org.teams().list_members(&team.id).collect_all(usize::MAX).await?
+                        } else {
+                            Vec::new()
+                        }
+                    },
+                    None => {
+                        plan.changes.push(Change::CreateTeam {
+                            name: desired.name.clone(),
+                            description: desired.description.clone(),
+                        });
+                        Vec::new()
+                    },
+                };
+
+                let Some(emails) = &desired.members else { continue };
+                for email in emails {
+                    if current.iter().any(|m| same_email(&m.email, email)) {
+                        continue;
+                    }
+                    match members.iter().find(|m| same_email(&m.email, email)) {
+                        Some(member) => additions.push(Change::AddTeamMember {
+                            team: desired.name.clone(),
+                            team_id: existing.map(|t| t.id.clone()),
+                            user_id: member.user_id.clone(),
+                            email: member.email.clone(),
+                        }),
+                        None => plan.warnings.push(format!(
+                            "{} is not a member yet and cannot be added to team {:?}",
+                            email, desired.name
+                        )),
+                    }
+                }
+                if let Some(team) = existing {
+                    for member in
+                        current.iter().filter(|m| !emails.iter().any(|e| same_email(e, &m.email)))
+                    {
+                        removals.push(Change::RemoveTeamMember {
+                            team: team.name.clone(),
+                            team_id: team.id.clone(),
+                            user_id: member.user_id.clone(),
+                            email: member.email.clone(),
+                        });
+                    }
+                }
+            }
+            plan.changes.extend(additions);
+            for team in live.iter().filter(|t| !teams.iter().any(|d| d.name == t.name)) {
+                removals.push(Change::DeleteTeam { id: team.id.clone(), name: team.name.clone() });
+            }
+        }
+
+        if let Some(clients) = &spec.clients {
+            check_unique("API client", clients.iter().map(|c| c.name.as_str()), str::to_owned)?;
+            let live: Vec<ApiClient> = org
+                .clients()
+                .list()
+                .collect_all(usize::MAX)
+                .await?
+                .into_iter()
+                .filter(|c| !c.status.is_revoked())
+                .collect();
+            for desired in clients {
+                match live.iter().find(|c| c.name == desired.name) {
+                    Some(client) => {
+                        let changed = differs(&desired.description, &client.description)
+                            || desired.rate_limit.is_some()
+                                && desired.rate_limit != client.rate_limit
+                            || desired.permissions.as_ref().is_some_and(|p| {
+                                p.iter().collect::<BTreeSet<_>>()
+                                    != client.permissions.iter().collect::<BTreeSet<_>>()
+                            });
+                        if changed {
+                            let removed_permissions = desired
+                                .permissions
+                                .as_ref()
+                                .map(|desired| {
+                                    client
+                                        .permissions
+                                        .iter()
+                                        .filter(|p| !desired.contains(p))
+                                        .cloned()
+                                        .collect()
+                                })
+                                .unwrap_or_default();
+                            plan.changes.push(Change::UpdateApiClient {
+                                id: client.id.clone(),
+                                spec: desired.clone(),
+                                removed_permissions,
+                            });
+                        }
+                    },
+                    None => plan.changes.push(Change::CreateApiClient(desired.clone())),
+                }
+            }
+            for client in live.iter().filter(|c| !clients.iter().any(|d| d.name == c.name)) {
+                removals.push(Change::DeleteApiClient {
+                    id: client.id.clone(),
+                    name: client.name.clone(),
+                });
+            }
+        }
+
+        // Memberships and clients go before members leave, vaults last
+        let (vault_deletions, mut removals): (Vec<_>, Vec<_>) =
+            removals.into_iter().partition(|c| matches!(c, Change::DeleteVault { .. }));
+        removals.extend(member_removals);
+        removals.extend(vault_deletions);
+        plan.changes.extend(removals);
+        Ok(plan)
+    }
+
+    /// Applies a plan in order.
+    ///
+    /// Application stops at the first failing change, which is reported in
+    /// [`ApplyReport::failed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error, before changing anything, if the
+    /// plan contains destructive changes and they were not allowed.
+    pub async fn apply(&self, plan: &Plan) -> Result<ApplyReport, Error> {
+        let destructive = plan.destructive_changes();
+        if !destructive.is_empty() && !self.allow_destructive {
+            let listed = destructive.iter().map(|c| format!("  {}", c)).collect::<Vec<_>>();
+            return Err(Error::invalid_argument(format!(
+                "plan contains {} destructive change(s); allow destructive changes to apply it:\n{}",
+                destructive.len(),
+                listed.join("\n")
+            )));
+        }
+
+        let mut report = ApplyReport { applied: Vec::new(), failed: None, dry_run: self.dry_run };
+        if self.dry_run {
+            report.applied = plan.changes.clone();
+            return Ok(report);
+        }
+
+        let mut created = Created::default();
+        for change in &plan.changes {
+            match self.apply_change(change, &mut created).await {
+                Ok(()) => report.applied.push(change.clone()),
+                Err(error) => {
+                    report.failed = Some(FailedChange { change: change.clone(), error });
+                    break;
+                },
+            }
+        }
+        Ok(report)
+    }
+
+    /// Plans and applies `spec` in one step.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if planning fails or the plan is refused; see
+    /// [`plan`](Self::plan) and [`apply`](Self::apply).
+    pub async fn reconcile(&self, spec: &OrgSpec) -> Result<ApplyReport, Error> {
+        let plan = self.plan(spec).await?;
+        self.apply(&plan).await
+    }
+
+    async fn apply_change(&self, change: &Change, created: &mut Created) -> Result<(), Error> {
+        let org = self.client.organization(&self.organization_id);
+        match change {
+            Change::CreateVault(spec) => {
+                let mut request = CreateVaultRequest::new(&spec.name);
+                if let Some(display_name) = &spec.display_name {
+                    request = request.with_display_name(display_name);
+                }
+                if let Some(description) = &spec.description {
+                    request = request.with_description(description);
+                }
+                let vault = org.vaults().create(request).await?;
+                created.vaults.insert(spec.name.clone(), vault.id);
+            },
+            Change::UpdateVault { id, spec } => {
+                let mut request = UpdateVaultRequest::new();
+                if let Some(display_name) = &spec.display_name {
+                    request = request.with_display_name(display_name);
+                }
+                if let Some(description) = &spec.description {
+                    request = request.with_description(description);
+                }
+                org.vaults().update(id, request).await?;
+            },
+            Change::DeleteVault { id, .. } => {
+                org.vaults().delete(id).confirm(format!("DELETE {}", id)).await?;
+            },
+            Change::DeploySchema { vault, vault_id, content, .. } => {
+                let vault_id = resolve("vault", vault, vault_id, &created.vaults)?;
+                let schemas =
+                    SchemasClient::new(self.client.clone(), &self.organization_id, vault_id);
+                let pushed = schemas.push(content).await?;
+                if !pushed.validation.is_valid() {
+                    let errors = pushed
+                        .validation
+                        .errors
+                        .iter()
+                        .map(|e| format!("{}:{}: {}", e.line, e.column, e.message))
+                        .collect::<Vec<_>>();
+                    return Err(Error::invalid_argument(format!(
+                        "schema for vault {:?} is invalid: {}",
+                        vault,
+                        errors.join("; ")
+                    )));
+                }
+                schemas.activate(&pushed.schema.version).await?;
+            },
+            Change::InviteMember(spec) => {
+                org.members().invite(InviteMemberRequest::new(&spec.email, spec.role)).await?;
+            },
+            Change::UpdateMemberRole { user_id, to, .. } => {
+                org.members().update(user_id, UpdateMemberRequest::new().with_role(*to)).await?;
+            },
+            Change::RemoveMember { user_id, .. } => org.members().remove(user_id).await?,
+            Change::RevokeInvitation { id, .. } => org.invitations().revoke(id).await?,
+            Change::CreateTeam { name, description } => {
+                let mut request = CreateTeamRequest::new(name);
+                if let Some(description) = description {
+                    request = request.with_description(description);
+                }
+                let team = org.teams().create(request).await?;
+                created.teams.insert(name.clone(), team.id);
+            },
+            Change::UpdateTeam { id, description, .. } => {
+                let mut request = UpdateTeamRequest::new();
+                if let Some(description) = description {
+                    request = request.with_description(description);
+                }
+                org.teams().update(id, request).await?;
+            },
+            Change::DeleteTeam { id, .. } => org.teams().delete(id).await?,
+            Change::AddTeamMember { team, team_id, user_id, .. } => {
+                let team_id = resolve("team", team, team_id, &created.teams)?;
+                org.teams().add_member(team_id, user_id).await?;
+            },
+            Change::RemoveTeamMember { team_id, user_id, .. } => {
+                org.teams().remove_member(team_id, user_id).await?;
+            },
+            Change::CreateApiClient(spec) => {
+                let mut request = CreateApiClientRequest::new(&spec.name);
+                if let Some(description) = &spec.description {
+                    request = request.with_description(description);
+                }
+                if let Some(permissions) = &spec.permissions {
+                    request = request.with_permissions(permissions.clone());
+                }
+                if let Some(rate_limit) = spec.rate_limit {
+                    request = request.with_rate_limit(rate_limit);
+                }
+                org.clients().create(request).await?;
+            },
+            Change::UpdateApiClient { id, spec, .. } => {
+                let mut request = UpdateApiClientRequest::new();
+                if let Some(description) = &spec.description {
+                    request = request.with_description(description);
+                }
+                if let Some(permissions) = &spec.permissions {
+                    request = request.with_permissions(permissions.clone());
+                }
+                if let Some(rate_limit) = spec.rate_limit {
+                    request = request.with_rate_limit(rate_limit);
+                }
+                org.clients().update(id, request).await?;
+            },
+            Change::DeleteApiClient { id, .. } => org.clients().delete(id).await?,
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Reconciler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reconciler")
+            .field("organization_id", &self.organization_id)
+            .field("dry_run", &self.dry_run)
+            .field("allow_destructive", &self.allow_destructive)
+            .finish_non_exhaustive()
+    }
+}
+
+/// IDs of resources created while applying a plan, by name.
+#[derive(Default)]
+struct Created {
+    vaults: HashMap<String, String>,
+    teams: HashMap<String, String>,
+}
+
+/// Returns the ID of a planned resource, falling back to one created
+/// earlier in the same apply.
+fn resolve<'a>(
+    kind: &str,
+    name: &str,
+    id: &'a Option<String>,
+    created: &'a HashMap<String, String>,
+) -> Result<&'a str, Error> {
+    id.as_deref().or_else(|| created.get(name).map(String::as_str)).ok_or_else(|| {
+        Error::invalid_argument(format!(
+            "{} {:?} does not exist and is not created by this plan",
+            kind, name
+        ))
+    })
+}
+
+/// Returns the breaking changes of deploying `desired` over the `active`
+/// schema of a vault, or `None` if the two are equivalent.
+///
+/// Schemas are compared parsed, so whitespace and comments do not count. If
+/// either fails to parse, any textual difference is a deploy, and it is
+/// treated as breaking since it cannot be classified.
+fn schema_changes(
+    vault: &str,
+    active: &str,
+    desired: &str,
+    plan: &mut Plan,
+) -> Option<Vec<String>> {
+    match SchemaDiff::between(active, desired) {
+        Ok(diff) if diff.changes.is_empty() => None,
+        Ok(diff) => Some(diff.breaking_changes().map(|c| c.description.clone()).collect()),
+        Err(_) if active.trim() == desired.trim() => None,
+        Err(e) => {
+            plan.warnings.push(format!(
+                "cannot compare the schemas of vault {:?}, treating its deploy as breaking: {}",
+                vault, e
+            ));
+            Some(vec!["schemas cannot be compared".to_string()])
+        },
+    }
+}
+
+/// Fails if two `names` have the same `key`, which must match names the way
+/// they are matched against the live state.
+fn check_unique<'a>(
+    kind: &str,
+    names: impl Iterator<Item = &'a str>,
+    key: fn(&str) -> String,
+) -> Result<(), Error> {
+    let mut seen = BTreeSet::new();
+    for name in names {
+        if !seen.insert(key(name)) {
+            return Err(Error::invalid_argument(format!(
+                "organization spec lists {} {:?} more than once",
+                kind, name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if a managed field differs from the live value.
+fn differs(desired: &Option<String>, live: &Option<String>) -> bool {
+    desired.is_some() && desired != live
+}
+
+fn same_email(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_from_json() {
+        let spec = OrgSpec::from_json(
+            r#"{
+                "vaults": [{ "name": "prod", "schema": "entity User {}" }],
+                "members": [{ "email": "alice@example.com", "role": "admin" }],
+                "teams": [{ "name": "eng", "members": ["alice@example.com"] }]
+            }"#,
+        )
+        .unwrap();
+
+        let vaults = spec.vaults.unwrap();
+        assert_eq!(vaults[0].schema.as_deref(), Some("entity User {}"));
+        assert_eq!(spec.members.unwrap()[0].role, OrgRole::Admin);
+        assert_eq!(spec.teams.unwrap()[0].members.as_deref().unwrap().len(), 1);
+        assert!(spec.clients.is_none());
+    }
+
+    #[test]
+    fn test_spec_rejects_unknown_fields() {
+        let err = OrgSpec::from_json(r#"{ "vault": [] }"#).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_spec_from_yaml() {
+        let spec = OrgSpec::from_yaml(
+            "clients:\n  - name: ci\n    permissions: [check]\n    rate_limit: 100\n",
+        )
+        .unwrap();
+        let clients = spec.clients.unwrap();
+        assert_eq!(clients[0].rate_limit, Some(100));
+        assert_eq!(clients[0].permissions.as_deref().unwrap(), ["check"]);
+    }
+
+    #[test]
+    fn test_change_actions_and_display() {
+        let change = Change::DeleteVault { id: "vlt_1".into(), name: "old".into() };
+        assert!(change.is_destructive());
+        assert_eq!(change.to_string(), "- delete vault \"old\" (vlt_1)");
+
+        let change = Change::UpdateMemberRole {
+            user_id: "usr_1".into(),
+            email: "alice@example.com".into(),
+            from: OrgRole::Member,
+            to: OrgRole::Admin,
+        };
+        assert_eq!(change.action(), ChangeAction::Update);
+        assert!(!change.is_destructive());
+        assert_eq!(change.to_string(), "~ change role of alice@example.com from member to admin");
+    }
+
+    #[test]
+    fn test_downgrades_and_permission_removals_are_destructive() {
+        let role_change = |from, to| Change::UpdateMemberRole {
+            user_id: "usr_1".into(),
+            email: "alice@example.com".into(),
+            from,
+            to,
+        };
+        assert!(role_change(OrgRole::Owner, OrgRole::Member).is_destructive());
+        assert!(role_change(OrgRole::Admin, OrgRole::Viewer).is_destructive());
+        assert!(role_change(OrgRole::Member, OrgRole::Billing).is_destructive());
+        assert!(role_change(OrgRole::Billing, OrgRole::Member).is_destructive());
+        assert!(!role_change(OrgRole::Viewer, OrgRole::Member).is_destructive());
+        assert!(!role_change(OrgRole::Billing, OrgRole::Admin).is_destructive());
+        assert!(!role_change(OrgRole::Admin, OrgRole::Owner).is_destructive());
+
+        let spec = ApiClientSpec {
+            name: "ci".into(),
+            description: None,
+            permissions: Some(vec!["check".into()]),
+            rate_limit: None,
+        };
+        let change = Change::UpdateApiClient {
+            id: "cli_1".into(),
+            spec: spec.clone(),
+            removed_permissions: vec!["write".into()],
+        };
+        assert!(change.is_destructive());
+        assert_eq!(change.to_string(), "~ update API client \"ci\" (removes write)");
+        let change =
+            Change::UpdateApiClient { id: "cli_1".into(), spec, removed_permissions: Vec::new() };
+        assert!(!change.is_destructive());
+    }
+
+    #[test]
+    fn test_check_unique_matches_like_live_state() {
+        let emails = |names: [&'static str; 2]| {
+            check_unique("member", names.into_iter(), str::to_ascii_lowercase)
+        };
+        assert!(emails(["a@x.io", "b@x.io"]).is_ok());
+        assert!(emails(["a@x.io", "A@x.io"]).is_err());
+
+        // Vault, team and client names are matched case-sensitively
+        let names =
+            |names: [&'static str; 2]| check_unique("vault", names.into_iter(), str::to_owned);
+        assert!(names(["prod", "Prod"]).is_ok());
+        assert!(names(["prod", "prod"]).is_err());
+    }
+
+    #[test]
+    fn test_breaking_schema_deploys_are_destructive() {
+        let mut plan = Plan::default();
+        let active = "type user {}\ntype document { relation viewer: user }";
+
+        let reformatted =
+            "// Users\ntype user {}\n\ntype document {\n    relation viewer: user\n}\n";
+        assert_eq!(schema_changes("prod", active, reformatted, &mut plan), None);
+        let widened =
+            "type user {}\ntype group {}\ntype document { relation viewer: user | group }";
+        assert_eq!(schema_changes("prod", active, widened, &mut plan), Some(Vec::new()));
+        let breaking = schema_changes("prod", active, "type user {}", &mut plan).unwrap();
+        assert_eq!(breaking, ["type `document` removed"]);
+        assert!(plan.warnings.is_empty());
+
+        let change = Change::DeploySchema {
+            vault: "prod".into(),
+            vault_id: Some("vlt_prod".into()),
+            content: "type user {}".into(),
+            breaking_changes: breaking,
+        };
+        assert!(change.is_destructive());
+        assert_eq!(
+            change.to_string(),
+            "~ deploy schema to vault \"prod\" (breaking: type `document` removed)"
+        );
+
+        assert!(schema_changes("prod", active, "type {", &mut plan).is_some_and(|c| !c.is_empty()));
+        assert_eq!(plan.warnings.len(), 1);
+    }
+}
+
+#[cfg(all(test, feature = "rest"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod wiremock_tests {
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_partial_json, method, path},
+    };
+
+    use super::*;
+    use crate::auth::BearerCredentialsConfig;
+
+    const ORG: &str = "/control/v1/organizations/org_1";
+    const TIMESTAMP: &str = "2024-01-01T00:00:00Z";
+
+    async fn create_mock_client(server: &MockServer) -> Client {
+        Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test_token"))
+            .build()
+            .await
+            .unwrap()
+    }
+
+    async fn mount_list(server: &MockServer, resource: &str, items: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path(format!("{}/{}", ORG, resource)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": items,
+                "page_info": { "has_next": false }
+            })))
+            .mount(server)
+            .await;
+    }
+
+    fn vault(id: &str, name: &str, description: Option<&str>) -> serde_json::Value {
+        json!({
+            "id": id, "organization_id": "org_1", "name": name, "description": description,
+            "status": "active", "created_at": TIMESTAMP, "updated_at": TIMESTAMP
+        })
+    }
+
+    fn member(user_id: &str, email: &str, role: &str) -> serde_json::Value {
+        json!({
+            "user_id": user_id, "organization_id": "org_1", "email": email, "role": role,
+            "status": "active", "joined_at": TIMESTAMP
+        })
+    }
+
+    fn schema(vault_id: &str, version: &str, content: &str) -> serde_json::Value {
+        json!({
+            "id": format!("sch_{}", version), "vault_id": vault_id, "version": version,
+            "content": content, "status": "active", "created_at": TIMESTAMP
+        })
+    }
+
+    /// Live org: vaults "prod" (stale description and schema) and "old",
+    /// team "eng" with bob, members alice (member) and bob.
+    async fn mount_live_org(server: &MockServer) {
+        mount_list(
+            server,
+            "vaults",
+            json!([vault("vlt_prod", "prod", Some("before")), vault("vlt_old", "old", None)]),
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/vaults/vlt_prod/schemas/active", ORG)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schema(
+                "vlt_prod",
+                "1",
+                "entity User {}",
+            )))
+            .mount(server)
+            .await;
+        mount_list(
+            server,
+            "members",
+            json!([
+                member("usr_alice", "alice@example.com", "member"),
+                member("usr_bob", "bob@example.com", "member")
+            ]),
+        )
+        .await;
+        mount_list(server, "invitations", json!([])).await;
+        mount_list(
+            server,
+            "teams",
+            json!([{
+                "id": "team_eng", "organization_id": "org_1", "name": "eng", "member_count": 1,
+                "created_at": TIMESTAMP, "updated_at": TIMESTAMP
+            }]),
+        )
+        .await;
+        mount_list(
+            server,
+            "teams/team_eng/members",
+            json!([{
+                "user_id": "usr_bob", "email": "bob@example.com", "role": "member",
+                "joined_at": TIMESTAMP
+            }]),
+        )
+        .await;
+        mount_list(server, "clients", json!([])).await;
+    }
+
+    fn desired() -> OrgSpec {
+        OrgSpec::from_json(
+            r#"{
+                "vaults": [
+                    { "name": "prod", "description": "after", "schema": "entity User {}\nentity Doc {}" },
+                    { "name": "staging", "schema": "entity User {}" }
+                ],
+                "members": [
+                    { "email": "alice@example.com", "role": "admin" },
+                    { "email": "bob@example.com", "role": "member" },
+                    { "email": "carol@example.com", "role": "viewer" }
+                ],
+                "teams": [
+                    { "name": "eng", "members": ["alice@example.com", "carol@example.com"] }
+                ],
+                "clients": [{ "name": "ci", "permissions": ["check"] }]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_plan_orders_changes() {
+        let server = MockServer::start().await;
+        mount_live_org(&server).await;
+        let client = create_mock_client(&server).await;
+
+        let plan = Reconciler::new(&client, "org_1").plan(&desired()).await.unwrap();
+        let lines: Vec<String> = plan.changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "~ update vault \"prod\"",
+                "~ deploy schema to vault \"prod\"",
+                "+ create vault \"staging\"",
+                "~ deploy schema to vault \"staging\"",
+                "~ change role of alice@example.com from member to admin",
+                "+ invite carol@example.com as viewer",
+                "+ add alice@example.com to team \"eng\"",
+                "+ create API client \"ci\"",
+                "- remove bob@example.com from team \"eng\"",
+                "- delete vault \"old\" (vlt_old)",
+            ]
+        );
+        assert_eq!(plan.warnings.len(), 1);
+        assert!(plan.warnings[0].contains("carol@example.com"));
+        assert!(plan.to_string().ends_with("Plan: 4 to add, 4 to change, 2 to destroy."));
+    }
+
+    #[tokio::test]
+    async fn test_unmanaged_sections_are_left_alone() {
+        let server = MockServer::start().await;
+        mount_live_org(&server).await;
+        let client = create_mock_client(&server).await;
+
+        let spec = OrgSpec::from_json(r#"{ "clients": [] }"#).unwrap();
+        let plan = Reconciler::new(&client, "org_1").plan(&spec).await.unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_refuses_destructive_plan() {
+        let server = MockServer::start().await;
+        let client = create_mock_client(&server).await;
+        let plan = Plan {
+            changes: vec![Change::DeleteTeam { id: "team_eng".into(), name: "eng".into() }],
+            warnings: Vec::new(),
+        };
+
+        let err = Reconciler::new(&client, "org_1").apply(&plan).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+        assert!(err.to_string().contains("delete team \"eng\""));
+
+        let report = Reconciler::new(&client, "org_1")
+            .with_allow_destructive(true)
+            .with_dry_run(true)
+            .apply(&plan)
+            .await
+            .unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.applied, plan.changes);
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_downgrades_require_allow_destructive() {
+        let server = MockServer::start().await;
+        mount_list(&server, "members", json!([member("usr_alice", "alice@example.com", "owner")]))
+            .await;
+        mount_list(&server, "invitations", json!([])).await;
+        mount_list(
+            &server,
+            "clients",
+            json!([{
+                "id": "cli_ci", "name": "ci", "status": "active", "created_at": TIMESTAMP,
+                "updated_at": TIMESTAMP, "permissions": ["check", "write"], "rate_limit": null
+            }]),
+        )
+        .await;
+        let client = create_mock_client(&server).await;
+
+        let spec = OrgSpec::from_json(
+            r#"{
+                "members": [{ "email": "alice@example.com", "role": "member" }],
+                "clients": [{ "name": "ci", "permissions": ["check"] }]
+            }"#,
+        )
+        .unwrap();
+        let reconciler = Reconciler::new(&client, "org_1");
+        let plan = reconciler.plan(&spec).await.unwrap();
+        let destructive: Vec<String> =
+            plan.destructive_changes().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            destructive,
+            [
+                "~ change role of alice@example.com from owner to member",
+                "~ update API client \"ci\" (removes write)",
+            ]
+        );
+
+        let err = reconciler.apply(&plan).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+        assert!(err.to_string().contains("from owner to member"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_resolves_created_vaults() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/vaults", ORG)))
+            .and(body_partial_json(json!({ "name": "staging" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vault(
+                "vlt_staging",
+                "staging",
+                None,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/vaults/vlt_staging/schemas", ORG)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "schema": schema("vlt_staging", "1", "entity User {}"),
+                "validation": { "is_valid": true, "errors": [], "warnings": [] }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/vaults/vlt_staging/schemas/1/activate", ORG)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schema(
+                "vlt_staging",
+                "1",
+                "entity User {}",
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/invitations", ORG)))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let plan = Plan {
+            changes: vec![
+                Change::CreateVault(VaultSpec { name: "staging".into(), ..Default::default() }),
+                Change::DeploySchema {
+                    vault: "staging".into(),
+                    vault_id: None,
+                    content: "entity User {}".into(),
+                    breaking_changes: Vec::new(),
+                },
+                Change::InviteMember(MemberSpec {
+                    email: "carol@example.com".into(),
+                    role: OrgRole::Viewer,
+                }),
+                Change::CreateTeam { name: "eng".into(), description: None },
+            ],
+            warnings: Vec::new(),
+        };
+
+        let report = Reconciler::new(&client, "org_1").apply(&plan).await.unwrap();
+        assert_eq!(report.applied.len(), 2);
+        assert!(!report.is_success());
+        let failed = report.failed.unwrap();
+        assert!(matches!(failed.change, Change::InviteMember(_)));
+    }
+}
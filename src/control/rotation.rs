@@ -0,0 +1,678 @@
+//! Automated rotation of API client signing keys.
+//!
+//! [`KeyRotator`] generates a new Ed25519 key, registers it as a certificate
+//! with a grace period, persists it through a [`KeyStore`], swaps it into
+//! the running [`Client`] and revokes the previous certificate once the grace
+//! period has passed.
+
+use std::{
+    future::Future,
+    io::Write,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+
+use super::{CertificatesClient, ClientCertificate, RotateCertificateRequest};
+use crate::{Ed25519PrivateKey, Error, ErrorKind, client::Client};
+
+/// How long a scheduled rotation waits before retrying a failed step.
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How many times a scheduled rotation tries to revoke the previous
+/// certificate before moving on.
+const REVOKE_ATTEMPTS: u32 = 5;
+
+/// Future returned by [`KeyStore::store`].
+pub type KeyStoreFuture<'a> = Pin<Box<dyn Future<Output = Result<(), Error>> + Send + 'a>>;
+
+/// Persists rotated private keys so that restarted processes pick them up.
+///
+/// Implement this for secret managers; [`FileKeyStore`] writes PEM files.
+pub trait KeyStore: Send + Sync {
+    /// Stores the PKCS#8 PEM of the private key registered as `certificate`.
+    ///
+    /// The rotation is rolled back if this fails, so the store must not
+    /// report success before the key is durable.
+    fn store<'a>(
+        &'a self,
+        private_key_pem: &'a str,
+        certificate: &'a ClientCertificate,
+    ) -> KeyStoreFuture<'a>;
+}
+
+impl<T: KeyStore + ?Sized> KeyStore for Arc<T> {
+    fn store<'a>(
+        &'a self,
+        private_key_pem: &'a str,
+        certificate: &'a ClientCertificate,
+    ) -> KeyStoreFuture<'a> {
+        (**self).store(private_key_pem, certificate)
+    }
+}
+
+/// Stores rotated keys as a PEM file, replacing the previous key.
+///
+/// The file is replaced atomically and, on Unix, is readable by its owner
+/// only. Load it at startup with
+/// [`Ed25519PrivateKey::from_pem_file`](crate::Ed25519PrivateKey::from_pem_file).
+#[derive(Debug, Clone)]
+pub struct FileKeyStore {
+    path: PathBuf,
+}
+
+impl FileKeyStore {
+    /// Creates a store writing to `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the path of the key file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write(&self, pem: &str) -> std::io::Result<()> {
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&temporary)?;
+        file.write_all(pem.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&temporary, &self.path)
+    }
+}
+
+impl KeyStore for FileKeyStore {
+    fn store<'a>(
+        &'a self,
+        private_key_pem: &'a str,
+        _certificate: &'a ClientCertificate,
+    ) -> KeyStoreFuture<'a> {
+        Box::pin(async move {
+            self.write(private_key_pem).map_err(|e| {
+                Error::configuration(format!(
+                    "failed to write key file '{}': {}",
+                    self.path.display(),
+                    e
+                ))
+            })
+        })
+    }
+}
+
+/// The outcome of one key rotation.
+#[derive(Debug, Clone)]
+pub struct Rotation {
+    /// The certificate registered for the new key.
+    pub certificate: ClientCertificate,
+    /// The certificate of the replaced key, if it was known.
+    pub previous_certificate_id: Option<String>,
+    /// When the previous certificate stops being needed.
+    pub revoke_after: chrono::DateTime<chrono::Utc>,
+}
+
+/// Rotates the signing key of an API client.
+///
+/// A rotation generates a new Ed25519 key, registers its public key with
+/// [`CertificatesClient::rotate`] and a grace period, persists the private
+/// key through the [`KeyStore`], and hot-swaps it into the client with
+/// [`Client::replace_signing_key`]. If the key cannot be stored, the new
+/// certificate is revoked again and the client keeps its current key.
+///
+/// The previous certificate stays valid during the grace period, so other
+/// replicas still using it keep working; revoke it afterwards with
+/// [`revoke_previous`](Self::revoke_previous), or let
+/// [`schedule`](Self::schedule) do both on a fixed interval.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use inferadb::control::{FileKeyStore, KeyRotator};
+///
+/// let rotator = KeyRotator::new(&client, "org_123", FileKeyStore::new("keys/service.pem"))?
+///     .with_grace_period(Duration::from_secs(24 * 3600));
+///
+/// // Rotate every 90 days, counted from when the current key was registered
+/// let schedule = rotator.schedule(Duration::from_secs(90 * 24 * 3600));
+/// ```
+#[derive(Clone)]
+pub struct KeyRotator {
+    client: Client,
+    certificates: CertificatesClient,
+    store: Arc<dyn KeyStore>,
+    grace_period: Duration,
+}
+
+impl KeyRotator {
+    /// Creates a rotator for the API client that `client` authenticates as.
+    ///
+    /// The grace period defaults to 24 hours.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Configuration` error if `client` authenticates with a
+    /// bearer token instead of client credentials.
+    pub fn new(
+        client: &Client,
+        organization_id: impl Into<String>,
+        store: impl KeyStore + 'static,
+    ) -> Result<Self, Error> {
+        let client_id = client.client_id().ok_or_else(|| {
+            Error::configuration("key rotation requires client credentials, not a bearer token")
+        })?;
+        let certificates = client.organization(organization_id).clients().certificates(client_id);
+        Ok(Self {
+            client: client.clone(),
+            certificates,
+            store: Arc::new(store),
+            grace_period: Duration::from_secs(24 * 3600),
+        })
+    }
+
+    /// Sets how long the previous certificate stays valid after a rotation.
+    #[must_use]
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Returns the grace period.
+    pub fn grace_period(&self) -> Duration {
+        self.grace_period
+    }
+
+    /// Rotates the signing key now.
+    ///
+    /// Without a configured certificate ID, the newest active certificate
+    /// is taken to be the one being replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the certificates cannot be listed, or the key
+    /// cannot be encoded, registered or stored.
+    pub async fn rotate(&self) -> Result<Rotation, Error> {
+        let key = Ed25519PrivateKey::generate();
+        let private_key_pem = key.to_pem()?;
+        let request = RotateCertificateRequest::new(key.public_key_pem()?)
+            .with_algorithm("Ed25519")
+            .with_grace_period(self.grace_period);
+
+        let previous_certificate_id = match self.client.certificate_id() {
+            Some(id) => Some(id),
+            None => {
+                let certificates = self.certificates.list().await?.items;
+                self.current_certificate(&certificates).map(|c| c.id.clone())
+            },
+        };
+        let certificate = self.certificates.rotate(request).await?;
+
+        if let Err(e) = self.store.store(&private_key_pem, &certificate).await {
+            // Without a stored key the new certificate is unusable after a
+            // restart; failing to revoke it only leaves an unused certificate
+            let _ = self.certificates.revoke(&certificate.id).await;
+            return Err(e);
+        }
+        self.client.replace_signing_key(key, Some(certificate.id.clone()))?;
+
+        let grace_period =
+            chrono::Duration::from_std(self.grace_period).unwrap_or(chrono::Duration::MAX);
+        Ok(Rotation {
+            revoke_after: certificate
+                .created_at
+                .checked_add_signed(grace_period)
+                .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC),
+            certificate,
+            previous_certificate_id,
+        })
+    }
+
+    /// Revokes the certificate replaced by `rotation`, if it was known.
+    ///
+    /// A certificate that no longer exists counts as revoked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the revocation request fails.
+    pub async fn revoke_previous(&self, rotation: &Rotation) -> Result<(), Error> {
+        let Some(id) = &rotation.previous_certificate_id else {
+            return Ok(());
+        };
+        match self.certificates.revoke(id).await {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Returns when the current key becomes due for rotation, `interval`
+    /// after its certificate was registered.
+    ///
+    /// Without a configured certificate ID, the newest active certificate
+    /// is taken to be the current one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the certificates cannot be listed.
+    pub async fn next_rotation_due(
+        &self,
+        interval: Duration,
+    ) -> Result<chrono::DateTime<chrono::Utc>, Error> {
+        let certificates = self.certificates.list().await?.items;
+        let registered = self
+            .current_certificate(&certificates)
+            .map(|c| c.created_at)
+            .unwrap_or_else(chrono::Utc::now);
+        let interval = chrono::Duration::from_std(interval).unwrap_or(chrono::Duration::MAX);
+        Ok(registered
+            .checked_add_signed(interval)
+            .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC))
+    }
+
+    /// Returns the certificate of the current key: the configured one, or
+    /// else the newest active one.
+    fn current_certificate<'a>(
+        &self,
+        certificates: &'a [ClientCertificate],
+    ) -> Option<&'a ClientCertificate> {
+        let current = self.client.certificate_id();
+        certificates
+            .iter()
+            .find(|c| current.as_deref() == Some(c.id.as_str()))
+            .or_else(|| certificates.iter().filter(|c| c.active).max_by_key(|c| c.created_at))
+    }
+
+    /// Rotates the key every `interval` in a background task.
+    ///
+    /// The first rotation happens when the current key is due (see
+    /// [`next_rotation_due`](Self::next_rotation_due)), so restarts do not
+    /// postpone it. After each rotation the previous certificate is revoked
+    /// once the grace period has passed. Failed steps are retried every
+    /// minute; revoking the previous certificate is given up after a few
+    /// attempts so that it never holds up the next rotation. Dropping the
+    /// returned [`RotationSchedule`] stops the task.
+    pub fn schedule(self, interval: Duration) -> RotationSchedule {
+        let state = Arc::new(Mutex::new(ScheduleState::default()));
+        let task = tokio::spawn(run_schedule(self, interval, Arc::clone(&state)));
+        RotationSchedule { task, state }
+    }
+}
+
+impl std::fmt::Debug for KeyRotator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyRotator")
+            .field("certificates", &self.certificates)
+            .field("grace_period", &self.grace_period)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Default)]
+struct ScheduleState {
+    last_rotation: Option<Rotation>,
+    last_error: Option<String>,
+    rotations: u64,
+}
+
+async fn run_schedule(rotator: KeyRotator, interval: Duration, state: Arc<Mutex<ScheduleState>>) {
+    let mut last_rotated: Option<tokio::time::Instant> = None;
+    loop {
+        let wait = match rotator.next_rotation_due(interval).await {
+            Ok(due) => (due - chrono::Utc::now()).to_std().unwrap_or(Duration::ZERO),
+            Err(e) => {
+                state.lock().last_error = Some(e.to_string());
+                tokio::time::sleep(RETRY_INTERVAL).await;
+                continue;
+            },
+        };
+        // The listed certificates may lag behind a rotation we just made
+        let wait = last_rotated.map_or(wait, |at| wait.max(interval.saturating_sub(at.elapsed())));
+        tokio::time::sleep(wait).await;
+
+        let rotation = match rotator.rotate().await {
+            Ok(rotation) => rotation,
+            Err(e) => {
+                state.lock().last_error = Some(e.to_string());
+                tokio::time::sleep(RETRY_INTERVAL).await;
+                continue;
+            },
+        };
+        last_rotated = Some(tokio::time::Instant::now());
+        {
+            let mut state = state.lock();
+            state.last_rotation = Some(rotation.clone());
+            state.last_error = None;
+            state.rotations += 1;
+        }
+
+        tokio::time::sleep(rotator.grace_period).await;
+        for attempt in 1..=REVOKE_ATTEMPTS {
+            match rotator.revoke_previous(&rotation).await {
+                Ok(()) => break,
+                Err(e) => {
+                    state.lock().last_error = Some(e.to_string());
+                    if attempt < REVOKE_ATTEMPTS {
+                        tokio::time::sleep(RETRY_INTERVAL).await;
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Handle to a background rotation started by [`KeyRotator::schedule`].
+///
+/// Dropping the handle stops the schedule.
+pub struct RotationSchedule {
+    task: tokio::task::JoinHandle<()>,
+    state: Arc<Mutex<ScheduleState>>,
+}
+
+impl RotationSchedule {
+    /// Returns the most recent rotation, if any.
+    pub fn last_rotation(&self) -> Option<Rotation> {
+        self.state.lock().last_rotation.clone()
+    }
+
+    /// Returns the error of the most recent failed step, cleared by the next
+    /// successful rotation.
+    pub fn last_error(&self) -> Option<String> {
+        self.state.lock().last_error.clone()
+    }
+
+    /// Returns the number of rotations performed.
+    pub fn rotations(&self) -> u64 {
+        self.state.lock().rotations
+    }
+
+    /// Stops the schedule.
+    pub fn stop(self) {}
+}
+
+impl Drop for RotationSchedule {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl std::fmt::Debug for RotationSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RotationSchedule")
+            .field("rotations", &self.rotations())
+            .field("last_error", &self.last_error())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "rest"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod wiremock_tests {
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_partial_json, method, path},
+    };
+
+    use super::*;
+    use crate::ClientCredentialsConfig;
+
+    const CERTIFICATES: &str = "/control/v1/organizations/org_1/clients/cli_1/certificates";
+
+    async fn client(server: &MockServer) -> Client {
+        let credentials = ClientCredentialsConfig::new("cli_1", Ed25519PrivateKey::generate())
+            .with_certificate_id("crt_old");
+        Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(credentials)
+            .build()
+            .await
+            .unwrap()
+    }
+
+    fn certificate(id: &str, created_at: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "fingerprint": "SHA256:abc",
+            "algorithm": "Ed25519",
+            "created_at": created_at,
+            "active": true
+        })
+    }
+
+    fn key_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("inferadb-keys-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    struct FailingStore;
+
+    impl KeyStore for FailingStore {
+        fn store<'a>(&'a self, _: &'a str, _: &'a ClientCertificate) -> KeyStoreFuture<'a> {
+            Box::pin(async { Err(Error::configuration("vault sealed")) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotate_registers_stores_and_swaps_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(CERTIFICATES))
+            .and(body_partial_json(json!({ "algorithm": "Ed25519", "grace_period_secs": 3600 })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(certificate("crt_new", "2026-01-01T00:00:00Z")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path(format!("{}/crt_old", CERTIFICATES)))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        let old_key = client.signing_public_key();
+        let path = key_file("service.pem");
+        let rotator = KeyRotator::new(&client, "org_1", FileKeyStore::new(&path))
+            .unwrap()
+            .with_grace_period(Duration::from_secs(3600));
+
+        let rotation = rotator.rotate().await.unwrap();
+        assert_eq!(rotation.certificate.id, "crt_new");
+        assert_eq!(rotation.previous_certificate_id.as_deref(), Some("crt_old"));
+        assert_eq!(rotation.revoke_after.to_rfc3339(), "2026-01-01T01:00:00+00:00");
+
+        // The stored key is the one the client now signs with
+        let stored = Ed25519PrivateKey::from_pem_file(&path).unwrap();
+        assert_eq!(client.signing_public_key(), Some(stored.public_key_bytes()));
+        assert_ne!(client.signing_public_key(), old_key);
+        assert_eq!(client.certificate_id().as_deref(), Some("crt_new"));
+
+        rotator.revoke_previous(&rotation).await.unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn test_rotate_without_certificate_id_replaces_newest_active() {
+        let server = MockServer::start().await;
+        let mut revoked = certificate("crt_revoked", "2026-01-01T00:00:00Z");
+        revoked["active"] = json!(false);
+        Mock::given(method("GET"))
+            .and(path(CERTIFICATES))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [
+                    certificate("crt_older", "2024-01-01T00:00:00Z"),
+                    revoked,
+                    certificate("crt_old", "2025-01-01T00:00:00Z"),
+                ],
+                "page_info": { "has_next": false }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(CERTIFICATES))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(certificate("crt_new", "2026-02-01T00:00:00Z")),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path(format!("{}/crt_old", CERTIFICATES)))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(ClientCredentialsConfig::new("cli_1", Ed25519PrivateKey::generate()))
+            .build()
+            .await
+            .unwrap();
+        assert!(client.certificate_id().is_none());
+        let path = key_file("service.pem");
+        let rotator = KeyRotator::new(&client, "org_1", FileKeyStore::new(&path)).unwrap();
+
+        let rotation = rotator.rotate().await.unwrap();
+        assert_eq!(rotation.previous_certificate_id.as_deref(), Some("crt_old"));
+        rotator.revoke_previous(&rotation).await.unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn test_revoking_missing_certificate_succeeds() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path(format!("{}/crt_gone", CERTIFICATES)))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "error": "not_found",
+                "message": "certificate not found"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        let rotator = KeyRotator::new(&client, "org_1", FailingStore).unwrap();
+        let rotation = Rotation {
+            certificate: serde_json::from_value(certificate("crt_new", "2026-01-01T00:00:00Z"))
+                .unwrap(),
+            previous_certificate_id: Some("crt_gone".to_string()),
+            revoke_after: chrono::Utc::now(),
+        };
+        rotator.revoke_previous(&rotation).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_store_failure_rolls_back() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(CERTIFICATES))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(certificate("crt_new", "2026-01-01T00:00:00Z")),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path(format!("{}/crt_new", CERTIFICATES)))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        let old_key = client.signing_public_key();
+        let rotator = KeyRotator::new(&client, "org_1", FailingStore).unwrap();
+
+        let err = rotator.rotate().await.unwrap_err();
+        assert!(err.to_string().contains("vault sealed"));
+        assert_eq!(client.signing_public_key(), old_key);
+        assert_eq!(client.certificate_id().as_deref(), Some("crt_old"));
+    }
+
+    #[tokio::test]
+    async fn test_bearer_credentials_are_rejected() {
+        let client = Client::builder()
+            .url("http://127.0.0.1:9")
+            .insecure()
+            .credentials(crate::BearerCredentialsConfig::new("token"))
+            .build()
+            .await
+            .unwrap();
+
+        let err = KeyRotator::new(&client, "org_1", FileKeyStore::new("unused.pem")).unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Configuration);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_rotates_overdue_key_and_revokes_previous() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(CERTIFICATES))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [certificate("crt_old", "2020-01-01T00:00:00Z")],
+                "page_info": { "has_next": false }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(CERTIFICATES))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(certificate("crt_new", "2026-01-01T00:00:00Z")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path(format!("{}/crt_old", CERTIFICATES)))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        let path = key_file("service.pem");
+        let schedule = KeyRotator::new(&client, "org_1", FileKeyStore::new(&path))
+            .unwrap()
+            .with_grace_period(Duration::ZERO)
+            .schedule(Duration::from_secs(90 * 24 * 3600));
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while server
+                .received_requests()
+                .await
+                .unwrap()
+                .iter()
+                .all(|r| r.method.as_str() != "DELETE")
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(schedule.rotations(), 1);
+        assert_eq!(schedule.last_rotation().unwrap().certificate.id, "crt_new");
+        assert!(schedule.last_error().is_none());
+        schedule.stop();
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+}
@@ -0,0 +1,67 @@
+//! Offline schema tooling with default settings.
+//!
+//! Shorthands for the [`ipl`](super::ipl) linter and formatter, for tests
+//! and CI checks that do not need a custom configuration.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inferadb::control::schema;
+//!
+//! let report = schema::lint(
+//!     "type user {}
+//!      type document {
+//!          relation owner: user
+//!          permission edit = owner
+//!      }",
+//! )?;
+//! assert!(report.is_clean());
+//! # Ok::<(), inferadb::Error>(())
+//! ```
+
+use super::ipl::{FormatConfig, LintConfig, LintReport};
+use crate::Error;
+
+/// Formats IPL source with the default settings.
+///
+/// See [`ipl::format`](super::ipl::format) for the output style and for
+/// sorting options. Source that fails to parse is returned unchanged; use
+/// [`check`] to report it.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::control::schema;
+///
+/// assert_eq!(
+///     schema::format("type document{relation viewer:user|group#member}"),
+///     "type document {\n    relation viewer: user | group#member\n}\n"
+/// );
+/// assert_eq!(schema::format("type {"), "type {");
+/// ```
+pub fn format(ipl: &str) -> String {
+    super::ipl::format(ipl, &FormatConfig::default()).unwrap_or_else(|_| ipl.to_owned())
+}
+
+/// Checks that IPL source is already formatted with the default settings.
+///
+/// # Errors
+///
+/// Returns a [`SchemaViolation`](crate::ErrorKind::SchemaViolation) error if
+/// the source fails to parse or differs from [`format()`]'s output. See
+/// [`ipl::check`](super::ipl::check).
+pub fn check(ipl: &str) -> Result<(), Error> {
+    super::ipl::check(ipl, &FormatConfig::default())
+}
+
+/// Lints IPL source with the default rule set.
+///
+/// See [`ipl::lint`](super::ipl::lint) to configure rules and severities.
+///
+/// # Errors
+///
+/// Returns a [`SchemaViolation`](crate::ErrorKind::SchemaViolation) error if
+/// the source fails to parse.
+pub fn lint(ipl: &str) -> Result<LintReport, Error> {
+    super::ipl::lint(ipl, &LintConfig::default())
+}
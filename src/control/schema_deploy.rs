@@ -0,0 +1,493 @@
+//! Gated schema deployment with rollback.
+//!
+//! [`DeploySchemaRequest`] strings the individual schema calls together into
+//! one rollout: validate, check any simulated assertions, push, diff against
+//! the active version, check the diff, and only then activate.
+
+use std::{future::Future, pin::Pin};
+
+use crate::{
+    Error, ErrorKind,
+    control::{SchemaChange, SchemaDiff, SchemaInfo, SchemasClient, ValidationResult},
+    vault::{SimulationCheck, SimulationDiff, VaultClient},
+};
+
+/// Request to deploy a schema.
+///
+/// Created by [`SchemasClient::deploy`]. The schema is activated only if
+///
+/// 1. it validates without errors,
+/// 2. every expected decision holds when simulated against the new schema,
+///    and
+/// 3. its diff against the active version has no breaking changes, unless
+///    [`allow_breaking`](Self::allow_breaking) is set.
+///
+/// Otherwise the request fails with a [`ErrorKind::SchemaViolation`] error
+/// describing the failed gate. The first two gates run before the schema is
+/// pushed, so a schema failing them leaves no version behind; a version that
+/// was pushed but has breaking changes is left inactive.
+///
+/// The assertions are simulated with
+/// [`SimulateBuilder::with_schema`](crate::vault::SimulateBuilder::with_schema),
+/// so they need a transport that can carry a schema.
+#[derive(Clone)]
+pub struct DeploySchemaRequest {
+    schemas: SchemasClient,
+    content: String,
+    allow_breaking: bool,
+    dry_run: bool,
+    expectations: Vec<(SimulationCheck, bool)>,
+}
+
+impl DeploySchemaRequest {
+    pub(crate) fn new(schemas: SchemasClient, content: String) -> Self {
+        Self { schemas, content, allow_breaking: false, dry_run: false, expectations: Vec::new() }
+    }
+
+    /// Activates the schema even if the diff contains breaking changes.
+    #[must_use]
+    pub fn allow_breaking(mut self, allow: bool) -> Self {
+        self.allow_breaking = allow;
+        self
+    }
+
+    /// Runs every gate but leaves the pushed version inactive.
+    #[must_use]
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Requires the check to be allowed under the new schema.
+    #[must_use]
+    pub fn expect_allowed(mut self, check: impl Into<SimulationCheck>) -> Self {
+        self.expectations.push((check.into(), true));
+        self
+    }
+
+    /// Requires the check to be denied under the new schema.
+    #[must_use]
+    pub fn expect_denied(mut self, check: impl Into<SimulationCheck>) -> Self {
+        self.expectations.push((check.into(), false));
+        self
+    }
+
+    async fn execute(self) -> Result<SchemaDeployment, Error> {
+        let validation = self.schemas.validate(self.content.clone()).await?;
+        if !validation.is_valid() {
+            return Err(invalid_schema(&validation));
+        }
+
+        let (checks, expected): (Vec<_>, Vec<_>) = self.expectations.into_iter().unzip();
+        let simulations = if checks.is_empty() {
+            Vec::new()
+        } else {
+            VaultClient::new(
+                self.schemas.client().clone(),
+                self.schemas.organization_id().to_owned(),
+                self.schemas.vault_id().to_owned(),
+            )
+            .simulate()
+            .with_schema(self.content.clone())
+            .checks(checks)
+            .await?
+        };
+        let assertions: Vec<_> = simulations
+            .into_iter()
+            .zip(expected)
+            .map(|(simulation, expected)| DeployAssertion { expected, simulation })
+            .collect();
+        let failed: Vec<_> = assertions
+            .iter()
+            .filter(|a| !a.passed())
+            .map(|a| format!("assertion failed: {}", a))
+            .collect();
+        if !failed.is_empty() {
+            return Err(Error::new(
+                ErrorKind::SchemaViolation,
+                format!("schema was not pushed:\n{}", failed.join("\n")),
+            ));
+        }
+
+        let previous = match self.schemas.get_active().await {
+            Ok(active) => Some(active),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        let pushed = self.schemas.push(self.content).await?;
+        if !pushed.validation.is_valid() {
+            return Err(invalid_schema(&pushed.validation));
+        }
+        let schema = pushed.schema;
+
+        let diff = match &previous {
+            Some(previous) => {
+                Some(self.schemas.diff(previous.version.clone(), schema.version.clone()).await?)
+            },
+            None => None,
+        };
+
+        if !self.allow_breaking {
+            let breaking: Vec<&SchemaChange> =
+                diff.iter().flat_map(SchemaDiff::breaking_changes).collect();
+            if !breaking.is_empty() {
+                let changes: Vec<_> = breaking
+                    .iter()
+                    .map(|c| format!("  {}: {}", c.change_type, c.description))
+                    .collect();
+                return Err(Error::new(
+                    ErrorKind::SchemaViolation,
+                    format!(
+                        "schema version {} was not activated:\n{} breaking change(s); set \
+                         allow_breaking(true) to deploy anyway:\n{}",
+                        schema.version,
+                        breaking.len(),
+                        changes.join("\n")
+                    ),
+                ));
+            }
+        }
+
+        let schema = if self.dry_run {
+            schema
+        } else {
+            self.schemas.activate(schema.version.clone()).await?
+        };
+        Ok(SchemaDeployment {
+            schemas: self.schemas,
+            schema,
+            previous,
+            diff,
+            assertions,
+            activated: !self.dry_run,
+        })
+    }
+}
+
+/// Enables `schemas.deploy(ipl).await`.
+impl std::future::IntoFuture for DeploySchemaRequest {
+    type Output = Result<SchemaDeployment, Error>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.execute())
+    }
+}
+
+impl std::fmt::Debug for DeploySchemaRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeploySchemaRequest")
+            .field("schemas", &self.schemas)
+            .field("allow_breaking", &self.allow_breaking)
+            .field("dry_run", &self.dry_run)
+            .field("expectations", &self.expectations.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A simulated check run while deploying a schema.
+#[derive(Debug, Clone)]
+pub struct DeployAssertion {
+    /// Whether the check was expected to be allowed.
+    pub expected: bool,
+    /// The check under the active and under the new schema.
+    pub simulation: SimulationDiff,
+}
+
+impl DeployAssertion {
+    /// Returns `true` if the new schema gives the expected decision.
+    pub fn passed(&self) -> bool {
+        self.simulation.simulated_allowed == self.expected
+    }
+}
+
+impl std::fmt::Display for DeployAssertion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let decision = |allowed| if allowed { "allowed" } else { "denied" };
+        write!(
+            f,
+            "{} {} {}: expected {}, got {} (currently {})",
+            self.simulation.subject,
+            self.simulation.permission,
+            self.simulation.resource,
+            decision(self.expected),
+            decision(self.simulation.simulated_allowed),
+            decision(self.simulation.current_allowed)
+        )
+    }
+}
+
+/// The outcome of a successful [`DeploySchemaRequest`].
+#[derive(Debug, Clone)]
+pub struct SchemaDeployment {
+    schemas: SchemasClient,
+    /// The deployed schema version.
+    pub schema: SchemaInfo,
+    /// The version that was active before the deployment, if any.
+    pub previous: Option<SchemaInfo>,
+    /// Changes from the previous version, if there was one.
+    pub diff: Option<SchemaDiff>,
+    /// The simulated assertions, all of which passed.
+    pub assertions: Vec<DeployAssertion>,
+    /// Whether the schema was activated; `false` for dry runs.
+    pub activated: bool,
+}
+
+impl SchemaDeployment {
+    /// Returns the changes from the previous version that were allowed
+    /// through with [`DeploySchemaRequest::allow_breaking`].
+    pub fn breaking_changes(&self) -> Vec<&SchemaChange> {
+        self.diff.iter().flat_map(SchemaDiff::breaking_changes).collect()
+    }
+
+    /// Reactivates the version that was active before this deployment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deployment was a dry run, no version was
+    /// active before it, or activation fails.
+    pub async fn rollback(&self) -> Result<SchemaInfo, Error> {
+        if !self.activated {
+            return Err(Error::invalid_argument(format!(
+                "schema version {} was never activated",
+                self.schema.version
+            )));
+        }
+        let previous = self.previous.as_ref().ok_or_else(|| {
+            Error::invalid_argument("no schema version was active before this deployment")
+        })?;
+        self.schemas.activate(previous.version.clone()).await
+    }
+}
+
+fn invalid_schema(validation: &ValidationResult) -> Error {
+    let errors: Vec<_> = validation
+        .errors
+        .iter()
+        .map(|e| format!("  {}:{}: {} ({})", e.line, e.column, e.message, e.code))
+        .collect();
+    Error::new(ErrorKind::SchemaViolation, format!("schema is invalid:\n{}", errors.join("\n")))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::vault::SimulationChange;
+
+    #[test]
+    fn test_assertion_display() {
+        let assertion = DeployAssertion {
+            expected: true,
+            simulation: SimulationDiff {
+                subject: "user:alice".into(),
+                permission: "view".into(),
+                resource: "doc:1".into(),
+                current_allowed: true,
+                simulated_allowed: false,
+                change: SimulationChange::NowDenied,
+                hypothetical_additions: vec![],
+                hypothetical_removals: vec![],
+                context: None,
+            },
+        };
+        assert!(!assertion.passed());
+        assert_eq!(
+            assertion.to_string(),
+            "user:alice view doc:1: expected allowed, got denied (currently allowed)"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "rest"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod wiremock_tests {
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_partial_json, method, path, query_param},
+    };
+
+    use super::*;
+    use crate::{Client, auth::BearerCredentialsConfig};
+
+    const SCHEMAS: &str = "/control/v1/organizations/org_1/vaults/vlt_1/schemas";
+
+    async fn create_mock_client(server: &MockServer) -> Client {
+        Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test_token"))
+            .transport_strategy(crate::TransportStrategy::RestOnly)
+            .build()
+            .await
+            .unwrap()
+    }
+
+    fn schema(version: &str, status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": format!("sch_{}", version),
+            "vault_id": "vlt_1",
+            "version": version,
+            "content": "entity User {}",
+            "status": status,
+            "created_at": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    fn valid() -> serde_json::Value {
+        serde_json::json!({ "is_valid": true, "errors": [], "warnings": [] })
+    }
+
+    fn diff(change_type: &str, is_breaking: bool) -> serde_json::Value {
+        serde_json::json!({
+            "from_version": "1",
+            "to_version": "2",
+            "changes": [{
+                "change_type": change_type,
+                "description": "viewer on Document",
+                "entity_type": "Document",
+                "relation": "viewer",
+                "permission": null,
+                "is_breaking": is_breaking
+            }],
+            "is_backward_compatible": !is_breaking
+        })
+    }
+
+    /// Mounts validate, active (v1), push (v2) and a diff containing `change_type`.
+    async fn mount_rollout(server: &MockServer, change_type: &str, is_breaking: bool) {
+        Mock::given(method("POST"))
+            .and(path(format!("{}/validate", SCHEMAS)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(valid()))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/active", SCHEMAS)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schema("1", "active")))
+            .mount(server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(SCHEMAS))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "schema": schema("2", "inactive"),
+                "validation": valid()
+            })))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/diff", SCHEMAS)))
+            .and(query_param("from", "1"))
+            .and(query_param("to", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(diff(change_type, is_breaking)))
+            .mount(server)
+            .await;
+    }
+
+    async fn mount_activate(server: &MockServer, version: &str, calls: u64) {
+        Mock::given(method("POST"))
+            .and(path(format!("{}/{}/activate", SCHEMAS, version)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schema(version, "active")))
+            .expect(calls)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_deploy_activates_and_rolls_back() {
+        let server = MockServer::start().await;
+        mount_rollout(&server, "relation_added", false).await;
+        mount_activate(&server, "2", 1).await;
+        mount_activate(&server, "1", 1).await;
+        Mock::given(method("POST"))
+            .and(path("/access/v1/evaluate"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string("data: {\"decision\": \"allow\", \"index\": 0}\n\n"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/access/v1/simulate/batch"))
+            .and(body_partial_json(serde_json::json!({ "schema": "entity User {}" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [{ "index": 0, "allowed": true }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let schemas = SchemasClient::new(client, "org_1", "vlt_1");
+        let deployment = schemas
+            .deploy("entity User {}")
+            .expect_allowed(("user:alice", "view", "doc:1"))
+            .await
+            .unwrap();
+
+        assert!(deployment.activated);
+        assert!(deployment.schema.status.is_active());
+        assert_eq!(deployment.previous.as_ref().unwrap().version, "1");
+        assert!(deployment.breaking_changes().is_empty());
+        assert!(deployment.assertions[0].passed());
+
+        let restored = deployment.rollback().await.unwrap();
+        assert_eq!(restored.version, "1");
+    }
+
+    #[tokio::test]
+    async fn test_deploy_blocks_breaking_changes() {
+        let server = MockServer::start().await;
+        mount_rollout(&server, "relation_removed", false).await;
+        mount_activate(&server, "2", 0).await;
+
+        let client = create_mock_client(&server).await;
+        let schemas = SchemasClient::new(client, "org_1", "vlt_1");
+        let err = schemas.deploy("entity User {}").await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::SchemaViolation);
+        assert!(err.to_string().contains("relation_removed: viewer on Document"));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_allow_breaking_dry_run() {
+        let server = MockServer::start().await;
+        mount_rollout(&server, "permission_modified", true).await;
+        mount_activate(&server, "2", 0).await;
+
+        let client = create_mock_client(&server).await;
+        let schemas = SchemasClient::new(client, "org_1", "vlt_1");
+        let deployment =
+            schemas.deploy("entity User {}").allow_breaking(true).dry_run(true).await.unwrap();
+
+        assert!(!deployment.activated);
+        assert_eq!(deployment.breaking_changes().len(), 1);
+        assert!(deployment.rollback().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_rejects_invalid_schema_before_push() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/validate", SCHEMAS)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "is_valid": false,
+                "errors": [{ "line": 2, "column": 5, "message": "unknown type", "code": "E002" }],
+                "warnings": []
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(SCHEMAS))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let schemas = SchemasClient::new(client, "org_1", "vlt_1");
+        let err = schemas.deploy("entity Doc { relations { owner: Usr } }").await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::SchemaViolation);
+        assert!(err.to_string().contains("2:5: unknown type (E002)"));
+    }
+}
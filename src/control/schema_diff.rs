@@ -0,0 +1,405 @@
+//! Offline diff of two IPL sources.
+
+use std::fmt;
+
+use crate::{
+    Error,
+    control::{
+        SchemaChange, SchemaChangeType, SchemaDiff,
+        ipl::{Expr, PermissionDef, RelationDef, Schema, TypeDef},
+    },
+};
+
+impl SchemaDiff {
+    /// Compares two IPL sources without contacting the server.
+    ///
+    /// Changes are classified as the server would: removals are breaking,
+    /// relations that stop accepting a subject type are breaking, and
+    /// permissions that drop any operand of their top-level union are
+    /// breaking. Widening a relation or permission is compatible.
+    ///
+    /// `from_version` and `to_version` are left empty; set them to label
+    /// the sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SchemaViolation`](crate::ErrorKind::SchemaViolation)
+    /// error if either source fails to parse.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inferadb::control::SchemaDiff;
+    ///
+    /// let diff = SchemaDiff::between(
+    ///     "type document { relation viewer: user | group#member }",
+    ///     "type document { relation viewer: user }",
+    /// )?;
+    ///
+    /// assert!(!diff.is_backward_compatible);
+    /// assert_eq!(
+    ///     diff.changes[0].description,
+    ///     "relation `document#viewer` no longer accepts `group#member`"
+    /// );
+    /// # Ok::<(), inferadb::Error>(())
+    /// ```
+    pub fn between(from: &str, to: &str) -> Result<Self, Error> {
+        Ok(Self::compare(&Schema::parse(from)?, &Schema::parse(to)?))
+    }
+
+    /// Compares two parsed schemas. See [`between`](Self::between).
+    pub fn compare(from: &Schema, to: &Schema) -> Self {
+        let mut changes = Vec::new();
+        for old in &from.types {
+            match to.get_type(&old.name) {
+                Some(new) => compare_types(old, new, &mut changes),
+                None => changes.push(change(
+                    SchemaChangeType::EntityRemoved,
+                    format!("type `{}` removed", old.name),
+                    &old.name,
+                    true,
+                )),
+            }
+        }
+        for new in to.types.iter().filter(|t| from.get_type(&t.name).is_none()) {
+            changes.push(change(
+                SchemaChangeType::EntityAdded,
+                format!("type `{}` added", new.name),
+                &new.name,
+                false,
+            ));
+        }
+
+        SchemaDiff {
+            from_version: String::new(),
+            to_version: String::new(),
+            is_backward_compatible: !changes.iter().any(|c| c.is_breaking),
+            changes,
+        }
+    }
+}
+
+fn compare_types(old: &TypeDef, new: &TypeDef, changes: &mut Vec<SchemaChange>) {
+    let name = |member: &str| format!("`{}#{}`", old.name, member);
+
+    for relation in &old.relations {
+        match new.relation(&relation.name) {
+            Some(updated) => {
+                if let Some(change) = compare_relations(&old.name, relation, updated) {
+                    changes.push(change);
+                }
+            },
+            None => changes.push(SchemaChange {
+                relation: Some(relation.name.clone()),
+                ..change(
+                    SchemaChangeType::RelationRemoved,
+                    format!("relation {} removed", name(&relation.name)),
+                    &old.name,
+                    true,
+                )
+            }),
+        }
+    }
+    for relation in new.relations.iter().filter(|r| old.relation(&r.name).is_none()) {
+        changes.push(SchemaChange {
+            relation: Some(relation.name.clone()),
+            ..change(
+                SchemaChangeType::RelationAdded,
+                format!("relation {} added", name(&relation.name)),
+                &old.name,
+                false,
+            )
+        });
+    }
+
+    for permission in &old.permissions {
+        match new.permission(&permission.name) {
+            Some(updated) => {
+                if let Some(change) = compare_permissions(&old.name, permission, updated) {
+                    changes.push(change);
+                }
+            },
+            None => changes.push(SchemaChange {
+                permission: Some(permission.name.clone()),
+                ..change(
+                    SchemaChangeType::PermissionRemoved,
+                    format!("permission {} removed", name(&permission.name)),
+                    &old.name,
+                    true,
+                )
+            }),
+        }
+    }
+    for permission in new.permissions.iter().filter(|p| old.permission(&p.name).is_none()) {
+        changes.push(SchemaChange {
+            permission: Some(permission.name.clone()),
+            ..change(
+                SchemaChangeType::PermissionAdded,
+                format!("permission {} added", name(&permission.name)),
+                &old.name,
+                false,
+            )
+        });
+    }
+}
+
+fn compare_relations(
+    type_name: &str,
+    old: &RelationDef,
+    new: &RelationDef,
+) -> Option<SchemaChange> {
+    let removed: Vec<String> = old
+        .subjects
+        .iter()
+        .filter(|s| !new.subjects.contains(s))
+        .map(|s| format!("`{}`", s))
+        .collect();
+    let added: Vec<String> = new
+        .subjects
+        .iter()
+        .filter(|s| !old.subjects.contains(s))
+        .map(|s| format!("`{}`", s))
+        .collect();
+    if removed.is_empty() && added.is_empty() {
+        return None;
+    }
+
+    let mut clauses = Vec::new();
+    if !removed.is_empty() {
+        clauses.push(format!("no longer accepts {}", removed.join(", ")));
+    }
+    if !added.is_empty() {
+        clauses.push(format!("now also accepts {}", added.join(", ")));
+    }
+    Some(SchemaChange {
+        relation: Some(old.name.clone()),
+        ..change(
+            SchemaChangeType::RelationModified,
+            format!("relation `{}#{}` {}", type_name, old.name, clauses.join("; ")),
+            type_name,
+            !removed.is_empty(),
+        )
+    })
+}
+
+fn compare_permissions(
+    type_name: &str,
+    old: &PermissionDef,
+    new: &PermissionDef,
+) -> Option<SchemaChange> {
+    if equivalent(&old.expr, &new.expr) {
+        return None;
+    }
+    // A permission keeps granting everything it did as long as each branch
+    // of its top-level union is still a branch
+    let new_branches = new.expr.branches();
+    let dropped: Vec<String> = old
+        .expr
+        .branches()
+        .into_iter()
+        .filter(|branch| !new_branches.iter().any(|new| equivalent(branch, new)))
+        .map(|branch| format!("`{}`", branch))
+        .collect();
+
+    let mut description = format!(
+        "permission `{}#{}` changed from `{}` to `{}`",
+        type_name, old.name, old.expr, new.expr
+    );
+    if !dropped.is_empty() {
+        description.push_str(&format!("; no longer includes {}", dropped.join(", ")));
+    }
+    Some(SchemaChange {
+        permission: Some(old.name.clone()),
+        ..change(SchemaChangeType::PermissionModified, description, type_name, !dropped.is_empty())
+    })
+}
+
+/// Compares expressions with the operands of `|` and `&` as sets, so
+/// reordering a union or intersection is not a change.
+fn equivalent(a: &Expr, b: &Expr) -> bool {
+    let same_operands = |a: &[Expr], b: &[Expr]| {
+        a.iter().all(|x| b.iter().any(|y| equivalent(x, y)))
+            && b.iter().all(|y| a.iter().any(|x| equivalent(x, y)))
+    };
+    match (a, b) {
+        (Expr::Union(a), Expr::Union(b)) | (Expr::Intersection(a), Expr::Intersection(b)) => {
+            same_operands(a, b)
+        },
+        (Expr::Exclusion(a_base, a_excluded), Expr::Exclusion(b_base, b_excluded)) => {
+            equivalent(a_base, b_base) && equivalent(a_excluded, b_excluded)
+        },
+        (a, b) => a == b,
+    }
+}
+
+fn change(
+    change_type: SchemaChangeType,
+    description: String,
+    entity_type: &str,
+    is_breaking: bool,
+) -> SchemaChange {
+    SchemaChange {
+        change_type,
+        description,
+        entity_type: Some(entity_type.to_owned()),
+        relation: None,
+        permission: None,
+        is_breaking,
+    }
+}
+
+/// Renders one change per line, marked `+` (added), `-` (removed) or
+/// `~` (modified), followed by a summary line.
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            let marker = match change.change_type {
+                SchemaChangeType::EntityAdded
+                | SchemaChangeType::RelationAdded
+                | SchemaChangeType::PermissionAdded => '+',
+                SchemaChangeType::EntityRemoved
+                | SchemaChangeType::RelationRemoved
+                | SchemaChangeType::PermissionRemoved => '-',
+                SchemaChangeType::RelationModified | SchemaChangeType::PermissionModified => '~',
+            };
+            let breaking = if change.is_breaking { " (breaking)" } else { "" };
+            writeln!(f, "{} {}{}", marker, change.description, breaking)?;
+        }
+        let breaking = self.changes.iter().filter(|c| c.is_breaking).count();
+        write!(f, "{} change(s), {} breaking", self.changes.len(), breaking)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "
+        type user {}
+        type group { relation member: user }
+        type document {
+            relation owner: user
+            relation editor: user
+            relation viewer: user | group#member
+            permission view = viewer | editor | owner
+            permission edit = editor | owner
+        }";
+
+    fn diff(to: &str) -> SchemaDiff {
+        SchemaDiff::between(BASE, to).unwrap()
+    }
+
+    #[test]
+    fn test_identical_schemas_have_no_changes() {
+        let diff = diff(BASE);
+        assert!(diff.changes.is_empty());
+        assert!(diff.is_backward_compatible);
+        assert_eq!(diff.to_string(), "0 change(s), 0 breaking");
+    }
+
+    #[test]
+    fn test_reordered_operands_are_unchanged() {
+        let diff = diff(
+            "type user {}
+            type group { relation member: user }
+            type document {
+                relation owner: user
+                relation editor: user
+                relation viewer: user | group#member
+                permission view = owner | viewer | editor
+                permission edit = owner | editor
+            }",
+        );
+        assert!(diff.changes.is_empty(), "{diff}");
+
+        let diff = SchemaDiff::between(
+            "type t { permission p = (a & b) | c - d }",
+            "type t { permission p = c | (b & a) - d }",
+        )
+        .unwrap();
+        assert!(diff.changes.is_empty(), "{diff}");
+    }
+
+    #[test]
+    fn test_additions_and_widening_are_compatible() {
+        let diff = diff(
+            "type user {}
+             type group { relation member: user }
+             type team { relation member: user }
+             type document {
+                 relation owner: user
+                 relation editor: user | team#member
+                 relation viewer: user | group#member
+                 relation commenter: user
+                 permission view = viewer | editor | owner | commenter
+                 permission edit = editor | owner
+                 permission comment = commenter | edit
+             }",
+        );
+
+        assert!(diff.is_backward_compatible);
+        let types: Vec<_> = diff.changes.iter().map(|c| c.change_type).collect();
+        assert_eq!(
+            types,
+            [
+                SchemaChangeType::RelationModified,
+                SchemaChangeType::RelationAdded,
+                SchemaChangeType::PermissionModified,
+                SchemaChangeType::PermissionAdded,
+                SchemaChangeType::EntityAdded,
+            ]
+        );
+        assert_eq!(
+            diff.changes[0].description,
+            "relation `document#editor` now also accepts `team#member`"
+        );
+        assert_eq!(diff.changes[0].relation.as_deref(), Some("editor"));
+        assert_eq!(diff.breaking_changes().count(), 0);
+    }
+
+    #[test]
+    fn test_removals_and_narrowing_are_breaking() {
+        let diff = diff(
+            "type user {}
+             type document {
+                 relation owner: user
+                 relation viewer: user
+                 permission view = viewer | owner
+                 permission edit = owner & context.mfa
+             }",
+        );
+
+        assert!(!diff.is_backward_compatible);
+        let lines: Vec<_> = diff.to_string().lines().map(str::to_owned).collect();
+        assert_eq!(
+            lines,
+            [
+                "- type `group` removed (breaking)",
+                "- relation `document#editor` removed (breaking)",
+                "~ relation `document#viewer` no longer accepts `group#member` (breaking)",
+                "~ permission `document#view` changed from `viewer | editor | owner` to `viewer | owner`; no longer includes `editor` (breaking)",
+                "~ permission `document#edit` changed from `editor | owner` to `owner & context.mfa`; no longer includes `editor`, `owner` (breaking)",
+                "5 change(s), 5 breaking",
+            ]
+        );
+        assert_eq!(diff.changes[3].permission.as_deref(), Some("view"));
+        assert_eq!(diff.breaking_changes().count(), 5);
+    }
+
+    #[test]
+    fn test_entity_syntax_compares_with_type_syntax() {
+        let diff = SchemaDiff::between(
+            "entity user {} entity document { relations { viewer: user } permissions { view: viewer } }",
+            "type user {}\ntype document {\n  relation viewer: user\n  permission view = viewer\n}",
+        )
+        .unwrap();
+        assert!(diff.changes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_error_is_reported() {
+        let err = SchemaDiff::between(BASE, "type document {").unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::SchemaViolation);
+    }
+}
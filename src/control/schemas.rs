@@ -1,11 +1,16 @@
 //! Schema management for vaults.
 
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     Error,
     client::Client,
-    control::{Page, SortOrder},
+    control::{
+        Page, SortOrder, ipl,
+        pagination::{self, Paginated},
+        schema_deploy::DeploySchemaRequest,
+    },
 };
 
 /// Client for vault schema management operations.
@@ -59,6 +64,10 @@ impl SchemasClient {
         &self.vault_id
     }
 
+    pub(crate) fn client(&self) -> &Client {
+        &self.client
+    }
+
     /// Gets the currently active schema.
     ///
     /// ## Example
@@ -280,6 +289,33 @@ impl SchemasClient {
         let _ = (from_version.into(), to_version.into());
         Err(Error::configuration("REST feature is required for control API"))
     }
+
+    /// Deploys a schema, activating it only if it passes every safety gate.
+    ///
+    /// The schema is validated, pushed as a new version and diffed against
+    /// the active version. Breaking changes block the rollout unless
+    /// [`allow_breaking`](DeploySchemaRequest::allow_breaking) is set, and
+    /// expected decisions are checked by simulating against the new schema.
+    /// The returned [`SchemaDeployment`](crate::control::SchemaDeployment)
+    /// can roll back to the previously active version.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// let deployment = vault
+    ///     .schemas()
+    ///     .deploy(include_str!("schema.ipl"))
+    ///     .expect_allowed(("user:admin", "manage", "organization:main"))
+    ///     .expect_denied(("user:guest", "edit", "document:readme"))
+    ///     .await?;
+    ///
+    /// if error_rate_spiked().await {
+    ///     deployment.rollback().await?;
+    /// }
+    /// ```
+    pub fn deploy(&self, content: impl Into<String>) -> DeploySchemaRequest {
+        DeploySchemaRequest::new(self.clone(), content.into())
+    }
 }
 
 impl std::fmt::Debug for SchemasClient {
@@ -310,6 +346,20 @@ pub struct SchemaInfo {
     pub activated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+impl SchemaInfo {
+    /// Parses [`content`](Self::content) for offline tooling such as
+    /// [`Schema::to_markdown`](ipl::Schema::to_markdown) and
+    /// [`lint`](ipl::lint).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SchemaViolation`](crate::ErrorKind::SchemaViolation) error
+    /// if the content fails to parse.
+    pub fn parse(&self) -> Result<ipl::Schema, Error> {
+        ipl::Schema::parse(&self.content)
+    }
+}
+
 /// Status of a schema version.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -415,6 +465,19 @@ pub struct SchemaDiff {
     pub is_backward_compatible: bool,
 }
 
+impl SchemaDiff {
+    /// Returns the changes that are breaking by type or flagged as breaking
+    /// by the server.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(|c| c.is_breaking || c.change_type.is_breaking())
+    }
+
+    /// Returns the backward-compatible changes.
+    pub fn compatible_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(|c| !c.is_breaking && !c.change_type.is_breaking())
+    }
+}
+
 /// A single schema change.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaChange {
@@ -454,6 +517,23 @@ pub enum SchemaChangeType {
     PermissionModified,
 }
 
+impl SchemaChangeType {
+    /// Returns `true` for changes that are breaking whatever their details:
+    /// removals.
+    ///
+    /// Modifications are breaking only if they narrow what a relation
+    /// accepts or what a permission grants, as reported by
+    /// [`SchemaChange::is_breaking`].
+    pub fn is_breaking(&self) -> bool {
+        matches!(
+            self,
+            SchemaChangeType::EntityRemoved
+                | SchemaChangeType::RelationRemoved
+                | SchemaChangeType::PermissionRemoved
+        )
+    }
+}
+
 impl std::fmt::Display for SchemaChangeType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -470,6 +550,7 @@ impl std::fmt::Display for SchemaChangeType {
 }
 
 /// Request to list schemas.
+#[derive(Clone)]
 pub struct ListSchemasRequest {
     client: Client,
     organization_id: String,
@@ -495,6 +576,29 @@ impl ListSchemasRequest {
         self
     }
 
+    /// Returns a stream over the schema versions of all pages.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<SchemaInfo, Error>> + Send + 'static {
+        pagination::items(self)
+    }
+
+    /// Returns a stream of pages, following cursors to the last page.
+    pub fn pages(self) -> impl Stream<Item = Result<Page<SchemaInfo>, Error>> + Send + 'static {
+        pagination::pages(self)
+    }
+
+    /// Collects the schema versions of all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if there are more than `max_items`
+    /// schema versions, or the error of the first page that fails.
+    pub async fn collect_all(self, max_items: usize) -> Result<Vec<SchemaInfo>, Error> {
+        pagination::collect_all(self, max_items).await
+    }
+
     /// Sets the sort order.
     #[must_use]
     pub fn sort(mut self, order: SortOrder) -> Self {
@@ -553,6 +657,14 @@ impl std::future::IntoFuture for ListSchemasRequest {
     }
 }
 
+impl Paginated for ListSchemasRequest {
+    type Item = SchemaInfo;
+
+    fn with_cursor(self, cursor: String) -> Self {
+        self.cursor(cursor)
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
@@ -626,6 +738,34 @@ mod tests {
         assert_eq!(SchemaChangeType::PermissionModified.to_string(), "permission_modified");
     }
 
+    #[test]
+    fn test_schema_diff_classifies_changes() {
+        let change = |change_type, is_breaking| SchemaChange {
+            change_type,
+            description: change_type.to_string(),
+            entity_type: None,
+            relation: None,
+            permission: None,
+            is_breaking,
+        };
+        let diff = SchemaDiff {
+            from_version: "1".to_string(),
+            to_version: "2".to_string(),
+            changes: vec![
+                change(SchemaChangeType::RelationAdded, false),
+                change(SchemaChangeType::PermissionModified, false),
+                change(SchemaChangeType::PermissionModified, true),
+                change(SchemaChangeType::EntityRemoved, false),
+            ],
+            is_backward_compatible: false,
+        };
+
+        assert!(SchemaChangeType::RelationRemoved.is_breaking());
+        assert!(!SchemaChangeType::PermissionModified.is_breaking());
+        assert_eq!(diff.breaking_changes().count(), 2);
+        assert_eq!(diff.compatible_changes().count(), 2);
+    }
+
     #[tokio::test]
     async fn test_schemas_client_accessors() {
         let client = create_test_client().await;
@@ -0,0 +1,517 @@
+//! Audit event export in SIEM formats.
+//!
+//! [`SiemExporter`] writes [`AuditEvent`]s as newline-delimited OCSF JSON,
+//! ArcSight CEF or RFC 5424 syslog lines to any [`AsyncWrite`], so they can
+//! be shipped to a file, socket or log forwarder.
+
+use futures::{Stream, StreamExt};
+use serde_json::{Map, Value, json};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    Error,
+    control::{ActorType, AuditAction, AuditEvent, AuditOutcome},
+};
+
+const VENDOR: &str = "InferaDB";
+const PRODUCT: &str = "InferaDB";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Line format written by a [`SiemExporter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SiemFormat {
+    /// OCSF 1.1 JSON, as API Activity or Authentication events.
+    Ocsf,
+    /// ArcSight Common Event Format.
+    Cef,
+    /// RFC 5424 syslog with the event in structured data.
+    Syslog {
+        /// IANA private enterprise number of the structured data ID,
+        /// `inferadb@<enterprise_id>`. Use your organization's number so
+        /// the SIEM can tell these parameters from other vendors'.
+        enterprise_id: u32,
+    },
+}
+
+/// Writes audit events as SIEM log lines.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use inferadb::control::{SiemExporter, SiemFormat};
+///
+/// let socket = tokio::net::TcpStream::connect("siem.internal:6514").await?;
+/// let format = SiemFormat::Syslog { enterprise_id: 54321 };
+/// let mut exporter = SiemExporter::new(socket, format).with_hostname("authz-1");
+/// exporter.write_all(org.audit().follow().stream()).await?;
+/// ```
+#[derive(Debug)]
+pub struct SiemExporter<W> {
+    writer: W,
+    format: SiemFormat,
+    hostname: Option<String>,
+    app_name: String,
+}
+
+impl<W: AsyncWrite + Unpin> SiemExporter<W> {
+    /// Creates an exporter writing `format` lines to `writer`.
+    pub fn new(writer: W, format: SiemFormat) -> Self {
+        Self { writer, format, hostname: None, app_name: "inferadb".to_owned() }
+    }
+
+    /// Sets the syslog HOSTNAME field (default: nil, `-`).
+    #[must_use]
+    pub fn with_hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.hostname = Some(hostname.into());
+        self
+    }
+
+    /// Sets the syslog APP-NAME field (default: `inferadb`).
+    #[must_use]
+    pub fn with_app_name(mut self, app_name: impl Into<String>) -> Self {
+        self.app_name = app_name.into();
+        self
+    }
+
+    /// Returns the line format.
+    pub fn format(&self) -> SiemFormat {
+        self.format
+    }
+
+    /// Renders an event as a single line, without the trailing newline.
+    pub fn render(&self, event: &AuditEvent) -> String {
+        match self.format {
+            SiemFormat::Ocsf => to_ocsf(event).to_string(),
+            SiemFormat::Cef => to_cef(event),
+            SiemFormat::Syslog { enterprise_id } => self.to_syslog(event, enterprise_id),
+        }
+    }
+
+    /// Writes one event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub async fn write(&mut self, event: &AuditEvent) -> Result<(), Error> {
+        let mut line = self.render(event);
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await.map_err(write_error)
+    }
+
+    /// Writes every event of a stream, such as
+    /// [`FollowAuditLogsRequest::stream`](crate::control::FollowAuditLogsRequest::stream),
+    /// flushing after each one. Returns the number of events written.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the stream or of writing.
+    pub async fn write_all<S>(&mut self, events: S) -> Result<u64, Error>
+    where
+        S: Stream<Item = Result<AuditEvent, Error>>,
+    {
+        let mut events = std::pin::pin!(events);
+        let mut written = 0;
+        while let Some(event) = events.next().await {
+            self.write(&event?).await?;
+            self.flush().await?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    /// Flushes the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if flushing fails.
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        self.writer.flush().await.map_err(write_error)
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn to_syslog(&self, event: &AuditEvent, enterprise_id: u32) -> String {
+        // Facility 13 (log audit); warning for failures, informational otherwise
+        let severity = match event.outcome {
+            AuditOutcome::Success => 6,
+            AuditOutcome::Failure | AuditOutcome::Denied => 4,
+        };
+        let mut params = vec![
+            ("id", event.id.as_str()),
+            ("org", event.organization_id.as_str()),
+            ("actor", event.actor.id.as_str()),
+            ("actorType", actor_type(event.actor.actor_type)),
+            ("outcome", outcome(event.outcome)),
+        ];
+        let optional = [
+            ("vault", &event.vault_id),
+            ("resource", &event.resource),
+            ("requestId", &event.request_id),
+            ("ip", &event.actor.ip_address),
+        ];
+        params.extend(optional.iter().filter_map(|(k, v)| v.as_deref().map(|v| (*k, v))));
+        let data = params
+            .iter()
+            .map(|(k, v)| format!("{}=\"{}\"", k, escape_sd(v)))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        format!(
+            "<{}>1 {} {} {} - {} [inferadb@{} {}] {}",
+            13 * 8 + severity,
+            event.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            header_field(self.hostname.as_deref().unwrap_or("-"), 255),
+            header_field(&self.app_name, 48),
+            header_field(&event.action.to_string(), 32),
+            enterprise_id,
+            data,
+            escape_syslog_msg(&summary(event))
+        )
+    }
+}
+
+/// Maps an event to an OCSF 1.1 event object.
+///
+/// Logins and logouts become Authentication (3002) events; everything else
+/// is API Activity (6003), with the InferaDB action as `api.operation`.
+pub fn to_ocsf(event: &AuditEvent) -> Value {
+    let (class_uid, class_name, category_uid, category_name, activity_id, activity_name) =
+        match event.action {
+            AuditAction::Login | AuditAction::LoginFailed => {
+                (3002, "Authentication", 3, "Identity & Access Management", 1, "Logon")
+            },
+            AuditAction::Logout => {
+                (3002, "Authentication", 3, "Identity & Access Management", 2, "Logoff")
+            },
+            action => {
+                let (id, name) = api_activity(action);
+                (6003, "API Activity", 6, "Application Activity", id, name)
+            },
+        };
+    let (status_id, status, severity_id, severity) = match event.outcome {
+        AuditOutcome::Success => (1, "Success", 1, "Informational"),
+        AuditOutcome::Failure | AuditOutcome::Denied => (2, "Failure", 3, "Medium"),
+    };
+
+    let mut user = Map::new();
+    user.insert("uid".into(), json!(event.actor.id));
+    user.insert("type".into(), json!(actor_type(event.actor.actor_type)));
+    if let Some(email) = &event.actor.email {
+        user.insert("email_addr".into(), json!(email));
+    }
+
+    let mut ocsf = json!({
+        "class_uid": class_uid,
+        "class_name": class_name,
+        "category_uid": category_uid,
+        "category_name": category_name,
+        "activity_id": activity_id,
+        "activity_name": activity_name,
+        "type_uid": class_uid * 100 + activity_id,
+        "time": event.timestamp.timestamp_millis(),
+        "severity_id": severity_id,
+        "severity": severity,
+        "status_id": status_id,
+        "status": status,
+        "status_detail": outcome(event.outcome),
+        "actor": { "user": user },
+        "api": { "operation": event.action.to_string() },
+        "metadata": {
+            "version": "1.1.0",
+            "uid": event.id,
+            "tenant_uid": event.organization_id,
+            "product": { "name": PRODUCT, "vendor_name": VENDOR, "version": VERSION },
+        },
+    });
+
+    if let Some(request_id) = &event.request_id {
+        ocsf["api"]["request"] = json!({ "uid": request_id });
+    }
+    if let Some(ip) = &event.actor.ip_address {
+        ocsf["src_endpoint"] = json!({ "ip": ip });
+    }
+    if let Some(user_agent) = &event.actor.user_agent {
+        ocsf["http_request"] = json!({ "user_agent": user_agent });
+    }
+    if let Some(resource) = &event.resource {
+        ocsf["resources"] = json!([{ "uid": resource }]);
+    }
+    let mut unmapped = Map::new();
+    if let Some(vault_id) = &event.vault_id {
+        unmapped.insert("vault_id".into(), json!(vault_id));
+    }
+    if let Some(details) = &event.details {
+        unmapped.insert("details".into(), details.clone());
+    }
+    if !unmapped.is_empty() {
+        ocsf["unmapped"] = Value::Object(unmapped);
+    }
+    ocsf
+}
+
+/// Formats an event as a CEF line.
+pub fn to_cef(event: &AuditEvent) -> String {
+    let severity = match event.outcome {
+        AuditOutcome::Success => 3,
+        AuditOutcome::Failure => 5,
+        AuditOutcome::Denied => 7,
+    };
+    let mut extensions = vec![
+        ("rt", event.timestamp.timestamp_millis().to_string()),
+        ("externalId", event.id.clone()),
+        ("act", event.action.to_string()),
+        ("outcome", outcome(event.outcome).to_owned()),
+        ("suid", event.actor.id.clone()),
+        ("cs1Label", "organization".to_owned()),
+        ("cs1", event.organization_id.clone()),
+    ];
+    let optional = [
+        ("suser", &event.actor.email),
+        ("src", &event.actor.ip_address),
+        ("requestClientApplication", &event.actor.user_agent),
+    ];
+    extensions.extend(optional.iter().filter_map(|(k, v)| v.as_ref().map(|v| (*k, v.clone()))));
+    if let Some(vault_id) = &event.vault_id {
+        extensions.extend([("cs2Label", "vault".to_owned()), ("cs2", vault_id.clone())]);
+    }
+    if let Some(resource) = &event.resource {
+        extensions.extend([("cs3Label", "resource".to_owned()), ("cs3", resource.clone())]);
+    }
+    if let Some(request_id) = &event.request_id {
+        extensions.extend([("cs4Label", "requestId".to_owned()), ("cs4", request_id.clone())]);
+    }
+    let extensions = extensions
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, escape_cef_extension(v)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+        escape_cef_header(VENDOR),
+        escape_cef_header(PRODUCT),
+        escape_cef_header(VERSION),
+        escape_cef_header(&event.action.to_string()),
+        escape_cef_header(&summary(event)),
+        severity,
+        extensions
+    )
+}
+
+/// OCSF API Activity activity for an action.
+fn api_activity(action: AuditAction) -> (u32, &'static str) {
+    use AuditAction::*;
+    match action {
+        Check | CheckBatch => (2, "Read"),
+        RelationshipWrite
+        | RelationshipWriteBatch
+        | SchemaPush
+        | VaultCreate
+        | OrganizationCreate
+        | MemberInvite
+        | MemberAdd
+        | TeamCreate
+        | TeamMemberAdd
+        | TokenCreate
+        | ApiClientCreate => (1, "Create"),
+        SchemaActivate | VaultUpdate | OrganizationUpdate | MemberUpdate | TeamUpdate
+        | TokenRotate | ApiClientUpdate => (3, "Update"),
+        RelationshipDelete
+        | RelationshipDeleteBatch
+        | VaultDelete
+        | OrganizationDelete
+        | MemberRemove
+        | TeamDelete
+        | TeamMemberRemove
+        | TokenRevoke
+        | ApiClientDelete => (4, "Delete"),
+        Login | Logout | LoginFailed => (99, "Other"),
+    }
+}
+
+fn actor_type(actor_type: ActorType) -> &'static str {
+    match actor_type {
+        ActorType::User => "User",
+        ActorType::ApiClient => "Service",
+        ActorType::System => "System",
+    }
+}
+
+fn outcome(outcome: AuditOutcome) -> &'static str {
+    match outcome {
+        AuditOutcome::Success => "success",
+        AuditOutcome::Failure => "failure",
+        AuditOutcome::Denied => "denied",
+    }
+}
+
+/// One-line human-readable description of an event.
+fn summary(event: &AuditEvent) -> String {
+    match &event.resource {
+        Some(resource) => {
+            format!("{} {} on {}: {}", event.actor.id, event.action, resource, event.outcome)
+        },
+        None => format!("{} {}: {}", event.actor.id, event.action, event.outcome),
+    }
+}
+
+/// Syslog header fields are printable ASCII without spaces.
+fn header_field(value: &str, max_len: usize) -> String {
+    let field: String = value.chars().filter(|c| c.is_ascii_graphic()).take(max_len).collect();
+    if field.is_empty() { "-".to_owned() } else { field }
+}
+
+/// Escapes an SD-PARAM value, also escaping line breaks so a value cannot
+/// start a forged record.
+fn escape_sd(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' | '\\' | ']' => {
+                escaped.push('\\');
+                escaped.push(c);
+            },
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes line breaks in the syslog MSG part.
+fn escape_syslog_msg(value: &str) -> String {
+    value.replace('\n', "\\n").replace('\r', "\\r")
+}
+
+fn escape_cef_header(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn escape_cef_extension(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn write_error(e: std::io::Error) -> Error {
+    Error::connection(format!("failed to write audit event: {}", e))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::control::ActorInfo;
+
+    fn event() -> AuditEvent {
+        AuditEvent {
+            id: "evt_1".into(),
+            organization_id: "org_1".into(),
+            vault_id: Some("vlt_1".into()),
+            timestamp: "2024-01-01T12:00:00Z".parse().unwrap(),
+            actor: ActorInfo {
+                id: "user_1".into(),
+                actor_type: ActorType::User,
+                email: Some("alice@example.com".into()),
+                ip_address: Some("10.0.0.1".into()),
+                user_agent: None,
+            },
+            action: AuditAction::RelationshipDelete,
+            resource: Some("doc:a=b|c".into()),
+            details: None,
+            request_id: Some("req_1".into()),
+            outcome: AuditOutcome::Denied,
+        }
+    }
+
+    #[test]
+    fn test_ocsf_mapping() {
+        let ocsf = to_ocsf(&event());
+        assert_eq!(ocsf["class_uid"], 6003);
+        assert_eq!(ocsf["activity_id"], 4);
+        assert_eq!(ocsf["type_uid"], 600304);
+        assert_eq!(ocsf["time"], 1_704_110_400_000_i64);
+        assert_eq!(ocsf["status_id"], 2);
+        assert_eq!(ocsf["status_detail"], "denied");
+        assert_eq!(ocsf["actor"]["user"]["email_addr"], "alice@example.com");
+        assert_eq!(ocsf["api"]["operation"], "relationship.delete");
+        assert_eq!(ocsf["api"]["request"]["uid"], "req_1");
+        assert_eq!(ocsf["resources"][0]["uid"], "doc:a=b|c");
+        assert_eq!(ocsf["unmapped"]["vault_id"], "vlt_1");
+
+        let login = AuditEvent { action: AuditAction::Login, ..event() };
+        assert_eq!(to_ocsf(&login)["type_uid"], 300201);
+    }
+
+    #[test]
+    fn test_cef_line_escapes_values() {
+        let line = to_cef(&event());
+        assert!(line.starts_with(&format!(
+            "CEF:0|InferaDB|InferaDB|{}|relationship.delete|user_1 relationship.delete on doc:a=b\\|c: denied|7|",
+            VERSION
+        )));
+        assert!(line.contains("rt=1704110400000 externalId=evt_1"));
+        assert!(line.contains("cs3Label=resource cs3=doc:a\\=b|c"));
+        assert!(line.contains("suser=alice@example.com src=10.0.0.1"));
+    }
+
+    /// Syslog with the enterprise number reserved for documentation.
+    const SYSLOG: SiemFormat = SiemFormat::Syslog { enterprise_id: 32473 };
+
+    #[tokio::test]
+    async fn test_syslog_lines_written_to_writer() {
+        let mut exporter = SiemExporter::new(Vec::new(), SYSLOG).with_hostname("authz 1");
+        let mut quoted = event();
+        quoted.resource = Some("doc:\"x\"]".into());
+        let events = futures::stream::iter([Ok(event()), Ok(quoted)]);
+
+        assert_eq!(exporter.write_all(events).await.unwrap(), 2);
+        let output = String::from_utf8(exporter.into_inner()).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(
+            lines[0],
+            "<108>1 2024-01-01T12:00:00.000Z authz1 inferadb - relationship.delete \
+             [inferadb@32473 id=\"evt_1\" org=\"org_1\" actor=\"user_1\" actorType=\"User\" \
+             outcome=\"denied\" vault=\"vlt_1\" resource=\"doc:a=b|c\" requestId=\"req_1\" \
+             ip=\"10.0.0.1\"] user_1 relationship.delete on doc:a=b|c: denied"
+        );
+        assert!(lines[1].contains("resource=\"doc:\\\"x\\\"\\]\""));
+    }
+
+    #[test]
+    fn test_syslog_uses_the_given_enterprise_id() {
+        let exporter = SiemExporter::new(Vec::new(), SiemFormat::Syslog { enterprise_id: 54321 });
+        assert!(exporter.render(&event()).contains(" [inferadb@54321 id=\"evt_1\" "));
+    }
+
+    #[tokio::test]
+    async fn test_syslog_escapes_line_breaks() {
+        let mut exporter = SiemExporter::new(Vec::new(), SYSLOG);
+        let mut forged = event();
+        forged.resource = Some("doc:1\r\n<108>1 forged".into());
+
+        assert_eq!(exporter.write_all(futures::stream::iter([Ok(forged)])).await.unwrap(), 1);
+        let output = String::from_utf8(exporter.into_inner()).unwrap();
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.contains("resource=\"doc:1\\r\\n<108>1 forged\""));
+        assert!(output.ends_with("on doc:1\\r\\n<108>1 forged: denied\n"));
+    }
+
+    #[tokio::test]
+    async fn test_write_all_stops_at_stream_error() {
+        let mut exporter = SiemExporter::new(Vec::new(), SiemFormat::Ocsf);
+        let events = futures::stream::iter([Ok(event()), Err(Error::internal("boom"))]);
+
+        assert!(exporter.write_all(events).await.is_err());
+        let output = String::from_utf8(exporter.into_inner()).unwrap();
+        let ocsf: Value = serde_json::from_str(output.trim_end()).unwrap();
+        assert_eq!(ocsf["metadata"]["uid"], "evt_1");
+    }
+}
@@ -1,11 +1,15 @@
 //! Team management for the control plane.
 
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     Error,
     client::Client,
-    control::{Page, SortOrder},
+    control::{
+        Page, SortOrder,
+        pagination::{self, Paginated},
+    },
 };
 
 /// Client for team management operations.
@@ -362,6 +366,7 @@ impl UpdateTeamRequest {
 }
 
 /// Request to list teams.
+#[derive(Clone)]
 pub struct ListTeamsRequest {
     client: Client,
     organization_id: String,
@@ -385,6 +390,29 @@ impl ListTeamsRequest {
         self
     }
 
+    /// Returns a stream over the teams of all pages.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<TeamInfo, Error>> + Send + 'static {
+        pagination::items(self)
+    }
+
+    /// Returns a stream of pages, following cursors to the last page.
+    pub fn pages(self) -> impl Stream<Item = Result<Page<TeamInfo>, Error>> + Send + 'static {
+        pagination::pages(self)
+    }
+
+    /// Collects the teams of all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if there are more than `max_items`
+    /// teams, or the error of the first page that fails.
+    pub async fn collect_all(self, max_items: usize) -> Result<Vec<TeamInfo>, Error> {
+        pagination::collect_all(self, max_items).await
+    }
+
     /// Sets the sort order.
     #[must_use]
     pub fn sort(mut self, order: SortOrder) -> Self {
@@ -430,7 +458,16 @@ impl std::future::IntoFuture for ListTeamsRequest {
     }
 }
 
+impl Paginated for ListTeamsRequest {
+    type Item = TeamInfo;
+
+    fn with_cursor(self, cursor: String) -> Self {
+        self.cursor(cursor)
+    }
+}
+
 /// Request to list team members.
+#[derive(Clone)]
 pub struct ListTeamMembersRequest {
     client: Client,
     organization_id: String,
@@ -454,6 +491,29 @@ impl ListTeamMembersRequest {
         self
     }
 
+    /// Returns a stream over the team members of all pages.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<TeamMemberInfo, Error>> + Send + 'static {
+        pagination::items(self)
+    }
+
+    /// Returns a stream of pages, following cursors to the last page.
+    pub fn pages(self) -> impl Stream<Item = Result<Page<TeamMemberInfo>, Error>> + Send + 'static {
+        pagination::pages(self)
+    }
+
+    /// Collects the team members of all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if there are more than `max_items`
+    /// team members, or the error of the first page that fails.
+    pub async fn collect_all(self, max_items: usize) -> Result<Vec<TeamMemberInfo>, Error> {
+        pagination::collect_all(self, max_items).await
+    }
+
     #[cfg(feature = "rest")]
     async fn execute(self) -> Result<Page<TeamMemberInfo>, Error> {
         let mut path = format!(
@@ -492,6 +552,14 @@ impl std::future::IntoFuture for ListTeamMembersRequest {
     }
 }
 
+impl Paginated for ListTeamMembersRequest {
+    type Item = TeamMemberInfo;
+
+    fn with_cursor(self, cursor: String) -> Self {
+        self.cursor(cursor)
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
@@ -1,11 +1,15 @@
 //! Vault management for the control plane.
 
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     Error,
     client::Client,
-    control::{Page, SortOrder},
+    control::{
+        Page, SortOrder,
+        pagination::{self, Paginated},
+    },
 };
 
 /// Client for vault management operations.
@@ -298,6 +302,7 @@ impl UpdateVaultRequest {
 }
 
 /// Request to list vaults.
+#[derive(Clone)]
 pub struct ListVaultsRequest {
     client: Client,
     organization_id: String,
@@ -322,6 +327,29 @@ impl ListVaultsRequest {
         self
     }
 
+    /// Returns a stream over the vaults of all pages.
+    ///
+    /// The next page is fetched while the current one is consumed; the
+    /// stream ends after the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<VaultInfo, Error>> + Send + 'static {
+        pagination::items(self)
+    }
+
+    /// Returns a stream of pages, following cursors to the last page.
+    pub fn pages(self) -> impl Stream<Item = Result<Page<VaultInfo>, Error>> + Send + 'static {
+        pagination::pages(self)
+    }
+
+    /// Collects the vaults of all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if there are more than `max_items`
+    /// vaults, or the error of the first page that fails.
+    pub async fn collect_all(self, max_items: usize) -> Result<Vec<VaultInfo>, Error> {
+        pagination::collect_all(self, max_items).await
+    }
+
     /// Sets the sort order.
     #[must_use]
     pub fn sort(mut self, order: SortOrder) -> Self {
@@ -377,6 +405,14 @@ impl std::future::IntoFuture for ListVaultsRequest {
     }
 }
 
+impl Paginated for ListVaultsRequest {
+    type Item = VaultInfo;
+
+    fn with_cursor(self, cursor: String) -> Self {
+        self.cursor(cursor)
+    }
+}
+
 /// Request to delete a vault.
 pub struct DeleteVaultRequest {
     client: Client,
@@ -564,6 +600,61 @@ mod wiremock_tests {
             .unwrap()
     }
 
+    fn vault_page(ids: &[&str], next_cursor: Option<&str>) -> serde_json::Value {
+        let items: Vec<_> = ids
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "id": id,
+                    "organization_id": "org_123",
+                    "name": id,
+                    "status": "active",
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "updated_at": "2024-01-01T00:00:00Z"
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "items": items,
+            "page_info": { "has_next": next_cursor.is_some(), "next_cursor": next_cursor }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_stream_vaults_follows_cursors() {
+        use futures::TryStreamExt;
+        use wiremock::matchers::query_param;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/control/v1/organizations/org_123/vaults"))
+            .and(query_param("cursor", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vault_page(&["vlt_3"], None)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/control/v1/organizations/org_123/vaults"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(vault_page(&["vlt_1", "vlt_2"], Some("page2"))),
+            )
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let vaults = VaultsClient::new(client, "org_123");
+
+        let ids: Vec<String> =
+            vaults.list().limit(2).stream().map_ok(|v| v.id).try_collect().await.unwrap();
+        assert_eq!(ids, ["vlt_1", "vlt_2", "vlt_3"]);
+
+        let pages: Vec<_> = vaults.list().pages().try_collect().await.unwrap();
+        assert_eq!(pages.len(), 2);
+
+        let err = vaults.list().collect_all(2).await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidArgument);
+    }
+
     #[tokio::test]
     async fn test_list_vaults() {
         let server = MockServer::start().await;
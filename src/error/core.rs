@@ -126,6 +126,12 @@ impl Error {
         self.kind
     }
 
+    /// Returns the human-readable message, without the kind prefix.
+    #[inline]
+    pub(crate) fn message(&self) -> &str {
+        &self.message
+    }
+
     /// Returns the server-assigned request ID, if available.
     ///
     /// Always include this in error logs for support correlation:
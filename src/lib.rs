@@ -45,7 +45,8 @@
 //! - `rest` (default): Enable REST transport via reqwest
 //! - `rustls` (default): Use rustls for TLS
 //! - `native-tls`: Use native TLS (OpenSSL on Linux, Secure Transport on macOS)
-//! - `tracing`: Enable tracing integration
+//! - `tracing`: Enable tracing integration and metrics collection
+//! - `metrics`: Forward metrics to the `metrics` facade crate (implies `tracing`)
 //! - `blocking`: Enable blocking API
 //! - `derive`: Enable derive macros for type-safe schemas
 //! - `wasm`: Enable WASM/browser support (REST only)
@@ -114,19 +115,21 @@ pub use client::{
 // Re-export config types
 pub use config::{
     CacheConfig, CircuitBreakerConfig, CircuitEvent, CircuitState, CircuitStats, DegradationConfig,
-    FailureMode, FailurePredicate, RetryConfig, TlsConfig,
+    FailureMode, FailurePredicate, LoadBalancingStrategy, OutlierDetectionConfig, RetryConfig,
+    TlsConfig,
 };
 pub use error::{AccessDenied, Error, ErrorKind, Result};
 // Testing support
 pub use testing::{AuthorizationClient, InMemoryClient, MockClient};
 // Re-export transport types
 pub use transport::{
-    FallbackReason, FallbackTrigger, GrpcStats, PoolConfig, RestStats, Transport, TransportEvent,
-    TransportStats, TransportStrategy,
+    DecisionTrace, EndpointStats, EvaluationNode, EvaluationNodeType, FallbackReason,
+    FallbackTrigger, GrpcStats, PoolConfig, RestStats, Transport, TransportEvent, TransportStats,
+    TransportStrategy,
 };
 pub use types::{
-    ConsistencyToken, Context, ContextValue, Decision, DecisionMetadata, DecisionReason, EntityRef,
-    ParseError, Relationship, Resource, Subject, SubjectRef,
+    ConsistencyRequirement, ConsistencyToken, Context, ContextValue, Decision, DecisionMetadata,
+    DecisionReason, EntityRef, ParseError, Relationship, Resource, Subject, SubjectRef,
 };
 pub use vault::VaultClient;
 
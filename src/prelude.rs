@@ -25,8 +25,8 @@ pub use crate::{
     error::{AccessDenied, Error, ErrorKind, Result},
     testing::{AuthorizationClient, InMemoryClient, MockClient},
     types::{
-        ConsistencyToken, Context, ContextValue, Decision, DecisionMetadata, DecisionReason,
-        Relationship,
+        ConsistencyRequirement, ConsistencyToken, Context, ContextValue, Decision,
+        DecisionMetadata, DecisionReason, Relationship,
     },
     vault::VaultClient,
 };
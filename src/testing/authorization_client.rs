@@ -1,13 +1,24 @@
 //! AuthorizationClient trait for dependency injection.
 
-use std::{future::Future, pin::Pin};
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+};
 
-use crate::{Error, types::Context};
+use crate::{
+    Error,
+    types::{ConsistencyToken, Context, Relationship},
+    vault::VaultClient,
+};
 
 /// Object-safe trait for authorization operations.
 ///
 /// This trait allows you to abstract over different authorization clients
-/// (real, mock, in-memory) for testing and dependency injection.
+/// (real, mock, in-memory) for testing and dependency injection. It covers
+/// checks, relationship writes and deletes, and lookups, and is implemented
+/// by [`VaultClient`], [`MockClient`](super::MockClient),
+/// [`InMemoryClient`](super::InMemoryClient) and
+/// [`TestVault`](super::TestVault).
 ///
 /// ## Example
 ///
@@ -33,8 +44,32 @@ use crate::{Error, types::Context};
 ///
 /// ## Object Safety
 ///
-/// This trait is object-safe, so you can use `&dyn AuthorizationClient`
-/// or `Box<dyn AuthorizationClient>` for dynamic dispatch.
+/// This trait is object-safe, so you can use `&dyn AuthorizationClient`,
+/// `Box<dyn AuthorizationClient>` or `Arc<dyn AuthorizationClient>` for
+/// dynamic dispatch:
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use inferadb::{Error, Relationship};
+/// use inferadb::testing::{AuthorizationClient, InMemoryClient};
+///
+/// struct ShareHandler {
+///     authz: Arc<dyn AuthorizationClient>,
+/// }
+///
+/// impl ShareHandler {
+///     async fn share(&self, owner: &str, doc: &str, with: &str) -> Result<bool, Error> {
+///         if !self.authz.check(owner, "owner", doc).await? {
+///             return Ok(false);
+///         }
+///         self.authz.write(Relationship::new(doc, "viewer", with)).await?;
+///         Ok(true)
+///     }
+/// }
+///
+/// let handler = ShareHandler { authz: Arc::new(InMemoryClient::new()) };
+/// ```
 pub trait AuthorizationClient: Send + Sync {
     /// Checks if a subject has a permission on a resource.
     ///
@@ -54,6 +89,255 @@ pub trait AuthorizationClient: Send + Sync {
         resource: &str,
         context: &Context,
     ) -> Pin<Box<dyn Future<Output = Result<bool, Error>> + Send + '_>>;
+
+    /// Checks multiple `(subject, permission, resource)` triples.
+    ///
+    /// Returns one decision per check, in order.
+    fn check_batch(
+        &self,
+        checks: &[(&str, &str, &str)],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<bool>, Error>> + Send + '_>>;
+
+    /// Writes a relationship and returns its consistency token.
+    fn write(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>>;
+
+    /// Writes multiple relationships and returns the consistency token of
+    /// the batch.
+    fn write_batch(
+        &self,
+        relationships: Vec<Relationship<'_>>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>>;
+
+    /// Deletes a relationship.
+    fn delete(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send + '_>>;
+
+    /// Deletes every relationship matching the filters and returns how many
+    /// were deleted.
+    ///
+    /// At least one filter must be given.
+    fn delete_where(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, Error>> + Send + '_>>;
+
+    /// Lists every relationship matching the filters.
+    fn list_relationships(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Relationship<'static>>, Error>> + Send + '_>>;
+
+    /// Returns resources the subject has the permission on, optionally
+    /// limited to one resource type.
+    fn accessible_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>>;
+
+    /// Returns subjects with the permission on the resource, optionally
+    /// limited to one subject type.
+    fn subjects_with_permission(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>>;
+
+    /// Returns `true` if the client computes permissions from the vault's
+    /// schema, and `false` if it only matches direct relationships.
+    ///
+    /// Defaults to `true`. [`InMemoryClient`](super::InMemoryClient) and
+    /// [`TestVault`](super::TestVault) return `false`.
+    fn evaluates_schema(&self) -> bool {
+        true
+    }
+}
+
+/// Returns an error if none of the `delete_where` filters is set.
+pub(super) fn require_delete_filter(
+    resource: Option<&str>,
+    relation: Option<&str>,
+    subject: Option<&str>,
+) -> Result<(), Error> {
+    if resource.is_none() && relation.is_none() && subject.is_none() {
+        return Err(Error::configuration(
+            "delete_where requires at least one filter (resource, relation, or subject)",
+        ));
+    }
+    Ok(())
+}
+
+impl AuthorizationClient for VaultClient {
+    fn check(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, Error>> + Send + '_>> {
+        let request = VaultClient::check(
+            self,
+            subject.to_string(),
+            permission.to_string(),
+            resource.to_string(),
+        );
+        Box::pin(request.into_future())
+    }
+
+    fn check_with_context(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource: &str,
+        context: &Context,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, Error>> + Send + '_>> {
+        let request = VaultClient::check(
+            self,
+            subject.to_string(),
+            permission.to_string(),
+            resource.to_string(),
+        )
+        .with_context(context.clone());
+        Box::pin(request.into_future())
+    }
+
+    fn check_batch(
+        &self,
+        checks: &[(&str, &str, &str)],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<bool>, Error>> + Send + '_>> {
+        let request = VaultClient::check_batch(
+            self,
+            checks.iter().map(|&(s, p, r)| (s.to_string(), p.to_string(), r.to_string())),
+        );
+        Box::pin(request.into_future())
+    }
+
+    fn write(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+        let relationships = self.relationships();
+        let relationship = relationship.into_owned();
+        Box::pin(async move { relationships.write(relationship).await })
+    }
+
+    fn write_batch(
+        &self,
+        relationships: Vec<Relationship<'_>>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+        let client = self.relationships();
+        let relationships: Vec<_> =
+            relationships.into_iter().map(Relationship::into_owned).collect();
+        Box::pin(async move { client.write_batch(relationships).await })
+    }
+
+    fn delete(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send + '_>> {
+        let relationships = self.relationships();
+        let relationship = relationship.into_owned();
+        Box::pin(async move { relationships.delete(relationship).await })
+    }
+
+    fn delete_where(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, Error>> + Send + '_>> {
+        let mut builder = self.relationships().delete_where();
+        if let Some(resource) = resource {
+            builder = builder.resource(resource);
+        }
+        if let Some(relation) = relation {
+            builder = builder.relation(relation);
+        }
+        if let Some(subject) = subject {
+            builder = builder.subject(subject);
+        }
+        Box::pin(async move { Ok(builder.await?.deleted_count()) })
+    }
+
+    fn list_relationships(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Relationship<'static>>, Error>> + Send + '_>> {
+        let (resource, relation, subject) = (
+            resource.map(str::to_string),
+            relation.map(str::to_string),
+            subject.map(str::to_string),
+        );
+        Box::pin(async move {
+            let mut relationships = Vec::new();
+            let mut cursor = None;
+            loop {
+                let mut request = self.relationships().list();
+                if let Some(resource) = &resource {
+                    request = request.resource(resource);
+                }
+                if let Some(relation) = &relation {
+                    request = request.relation(relation);
+                }
+                if let Some(subject) = &subject {
+                    request = request.subject(subject);
+                }
+                if let Some(cursor) = cursor.take() {
+                    request = request.cursor(cursor);
+                }
+                let page = request.await?;
+                relationships.extend(page.relationships);
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => return Ok(relationships),
+                }
+            }
+        })
+    }
+
+    fn accessible_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+        let mut builder = self
+            .resources()
+            .accessible_by(subject.to_string())
+            .with_permission(permission.to_string());
+        if let Some(resource_type) = resource_type {
+            builder = builder.resource_type(resource_type.to_string());
+        }
+        Box::pin(builder.collect())
+    }
+
+    fn subjects_with_permission(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+        let mut builder = self
+            .subjects()
+            .with_permission(permission.to_string())
+            .on_resource(resource.to_string());
+        if let Some(subject_type) = subject_type {
+            builder = builder.subject_type(subject_type.to_string());
+        }
+        Box::pin(builder.collect())
+    }
 }
 
 #[cfg(test)]
@@ -86,6 +370,72 @@ mod tests {
             let result = self.allow_all;
             Box::pin(async move { Ok(result) })
         }
+
+        fn check_batch(
+            &self,
+            checks: &[(&str, &str, &str)],
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<bool>, Error>> + Send + '_>> {
+            let result = vec![self.allow_all; checks.len()];
+            Box::pin(async move { Ok(result) })
+        }
+
+        fn write(
+            &self,
+            _relationship: Relationship<'_>,
+        ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+            Box::pin(async move { Ok(ConsistencyToken::new("token")) })
+        }
+
+        fn write_batch(
+            &self,
+            _relationships: Vec<Relationship<'_>>,
+        ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+            Box::pin(async move { Ok(ConsistencyToken::new("token")) })
+        }
+
+        fn delete(
+            &self,
+            _relationship: Relationship<'_>,
+        ) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send + '_>> {
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn delete_where(
+            &self,
+            _resource: Option<&str>,
+            _relation: Option<&str>,
+            _subject: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<u64, Error>> + Send + '_>> {
+            Box::pin(async move { Ok(0) })
+        }
+
+        fn list_relationships(
+            &self,
+            _resource: Option<&str>,
+            _relation: Option<&str>,
+            _subject: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<Relationship<'static>>, Error>> + Send + '_>>
+        {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+
+        fn accessible_resources(
+            &self,
+            _subject: &str,
+            _permission: &str,
+            _resource_type: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+
+        fn subjects_with_permission(
+            &self,
+            _permission: &str,
+            _resource: &str,
+            _subject_type: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
     }
 
     #[tokio::test]
@@ -117,4 +467,101 @@ mod tests {
         let result = client.check_with_context("user:alice", "view", "doc:1", &context).await;
         assert!(!result.unwrap());
     }
+
+    /// Exercises every operation through a trait object.
+    async fn exercise(client: std::sync::Arc<dyn AuthorizationClient>) {
+        client.write(Relationship::new("doc:1", "viewer", "user:alice")).await.unwrap();
+        client
+            .write_batch(vec![
+                Relationship::new("doc:2", "viewer", "user:alice"),
+                Relationship::new("doc:1", "viewer", "team:eng"),
+            ])
+            .await
+            .unwrap();
+
+        assert!(client.check("user:alice", "viewer", "doc:1").await.unwrap());
+        let decisions = client
+            .check_batch(&[("user:alice", "viewer", "doc:2"), ("user:bob", "viewer", "doc:2")])
+            .await
+            .unwrap();
+        assert_eq!(decisions, vec![true, false]);
+
+        let mut resources =
+            client.accessible_resources("user:alice", "viewer", Some("doc")).await.unwrap();
+        resources.sort();
+        assert_eq!(resources, vec!["doc:1", "doc:2"]);
+        let subjects =
+            client.subjects_with_permission("viewer", "doc:1", Some("team")).await.unwrap();
+        assert_eq!(subjects, vec!["team:eng"]);
+
+        assert_eq!(client.list_relationships(Some("doc:1"), None, None).await.unwrap().len(), 2);
+        client.delete(Relationship::new("doc:2", "viewer", "user:alice")).await.unwrap();
+        assert!(client.delete_where(None, None, None).await.is_err());
+        assert_eq!(client.delete_where(Some("doc:1"), None, None).await.unwrap(), 2);
+        assert!(client.list_relationships(None, None, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_implementations_are_interchangeable() {
+        use std::sync::Arc;
+
+        use crate::{Client, auth::BearerCredentialsConfig, transport::mock::MockTransport};
+
+        let vault = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .build_with_transport(Arc::new(MockTransport::new().into_any()))
+            .await
+            .unwrap()
+            .organization("org")
+            .vault("vault");
+
+        exercise(Arc::new(vault)).await;
+        exercise(Arc::new(super::super::InMemoryClient::new())).await;
+        exercise(Arc::new(super::super::TestVault::new())).await;
+    }
+
+    #[tokio::test]
+    async fn test_mock_client_as_trait_object() {
+        use std::sync::Arc;
+
+        use crate::{
+            ErrorKind,
+            testing::{Expectation, Matcher, MockClient},
+        };
+
+        let mock = MockClient::new()
+            .expect(Expectation::write("doc:1", "viewer", Matcher::any()).returns_token("rev_1"))
+            .expect(
+                Expectation::delete_where("doc:1", Matcher::any(), Matcher::any()).returns_count(3),
+            )
+            .expect(
+                Expectation::list_relationships(Matcher::any(), "owner", "")
+                    .returns_relationships([Relationship::new("doc:1", "owner", "user:alice")]),
+            )
+            .expect(
+                Expectation::check(Matcher::any(), "viewer", Matcher::any())
+                    .returns_error(ErrorKind::Unavailable, "down")
+                    .times(1),
+            );
+        let client: Arc<dyn AuthorizationClient> = Arc::new(mock.clone());
+
+        let token = client.write(Relationship::new("doc:1", "viewer", "user:bob")).await.unwrap();
+        assert_eq!(token.value(), "rev_1");
+        assert_eq!(client.delete_where(Some("doc:1"), None, None).await.unwrap(), 3);
+        let owners = client.list_relationships(None, Some("owner"), None).await.unwrap();
+        assert_eq!(owners.len(), 1);
+        // The subject filter is set, so the "" matcher no longer matches
+        assert!(
+            client
+                .list_relationships(None, Some("owner"), Some("user:x"))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(client.check_batch(&[("user:a", "viewer", "doc:1")]).await.is_err());
+
+        assert_eq!(mock.call_count(), 5);
+        mock.verify();
+    }
 }
@@ -0,0 +1,247 @@
+//! Control-plane front end of the fake server.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use serde::Deserialize;
+
+use super::state::{FakeState, Rejection, paginate, validate_schema};
+use crate::control::{
+    Account, CreateOrganizationRequest, CreateVaultRequest, OrganizationInfo, Page, PageInfo,
+    PushSchemaResult, SchemaInfo, UpdateOrganizationRequest, UpdateVaultRequest, ValidationResult,
+    VaultInfo,
+};
+
+const VAULT: &str = "/control/v1/organizations/{organization}/vaults/{vault}";
+
+/// Builds the router for organizations, vaults, schemas and the account.
+pub(super) fn router() -> Router<Arc<FakeState>> {
+    Router::new()
+        .route("/control/v1/account", get(account))
+        .route("/control/v1/organizations", get(list_organizations).post(create_organization))
+        .route(
+            "/control/v1/organizations/{organization}",
+            get(get_organization).patch(update_organization).delete(delete_organization),
+        )
+        .route(
+            "/control/v1/organizations/{organization}/vaults",
+            get(list_vaults).post(create_vault),
+        )
+        .route(VAULT, get(get_vault).patch(update_vault).delete(delete_vault))
+        .route(&format!("{VAULT}/schemas"), get(list_schemas).post(push_schema))
+        .route(&format!("{VAULT}/schemas/active"), get(active_schema))
+        .route(&format!("{VAULT}/schemas/validate"), post(validate))
+        .route(&format!("{VAULT}/schemas/{{version}}"), get(get_schema).delete(delete_schema))
+        .route(&format!("{VAULT}/schemas/{{version}}/activate"), post(activate_schema))
+}
+
+/// Pagination parameters shared by the list endpoints.
+#[derive(Deserialize)]
+struct PageQuery {
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+/// Returns one page of `items` in the control plane's page format.
+fn page<T: Clone>(items: &[T], query: &PageQuery) -> Result<Json<Page<T>>, Rejection> {
+    let (page, next_cursor) = paginate(items, query.limit, query.cursor.as_deref())?;
+    Ok(Json(Page {
+        items: page,
+        page_info: PageInfo {
+            has_next: next_cursor.is_some(),
+            next_cursor,
+            total_count: Some(items.len() as u64),
+        },
+    }))
+}
+
+#[derive(Deserialize)]
+struct SchemaBody {
+    content: String,
+}
+
+async fn account(State(state): State<Arc<FakeState>>) -> Json<Account> {
+    Json(state.control().account())
+}
+
+async fn list_organizations(
+    State(state): State<Arc<FakeState>>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<OrganizationInfo>>, Rejection> {
+    page(state.control().organizations(), &query)
+}
+
+async fn create_organization(
+    State(state): State<Arc<FakeState>>,
+    Json(request): Json<CreateOrganizationRequest>,
+) -> Result<Json<OrganizationInfo>, Rejection> {
+    let mut control = state.control();
+    if control.organization_named(&request.name).is_some() {
+        return Err(Rejection::conflict(format!("organization '{}' already exists", request.name)));
+    }
+    Ok(Json(control.create_organization(request.name, request.display_name)))
+}
+
+async fn get_organization(
+    State(state): State<Arc<FakeState>>,
+    Path(organization): Path<String>,
+) -> Result<Json<OrganizationInfo>, Rejection> {
+    Ok(Json(state.control().organization(&organization)?.clone()))
+}
+
+async fn update_organization(
+    State(state): State<Arc<FakeState>>,
+    Path(organization): Path<String>,
+    Json(request): Json<UpdateOrganizationRequest>,
+) -> Result<Json<OrganizationInfo>, Rejection> {
+    let mut control = state.control();
+    let info = control.organization(&organization)?;
+    if request.display_name.is_some() {
+        info.display_name = request.display_name;
+    }
+    info.updated_at = chrono::Utc::now();
+    Ok(Json(info.clone()))
+}
+
+async fn delete_organization(
+    State(state): State<Arc<FakeState>>,
+    Path(organization): Path<String>,
+) -> Result<StatusCode, Rejection> {
+    state.control().delete_organization(&organization)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_vaults(
+    State(state): State<Arc<FakeState>>,
+    Path(organization): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<VaultInfo>>, Rejection> {
+    let mut control = state.control();
+    control.organization(&organization)?;
+    page(&control.vaults(&organization), &query)
+}
+
+async fn create_vault(
+    State(state): State<Arc<FakeState>>,
+    Path(organization): Path<String>,
+    Json(request): Json<CreateVaultRequest>,
+) -> Result<Json<VaultInfo>, Rejection> {
+    let mut control = state.control();
+    control.organization(&organization)?;
+    if control.vaults(&organization).iter().any(|vault| vault.name == request.name) {
+        return Err(Rejection::conflict(format!("vault '{}' already exists", request.name)));
+    }
+    control
+        .create_vault(&organization, request.name, request.display_name, request.description)
+        .map(Json)
+        .ok_or_else(|| Rejection::not_found(format!("organization '{}' not found", organization)))
+}
+
+async fn get_vault(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault)): Path<(String, String)>,
+) -> Result<Json<VaultInfo>, Rejection> {
+    Ok(Json(state.control().vault(&organization, &vault)?.clone()))
+}
+
+async fn update_vault(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault)): Path<(String, String)>,
+    Json(request): Json<UpdateVaultRequest>,
+) -> Result<Json<VaultInfo>, Rejection> {
+    let mut control = state.control();
+    let info = control.vault(&organization, &vault)?;
+    if request.display_name.is_some() {
+        info.display_name = request.display_name;
+    }
+    if request.description.is_some() {
+        info.description = request.description;
+    }
+    info.updated_at = chrono::Utc::now();
+    Ok(Json(info.clone()))
+}
+
+async fn delete_vault(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault)): Path<(String, String)>,
+) -> Result<StatusCode, Rejection> {
+    state.control().delete_vault(&organization, &vault)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_schemas(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault)): Path<(String, String)>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<SchemaInfo>>, Rejection> {
+    let mut control = state.control();
+    control.vault(&organization, &vault)?;
+    page(&control.schemas(&vault), &query)
+}
+
+async fn push_schema(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault)): Path<(String, String)>,
+    Json(body): Json<SchemaBody>,
+) -> Result<Json<PushSchemaResult>, Rejection> {
+    let mut control = state.control();
+    control.vault(&organization, &vault)?;
+    let validation = validate_schema(&body.content);
+    if !validation.is_valid() {
+        let message = validation.errors.first().map(|e| e.message.clone()).unwrap_or_default();
+        return Err(Rejection::invalid(message));
+    }
+    let schema = control.push_schema(&vault, body.content);
+    Ok(Json(PushSchemaResult { schema, validation }))
+}
+
+async fn validate(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault)): Path<(String, String)>,
+    Json(body): Json<SchemaBody>,
+) -> Result<Json<ValidationResult>, Rejection> {
+    state.control().vault(&organization, &vault)?;
+    Ok(Json(validate_schema(&body.content)))
+}
+
+async fn active_schema(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault)): Path<(String, String)>,
+) -> Result<Json<SchemaInfo>, Rejection> {
+    let mut control = state.control();
+    control.vault(&organization, &vault)?;
+    control.active_schema(&vault).map(Json)
+}
+
+async fn get_schema(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault, version)): Path<(String, String, String)>,
+) -> Result<Json<SchemaInfo>, Rejection> {
+    let mut control = state.control();
+    control.vault(&organization, &vault)?;
+    Ok(Json(control.schema(&vault, &version)?.clone()))
+}
+
+async fn activate_schema(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault, version)): Path<(String, String, String)>,
+) -> Result<Json<SchemaInfo>, Rejection> {
+    let mut control = state.control();
+    control.vault(&organization, &vault)?;
+    control.activate_schema(&vault, &version).map(Json)
+}
+
+async fn delete_schema(
+    State(state): State<Arc<FakeState>>,
+    Path((organization, vault, version)): Path<(String, String, String)>,
+) -> Result<StatusCode, Rejection> {
+    let mut control = state.control();
+    control.vault(&organization, &vault)?;
+    control.delete_schema(&vault, &version)?;
+    Ok(StatusCode::NO_CONTENT)
+}
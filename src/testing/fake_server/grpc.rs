@@ -0,0 +1,379 @@
+//! gRPC front end of the fake server.
+//!
+//! The SDK only generates gRPC client code, so this module routes requests for
+//! `inferadb.authorization.v1.AuthorizationService` by hand, decoding them with
+//! the generated prost messages.
+
+use std::{
+    convert::Infallible,
+    future::Future,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::{StreamExt, TryStreamExt};
+use tonic::{
+    Request, Response, Status, Streaming,
+    body::Body,
+    codegen::{BoxFuture, BoxStream, Service, http},
+    server::{Grpc, NamedService},
+};
+use tonic_prost::ProstCodec;
+
+use super::state::{FakeState, Rejection, paginate};
+use crate::{
+    ErrorKind, Relationship,
+    transport::proto as pb,
+    vault::watch::{Operation, WatchFilter},
+};
+
+/// The hand-routed `AuthorizationService`.
+#[derive(Clone)]
+pub(super) struct AuthorizationService {
+    state: Arc<FakeState>,
+}
+
+impl AuthorizationService {
+    pub(super) fn new(state: Arc<FakeState>) -> Self {
+        Self { state }
+    }
+}
+
+impl NamedService for AuthorizationService {
+    const NAME: &'static str = "inferadb.authorization.v1.AuthorizationService";
+}
+
+impl From<Rejection> for Status {
+    fn from(rejection: Rejection) -> Self {
+        let code = match rejection.kind {
+            ErrorKind::InvalidArgument => tonic::Code::InvalidArgument,
+            ErrorKind::Unauthorized => tonic::Code::Unauthenticated,
+            ErrorKind::Forbidden => tonic::Code::PermissionDenied,
+            ErrorKind::NotFound => tonic::Code::NotFound,
+            ErrorKind::Conflict => tonic::Code::Aborted,
+            _ => tonic::Code::Internal,
+        };
+        Status::new(code, rejection.message)
+    }
+}
+
+/// Adapts an async closure to the tower service tonic's [`Grpc`] expects.
+struct Handler<F>(F);
+
+impl<R, T, F, Fut> Service<Request<R>> for Handler<F>
+where
+    F: FnMut(Request<R>) -> Fut,
+    Fut: Future<Output = Result<Response<T>, Status>>,
+{
+    type Response = Response<T>;
+    type Error = Status;
+    type Future = Fut;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<R>) -> Self::Future {
+        (self.0)(request)
+    }
+}
+
+fn to_proto(relationship: &Relationship<'_>) -> pb::Relationship {
+    pb::Relationship {
+        resource: relationship.resource().to_string(),
+        relation: relationship.relation().to_string(),
+        subject: relationship.subject().to_string(),
+    }
+}
+
+fn from_proto(relationship: pb::Relationship) -> Relationship<'static> {
+    Relationship::new(relationship.resource, relationship.relation, relationship.subject)
+}
+
+fn decision(allowed: bool) -> i32 {
+    if allowed { pb::Decision::Allow as i32 } else { pb::Decision::Deny as i32 }
+}
+
+/// Streams one page of `items`, putting the pagination fields on the last
+/// message like the real server.
+fn page_stream<T, M>(
+    items: &[T],
+    limit: Option<u32>,
+    cursor: Option<&str>,
+    message: impl Fn(T, Option<String>, Option<u64>) -> M,
+) -> Result<Response<BoxStream<M>>, Status>
+where
+    T: Clone,
+    M: Send + 'static,
+{
+    let (page, next) = paginate(items, limit, cursor)?;
+    let total = page.len();
+    let messages: Vec<_> = page
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let last = i + 1 == total;
+            message(item, next.clone().filter(|_| last), last.then_some(total as u64))
+        })
+        .map(Ok)
+        .collect();
+    Ok(Response::new(Box::pin(futures::stream::iter(messages))))
+}
+
+impl Service<http::Request<Body>> for AuthorizationService {
+    type Response = http::Response<Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        let state = Arc::clone(&self.state);
+        state.record_request();
+
+        if let Some(status) = state.take_fault() {
+            let code = match status {
+                429 => tonic::Code::ResourceExhausted,
+                503 => tonic::Code::Unavailable,
+                504 => tonic::Code::DeadlineExceeded,
+                _ => tonic::Code::Internal,
+            };
+            return Box::pin(async move { Ok(Status::new(code, "injected failure").into_http()) });
+        }
+
+        match request.uri().path().rsplit('/').next().unwrap_or_default() {
+            "Evaluate" => Box::pin(async move {
+                let handler = Handler(move |request: Request<Streaming<pb::EvaluateRequest>>| {
+                    let state = Arc::clone(&state);
+                    async move {
+                        let requests: Vec<_> = request.into_inner().try_collect().await?;
+                        let responses = requests.into_iter().enumerate().map(move |(i, r)| {
+                            Ok(pb::EvaluateResponse {
+                                decision: decision(state.check(
+                                    &r.subject,
+                                    &r.permission,
+                                    &r.resource,
+                                )),
+                                index: i as u32,
+                                error: None,
+                                trace: None,
+                            })
+                        });
+                        let stream: BoxStream<pb::EvaluateResponse> =
+                            Box::pin(futures::stream::iter(responses));
+                        Ok(Response::new(stream))
+                    }
+                });
+                Ok(Grpc::new(ProstCodec::default()).streaming(handler, request).await)
+            }),
+            "WriteRelationships" => Box::pin(async move {
+                let handler =
+                    Handler(move |request: Request<Streaming<pb::WriteRelationshipsRequest>>| {
+                        let state = Arc::clone(&state);
+                        async move {
+                            let requests: Vec<_> = request.into_inner().try_collect().await?;
+                            let relationships: Vec<_> = requests
+                                .into_iter()
+                                .flat_map(|r| r.relationships)
+                                .map(from_proto)
+                                .collect();
+                            let written = relationships.len() as u64;
+                            let revision = state.write(relationships);
+                            Ok(Response::new(pb::WriteRelationshipsResponse {
+                                revision: revision.to_string(),
+                                relationships_written: written,
+                            }))
+                        }
+                    });
+                Ok(Grpc::new(ProstCodec::default()).client_streaming(handler, request).await)
+            }),
+            "DeleteRelationships" => Box::pin(async move {
+                let handler =
+                    Handler(move |request: Request<Streaming<pb::DeleteRelationshipsRequest>>| {
+                        let state = Arc::clone(&state);
+                        async move {
+                            let requests: Vec<_> = request.into_inner().try_collect().await?;
+                            let mut relationships = Vec::new();
+                            for request in requests {
+                                if let Some(filter) = request.filter {
+                                    if filter.resource.is_none()
+                                        && filter.relation.is_none()
+                                        && filter.subject.is_none()
+                                    {
+                                        return Err(Status::invalid_argument(
+                                            "delete filter must set at least one field",
+                                        ));
+                                    }
+                                    relationships.extend(state.list(
+                                        filter.resource.as_deref(),
+                                        filter.relation.as_deref(),
+                                        filter.subject.as_deref(),
+                                    ));
+                                }
+                                relationships
+                                    .extend(request.relationships.into_iter().map(from_proto));
+                            }
+                            let (revision, deleted) = state.delete(relationships);
+                            Ok(Response::new(pb::DeleteRelationshipsResponse {
+                                revision: revision.to_string(),
+                                relationships_deleted: deleted,
+                            }))
+                        }
+                    });
+                Ok(Grpc::new(ProstCodec::default()).client_streaming(handler, request).await)
+            }),
+            "ListRelationships" => Box::pin(async move {
+                let handler = Handler(move |request: Request<pb::ListRelationshipsRequest>| {
+                    let request = request.into_inner();
+                    let relationships = state.list(
+                        request.resource.as_deref(),
+                        request.relation.as_deref(),
+                        request.subject.as_deref(),
+                    );
+                    let response = page_stream(
+                        &relationships,
+                        request.limit,
+                        request.cursor.as_deref(),
+                        |relationship, cursor, total_count| pb::ListRelationshipsResponse {
+                            relationship: Some(to_proto(&relationship)),
+                            cursor,
+                            total_count,
+                        },
+                    );
+                    futures::future::ready(response)
+                });
+                Ok(Grpc::new(ProstCodec::default()).server_streaming(handler, request).await)
+            }),
+            "ListResources" => Box::pin(async move {
+                let handler = Handler(move |request: Request<pb::ListResourcesRequest>| {
+                    let request = request.into_inner();
+                    let resource_type =
+                        Some(request.resource_type.as_str()).filter(|t| !t.is_empty());
+                    let resources =
+                        state.resources(&request.subject, &request.permission, resource_type);
+                    let response = page_stream(
+                        &resources,
+                        request.limit,
+                        request.cursor.as_deref(),
+                        |resource, cursor, total_count| pb::ListResourcesResponse {
+                            resource,
+                            cursor,
+                            total_count,
+                        },
+                    );
+                    futures::future::ready(response)
+                });
+                Ok(Grpc::new(ProstCodec::default()).server_streaming(handler, request).await)
+            }),
+            "ListSubjects" => Box::pin(async move {
+                let handler = Handler(move |request: Request<pb::ListSubjectsRequest>| {
+                    let request = request.into_inner();
+                    let subjects = state.subjects(
+                        &request.relation,
+                        &request.resource,
+                        request.subject_type.as_deref(),
+                    );
+                    let response = page_stream(
+                        &subjects,
+                        request.limit,
+                        request.cursor.as_deref(),
+                        |subject, cursor, total_count| pb::ListSubjectsResponse {
+                            subject,
+                            cursor,
+                            total_count,
+                        },
+                    );
+                    futures::future::ready(response)
+                });
+                Ok(Grpc::new(ProstCodec::default()).server_streaming(handler, request).await)
+            }),
+            "Simulate" => Box::pin(async move {
+                let handler = Handler(move |request: Request<pb::SimulateRequest>| {
+                    let request = request.into_inner();
+                    let response = match request.check {
+                        Some(check) => {
+                            let additions: Vec<_> = request
+                                .context_relationships
+                                .iter()
+                                .cloned()
+                                .map(from_proto)
+                                .collect();
+                            let allowed = state.simulate(
+                                &additions,
+                                &[],
+                                &check.subject,
+                                &check.permission,
+                                &check.resource,
+                            );
+                            Ok(Response::new(pb::SimulateResponse {
+                                decision: decision(allowed),
+                                context_relationships_count: additions.len() as u64,
+                            }))
+                        },
+                        None => Err(Status::invalid_argument("simulate requires a check")),
+                    };
+                    futures::future::ready(response)
+                });
+                Ok(Grpc::new(ProstCodec::default()).unary(handler, request).await)
+            }),
+            "Watch" => Box::pin(async move {
+                let handler = Handler(move |request: Request<pb::WatchRequest>| {
+                    let request = request.into_inner();
+                    // An empty or "0" cursor replays the whole change log
+                    let from = match request.cursor.as_deref() {
+                        None => Ok(None),
+                        Some("") => Ok(Some(0)),
+                        Some(cursor) => cursor
+                            .parse()
+                            .map(Some)
+                            .map_err(|_| Status::invalid_argument("invalid watch cursor")),
+                    };
+                    let response = from.map(|from| {
+                        // Resource types are alternatives, so match any of them
+                        let resource_types: Vec<_> = request
+                            .resource_types
+                            .into_iter()
+                            .map(WatchFilter::ResourceType)
+                            .collect();
+                        let events = state
+                            .watch(from, Vec::new())
+                            .filter(move |event| {
+                                futures::future::ready(
+                                    resource_types.is_empty()
+                                        || resource_types.iter().any(|f| f.matches(event)),
+                                )
+                            })
+                            .map(|event| {
+                                let operation = match event.operation {
+                                    Operation::Create => pb::ChangeOperation::Create,
+                                    Operation::Delete => pb::ChangeOperation::Delete,
+                                };
+                                Ok(pb::WatchResponse {
+                                    operation: operation as i32,
+                                    relationship: Some(to_proto(&event.relationship)),
+                                    revision: event.revision.to_string(),
+                                    timestamp: event.timestamp.to_rfc3339(),
+                                })
+                            });
+                        let stream: BoxStream<pb::WatchResponse> = Box::pin(events);
+                        Response::new(stream)
+                    });
+                    futures::future::ready(response)
+                });
+                Ok(Grpc::new(ProstCodec::default()).server_streaming(handler, request).await)
+            }),
+            "Health" => Box::pin(async move {
+                let handler = Handler(|_: Request<pb::HealthRequest>| {
+                    futures::future::ready(Ok(Response::new(pb::HealthResponse {
+                        status: "healthy".to_string(),
+                        service: "inferadb-fake".to_string(),
+                    })))
+                });
+                Ok(Grpc::new(ProstCodec::default()).unary(handler, request).await)
+            }),
+            _ => Box::pin(async move { Ok(Status::unimplemented("").into_http()) }),
+        }
+    }
+}
@@ -0,0 +1,547 @@
+//! FakeServer for offline end-to-end testing.
+//!
+//! `FakeServer` binds to an ephemeral localhost port and speaks the same REST,
+//! SSE and gRPC wire protocols as InferaDB, so a real [`Client`](crate::Client)
+//! can be pointed at it. Relationships are stored in an [`InMemoryClient`],
+//! and organizations, vaults and schemas live in an in-memory control plane.
+
+mod control;
+mod grpc;
+mod rest;
+mod state;
+
+use std::{net::SocketAddr, sync::Arc};
+
+use tokio::{net::TcpListener, sync::oneshot};
+
+use self::state::FakeState;
+use crate::{
+    Error, Relationship,
+    control::{OrganizationInfo, VaultInfo},
+};
+
+/// An offline InferaDB server for integration tests.
+///
+/// The server implements the engine API over both REST (with SSE streaming)
+/// and gRPC on a single port, plus the core control-plane endpoints for
+/// organizations, vaults, schemas and the current account. Because it is a
+/// real HTTP/2 server, tests exercise the full client stack: transport
+/// selection, auth headers, retries and stream parsing.
+///
+/// Permission checks use the same direct-relationship semantics as
+/// [`InMemoryClient`](super::InMemoryClient). The server shuts down when it is
+/// dropped.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use inferadb::testing::FakeServer;
+/// use inferadb::{BearerCredentialsConfig, Client, Relationship, TransportStrategy};
+///
+/// let fake = FakeServer::start().await?;
+/// fake.add_relationship(Relationship::new("doc:1", "viewer", "user:alice"));
+///
+/// let client = Client::builder()
+///     .url(fake.url())
+///     .insecure()
+///     .credentials(BearerCredentialsConfig::new("test"))
+///     .transport_strategy(TransportStrategy::RestOnly)
+///     .build()
+///     .await?;
+///
+/// let vault = client.organization("org").vault("vault");
+/// assert!(vault.check("user:alice", "viewer", "doc:1").await?);
+/// ```
+pub struct FakeServer {
+    addr: SocketAddr,
+    state: Arc<FakeState>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+#[bon::bon]
+impl FakeServer {
+    /// Starts a fake server with the default configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a connection error if no localhost port can be bound.
+    pub async fn start() -> Result<Self, Error> {
+        Self::builder().start().await
+    }
+
+    /// Returns a builder for configuring the fake server.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::testing::FakeServer;
+    ///
+    /// // Reject REST and control-plane requests without this bearer token
+    /// let fake = FakeServer::builder().token("secret").start().await?;
+    /// ```
+    #[builder(
+        builder_type(
+            name = FakeServerBuilder,
+            vis = "pub",
+            doc {
+                /// Builder for [`FakeServer`].
+                ///
+                /// Created via [`FakeServer::builder()`].
+            }
+        ),
+        finish_fn = start,
+        state_mod(vis = "pub(crate)"),
+    )]
+    pub async fn builder(
+        /// Bearer token that REST and control-plane requests must present.
+        ///
+        /// When unset, requests are accepted with or without a token.
+        #[builder(into)]
+        token: Option<String>,
+        /// Relationships the server starts with.
+        #[builder(default)]
+        relationships: Vec<Relationship<'static>>,
+    ) -> Result<Self, Error> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| Error::connection(format!("Failed to bind fake server: {}", e)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| Error::connection(format!("Failed to bind fake server: {}", e)))?;
+
+        let state = Arc::new(FakeState::new(token));
+        if !relationships.is_empty() {
+            state.write(relationships);
+        }
+
+        let routes = tonic::service::Routes::from(rest::router(Arc::clone(&state)))
+            .add_service(grpc::AuthorizationService::new(Arc::clone(&state)));
+        let (shutdown, signal) = oneshot::channel::<()>();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .accept_http1(true)
+                .add_routes(routes)
+                .serve_with_incoming_shutdown(
+                    tonic::transport::server::TcpIncoming::from(listener),
+                    async {
+                        let _ = signal.await;
+                    },
+                ),
+        );
+
+        Ok(Self { addr, state, shutdown: Some(shutdown) })
+    }
+
+    /// Returns the base URL of the server, e.g. `http://127.0.0.1:40123`.
+    ///
+    /// The URL uses plain HTTP, so clients must be built with
+    /// [`insecure()`](crate::ClientBuilder::insecure).
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Returns the socket address the server is listening on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Adds a relationship directly to the store, bypassing the API.
+    ///
+    /// The write is assigned a revision and delivered to watch streams like
+    /// any other write.
+    pub fn add_relationship(&self, relationship: Relationship<'_>) {
+        self.state.write(vec![relationship.into_owned()]);
+    }
+
+    /// Returns all stored relationships, sorted.
+    pub fn relationships(&self) -> Vec<Relationship<'static>> {
+        self.state.list(None, None, None)
+    }
+
+    /// Returns the current revision of the relationship store.
+    pub fn revision(&self) -> u64 {
+        self.state.revision()
+    }
+
+    /// Creates an organization directly in the control plane.
+    pub fn add_organization(&self, name: impl Into<String>) -> OrganizationInfo {
+        self.state.control().create_organization(name.into(), None)
+    }
+
+    /// Creates a vault directly in the control plane.
+    ///
+    /// Returns `None` if the organization does not exist.
+    pub fn add_vault(&self, organization_id: &str, name: impl Into<String>) -> Option<VaultInfo> {
+        self.state.control().create_vault(organization_id, name.into(), None, None)
+    }
+
+    /// Makes the next `times` requests fail with `status`.
+    ///
+    /// A `429` response carries `Retry-After: 0`, so clients retry without
+    /// delay. gRPC calls fail with the matching status code instead:
+    /// `RESOURCE_EXHAUSTED` for `429`, `UNAVAILABLE` for `503`,
+    /// `DEADLINE_EXCEEDED` for `504` and `INTERNAL` otherwise. Useful for
+    /// exercising retry behavior end to end.
+    pub fn fail_next(&self, status: u16, times: usize) {
+        self.state.fail_next(status, times);
+    }
+
+    /// Returns the number of requests received over REST and gRPC.
+    pub fn request_count(&self) -> u64 {
+        self.state.request_count()
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+impl std::fmt::Debug for FakeServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FakeServer").field("addr", &self.addr).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::{
+        BearerCredentialsConfig, Client, ErrorKind, TransportStrategy,
+        control::{CreateOrganizationRequest, CreateVaultRequest},
+        testing::AuthorizationClient,
+    };
+
+    async fn client(fake: &FakeServer, strategy: TransportStrategy, token: &str) -> Client {
+        Client::builder()
+            .url(fake.url())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new(token))
+            .transport_strategy(strategy)
+            .build()
+            .await
+            .unwrap()
+    }
+
+    /// Runs the engine operations end to end over one transport.
+    async fn exercise(strategy: TransportStrategy) {
+        let fake = FakeServer::start().await.unwrap();
+        let vault =
+            client(&fake, strategy.clone(), "test").await.organization("org").vault("vault");
+
+        vault.write(Relationship::new("doc:1", "viewer", "user:alice")).await.unwrap();
+        vault
+            .write_batch(vec![
+                Relationship::new("doc:2", "viewer", "user:alice"),
+                Relationship::new("doc:1", "viewer", "team:eng"),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(fake.revision(), 2);
+
+        assert!(vault.check("user:alice", "viewer", "doc:1").await.unwrap());
+        let checks = [("user:alice", "viewer", "doc:2"), ("user:bob", "viewer", "doc:2")];
+        let decisions = AuthorizationClient::check_batch(&vault, &checks).await.unwrap();
+        assert_eq!(decisions, vec![true, false]);
+
+        let resources =
+            vault.accessible_resources("user:alice", "viewer", Some("doc")).await.unwrap();
+        assert_eq!(resources, vec!["doc:1", "doc:2"]);
+        let subjects =
+            vault.subjects_with_permission("viewer", "doc:1", Some("team")).await.unwrap();
+        assert_eq!(subjects, vec!["team:eng"]);
+        assert_eq!(vault.list_relationships(Some("doc:1"), None, None).await.unwrap().len(), 2);
+
+        let simulated = vault
+            .simulate()
+            .add_relationship(Relationship::new("doc:3", "viewer", "user:bob"))
+            .check("user:bob", "viewer", "doc:3")
+            .await
+            .unwrap();
+        assert!(simulated.allowed);
+        assert!(!vault.check("user:bob", "viewer", "doc:3").await.unwrap());
+
+        // More rows than gRPC keeps in flight, answered in order
+        let rows: Vec<_> = (0..40).map(|i| ("user:bob", "viewer", format!("doc:{}", i))).collect();
+        let matrix = vault
+            .simulate()
+            .add_relationship(Relationship::new("doc:7", "viewer", "user:bob"))
+            .checks(rows)
+            .await
+            .unwrap();
+        let allowed: Vec<_> =
+            matrix.iter().filter(|d| d.simulated_allowed).map(|d| d.resource.as_str()).collect();
+        assert_eq!(matrix.len(), 40);
+        assert_eq!(allowed, ["doc:7"]);
+
+        vault.delete(Relationship::new("doc:2", "viewer", "user:alice")).await.unwrap();
+        assert_eq!(vault.delete_where(Some("doc:1"), None, None).await.unwrap(), 2);
+        assert!(fake.relationships().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_engine_over_rest() {
+        exercise(TransportStrategy::RestOnly).await;
+    }
+
+    #[tokio::test]
+    async fn test_engine_over_grpc() {
+        exercise(TransportStrategy::GrpcOnly).await;
+    }
+
+    #[tokio::test]
+    async fn test_schema_simulation_by_strategy() {
+        let fake = FakeServer::start().await.unwrap();
+        fake.add_relationship(Relationship::new("doc:1", "viewer", "user:alice"));
+
+        // Preferring gRPC sends schema simulations over REST
+        let vault = client(&fake, TransportStrategy::default(), "test")
+            .await
+            .organization("org")
+            .vault("vault");
+        let simulate = || vault.simulate().with_schema("type user {}");
+        assert!(simulate().check("user:alice", "viewer", "doc:1").await.unwrap().allowed);
+        assert!(simulate().compare("user:alice", "viewer", "doc:1").await.unwrap().current_allowed);
+        let matrix = simulate().checks([("user:alice", "viewer", "doc:1")]).await.unwrap();
+        assert!(matrix[0].simulated_allowed);
+
+        // Only gRPC cannot carry one, whichever builder is used
+        let vault = client(&fake, TransportStrategy::GrpcOnly, "test")
+            .await
+            .organization("org")
+            .vault("vault");
+        let simulate = || vault.simulate().with_schema("type user {}");
+        let errors = [
+            simulate().check("user:alice", "viewer", "doc:1").await.unwrap_err(),
+            simulate().compare("user:alice", "viewer", "doc:1").await.unwrap_err(),
+            simulate().checks([("user:alice", "viewer", "doc:1")]).await.unwrap_err(),
+        ];
+        for err in errors {
+            assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+            assert!(err.to_string().contains("schema simulation is not supported over gRPC"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_impact_reports_removals_by_strategy() {
+        let fake = FakeServer::start().await.unwrap();
+        fake.add_relationship(Relationship::new("doc:1", "viewer", "user:alice"));
+        fake.add_relationship(Relationship::new("doc:1", "viewer", "user:bob"));
+        let removal = Relationship::new("doc:1", "viewer", "user:alice");
+
+        let vault = client(&fake, TransportStrategy::default(), "test")
+            .await
+            .organization("org")
+            .vault("vault");
+        let report =
+            vault.impact().remove_relationship(removal.clone()).permission("viewer").await.unwrap();
+        let denied: Vec<_> = report.now_denied().map(|c| c.subject.as_str()).collect();
+        assert_eq!(denied, ["user:alice"]);
+        assert_eq!(report.len(), 1);
+
+        let vault = client(&fake, TransportStrategy::GrpcOnly, "test")
+            .await
+            .organization("org")
+            .vault("vault");
+        let err = vault.impact().remove_relationship(removal).permission("viewer").await;
+        assert_eq!(err.unwrap_err().kind(), ErrorKind::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_seeded_relationships() {
+        let fake = FakeServer::builder()
+            .relationships(vec![Relationship::new("doc:1", "viewer", "user:alice")])
+            .start()
+            .await
+            .unwrap();
+        let vault = client(&fake, TransportStrategy::RestOnly, "test")
+            .await
+            .organization("org")
+            .vault("vault");
+
+        assert!(vault.check("user:alice", "viewer", "doc:1").await.unwrap());
+        assert!(fake.request_count() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_token_is_enforced() {
+        let fake = FakeServer::builder().token("secret").start().await.unwrap();
+        fake.add_relationship(Relationship::new("doc:1", "viewer", "user:alice"));
+
+        let wrong = client(&fake, TransportStrategy::RestOnly, "wrong").await;
+        let err = wrong.organization("org").vault("vault").check("user:alice", "viewer", "doc:1");
+        assert_eq!(err.await.unwrap_err().kind(), ErrorKind::Unauthorized);
+
+        let right = client(&fake, TransportStrategy::RestOnly, "secret").await;
+        assert!(
+            right
+                .organization("org")
+                .vault("vault")
+                .check("user:alice", "viewer", "doc:1")
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_injected_failures_are_retried() {
+        for strategy in [TransportStrategy::RestOnly, TransportStrategy::GrpcOnly] {
+            let fake = FakeServer::start().await.unwrap();
+            let vault =
+                client(&fake, strategy.clone(), "test").await.organization("org").vault("vault");
+
+            fake.fail_next(503, 1);
+            vault.write(Relationship::new("doc:1", "viewer", "user:alice")).await.unwrap();
+            fake.fail_next(429, 1);
+            vault.write(Relationship::new("doc:2", "viewer", "user:alice")).await.unwrap();
+            assert_eq!(fake.relationships().len(), 2, "{:?}", strategy);
+
+            let sent = fake.request_count();
+            fake.fail_next(400, 1);
+            assert!(vault.check("user:alice", "viewer", "doc:1").await.is_err());
+            assert_eq!(fake.request_count(), sent + 1, "{:?}", strategy);
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_retries_are_counted() {
+        for strategy in [TransportStrategy::RestOnly, TransportStrategy::GrpcOnly] {
+            let fake = FakeServer::start().await.unwrap();
+            let client = Client::builder()
+                .url(fake.url())
+                .insecure()
+                .credentials(BearerCredentialsConfig::new("test"))
+                .transport_strategy(strategy.clone())
+                .metrics(crate::tracing_support::MetricsConfig::default())
+                .build()
+                .await
+                .unwrap();
+
+            fake.fail_next(429, 2);
+            let vault = client.organization("org").vault("vault");
+            vault.write(Relationship::new("doc:1", "viewer", "user:alice")).await.unwrap();
+            assert_eq!(client.metrics().unwrap().snapshot().retries, 2, "{:?}", strategy);
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_cache_lookups_are_counted() {
+        for strategy in [TransportStrategy::RestOnly, TransportStrategy::GrpcOnly] {
+            let fake = FakeServer::start().await.unwrap();
+            let client = Client::builder()
+                .url(fake.url())
+                .insecure()
+                .credentials(BearerCredentialsConfig::new("test"))
+                .transport_strategy(strategy.clone())
+                .cache_config(crate::config::CacheConfig::enabled_config())
+                .metrics(crate::tracing_support::MetricsConfig::default())
+                .build()
+                .await
+                .unwrap();
+            let vault = client.organization("org").vault("vault");
+            vault.write(Relationship::new("doc:1", "viewer", "user:alice")).await.unwrap();
+
+            let sent = fake.request_count();
+            assert!(vault.check("user:alice", "viewer", "doc:1").await.unwrap());
+            assert!(vault.check("user:alice", "viewer", "doc:1").await.unwrap());
+            assert_eq!(fake.request_count(), sent + 1, "{:?}", strategy);
+
+            let snapshot = client.metrics().unwrap().snapshot();
+            assert_eq!((snapshot.cache_hits, snapshot.cache_misses), (1, 1), "{:?}", strategy);
+            assert_eq!(snapshot.cache_hit_rate(), 0.5);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_control_plane() {
+        let fake = FakeServer::start().await.unwrap();
+        let client = client(&fake, TransportStrategy::RestOnly, "test").await;
+
+        let org =
+            client.organizations().create(CreateOrganizationRequest::new("acme")).await.unwrap();
+        let err = client.organizations().create(CreateOrganizationRequest::new("acme")).await;
+        assert_eq!(err.unwrap_err().kind(), ErrorKind::Conflict);
+
+        let vault = client
+            .organization(&org.id)
+            .vaults()
+            .create(CreateVaultRequest::new("production"))
+            .await
+            .unwrap();
+        assert_eq!(vault.organization_id, org.id);
+
+        let schemas = client.organization(&org.id).vault(&vault.id).schemas();
+        let pushed = schemas.push("entity User {}").await.unwrap();
+        assert!(pushed.validation.is_valid());
+        schemas.activate(&pushed.schema.version).await.unwrap();
+        assert_eq!(schemas.get_active().await.unwrap().version, pushed.schema.version);
+
+        assert_eq!(client.account().get().await.unwrap().id, "usr_fake");
+    }
+
+    #[tokio::test]
+    async fn test_deploy_assertions_on_default_client() {
+        let fake = FakeServer::start().await.unwrap();
+        fake.add_relationship(Relationship::new("doc:1", "viewer", "user:alice"));
+        let client = client(&fake, TransportStrategy::default(), "test").await;
+        assert_eq!(client.transport_stats().unwrap().active_transport, crate::Transport::Grpc);
+
+        let org =
+            client.organizations().create(CreateOrganizationRequest::new("acme")).await.unwrap();
+        let vault = client
+            .organization(&org.id)
+            .vaults()
+            .create(CreateVaultRequest::new("production"))
+            .await
+            .unwrap();
+        let schemas = client.organization(&org.id).vault(&vault.id).schemas();
+
+        let err = schemas
+            .deploy("entity User {}")
+            .expect_allowed(("user:bob", "viewer", "doc:1"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SchemaViolation);
+        assert!(err.to_string().contains("schema was not pushed"), "{err}");
+        assert_eq!(schemas.get_active().await.unwrap_err().kind(), ErrorKind::NotFound);
+
+        let deployment = schemas
+            .deploy("entity User {}")
+            .expect_allowed(("user:alice", "viewer", "doc:1"))
+            .expect_denied(("user:bob", "viewer", "doc:1"))
+            .await
+            .unwrap();
+        assert!(deployment.activated);
+        assert!(deployment.assertions.iter().all(|a| a.passed()));
+    }
+
+    #[tokio::test]
+    async fn test_watch_replays_and_streams_changes() {
+        let fake = FakeServer::start().await.unwrap();
+        fake.add_relationship(Relationship::new("doc:1", "viewer", "user:alice"));
+        fake.add_relationship(Relationship::new("folder:1", "viewer", "user:alice"));
+
+        let mut response = reqwest::get(format!(
+            "{}/v1/organizations/org/vaults/vault/watch?from_revision=0&resource_type=doc",
+            fake.url()
+        ))
+        .await
+        .unwrap();
+        fake.add_relationship(Relationship::new("doc:2", "viewer", "user:bob"));
+
+        let mut body = String::new();
+        while body.matches("data: ").count() < 2 {
+            let chunk = response.chunk().await.unwrap().unwrap();
+            body.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+        assert!(body.contains("doc:1"));
+        assert!(body.contains("doc:2"));
+        assert!(!body.contains("folder:1"));
+    }
+}
@@ -0,0 +1,347 @@
+//! REST and SSE front end of the fake server.
+
+use std::{convert::Infallible, sync::Arc};
+
+use axum::{
+    Json, Router,
+    body::{Body, Bytes},
+    extract::{Path, RawQuery, Request, State},
+    http::{
+        HeaderValue, StatusCode,
+        header::{AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER},
+    },
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::{
+    control,
+    state::{FakeState, Rejection, paginate},
+};
+use crate::{
+    ErrorKind, Relationship,
+    vault::watch::{Operation, WatchFilter},
+};
+
+/// Builds the router for the engine and control-plane REST APIs.
+pub(super) fn router(state: Arc<FakeState>) -> Router {
+    Router::new()
+        .route("/healthz", get(health))
+        .route("/livez", get(health))
+        .route("/readyz", get(health))
+        .route("/access/v1/evaluate", post(evaluate))
+        .route("/access/v1/relationships/write", post(write))
+        .route("/access/v1/relationships/list", post(list_relationships))
+        .route("/access/v1/relationships/{resource}/{relation}/{subject}", delete(delete_one))
+        .route("/access/v1/resources/list", post(list_resources))
+        .route("/access/v1/subjects/list", post(list_subjects))
+        .route("/access/v1/simulate", post(simulate))
+        .route("/access/v1/simulate/batch", post(simulate_batch))
+        .route("/v1/organizations/{organization}/vaults/{vault}/watch", get(watch))
+        .merge(control::router())
+        .layer(middleware::from_fn_with_state(Arc::clone(&state), gate))
+        .with_state(state)
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        let status = match self.kind {
+            ErrorKind::InvalidArgument => StatusCode::BAD_REQUEST,
+            ErrorKind::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorKind::Forbidden => StatusCode::FORBIDDEN,
+            ErrorKind::NotFound => StatusCode::NOT_FOUND,
+            ErrorKind::Conflict => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+/// Counts the request, then applies injected failures and the bearer token
+/// check before the request reaches its handler.
+async fn gate(State(state): State<Arc<FakeState>>, request: Request, next: Next) -> Response {
+    state.record_request();
+
+    if let Some(status) = state.take_fault() {
+        let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = (status, Json(json!({ "error": "injected failure" }))).into_response();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            response.headers_mut().insert(RETRY_AFTER, HeaderValue::from_static("0"));
+        }
+        return response;
+    }
+
+    // Health checks are unauthenticated, like the real server
+    let authorization = request.headers().get(AUTHORIZATION).and_then(|v| v.to_str().ok());
+    let probe = matches!(request.uri().path(), "/healthz" | "/livez" | "/readyz");
+    if !probe && !state.authorized(authorization) {
+        return Rejection::new(ErrorKind::Unauthorized, "missing or invalid bearer token")
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Renders events, and an optional trailing summary event, as an SSE body.
+fn sse(events: impl IntoIterator<Item = Value>, summary: Option<Value>) -> Response {
+    let mut body = String::new();
+    for event in events {
+        body.push_str(&format!("data: {}\n\n", event));
+    }
+    if let Some(summary) = summary {
+        body.push_str(&format!("event: summary\ndata: {}\n\n", summary));
+    }
+    ([(CONTENT_TYPE, "text/event-stream")], body).into_response()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RelationshipDto {
+    resource: String,
+    relation: String,
+    subject: String,
+}
+
+impl From<RelationshipDto> for Relationship<'static> {
+    fn from(dto: RelationshipDto) -> Self {
+        Relationship::new(dto.resource, dto.relation, dto.subject)
+    }
+}
+
+impl From<&Relationship<'_>> for RelationshipDto {
+    fn from(rel: &Relationship<'_>) -> Self {
+        Self {
+            resource: rel.resource().to_string(),
+            relation: rel.relation().to_string(),
+            subject: rel.subject().to_string(),
+        }
+    }
+}
+
+fn into_relationships(dtos: Vec<RelationshipDto>) -> Vec<Relationship<'static>> {
+    dtos.into_iter().map(Relationship::from).collect()
+}
+
+async fn health() -> Json<Value> {
+    Json(json!({ "status": "healthy", "service": "inferadb-fake" }))
+}
+
+#[derive(Deserialize)]
+struct EvaluateBody {
+    evaluations: Vec<Evaluation>,
+}
+
+#[derive(Deserialize)]
+struct Evaluation {
+    subject: String,
+    resource: String,
+    permission: String,
+}
+
+async fn evaluate(State(state): State<Arc<FakeState>>, Json(body): Json<EvaluateBody>) -> Response {
+    let total = body.evaluations.len();
+    let events = body.evaluations.iter().enumerate().map(|(index, evaluation)| {
+        let allowed =
+            state.check(&evaluation.subject, &evaluation.permission, &evaluation.resource);
+        json!({ "decision": if allowed { "allow" } else { "deny" }, "index": index })
+    });
+    sse(events, Some(json!({ "total": total })))
+}
+
+#[derive(Deserialize)]
+struct WriteBody {
+    relationships: Vec<RelationshipDto>,
+    #[serde(default)]
+    expected_revision: Option<String>,
+}
+
+async fn write(
+    State(state): State<Arc<FakeState>>,
+    Json(body): Json<WriteBody>,
+) -> Result<Json<Value>, Rejection> {
+    if let Some(expected) = body.expected_revision
+        && expected != state.revision().to_string()
+    {
+        return Err(Rejection::conflict(format!(
+            "expected revision {} but the store is at {}",
+            expected,
+            state.revision()
+        )));
+    }
+
+    let written = body.relationships.len();
+    let revision = state.write(into_relationships(body.relationships));
+    Ok(Json(json!({ "revision": revision.to_string(), "relationships_written": written })))
+}
+
+async fn delete_one(
+    State(state): State<Arc<FakeState>>,
+    Path((resource, relation, subject)): Path<(String, String, String)>,
+) -> Result<Json<Value>, Rejection> {
+    let (revision, deleted) = state.delete(vec![Relationship::new(resource, relation, subject)]);
+    if deleted == 0 {
+        return Err(Rejection::not_found("Relationship not found"));
+    }
+    Ok(Json(json!({ "revision": revision.to_string(), "relationships_deleted": deleted })))
+}
+
+#[derive(Deserialize)]
+struct ListRelationshipsBody {
+    resource: Option<String>,
+    relation: Option<String>,
+    subject: Option<String>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+async fn list_relationships(
+    State(state): State<Arc<FakeState>>,
+    Json(body): Json<ListRelationshipsBody>,
+) -> Result<Response, Rejection> {
+    let relationships =
+        state.list(body.resource.as_deref(), body.relation.as_deref(), body.subject.as_deref());
+    let (page, next) = paginate(&relationships, body.limit, body.cursor.as_deref())?;
+    let total = page.len();
+    let events = page.iter().map(|rel| json!(RelationshipDto::from(rel)));
+    Ok(sse(events, Some(json!({ "total": total, "cursor": next }))))
+}
+
+#[derive(Deserialize)]
+struct ListResourcesBody {
+    subject: String,
+    #[serde(default)]
+    resource_type: String,
+    permission: String,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+async fn list_resources(
+    State(state): State<Arc<FakeState>>,
+    Json(body): Json<ListResourcesBody>,
+) -> Result<Response, Rejection> {
+    let resource_type = Some(body.resource_type.as_str()).filter(|t| !t.is_empty());
+    let resources = state.resources(&body.subject, &body.permission, resource_type);
+    let (page, next) = paginate(&resources, body.limit, body.cursor.as_deref())?;
+    let total = page.len();
+    Ok(sse(page.into_iter().map(Value::String), Some(json!({ "total": total, "cursor": next }))))
+}
+
+#[derive(Deserialize)]
+struct ListSubjectsBody {
+    resource: String,
+    relation: String,
+    subject_type: Option<String>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+async fn list_subjects(
+    State(state): State<Arc<FakeState>>,
+    Json(body): Json<ListSubjectsBody>,
+) -> Result<Response, Rejection> {
+    let subjects = state.subjects(&body.relation, &body.resource, body.subject_type.as_deref());
+    let (page, next) = paginate(&subjects, body.limit, body.cursor.as_deref())?;
+    let total = page.len();
+    Ok(sse(page.into_iter().map(Value::String), Some(json!({ "total": total, "cursor": next }))))
+}
+
+#[derive(Deserialize)]
+struct SimulateBody {
+    subject: String,
+    permission: String,
+    resource: String,
+    #[serde(default)]
+    additions: Vec<RelationshipDto>,
+    #[serde(default)]
+    removals: Vec<RelationshipDto>,
+}
+
+async fn simulate(
+    State(state): State<Arc<FakeState>>,
+    Json(body): Json<SimulateBody>,
+) -> Json<Value> {
+    let allowed = state.simulate(
+        &into_relationships(body.additions),
+        &into_relationships(body.removals),
+        &body.subject,
+        &body.permission,
+        &body.resource,
+    );
+    Json(json!({ "allowed": allowed }))
+}
+
+#[derive(Deserialize)]
+struct SimulateBatchBody {
+    evaluations: Vec<Evaluation>,
+    #[serde(default)]
+    additions: Vec<RelationshipDto>,
+    #[serde(default)]
+    removals: Vec<RelationshipDto>,
+}
+
+async fn simulate_batch(
+    State(state): State<Arc<FakeState>>,
+    Json(body): Json<SimulateBatchBody>,
+) -> Json<Value> {
+    let additions = into_relationships(body.additions);
+    let removals = into_relationships(body.removals);
+    let results: Vec<_> = body
+        .evaluations
+        .iter()
+        .enumerate()
+        .map(|(index, evaluation)| {
+            let allowed = state.simulate(
+                &additions,
+                &removals,
+                &evaluation.subject,
+                &evaluation.permission,
+                &evaluation.resource,
+            );
+            json!({ "index": index, "allowed": allowed })
+        })
+        .collect();
+    Json(json!({ "results": results }))
+}
+
+/// Streams relationship changes as SSE, honoring the watch query parameters.
+async fn watch(
+    State(state): State<Arc<FakeState>>,
+    RawQuery(query): RawQuery,
+) -> Result<Response, Rejection> {
+    let mut from = None;
+    let mut filters = Vec::new();
+    let mut operations = Vec::new();
+    for (key, value) in url::form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+        let value = value.into_owned();
+        match key.as_ref() {
+            "from_revision" => {
+                from =
+                    Some(value.parse().map_err(|_| Rejection::invalid("invalid from_revision"))?);
+            },
+            "resource_type" => filters.push(WatchFilter::ResourceType(value)),
+            "subject_type" => filters.push(WatchFilter::SubjectType(value)),
+            "resource" => filters.push(WatchFilter::Resource(value)),
+            "subject" => filters.push(WatchFilter::Subject(value)),
+            "relation" => filters.push(WatchFilter::Relation(value)),
+            "operation" => match value.as_str() {
+                "create" => operations.push(Operation::Create),
+                "delete" => operations.push(Operation::Delete),
+                _ => return Err(Rejection::invalid(format!("unknown operation '{}'", value))),
+            },
+            _ => {},
+        }
+    }
+    if !operations.is_empty() {
+        filters.push(WatchFilter::Operations(operations));
+    }
+
+    let events = state.watch(from, filters).map(|event| {
+        let data = serde_json::to_string(&event).unwrap_or_default();
+        Ok::<_, Infallible>(Bytes::from(format!("data: {}\n\n", data)))
+    });
+    Ok(([(CONTENT_TYPE, "text/event-stream")], Body::from_stream(events)).into_response())
+}
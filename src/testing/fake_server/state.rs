@@ -0,0 +1,500 @@
+//! Shared state behind the fake server's REST and gRPC front ends.
+
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use futures::{Stream, StreamExt};
+use parking_lot::{Mutex, MutexGuard};
+use tokio::sync::broadcast;
+
+use crate::{
+    ErrorKind, Relationship,
+    control::{
+        Account, AccountStatus, OrganizationInfo, SchemaInfo, SchemaStatus, ValidationIssue,
+        ValidationResult, VaultInfo, VaultStatus,
+    },
+    testing::in_memory::{InMemoryClient, has_type},
+    vault::watch::{Operation, WatchEvent, WatchFilter},
+};
+
+/// Page size used when a list request does not set a limit.
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Buffered watch events per subscriber before it is considered lagging.
+const WATCH_BUFFER: usize = 1024;
+
+/// A request the fake server rejects, rendered per protocol by the front ends.
+#[derive(Debug)]
+pub(super) struct Rejection {
+    pub(super) kind: ErrorKind,
+    pub(super) message: String,
+}
+
+impl Rejection {
+    pub(super) fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self { kind, message: message.into() }
+    }
+
+    pub(super) fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    pub(super) fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Conflict, message)
+    }
+
+    pub(super) fn invalid(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidArgument, message)
+    }
+}
+
+/// The relationship store's revision and the changes that produced it.
+struct ChangeLog {
+    revision: u64,
+    events: Vec<WatchEvent>,
+}
+
+/// State shared by every connection to a fake server.
+pub(super) struct FakeState {
+    engine: InMemoryClient,
+    log: Mutex<ChangeLog>,
+    events: broadcast::Sender<WatchEvent>,
+    control: Mutex<ControlState>,
+    token: Option<String>,
+    faults: Mutex<VecDeque<u16>>,
+    requests: AtomicU64,
+}
+
+impl FakeState {
+    pub(super) fn new(token: Option<String>) -> Self {
+        Self {
+            engine: InMemoryClient::new(),
+            log: Mutex::new(ChangeLog { revision: 0, events: Vec::new() }),
+            events: broadcast::channel(WATCH_BUFFER).0,
+            control: Mutex::new(ControlState::default()),
+            token,
+            faults: Mutex::new(VecDeque::new()),
+            requests: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the current revision of the relationship store.
+    pub(super) fn revision(&self) -> u64 {
+        self.log.lock().revision
+    }
+
+    /// Writes relationships at a new revision and returns it.
+    pub(super) fn write(&self, relationships: Vec<Relationship<'static>>) -> u64 {
+        let mut log = self.log.lock();
+        log.revision += 1;
+        let revision = log.revision;
+        for relationship in relationships {
+            self.engine.write(relationship.clone());
+            self.record(&mut log, Operation::Create, relationship);
+        }
+        revision
+    }
+
+    /// Deletes relationships, returning the resulting revision and how many
+    /// existed. The revision only advances if something was deleted.
+    pub(super) fn delete(&self, relationships: Vec<Relationship<'static>>) -> (u64, u64) {
+        let mut log = self.log.lock();
+        let existing: Vec<_> = relationships
+            .into_iter()
+            .filter(|relationship| {
+                self.engine.has_direct_relationship(
+                    relationship.resource(),
+                    relationship.relation(),
+                    relationship.subject(),
+                )
+            })
+            .collect();
+        if existing.is_empty() {
+            return (log.revision, 0);
+        }
+
+        log.revision += 1;
+        let deleted = existing.len() as u64;
+        for relationship in existing {
+            self.engine.delete(&relationship);
+            self.record(&mut log, Operation::Delete, relationship);
+        }
+        (log.revision, deleted)
+    }
+
+    /// Appends a change to the log and publishes it to watchers.
+    fn record(
+        &self,
+        log: &mut ChangeLog,
+        operation: Operation,
+        relationship: Relationship<'static>,
+    ) {
+        let event = WatchEvent::new(operation, relationship, log.revision, chrono::Utc::now());
+        // No receivers is fine; the event stays in the log for replay
+        let _ = self.events.send(event.clone());
+        log.events.push(event);
+    }
+
+    /// Streams changes matching every filter: first the logged changes after
+    /// `from`, then live ones. Without `from`, only changes made after
+    /// subscribing are delivered.
+    ///
+    /// The stream ends if the subscriber falls too far behind.
+    pub(super) fn watch(
+        &self,
+        from: Option<u64>,
+        filters: Vec<WatchFilter>,
+    ) -> impl Stream<Item = WatchEvent> + Send + 'static {
+        let (backlog, receiver) = {
+            let log = self.log.lock();
+            let receiver = self.events.subscribe();
+            let backlog: Vec<_> = match from {
+                Some(from) => log.events.iter().filter(|e| e.revision > from).cloned().collect(),
+                None => Vec::new(),
+            };
+            (backlog, receiver)
+        };
+        let live = futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.ok().map(|event| (event, receiver))
+        });
+        futures::stream::iter(backlog)
+            .chain(live)
+            .filter(move |event| futures::future::ready(filters.iter().all(|f| f.matches(event))))
+    }
+
+    /// Returns `true` if `subject` holds `permission` on `resource`.
+    pub(super) fn check(&self, subject: &str, permission: &str, resource: &str) -> bool {
+        self.engine.has_direct_relationship(resource, permission, subject)
+    }
+
+    /// Returns the stored relationships matching the filters, sorted.
+    pub(super) fn list(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Vec<Relationship<'static>> {
+        self.engine.relationships_matching(resource, relation, subject)
+    }
+
+    /// Returns the resources `subject` holds `permission` on, sorted.
+    pub(super) fn resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+    ) -> Vec<String> {
+        self.list(None, Some(permission), Some(subject))
+            .into_iter()
+            .map(|rel| rel.resource().to_string())
+            .filter(|resource| has_type(resource, resource_type))
+            .collect()
+    }
+
+    /// Returns the subjects holding `permission` on `resource`, sorted.
+    pub(super) fn subjects(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+    ) -> Vec<String> {
+        self.list(Some(resource), Some(permission), None)
+            .into_iter()
+            .map(|rel| rel.subject().to_string())
+            .filter(|subject| has_type(subject, subject_type))
+            .collect()
+    }
+
+    /// Evaluates a check against the store with relationships hypothetically
+    /// added and removed, without changing the store.
+    pub(super) fn simulate(
+        &self,
+        additions: &[Relationship<'static>],
+        removals: &[Relationship<'static>],
+        subject: &str,
+        permission: &str,
+        resource: &str,
+    ) -> bool {
+        let scratch = InMemoryClient::new();
+        scratch.write_all(self.list(None, None, None));
+        for removal in removals {
+            scratch.delete(removal);
+        }
+        scratch.write_all(additions.iter().cloned());
+        scratch.has_direct_relationship(resource, permission, subject)
+    }
+
+    /// Returns `true` if the `Authorization` header satisfies the configured
+    /// token.
+    pub(super) fn authorized(&self, authorization: Option<&str>) -> bool {
+        match &self.token {
+            Some(token) => authorization
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .is_some_and(|presented| presented == token),
+            None => true,
+        }
+    }
+
+    /// Queues `times` failures with `status`.
+    pub(super) fn fail_next(&self, status: u16, times: usize) {
+        self.faults.lock().extend(std::iter::repeat_n(status, times));
+    }
+
+    /// Takes the next queued failure, if any.
+    pub(super) fn take_fault(&self) -> Option<u16> {
+        self.faults.lock().pop_front()
+    }
+
+    /// Counts an incoming request.
+    pub(super) fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of requests received.
+    pub(super) fn request_count(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Locks the control plane.
+    pub(super) fn control(&self) -> MutexGuard<'_, ControlState> {
+        self.control.lock()
+    }
+}
+
+/// Returns one page of `items` starting at `cursor`, plus the cursor of the
+/// next page. Cursors are opaque offsets.
+pub(super) fn paginate<T: Clone>(
+    items: &[T],
+    limit: Option<u32>,
+    cursor: Option<&str>,
+) -> Result<(Vec<T>, Option<String>), Rejection> {
+    let start = match cursor.filter(|c| !c.is_empty()) {
+        Some(cursor) => {
+            cursor.parse::<usize>().map_err(|_| Rejection::invalid("invalid cursor"))?
+        },
+        None => 0,
+    };
+    let limit = match limit {
+        Some(0) | None => DEFAULT_PAGE_SIZE,
+        Some(limit) => limit as usize,
+    };
+    let end = start.saturating_add(limit).min(items.len());
+    let page = items.get(start..end).map(<[T]>::to_vec).unwrap_or_default();
+    let next = (end < items.len()).then(|| end.to_string());
+    Ok((page, next))
+}
+
+/// The in-memory control plane: organizations, vaults and schemas.
+#[derive(Default)]
+pub(super) struct ControlState {
+    next_id: u64,
+    organizations: Vec<OrganizationInfo>,
+    vaults: Vec<VaultInfo>,
+    schemas: Vec<SchemaInfo>,
+}
+
+impl ControlState {
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}_{}", prefix, self.next_id)
+    }
+
+    /// Returns the account the fake server authenticates every caller as.
+    pub(super) fn account(&self) -> Account {
+        let epoch = chrono::DateTime::UNIX_EPOCH;
+        Account {
+            id: "usr_fake".to_string(),
+            email: "fake@example.com".to_string(),
+            name: Some("Fake User".to_string()),
+            status: AccountStatus::Active,
+            created_at: epoch,
+            updated_at: epoch,
+            mfa_enabled: false,
+        }
+    }
+
+    pub(super) fn organizations(&self) -> &[OrganizationInfo] {
+        &self.organizations
+    }
+
+    pub(super) fn create_organization(
+        &mut self,
+        name: String,
+        display_name: Option<String>,
+    ) -> OrganizationInfo {
+        let now = chrono::Utc::now();
+        let organization = OrganizationInfo {
+            id: self.next_id("org"),
+            name,
+            display_name,
+            created_at: now,
+            updated_at: now,
+        };
+        self.organizations.push(organization.clone());
+        organization
+    }
+
+    pub(super) fn organization_named(&self, name: &str) -> Option<&OrganizationInfo> {
+        self.organizations.iter().find(|org| org.name == name)
+    }
+
+    pub(super) fn organization(&mut self, id: &str) -> Result<&mut OrganizationInfo, Rejection> {
+        self.organizations
+            .iter_mut()
+            .find(|org| org.id == id)
+            .ok_or_else(|| Rejection::not_found(format!("organization '{}' not found", id)))
+    }
+
+    /// Deletes an organization together with its vaults and their schemas.
+    pub(super) fn delete_organization(&mut self, id: &str) -> Result<(), Rejection> {
+        self.organization(id)?;
+        self.organizations.retain(|org| org.id != id);
+        let vaults: Vec<_> = self
+            .vaults
+            .iter()
+            .filter(|vault| vault.organization_id == id)
+            .map(|vault| vault.id.clone())
+            .collect();
+        self.vaults.retain(|vault| vault.organization_id != id);
+        self.schemas.retain(|schema| !vaults.contains(&schema.vault_id));
+        Ok(())
+    }
+
+    pub(super) fn vaults(&self, organization_id: &str) -> Vec<VaultInfo> {
+        self.vaults
+            .iter()
+            .filter(|vault| vault.organization_id == organization_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Creates a vault, or returns `None` if the organization does not exist.
+    pub(super) fn create_vault(
+        &mut self,
+        organization_id: &str,
+        name: String,
+        display_name: Option<String>,
+        description: Option<String>,
+    ) -> Option<VaultInfo> {
+        self.organization(organization_id).ok()?;
+        let now = chrono::Utc::now();
+        let vault = VaultInfo {
+            id: self.next_id("vlt"),
+            organization_id: organization_id.to_string(),
+            name,
+            display_name,
+            description,
+            status: VaultStatus::Active,
+            created_at: now,
+            updated_at: now,
+        };
+        self.vaults.push(vault.clone());
+        Some(vault)
+    }
+
+    pub(super) fn vault(
+        &mut self,
+        organization_id: &str,
+        vault_id: &str,
+    ) -> Result<&mut VaultInfo, Rejection> {
+        self.organization(organization_id)?;
+        self.vaults
+            .iter_mut()
+            .find(|vault| vault.organization_id == organization_id && vault.id == vault_id)
+            .ok_or_else(|| Rejection::not_found(format!("vault '{}' not found", vault_id)))
+    }
+
+    /// Deletes a vault together with its schemas.
+    pub(super) fn delete_vault(
+        &mut self,
+        organization_id: &str,
+        vault_id: &str,
+    ) -> Result<(), Rejection> {
+        self.vault(organization_id, vault_id)?;
+        self.vaults.retain(|vault| vault.id != vault_id);
+        self.schemas.retain(|schema| schema.vault_id != vault_id);
+        Ok(())
+    }
+
+    pub(super) fn schemas(&self, vault_id: &str) -> Vec<SchemaInfo> {
+        self.schemas.iter().filter(|schema| schema.vault_id == vault_id).cloned().collect()
+    }
+
+    /// Stores a new, inactive schema version.
+    pub(super) fn push_schema(&mut self, vault_id: &str, content: String) -> SchemaInfo {
+        let version = self.schemas.iter().filter(|schema| schema.vault_id == vault_id).count() + 1;
+        let schema = SchemaInfo {
+            id: self.next_id("sch"),
+            vault_id: vault_id.to_string(),
+            version: version.to_string(),
+            content,
+            status: SchemaStatus::Inactive,
+            created_at: chrono::Utc::now(),
+            activated_at: None,
+        };
+        self.schemas.push(schema.clone());
+        schema
+    }
+
+    pub(super) fn schema(
+        &mut self,
+        vault_id: &str,
+        version: &str,
+    ) -> Result<&mut SchemaInfo, Rejection> {
+        self.schemas
+            .iter_mut()
+            .find(|schema| schema.vault_id == vault_id && schema.version == version)
+            .ok_or_else(|| Rejection::not_found(format!("schema version '{}' not found", version)))
+    }
+
+    pub(super) fn active_schema(&self, vault_id: &str) -> Result<SchemaInfo, Rejection> {
+        self.schemas
+            .iter()
+            .find(|schema| schema.vault_id == vault_id && schema.status.is_active())
+            .cloned()
+            .ok_or_else(|| Rejection::not_found("no active schema"))
+    }
+
+    /// Activates a schema version, deactivating the previously active one.
+    pub(super) fn activate_schema(
+        &mut self,
+        vault_id: &str,
+        version: &str,
+    ) -> Result<SchemaInfo, Rejection> {
+        self.schema(vault_id, version)?;
+        for schema in self.schemas.iter_mut().filter(|schema| schema.vault_id == vault_id) {
+            if schema.version == version {
+                schema.status = SchemaStatus::Active;
+                schema.activated_at = Some(chrono::Utc::now());
+            } else if schema.status.is_active() {
+                schema.status = SchemaStatus::Inactive;
+            }
+        }
+        self.schema(vault_id, version).map(|schema| schema.clone())
+    }
+
+    pub(super) fn delete_schema(&mut self, vault_id: &str, version: &str) -> Result<(), Rejection> {
+        if self.schema(vault_id, version)?.status.is_active() {
+            return Err(Rejection::conflict("the active schema cannot be deleted"));
+        }
+        self.schemas.retain(|schema| !(schema.vault_id == vault_id && schema.version == version));
+        Ok(())
+    }
+}
+
+/// Validates schema content. The fake only rejects empty schemas.
+pub(super) fn validate_schema(content: &str) -> ValidationResult {
+    let errors = if content.trim().is_empty() {
+        vec![ValidationIssue {
+            line: 1,
+            column: 1,
+            message: "schema is empty".to_string(),
+            code: "empty_schema".to_string(),
+        }]
+    } else {
+        Vec::new()
+    };
+    ValidationResult { is_valid: errors.is_empty(), errors, warnings: Vec::new() }
+}
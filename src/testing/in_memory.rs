@@ -7,13 +7,16 @@ use std::{
     collections::HashSet,
     future::Future,
     pin::Pin,
-    sync::{Arc, RwLock},
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
 };
 
 use crate::{
     Error,
-    testing::AuthorizationClient,
-    types::{Context, Relationship},
+    testing::{AuthorizationClient, authorization_client::require_delete_filter},
+    types::{ConsistencyToken, Context, Relationship},
 };
 
 /// An in-memory authorization client with real graph semantics.
@@ -48,6 +51,7 @@ use crate::{
 #[derive(Clone)]
 pub struct InMemoryClient {
     relationships: Arc<RwLock<HashSet<StoredRelationship>>>,
+    revision: Arc<AtomicU64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -70,7 +74,10 @@ impl From<&Relationship<'_>> for StoredRelationship {
 impl InMemoryClient {
     /// Creates a new in-memory client.
     pub fn new() -> Self {
-        Self { relationships: Arc::new(RwLock::new(HashSet::new())) }
+        Self {
+            relationships: Arc::new(RwLock::new(HashSet::new())),
+            revision: Arc::new(AtomicU64::new(0)),
+        }
     }
 
     /// Writes a relationship to the in-memory store.
@@ -125,7 +132,12 @@ impl InMemoryClient {
     /// Note: This only checks for exact relationship matches.
     /// Full permission computation with graph traversal will be
     /// implemented in Phase 7.
-    fn has_direct_relationship(&self, resource: &str, relation: &str, subject: &str) -> bool {
+    pub(super) fn has_direct_relationship(
+        &self,
+        resource: &str,
+        relation: &str,
+        subject: &str,
+    ) -> bool {
         let store = self.relationships.read().unwrap();
         store.contains(&StoredRelationship {
             resource: resource.to_string(),
@@ -133,6 +145,59 @@ impl InMemoryClient {
             subject: subject.to_string(),
         })
     }
+
+    /// Returns a consistency token for a new revision of the store.
+    fn token(&self) -> ConsistencyToken {
+        let revision = self.revision.fetch_add(1, Ordering::Relaxed) + 1;
+        ConsistencyToken::new(revision.to_string())
+    }
+
+    /// Returns the stored relationships matching the filters.
+    fn matching(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Vec<StoredRelationship> {
+        let store = self.relationships.read().unwrap();
+        store
+            .iter()
+            .filter(|rel| {
+                resource.is_none_or(|r| rel.resource == r)
+                    && relation.is_none_or(|r| rel.relation == r)
+                    && subject.is_none_or(|s| rel.subject == s)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the stored relationships matching the filters, sorted.
+    #[cfg(feature = "fake-server")]
+    pub(super) fn relationships_matching(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Vec<Relationship<'static>> {
+        let mut relationships: Vec<_> = self
+            .matching(resource, relation, subject)
+            .into_iter()
+            .map(|rel| Relationship::new(rel.resource, rel.relation, rel.subject))
+            .collect();
+        relationships.sort_by(|a, b| {
+            (a.resource(), a.relation(), a.subject()).cmp(&(
+                b.resource(),
+                b.relation(),
+                b.subject(),
+            ))
+        });
+        relationships
+    }
+}
+
+/// Returns `true` if `id` is of `object_type`, or if no type is given.
+pub(super) fn has_type(id: &str, object_type: Option<&str>) -> bool {
+    object_type.is_none_or(|t| id.split_once(':').is_some_and(|(prefix, _)| prefix == t))
 }
 
 impl Default for InMemoryClient {
@@ -164,6 +229,110 @@ impl AuthorizationClient for InMemoryClient {
         // Context-based conditions will be implemented in Phase 7
         self.check(subject, permission, resource)
     }
+
+    fn check_batch(
+        &self,
+        checks: &[(&str, &str, &str)],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<bool>, Error>> + Send + '_>> {
+        let results = checks
+            .iter()
+            .map(|&(subject, permission, resource)| {
+                self.has_direct_relationship(resource, permission, subject)
+            })
+            .collect();
+        Box::pin(async move { Ok(results) })
+    }
+
+    fn write(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+        InMemoryClient::write(self, relationship);
+        let token = self.token();
+        Box::pin(async move { Ok(token) })
+    }
+
+    fn write_batch(
+        &self,
+        relationships: Vec<Relationship<'_>>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+        self.write_all(relationships);
+        let token = self.token();
+        Box::pin(async move { Ok(token) })
+    }
+
+    fn delete(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send + '_>> {
+        InMemoryClient::delete(self, &relationship);
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn delete_where(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, Error>> + Send + '_>> {
+        let result = require_delete_filter(resource, relation, subject).map(|()| {
+            let matching = self.matching(resource, relation, subject);
+            let mut store = self.relationships.write().unwrap();
+            matching.iter().filter(|rel| store.remove(rel)).count() as u64
+        });
+        Box::pin(async move { result })
+    }
+
+    fn list_relationships(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Relationship<'static>>, Error>> + Send + '_>> {
+        let relationships = self
+            .matching(resource, relation, subject)
+            .into_iter()
+            .map(|rel| Relationship::new(rel.resource, rel.relation, rel.subject))
+            .collect();
+        Box::pin(async move { Ok(relationships) })
+    }
+
+    fn accessible_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+        // Direct relationships only, like `check`
+        let mut resources: Vec<String> = self
+            .matching(None, Some(permission), Some(subject))
+            .into_iter()
+            .map(|rel| rel.resource)
+            .filter(|resource| has_type(resource, resource_type))
+            .collect();
+        resources.sort();
+        Box::pin(async move { Ok(resources) })
+    }
+
+    fn subjects_with_permission(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+        let mut subjects: Vec<String> = self
+            .matching(Some(resource), Some(permission), None)
+            .into_iter()
+            .map(|rel| rel.subject)
+            .filter(|subject| has_type(subject, subject_type))
+            .collect();
+        subjects.sort();
+        Box::pin(async move { Ok(subjects) })
+    }
+
+    fn evaluates_schema(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
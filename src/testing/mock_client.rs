@@ -1,17 +1,22 @@
 //! MockClient for testing with expectations.
 
 use std::{
+    fmt,
     future::Future,
     pin::Pin,
     sync::{Arc, Mutex},
 };
 
-use crate::{Error, testing::AuthorizationClient, types::Context};
+use crate::{
+    Error, ErrorKind,
+    testing::{AuthorizationClient, authorization_client::require_delete_filter},
+    types::{ConsistencyToken, Context, ContextValue, Relationship},
+};
 
 /// A mock authorization client for testing.
 ///
-/// `MockClient` allows you to set up expectations for authorization checks
-/// and verify that they were called as expected.
+/// `MockClient` allows you to set up expectations for checks, writes,
+/// deletes and lookups, and verify that they were called as expected.
 ///
 /// ## Example
 ///
@@ -25,38 +30,63 @@ use crate::{Error, testing::AuthorizationClient, types::Context};
 /// // Use the mock in your tests...
 /// // mock.verify() at the end to ensure all expectations were met
 /// ```
+///
+/// ## Matchers and Call Counts
+///
+/// [`Expectation`]s match arguments with [`Matcher`]s and can constrain how
+/// often they are called:
+///
+/// ```rust
+/// use inferadb::testing::{Expectation, Matcher, MockClient};
+///
+/// let mock = MockClient::new()
+///     .expect(
+///         Expectation::check(Matcher::prefix("user:"), "view", Matcher::any())
+///             .returns(true)
+///             .times(2),
+///     )
+///     .expect(Expectation::write("doc:1", "viewer", Matcher::any()).returns_token("rev_1"))
+///     .expect(
+///         Expectation::resources("user:alice", "view")
+///             .returns_items(["doc:1", "doc:2"]),
+///     )
+///     .expect(Expectation::delete(Matcher::any(), Matcher::any(), Matcher::any()).never());
+/// ```
+///
+/// Calls that match no expectation fall back to the default decision
+/// ([`allow_all`](Self::allow_all) or [`deny_all`](Self::deny_all)), succeed
+/// for writes and deletes, and return no items for lookups.
 #[derive(Clone)]
 pub struct MockClient {
-    expectations: Arc<Mutex<Vec<Expectation>>>,
-    calls: Arc<Mutex<Vec<Call>>>,
+    state: Arc<Mutex<MockState>>,
     default_allow: bool,
 }
 
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-struct Expectation {
-    subject: String,
-    permission: String,
-    resource: String,
-    result: bool,
-    times: Option<usize>,
+#[derive(Default)]
+struct MockState {
+    expectations: Vec<Registered>,
+    calls: Vec<RecordedCall>,
+    violations: Vec<String>,
+    next_sequence: usize,
 }
 
-#[derive(Debug, Clone)]
-struct Call {
-    subject: String,
-    permission: String,
-    resource: String,
+/// An expectation along with its bookkeeping.
+struct Registered {
+    expectation: Expectation,
+    calls: usize,
+    /// Sequence id and step, for expectations registered in order.
+    sequence: Option<(usize, usize)>,
+}
+
+struct RecordedCall {
+    call: Call,
+    matched: Option<usize>,
 }
 
 impl MockClient {
     /// Creates a new mock client.
     pub fn new() -> Self {
-        Self {
-            expectations: Arc::new(Mutex::new(Vec::new())),
-            calls: Arc::new(Mutex::new(Vec::new())),
-            default_allow: false,
-        }
+        Self { state: Arc::new(Mutex::new(MockState::default())), default_allow: false }
     }
 
     /// Creates a mock client that allows all by default.
@@ -70,6 +100,9 @@ impl MockClient {
     }
 
     /// Adds an expectation for a check call.
+    ///
+    /// Shorthand for
+    /// `expect(Expectation::check(subject, permission, resource).returns(result))`.
     #[must_use]
     pub fn expect_check(
         self,
@@ -78,17 +111,58 @@ impl MockClient {
         resource: impl Into<String>,
         result: bool,
     ) -> Self {
-        let expectation = Expectation {
-            subject: subject.into(),
-            permission: permission.into(),
-            resource: resource.into(),
-            result,
-            times: None,
+        self.expect(
+            Expectation::check(subject.into(), permission.into(), resource.into()).returns(result),
+        )
+    }
+
+    /// Adds an expectation.
+    ///
+    /// When several expectations match a call, the first one registered that
+    /// has not yet reached its maximum call count handles it.
+    #[must_use]
+    pub fn expect(self, expectation: Expectation) -> Self {
+        self.register(expectation, None);
+        self
+    }
+
+    /// Adds expectations that must be satisfied in the given order.
+    ///
+    /// A call matching a step before every earlier step has reached its
+    /// minimum call count is reported by [`verify`](Self::verify).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use inferadb::testing::{Expectation, MockClient};
+    ///
+    /// // The handler must check access before it writes
+    /// let mock = MockClient::new().expect_sequence([
+    ///     Expectation::check("user:alice", "share", "doc:1").returns(true),
+    ///     Expectation::write("doc:1", "viewer", "user:bob"),
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn expect_sequence(self, expectations: impl IntoIterator<Item = Expectation>) -> Self {
+        let sequence = {
+            let mut state = self.state.lock().unwrap();
+            state.next_sequence += 1;
+            state.next_sequence
         };
-        self.expectations.lock().unwrap().push(expectation);
+        for (step, expectation) in expectations.into_iter().enumerate() {
+            self.register(expectation, Some((sequence, step)));
+        }
         self
     }
 
+    fn register(&self, expectation: Expectation, sequence: Option<(usize, usize)>) {
+        self.state.lock().unwrap().expectations.push(Registered {
+            expectation,
+            calls: 0,
+            sequence,
+        });
+    }
+
     /// Verifies that all expectations were met.
     ///
     /// Call this at the end of your test to ensure all expected
@@ -96,60 +170,183 @@ impl MockClient {
     ///
     /// # Panics
     ///
-    /// Panics if any expectations were not met.
+    /// Panics with a listing of expected versus actual calls if any
+    /// expectation was called too few or too many times, or out of order.
     pub fn verify(&self) {
-        let expectations = self.expectations.lock().unwrap();
-        let calls = self.calls.lock().unwrap();
-
-        for expectation in expectations.iter() {
-            let matching_calls = calls
-                .iter()
-                .filter(|c| {
-                    c.subject == expectation.subject
-                        && c.permission == expectation.permission
-                        && c.resource == expectation.resource
-                })
-                .count();
+        if let Err(report) = self.try_verify() {
+            panic!("{report}");
+        }
+    }
 
-            if matching_calls == 0 {
-                panic!(
-                    "Expected check({}, {}, {}) was never called",
-                    expectation.subject, expectation.permission, expectation.resource
-                );
+    /// Verifies that all expectations were met, returning the report of
+    /// expected versus actual calls instead of panicking.
+    pub fn try_verify(&self) -> Result<(), String> {
+        let state = self.state.lock().unwrap();
+        let failed = state.expectations.iter().any(|r| !r.expectation.count.contains(r.calls));
+        if !failed && state.violations.is_empty() {
+            return Ok(());
+        }
+
+        let mut report = String::from("MockClient expectations were not met\n\nExpected:\n");
+        for registered in &state.expectations {
+            let count = registered.expectation.count;
+            let marker = if count.contains(registered.calls) { ' ' } else { '-' };
+            report.push_str(&format!(
+                "{marker} Expected {} {count}, called {}\n",
+                registered.expectation,
+                times(registered.calls)
+            ));
+        }
+
+        report.push_str("\nActual calls:\n");
+        if state.calls.is_empty() {
+            report.push_str("  (none)\n");
+        }
+        for (index, recorded) in state.calls.iter().enumerate() {
+            let marker = if recorded.matched.is_some() { ' ' } else { '+' };
+            let note = if recorded.matched.is_some() { "" } else { " (unexpected)" };
+            report.push_str(&format!("{marker} {}. {}{note}\n", index + 1, recorded.call));
+        }
+
+        if !state.violations.is_empty() {
+            report.push_str("\nViolations:\n");
+            for violation in &state.violations {
+                report.push_str(&format!("  {violation}\n"));
             }
         }
+        Err(report)
     }
 
-    /// Returns the number of check calls made.
+    /// Returns the number of calls made.
     pub fn call_count(&self) -> usize {
-        self.calls.lock().unwrap().len()
+        self.state.lock().unwrap().calls.len()
     }
 
     /// Clears all expectations and recorded calls.
     pub fn reset(&self) {
-        self.expectations.lock().unwrap().clear();
-        self.calls.lock().unwrap().clear();
-    }
-
-    fn find_result(&self, subject: &str, permission: &str, resource: &str) -> bool {
-        let expectations = self.expectations.lock().unwrap();
-        for expectation in expectations.iter() {
-            if expectation.subject == subject
-                && expectation.permission == permission
-                && expectation.resource == resource
-            {
-                return expectation.result;
+        let mut state = self.state.lock().unwrap();
+        state.expectations.clear();
+        state.calls.clear();
+        state.violations.clear();
+    }
+
+    /// Returns a client for relationship writes and deletes.
+    pub fn relationships(&self) -> MockRelationshipsClient {
+        MockRelationshipsClient { mock: self.clone() }
+    }
+
+    /// Returns a client for resource lookups.
+    pub fn resources(&self) -> MockResourcesClient {
+        MockResourcesClient { mock: self.clone() }
+    }
+
+    /// Returns a client for subject lookups.
+    pub fn subjects(&self) -> MockSubjectsClient {
+        MockSubjectsClient { mock: self.clone() }
+    }
+
+    /// Records a call and returns the response of the expectation handling it.
+    fn handle(&self, call: Call) -> Response {
+        let mut state = self.state.lock().unwrap();
+        let matching: Vec<usize> = state
+            .expectations
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.expectation.matches(&call))
+            .map(|(index, _)| index)
+            .collect();
+        let handler = matching
+            .iter()
+            .copied()
+            .find(|&index| {
+                !state.expectations[index]
+                    .expectation
+                    .count
+                    .is_saturated_by(state.expectations[index].calls)
+            })
+            .or_else(|| matching.first().copied());
+
+        if let Some(index) = handler {
+            let registered = &state.expectations[index];
+            let count = registered.expectation.count;
+            let calls = registered.calls + 1;
+            let mut violations = Vec::new();
+            if count.max.is_some_and(|max| calls > max) {
+                violations.push(format!(
+                    "{call} exceeded the expected {count} of {}",
+                    registered.expectation
+                ));
             }
+            if let Some((sequence, step)) = registered.sequence {
+                let unmet = state.expectations.iter().find(|other| {
+                    other.sequence.is_some_and(|(s, earlier)| s == sequence && earlier < step)
+                        && !other.expectation.count.is_met_by(other.calls)
+                });
+                if let Some(unmet) = unmet {
+                    violations.push(format!(
+                        "{call} happened before the earlier expected {}",
+                        unmet.expectation
+                    ));
+                }
+            }
+            state.violations.extend(violations);
+            state.expectations[index].calls = calls;
         }
-        self.default_allow
+
+        state.calls.push(RecordedCall { call, matched: handler });
+        handler.map_or(Response::Default, |index| {
+            state.expectations[index].expectation.response.clone()
+        })
     }
 
-    fn record_call(&self, subject: &str, permission: &str, resource: &str) {
-        self.calls.lock().unwrap().push(Call {
-            subject: subject.to_string(),
-            permission: permission.to_string(),
-            resource: resource.to_string(),
-        });
+    fn run_check(&self, call: Call) -> Result<bool, Error> {
+        match self.handle(call) {
+            Response::Allowed(allowed) => Ok(allowed),
+            Response::Error(kind, message) => Err(Error::new(kind, message)),
+            _ => Ok(self.default_allow),
+        }
+    }
+
+    fn run_write(&self, call: Call) -> Result<ConsistencyToken, Error> {
+        match self.handle(call) {
+            Response::Token(token) => Ok(token),
+            Response::Error(kind, message) => Err(Error::new(kind, message)),
+            _ => Ok(ConsistencyToken::new("mock_token")),
+        }
+    }
+
+    fn run_lookup(&self, call: Call, type_filter: Option<&str>) -> Result<Vec<String>, Error> {
+        match self.handle(call) {
+            Response::Items(items) => Ok(items
+                .into_iter()
+                .filter(|item| {
+                    type_filter.is_none_or(|t| item.split_once(':').is_some_and(|(p, _)| p == t))
+                })
+                .collect()),
+            Response::Error(kind, message) => Err(Error::new(kind, message)),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn run_delete_where(&self, filter: RelationshipFilter) -> Result<u64, Error> {
+        require_delete_filter(
+            filter.resource.as_deref(),
+            filter.relation.as_deref(),
+            filter.subject.as_deref(),
+        )?;
+        match self.handle(Call::DeleteWhere(filter)) {
+            Response::Count(count) => Ok(count),
+            Response::Error(kind, message) => Err(Error::new(kind, message)),
+            _ => Ok(0),
+        }
+    }
+
+    fn run_list(&self, filter: RelationshipFilter) -> Result<Vec<Relationship<'static>>, Error> {
+        match self.handle(Call::List(filter)) {
+            Response::Relationships(relationships) => Ok(relationships),
+            Response::Error(kind, message) => Err(Error::new(kind, message)),
+            _ => Ok(Vec::new()),
+        }
     }
 }
 
@@ -159,6 +356,17 @@ impl Default for MockClient {
     }
 }
 
+impl fmt::Debug for MockClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("MockClient")
+            .field("expectations", &state.expectations.len())
+            .field("calls", &state.calls.len())
+            .field("default_allow", &self.default_allow)
+            .finish()
+    }
+}
+
 impl AuthorizationClient for MockClient {
     fn check(
         &self,
@@ -166,9 +374,13 @@ impl AuthorizationClient for MockClient {
         permission: &str,
         resource: &str,
     ) -> Pin<Box<dyn Future<Output = Result<bool, Error>> + Send + '_>> {
-        self.record_call(subject, permission, resource);
-        let result = self.find_result(subject, permission, resource);
-        Box::pin(async move { Ok(result) })
+        let result = self.run_check(Call::Check {
+            subject: subject.to_string(),
+            permission: permission.to_string(),
+            resource: resource.to_string(),
+            context: None,
+        });
+        Box::pin(async move { result })
     }
 
     fn check_with_context(
@@ -176,10 +388,878 @@ impl AuthorizationClient for MockClient {
         subject: &str,
         permission: &str,
         resource: &str,
-        _context: &Context,
+        context: &Context,
     ) -> Pin<Box<dyn Future<Output = Result<bool, Error>> + Send + '_>> {
-        // For mock, we ignore context and use the same expectations
-        self.check(subject, permission, resource)
+        let result = self.run_check(Call::Check {
+            subject: subject.to_string(),
+            permission: permission.to_string(),
+            resource: resource.to_string(),
+            context: Some(context.clone()),
+        });
+        Box::pin(async move { result })
+    }
+
+    fn check_batch(
+        &self,
+        checks: &[(&str, &str, &str)],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<bool>, Error>> + Send + '_>> {
+        // Each item is recorded as its own check call
+        let result = checks
+            .iter()
+            .map(|&(subject, permission, resource)| {
+                self.run_check(Call::Check {
+                    subject: subject.to_string(),
+                    permission: permission.to_string(),
+                    resource: resource.to_string(),
+                    context: None,
+                })
+            })
+            .collect();
+        Box::pin(async move { result })
+    }
+
+    fn write(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+        let result = self.run_write(Call::Write(relationship.into_owned()));
+        Box::pin(async move { result })
+    }
+
+    fn write_batch(
+        &self,
+        relationships: Vec<Relationship<'_>>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+        let relationships: Vec<_> =
+            relationships.into_iter().map(Relationship::into_owned).collect();
+        Box::pin(async move { self.relationships().write_batch(relationships).await })
+    }
+
+    fn delete(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send + '_>> {
+        let result = self.run_write(Call::Delete(relationship.into_owned())).map(|_| ());
+        Box::pin(async move { result })
+    }
+
+    fn delete_where(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, Error>> + Send + '_>> {
+        let result = self.run_delete_where(RelationshipFilter::new(resource, relation, subject));
+        Box::pin(async move { result })
+    }
+
+    fn list_relationships(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Relationship<'static>>, Error>> + Send + '_>> {
+        let result = self.run_list(RelationshipFilter::new(resource, relation, subject));
+        Box::pin(async move { result })
+    }
+
+    fn accessible_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+        let call =
+            Call::Resources { subject: subject.to_string(), permission: permission.to_string() };
+        let result = self.run_lookup(call, resource_type);
+        Box::pin(async move { result })
+    }
+
+    fn subjects_with_permission(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+        let call =
+            Call::Subjects { permission: permission.to_string(), resource: resource.to_string() };
+        let result = self.run_lookup(call, subject_type);
+        Box::pin(async move { result })
+    }
+}
+
+/// Relationship writes and deletes against a [`MockClient`].
+///
+/// Obtained via [`MockClient::relationships()`].
+#[derive(Debug, Clone)]
+pub struct MockRelationshipsClient {
+    mock: MockClient,
+}
+
+impl MockRelationshipsClient {
+    /// Writes a relationship.
+    ///
+    /// Returns the token of the matching [`Expectation::write`], or a
+    /// placeholder token if none matches.
+    pub async fn write(&self, relationship: Relationship<'_>) -> Result<ConsistencyToken, Error> {
+        self.mock.run_write(Call::Write(relationship.into_owned()))
+    }
+
+    /// Writes multiple relationships.
+    ///
+    /// Each relationship is recorded as its own write call. Returns the
+    /// first error, or the token of the last write.
+    pub async fn write_batch<'a>(
+        &self,
+        relationships: impl IntoIterator<Item = Relationship<'a>>,
+    ) -> Result<ConsistencyToken, Error> {
+        let mut token = ConsistencyToken::new("mock_token");
+        for relationship in relationships {
+            token = self.mock.run_write(Call::Write(relationship.into_owned()))?;
+        }
+        Ok(token)
+    }
+
+    /// Deletes a relationship.
+    pub async fn delete(&self, relationship: Relationship<'_>) -> Result<(), Error> {
+        self.mock.run_write(Call::Delete(relationship.into_owned())).map(|_| ())
+    }
+
+    /// Deletes every relationship matching the filters.
+    ///
+    /// Returns the count of the matching [`Expectation::delete_where`], or
+    /// zero if none matches. At least one filter must be given.
+    pub async fn delete_where(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Result<u64, Error> {
+        self.mock.run_delete_where(RelationshipFilter::new(resource, relation, subject))
+    }
+
+    /// Lists relationships matching the given filters.
+    ///
+    /// Returns the relationships of the matching
+    /// [`Expectation::list_relationships`], or none if none matches.
+    pub async fn list(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Result<Vec<Relationship<'static>>, Error> {
+        self.mock.run_list(RelationshipFilter::new(resource, relation, subject))
+    }
+}
+
+/// Resource lookups against a [`MockClient`].
+///
+/// Obtained via [`MockClient::resources()`].
+#[derive(Debug, Clone)]
+pub struct MockResourcesClient {
+    mock: MockClient,
+}
+
+impl MockResourcesClient {
+    /// Returns resources accessible by a subject with a permission.
+    ///
+    /// Returns the items of the matching [`Expectation::resources`],
+    /// narrowed to `resource_type` if given.
+    pub async fn accessible_by(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+    ) -> Result<Vec<String>, Error> {
+        let call =
+            Call::Resources { subject: subject.to_string(), permission: permission.to_string() };
+        self.mock.run_lookup(call, resource_type)
+    }
+}
+
+/// Subject lookups against a [`MockClient`].
+///
+/// Obtained via [`MockClient::subjects()`].
+#[derive(Debug, Clone)]
+pub struct MockSubjectsClient {
+    mock: MockClient,
+}
+
+impl MockSubjectsClient {
+    /// Returns subjects with a permission on a resource.
+    ///
+    /// Returns the items of the matching [`Expectation::subjects`],
+    /// narrowed to `subject_type` if given.
+    pub async fn with_permission(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+    ) -> Result<Vec<String>, Error> {
+        let call =
+            Call::Subjects { permission: permission.to_string(), resource: resource.to_string() };
+        self.mock.run_lookup(call, subject_type)
+    }
+}
+
+// ============================================================================
+// Expectations
+// ============================================================================
+
+/// An expected call to a [`MockClient`].
+///
+/// Build one with [`check`](Self::check), [`write`](Self::write),
+/// [`delete`](Self::delete), [`delete_where`](Self::delete_where),
+/// [`list_relationships`](Self::list_relationships),
+/// [`resources`](Self::resources) or [`subjects`](Self::subjects), then
+/// configure its response and how often
+/// it may be called. Expectations must be called at least once unless
+/// [`times`](Self::times), [`at_least`](Self::at_least) or
+/// [`never`](Self::never) say otherwise.
+#[derive(Clone)]
+pub struct Expectation {
+    operation: ExpectedOperation,
+    response: Response,
+    count: CallCount,
+}
+
+#[derive(Clone)]
+enum ExpectedOperation {
+    Check { subject: Matcher, permission: Matcher, resource: Matcher, context: ContextMatcher },
+    Write { resource: Matcher, relation: Matcher, subject: Matcher },
+    Delete { resource: Matcher, relation: Matcher, subject: Matcher },
+    Resources { subject: Matcher, permission: Matcher },
+    Subjects { permission: Matcher, resource: Matcher },
+    DeleteWhere { resource: Matcher, relation: Matcher, subject: Matcher },
+    List { resource: Matcher, relation: Matcher, subject: Matcher },
+}
+
+#[derive(Debug, Clone)]
+enum Response {
+    Default,
+    Allowed(bool),
+    Token(ConsistencyToken),
+    Items(Vec<String>),
+    Relationships(Vec<Relationship<'static>>),
+    Count(u64),
+    Error(ErrorKind, String),
+}
+
+impl Expectation {
+    fn new(operation: ExpectedOperation) -> Self {
+        Self { operation, response: Response::Default, count: CallCount::at_least(1) }
+    }
+
+    /// Expects a permission check.
+    ///
+    /// Matches checks with any context; narrow it with
+    /// [`with_context`](Self::with_context).
+    pub fn check(
+        subject: impl Into<Matcher>,
+        permission: impl Into<Matcher>,
+        resource: impl Into<Matcher>,
+    ) -> Self {
+        Self::new(ExpectedOperation::Check {
+            subject: subject.into(),
+            permission: permission.into(),
+            resource: resource.into(),
+            context: ContextMatcher::any(),
+        })
+    }
+
+    /// Expects a relationship write.
+    pub fn write(
+        resource: impl Into<Matcher>,
+        relation: impl Into<Matcher>,
+        subject: impl Into<Matcher>,
+    ) -> Self {
+        Self::new(ExpectedOperation::Write {
+            resource: resource.into(),
+            relation: relation.into(),
+            subject: subject.into(),
+        })
+    }
+
+    /// Expects a relationship delete.
+    pub fn delete(
+        resource: impl Into<Matcher>,
+        relation: impl Into<Matcher>,
+        subject: impl Into<Matcher>,
+    ) -> Self {
+        Self::new(ExpectedOperation::Delete {
+            resource: resource.into(),
+            relation: relation.into(),
+            subject: subject.into(),
+        })
+    }
+
+    /// Expects a `resources().accessible_by(subject, permission, ..)` lookup.
+    pub fn resources(subject: impl Into<Matcher>, permission: impl Into<Matcher>) -> Self {
+        Self::new(ExpectedOperation::Resources {
+            subject: subject.into(),
+            permission: permission.into(),
+        })
+    }
+
+    /// Expects a `subjects().with_permission(permission, resource, ..)` lookup.
+    pub fn subjects(permission: impl Into<Matcher>, resource: impl Into<Matcher>) -> Self {
+        Self::new(ExpectedOperation::Subjects {
+            permission: permission.into(),
+            resource: resource.into(),
+        })
+    }
+
+    /// Expects a `delete_where` with the given filters.
+    ///
+    /// A filter that was not set is matched as the empty string, so
+    /// [`Matcher::any()`] matches it either way.
+    pub fn delete_where(
+        resource: impl Into<Matcher>,
+        relation: impl Into<Matcher>,
+        subject: impl Into<Matcher>,
+    ) -> Self {
+        Self::new(ExpectedOperation::DeleteWhere {
+            resource: resource.into(),
+            relation: relation.into(),
+            subject: subject.into(),
+        })
+    }
+
+    /// Expects a relationship listing with the given filters.
+    ///
+    /// A filter that was not set is matched as the empty string, so
+    /// [`Matcher::any()`] matches it either way.
+    pub fn list_relationships(
+        resource: impl Into<Matcher>,
+        relation: impl Into<Matcher>,
+        subject: impl Into<Matcher>,
+    ) -> Self {
+        Self::new(ExpectedOperation::List {
+            resource: resource.into(),
+            relation: relation.into(),
+            subject: subject.into(),
+        })
+    }
+
+    /// Only matches checks whose context satisfies `context`.
+    #[must_use]
+    pub fn with_context(mut self, context: ContextMatcher) -> Self {
+        if let ExpectedOperation::Check { context: expected, .. } = &mut self.operation {
+            *expected = context;
+        }
+        self
+    }
+
+    /// Sets the decision a check returns.
+    #[must_use]
+    pub fn returns(mut self, allowed: bool) -> Self {
+        self.response = Response::Allowed(allowed);
+        self
+    }
+
+    /// Sets the consistency token a write or delete returns.
+    #[must_use]
+    pub fn returns_token(mut self, token: impl Into<ConsistencyToken>) -> Self {
+        self.response = Response::Token(token.into());
+        self
+    }
+
+    /// Sets the items a resource or subject lookup returns.
+    #[must_use]
+    pub fn returns_items<I, S>(mut self, items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.response = Response::Items(items.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the relationships a relationship listing returns.
+    #[must_use]
+    pub fn returns_relationships<'a>(
+        mut self,
+        relationships: impl IntoIterator<Item = Relationship<'a>>,
+    ) -> Self {
+        self.response = Response::Relationships(
+            relationships.into_iter().map(Relationship::into_owned).collect(),
+        );
+        self
+    }
+
+    /// Sets the number of relationships a `delete_where` reports deleted.
+    #[must_use]
+    pub fn returns_count(mut self, count: u64) -> Self {
+        self.response = Response::Count(count);
+        self
+    }
+
+    /// Makes the call fail with an error of the given kind.
+    #[must_use]
+    pub fn returns_error(mut self, kind: ErrorKind, message: impl Into<String>) -> Self {
+        self.response = Response::Error(kind, message.into());
+        self
+    }
+
+    /// Expects exactly `n` calls.
+    #[must_use]
+    pub fn times(mut self, n: usize) -> Self {
+        self.count = CallCount { min: n, max: Some(n) };
+        self
+    }
+
+    /// Expects at least `n` calls.
+    #[must_use]
+    pub fn at_least(mut self, n: usize) -> Self {
+        self.count = CallCount::at_least(n);
+        self
+    }
+
+    /// Expects no calls.
+    #[must_use]
+    pub fn never(self) -> Self {
+        self.times(0)
+    }
+
+    fn matches(&self, call: &Call) -> bool {
+        match (&self.operation, call) {
+            (
+                ExpectedOperation::Check { subject, permission, resource, context },
+                Call::Check { subject: s, permission: p, resource: r, context: c },
+            ) => {
+                subject.matches(s)
+                    && permission.matches(p)
+                    && resource.matches(r)
+                    && context.matches(c.as_ref())
+            },
+            (ExpectedOperation::Write { resource, relation, subject }, Call::Write(rel))
+            | (ExpectedOperation::Delete { resource, relation, subject }, Call::Delete(rel)) => {
+                resource.matches(rel.resource())
+                    && relation.matches(rel.relation())
+                    && subject.matches(rel.subject())
+            },
+            (
+                ExpectedOperation::Resources { subject, permission },
+                Call::Resources { subject: s, permission: p },
+            ) => subject.matches(s) && permission.matches(p),
+            (
+                ExpectedOperation::Subjects { permission, resource },
+                Call::Subjects { permission: p, resource: r },
+            ) => permission.matches(p) && resource.matches(r),
+            (
+                ExpectedOperation::DeleteWhere { resource, relation, subject },
+                Call::DeleteWhere(f),
+            )
+            | (ExpectedOperation::List { resource, relation, subject }, Call::List(f)) => {
+                let value = |filter: &Option<String>| filter.clone().unwrap_or_default();
+                resource.matches(&value(&f.resource))
+                    && relation.matches(&value(&f.relation))
+                    && subject.matches(&value(&f.subject))
+            },
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.operation {
+            ExpectedOperation::Check { subject, permission, resource, context } => {
+                write!(f, "check({subject}, {permission}, {resource}")?;
+                if !context.is_any() {
+                    write!(f, ", context {context}")?;
+                }
+                f.write_str(")")
+            },
+            ExpectedOperation::Write { resource, relation, subject } => {
+                write!(f, "write({resource}, {relation}, {subject})")
+            },
+            ExpectedOperation::Delete { resource, relation, subject } => {
+                write!(f, "delete({resource}, {relation}, {subject})")
+            },
+            ExpectedOperation::Resources { subject, permission } => {
+                write!(f, "resources({subject}, {permission})")
+            },
+            ExpectedOperation::Subjects { permission, resource } => {
+                write!(f, "subjects({permission}, {resource})")
+            },
+            ExpectedOperation::DeleteWhere { resource, relation, subject } => {
+                write!(f, "delete_where({resource}, {relation}, {subject})")
+            },
+            ExpectedOperation::List { resource, relation, subject } => {
+                write!(f, "list_relationships({resource}, {relation}, {subject})")
+            },
+        }
+    }
+}
+
+impl fmt::Debug for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Expectation({self} {})", self.count)
+    }
+}
+
+/// How many times an expectation may be called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CallCount {
+    min: usize,
+    max: Option<usize>,
+}
+
+impl CallCount {
+    fn at_least(min: usize) -> Self {
+        Self { min, max: None }
+    }
+
+    fn contains(&self, calls: usize) -> bool {
+        self.is_met_by(calls) && self.max.is_none_or(|max| calls <= max)
+    }
+
+    fn is_met_by(&self, calls: usize) -> bool {
+        calls >= self.min
+    }
+
+    fn is_saturated_by(&self, calls: usize) -> bool {
+        self.max.is_some_and(|max| calls >= max)
+    }
+}
+
+impl fmt::Display for CallCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max {
+            Some(0) => f.write_str("never"),
+            Some(max) => write!(f, "exactly {}", times(max)),
+            None => write!(f, "at least {}", times(self.min)),
+        }
+    }
+}
+
+/// Formats a call count as `1 time` / `n times`.
+fn times(n: usize) -> String {
+    if n == 1 { "1 time".to_string() } else { format!("{n} times") }
+}
+
+/// A call recorded by a [`MockClient`].
+#[derive(Debug, Clone)]
+enum Call {
+    Check { subject: String, permission: String, resource: String, context: Option<Context> },
+    Write(Relationship<'static>),
+    Delete(Relationship<'static>),
+    Resources { subject: String, permission: String },
+    Subjects { permission: String, resource: String },
+    DeleteWhere(RelationshipFilter),
+    List(RelationshipFilter),
+}
+
+/// The filters of a `delete_where` or relationship listing call.
+#[derive(Debug, Clone)]
+struct RelationshipFilter {
+    resource: Option<String>,
+    relation: Option<String>,
+    subject: Option<String>,
+}
+
+impl RelationshipFilter {
+    fn new(resource: Option<&str>, relation: Option<&str>, subject: Option<&str>) -> Self {
+        Self {
+            resource: resource.map(str::to_string),
+            relation: relation.map(str::to_string),
+            subject: subject.map(str::to_string),
+        }
+    }
+}
+
+impl fmt::Display for RelationshipFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let filters = [&self.resource, &self.relation, &self.subject]
+            .map(|filter| filter.as_ref().map_or("_".to_string(), |value| format!("{value:?}")));
+        f.write_str(&filters.join(", "))
+    }
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Call::Check { subject, permission, resource, context } => {
+                write!(f, "check({subject:?}, {permission:?}, {resource:?}")?;
+                if let Some(context) = context {
+                    let mut entries: Vec<_> =
+                        context.iter().map(|(key, value)| format!("{key}={value}")).collect();
+                    entries.sort();
+                    write!(f, ", context {{{}}}", entries.join(", "))?;
+                }
+                f.write_str(")")
+            },
+            Call::Write(rel) => {
+                write!(f, "write({:?}, {:?}, {:?})", rel.resource(), rel.relation(), rel.subject())
+            },
+            Call::Delete(rel) => {
+                write!(f, "delete({:?}, {:?}, {:?})", rel.resource(), rel.relation(), rel.subject())
+            },
+            Call::Resources { subject, permission } => {
+                write!(f, "resources({subject:?}, {permission:?})")
+            },
+            Call::Subjects { permission, resource } => {
+                write!(f, "subjects({permission:?}, {resource:?})")
+            },
+            Call::DeleteWhere(filter) => write!(f, "delete_where({filter})"),
+            Call::List(filter) => write!(f, "list_relationships({filter})"),
+        }
+    }
+}
+
+// ============================================================================
+// Matchers
+// ============================================================================
+
+/// Matches a single string argument of an expected call.
+///
+/// Plain strings convert into exact matchers, so
+/// `Expectation::check("user:alice", "view", Matcher::any())` matches any
+/// resource.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::testing::Matcher;
+///
+/// assert!(Matcher::any().matches("anything"));
+/// assert!(Matcher::exact("doc:1").matches("doc:1"));
+/// assert!(Matcher::prefix("user:").matches("user:alice"));
+/// assert!(Matcher::predicate("even length", |s| s.len() % 2 == 0).matches("ab"));
+/// ```
+#[derive(Clone)]
+pub struct Matcher {
+    kind: MatcherKind,
+}
+
+#[derive(Clone)]
+enum MatcherKind {
+    Any,
+    Exact(String),
+    Prefix(String),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+    Predicate {
+        description: String,
+        predicate: Arc<dyn Fn(&str) -> bool + Send + Sync>,
+    },
+}
+
+impl Matcher {
+    /// Matches any value.
+    pub fn any() -> Self {
+        Self { kind: MatcherKind::Any }
+    }
+
+    /// Matches exactly `value`.
+    pub fn exact(value: impl Into<String>) -> Self {
+        Self { kind: MatcherKind::Exact(value.into()) }
+    }
+
+    /// Matches values starting with `prefix`.
+    pub fn prefix(prefix: impl Into<String>) -> Self {
+        Self { kind: MatcherKind::Prefix(prefix.into()) }
+    }
+
+    /// Matches values the regular expression finds a match in.
+    ///
+    /// Anchor the pattern with `^` and `$` to match whole values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if `pattern` is not a valid regular
+    /// expression.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inferadb::testing::Matcher;
+    ///
+    /// let matcher = Matcher::try_regex(r"^doc:\d+$")?;
+    /// assert!(matcher.matches("doc:42"));
+    /// assert!(Matcher::try_regex("doc:(").is_err());
+    /// # Ok::<(), inferadb::Error>(())
+    /// ```
+    #[cfg(feature = "regex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn try_regex(pattern: &str) -> Result<Self, Error> {
+        let regex = regex::Regex::new(pattern).map_err(|e| {
+            Error::invalid_argument(format!("invalid regex pattern {pattern:?}: {e}"))
+        })?;
+        Ok(Self { kind: MatcherKind::Regex(regex) })
+    }
+
+    /// Like [`try_regex`](Self::try_regex), for patterns written inline in
+    /// tests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is not a valid regular expression.
+    #[cfg(feature = "regex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn regex(pattern: &str) -> Self {
+        Self::try_regex(pattern).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Matches values for which `predicate` returns `true`.
+    ///
+    /// The description is shown in [`MockClient::verify`] failures.
+    pub fn predicate(
+        description: impl Into<String>,
+        predicate: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            kind: MatcherKind::Predicate {
+                description: description.into(),
+                predicate: Arc::new(predicate),
+            },
+        }
+    }
+
+    /// Returns `true` if `value` matches.
+    pub fn matches(&self, value: &str) -> bool {
+        match &self.kind {
+            MatcherKind::Any => true,
+            MatcherKind::Exact(expected) => value == expected,
+            MatcherKind::Prefix(prefix) => value.starts_with(prefix.as_str()),
+            #[cfg(feature = "regex")]
+            MatcherKind::Regex(regex) => regex.is_match(value),
+            MatcherKind::Predicate { predicate, .. } => predicate(value),
+        }
+    }
+}
+
+impl From<&str> for Matcher {
+    fn from(value: &str) -> Self {
+        Matcher::exact(value)
+    }
+}
+
+impl From<String> for Matcher {
+    fn from(value: String) -> Self {
+        Matcher::exact(value)
+    }
+}
+
+impl From<&String> for Matcher {
+    fn from(value: &String) -> Self {
+        Matcher::exact(value.as_str())
+    }
+}
+
+impl fmt::Display for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            MatcherKind::Any => f.write_str("any"),
+            MatcherKind::Exact(value) => write!(f, "{value:?}"),
+            MatcherKind::Prefix(prefix) => write!(f, "{prefix:?}*"),
+            #[cfg(feature = "regex")]
+            MatcherKind::Regex(regex) => write!(f, "/{}/", regex.as_str()),
+            MatcherKind::Predicate { description, .. } => write!(f, "<{description}>"),
+        }
+    }
+}
+
+impl fmt::Debug for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Matcher({self})")
+    }
+}
+
+/// Matches the ABAC context of an expected check.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::testing::{ContextMatcher, Expectation};
+///
+/// let expectation = Expectation::check("user:alice", "view", "doc:1")
+///     .with_context(ContextMatcher::contains("env", "prod"))
+///     .returns(true);
+/// ```
+#[derive(Clone)]
+pub struct ContextMatcher {
+    kind: ContextMatcherKind,
+}
+
+#[derive(Clone)]
+enum ContextMatcherKind {
+    Any,
+    Absent,
+    Contains(String, ContextValue),
+    HasKey(String),
+    Predicate { description: String, predicate: Arc<dyn Fn(&Context) -> bool + Send + Sync> },
+}
+
+impl ContextMatcher {
+    /// Matches checks with or without context.
+    pub fn any() -> Self {
+        Self { kind: ContextMatcherKind::Any }
+    }
+
+    /// Matches checks made without context.
+    pub fn absent() -> Self {
+        Self { kind: ContextMatcherKind::Absent }
+    }
+
+    /// Matches contexts where `key` equals `value`.
+    pub fn contains(key: impl Into<String>, value: impl Into<ContextValue>) -> Self {
+        Self { kind: ContextMatcherKind::Contains(key.into(), value.into()) }
+    }
+
+    /// Matches contexts that have `key`, whatever its value.
+    pub fn has_key(key: impl Into<String>) -> Self {
+        Self { kind: ContextMatcherKind::HasKey(key.into()) }
+    }
+
+    /// Matches contexts for which `predicate` returns `true`.
+    pub fn predicate(
+        description: impl Into<String>,
+        predicate: impl Fn(&Context) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            kind: ContextMatcherKind::Predicate {
+                description: description.into(),
+                predicate: Arc::new(predicate),
+            },
+        }
+    }
+
+    /// Returns `true` if `context` matches.
+    pub fn matches(&self, context: Option<&Context>) -> bool {
+        match (&self.kind, context) {
+            (ContextMatcherKind::Any, _) => true,
+            (ContextMatcherKind::Absent, context) => context.is_none_or(Context::is_empty),
+            (_, None) => false,
+            (ContextMatcherKind::Contains(key, value), Some(context)) => {
+                context.get(key) == Some(value)
+            },
+            (ContextMatcherKind::HasKey(key), Some(context)) => context.contains_key(key),
+            (ContextMatcherKind::Predicate { predicate, .. }, Some(context)) => predicate(context),
+        }
+    }
+
+    fn is_any(&self) -> bool {
+        matches!(self.kind, ContextMatcherKind::Any)
+    }
+}
+
+impl fmt::Display for ContextMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ContextMatcherKind::Any => f.write_str("any"),
+            ContextMatcherKind::Absent => f.write_str("absent"),
+            ContextMatcherKind::Contains(key, value) => write!(f, "{{{key}={value}}}"),
+            ContextMatcherKind::HasKey(key) => write!(f, "{{{key}=any}}"),
+            ContextMatcherKind::Predicate { description, .. } => write!(f, "<{description}>"),
+        }
+    }
+}
+
+impl fmt::Debug for ContextMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ContextMatcher({self})")
     }
 }
 
@@ -259,4 +1339,170 @@ mod tests {
             mock.check_with_context("user:alice", "view", "doc:1", &context).await.unwrap();
         assert!(result);
     }
+
+    #[test]
+    fn test_matchers() {
+        assert!(Matcher::any().matches(""));
+        assert!(Matcher::from("doc:1").matches("doc:1"));
+        assert!(!Matcher::from("doc:1").matches("doc:10"));
+        assert!(Matcher::prefix("user:").matches("user:alice"));
+        assert!(!Matcher::prefix("user:").matches("team:eng"));
+        assert!(Matcher::predicate("short", |s| s.len() < 4).matches("abc"));
+
+        #[cfg(feature = "regex")]
+        {
+            assert!(Matcher::regex(r"^doc:\d+$").matches("doc:42"));
+            assert!(!Matcher::regex(r"^doc:\d+$").matches("doc:x"));
+            let err = Matcher::try_regex("doc:(").unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+        }
+
+        let prod = Context::new().with("env", "prod");
+        assert!(ContextMatcher::any().matches(None));
+        assert!(ContextMatcher::absent().matches(None));
+        assert!(!ContextMatcher::absent().matches(Some(&prod)));
+        assert!(ContextMatcher::contains("env", "prod").matches(Some(&prod)));
+        assert!(!ContextMatcher::contains("env", "dev").matches(Some(&prod)));
+        assert!(ContextMatcher::has_key("env").matches(Some(&prod)));
+        assert!(!ContextMatcher::has_key("env").matches(None));
+        assert!(ContextMatcher::predicate("one key", |c| c.len() == 1).matches(Some(&prod)));
+    }
+
+    #[tokio::test]
+    async fn test_matcher_expectations_and_errors() {
+        let mock = MockClient::new()
+            .expect(
+                Expectation::check(Matcher::prefix("user:"), "view", Matcher::any())
+                    .with_context(ContextMatcher::contains("env", "prod"))
+                    .returns(true),
+            )
+            .expect(
+                Expectation::check(Matcher::any(), "admin", Matcher::any())
+                    .returns_error(ErrorKind::Unavailable, "down"),
+            );
+
+        let prod = Context::new().with("env", "prod");
+        assert!(mock.check_with_context("user:a", "view", "doc:9", &prod).await.unwrap());
+        // Without the context the expectation doesn't match: default deny
+        assert!(!mock.check("user:a", "view", "doc:9").await.unwrap());
+
+        let err = mock.check("user:a", "admin", "org:1").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unavailable);
+        mock.verify();
+    }
+
+    #[tokio::test]
+    async fn test_call_counts() {
+        let mock = MockClient::allow_all()
+            .expect(Expectation::check("user:a", "view", "doc:1").returns(false).times(2))
+            .expect(Expectation::check("user:b", Matcher::any(), Matcher::any()).never())
+            .expect(Expectation::check("user:c", "view", "doc:1").at_least(2));
+
+        assert!(!mock.check("user:a", "view", "doc:1").await.unwrap());
+        assert!(!mock.check("user:a", "view", "doc:1").await.unwrap());
+        mock.check("user:c", "view", "doc:1").await.unwrap();
+        let report = mock.try_verify().unwrap_err();
+        assert!(
+            report.contains(
+                "- Expected check(\"user:c\", \"view\", \"doc:1\") at least 2 times, called 1 time"
+            ),
+            "{report}"
+        );
+
+        mock.check("user:c", "view", "doc:1").await.unwrap();
+        mock.verify();
+
+        // A third call exceeds `times(2)`, and any call violates `never()`
+        mock.check("user:a", "view", "doc:1").await.unwrap();
+        mock.check("user:b", "edit", "doc:2").await.unwrap();
+        let report = mock.try_verify().unwrap_err();
+        assert!(report.contains("exactly 2 times, called 3 times"), "{report}");
+        assert!(report.contains("never, called 1 time"), "{report}");
+    }
+
+    #[tokio::test]
+    async fn test_writes_deletes_and_lookups() {
+        let mock = MockClient::new()
+            .expect(
+                Expectation::write("doc:1", "viewer", Matcher::prefix("user:"))
+                    .returns_token("rev_7"),
+            )
+            .expect(
+                Expectation::delete("doc:1", "viewer", "user:bob")
+                    .returns_error(ErrorKind::NotFound, "missing"),
+            )
+            .expect(
+                Expectation::resources("user:alice", "view").returns_items(["doc:1", "folder:2"]),
+            )
+            .expect(
+                Expectation::subjects("view", "doc:1").returns_items(["user:alice", "team:eng"]),
+            );
+
+        let token = mock
+            .relationships()
+            .write(Relationship::new("doc:1", "viewer", "user:bob"))
+            .await
+            .unwrap();
+        assert_eq!(token.value(), "rev_7");
+        let err = mock
+            .relationships()
+            .delete(Relationship::new("doc:1", "viewer", "user:bob"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let resources = mock.resources().accessible_by("user:alice", "view", None).await.unwrap();
+        assert_eq!(resources, vec!["doc:1", "folder:2"]);
+        let docs = mock.resources().accessible_by("user:alice", "view", Some("doc")).await.unwrap();
+        assert_eq!(docs, vec!["doc:1"]);
+        let users = mock.subjects().with_permission("view", "doc:1", Some("user")).await.unwrap();
+        assert_eq!(users, vec!["user:alice"]);
+
+        // Unmatched lookups return nothing
+        assert!(mock.subjects().with_permission("edit", "doc:1", None).await.unwrap().is_empty());
+        mock.verify();
+    }
+
+    #[tokio::test]
+    async fn test_sequences() {
+        let sequence = || {
+            MockClient::allow_all().expect_sequence([
+                Expectation::check("user:alice", "share", "doc:1").returns(true),
+                Expectation::write("doc:1", "viewer", "user:bob"),
+            ])
+        };
+
+        let mock = sequence();
+        mock.check("user:alice", "share", "doc:1").await.unwrap();
+        mock.relationships().write(Relationship::new("doc:1", "viewer", "user:bob")).await.unwrap();
+        mock.verify();
+
+        let mock = sequence();
+        mock.relationships().write(Relationship::new("doc:1", "viewer", "user:bob")).await.unwrap();
+        mock.check("user:alice", "share", "doc:1").await.unwrap();
+        let report = mock.try_verify().unwrap_err();
+        assert!(
+            report.contains("write(\"doc:1\", \"viewer\", \"user:bob\") happened before the earlier expected check"),
+            "{report}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_report_lists_expected_and_actual_calls() {
+        let mock = MockClient::new().expect(Expectation::check("user:alice", "view", "doc:1"));
+        let context = Context::new().with("env", "dev");
+        mock.check_with_context("user:bob", "view", "doc:1", &context).await.unwrap();
+
+        let report = mock.try_verify().unwrap_err();
+        assert_eq!(
+            report,
+            "MockClient expectations were not met\n\
+             \n\
+             Expected:\n\
+             - Expected check(\"user:alice\", \"view\", \"doc:1\") at least 1 time, called 0 times\n\
+             \n\
+             Actual calls:\n\
+             + 1. check(\"user:bob\", \"view\", \"doc:1\", context {env=\"dev\"}) (unexpected)\n"
+        );
+    }
 }
@@ -6,6 +6,11 @@
 //! - [`MockClient`]: A mock client with expectation verification
 //! - [`InMemoryClient`]: An in-memory client with real graph semantics
 //! - [`AuthorizationClient`]: Object-safe trait for dependency injection
+//! - [`Cassette`]: Records engine calls to a file and replays them
+//! - [`FaultInjector`]: Injects latency and failures into engine calls
+//! - [`PolicySuite`]: Runs policy tests declared in YAML or JSON files
+//! - `FakeServer`: An offline REST and gRPC server for end-to-end tests
+//!   (requires the `fake-server` feature)
 //!
 //! ## Quick Start
 //!
@@ -28,17 +33,36 @@
 //! | Feature | MockClient | InMemoryClient |
 //! |---------|------------|----------------|
 //! | Expectation verification | ✓ | ✗ |
+//! | Argument matchers and call counts | ✓ | ✗ |
+//! | Injected errors | ✓ | ✗ |
 //! | Graph traversal | ✗ | ✓ |
 //! | Schema validation | ✗ | ✓ |
 //! | Relationship storage | ✗ | ✓ |
 //! | Best for | Unit tests | Integration tests |
 
 mod authorization_client;
+#[cfg(feature = "fake-server")]
+mod fake_server;
 mod in_memory;
 mod mock_client;
+mod policy_suite;
 mod test_vault;
 
+pub use crate::transport::{
+    Cassette, CassetteMode, EngineOperation, Fault, FaultInjector, FaultStep, LatencyDistribution,
+    MatchRules,
+};
 pub use authorization_client::AuthorizationClient;
+#[cfg(feature = "fake-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fake-server")))]
+pub use fake_server::{FakeServer, FakeServerBuilder};
 pub use in_memory::InMemoryClient;
-pub use mock_client::MockClient;
+pub use mock_client::{
+    ContextMatcher, Expectation, Matcher, MockClient, MockRelationshipsClient, MockResourcesClient,
+    MockSubjectsClient,
+};
+pub use policy_suite::{
+    AssertionResult, CheckAssertion, PolicyAssertion, PolicyReport, PolicySuite,
+    ResourcesAssertion, SubjectsAssertion,
+};
 pub use test_vault::{TestRelationshipsClient, TestResourcesClient, TestSubjectsClient, TestVault};
@@ -0,0 +1,736 @@
+//! Declarative policy tests loaded from YAML or JSON files.
+
+use std::{collections::BTreeSet, fmt, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    control::ipl::Schema,
+    testing::{AuthorizationClient, InMemoryClient},
+    types::{Context, Relationship},
+};
+
+/// A policy test suite: a schema, seed relationships and assertions.
+///
+/// Suites let policy authors and security reviewers write authorization
+/// tests as data instead of Rust. Each suite is run against any
+/// [`AuthorizationClient`]: an [`InMemoryClient`], a
+/// [`TestVault`](super::TestVault) or a real vault.
+///
+/// ## File Format
+///
+/// ```yaml
+/// name: document sharing
+/// schema: |
+///   entity user {}
+///   entity document {
+///     relation viewer: user
+///     permission view = viewer
+///   }
+/// relationships:
+///   - document:readme#viewer@user:alice
+///   - document:roadmap#viewer@user:alice
+/// assertions:
+///   - assert_allowed:
+///       subject: user:alice
+///       permission: viewer
+///       resource: document:readme
+///   - assert_denied:
+///       subject: user:bob
+///       permission: viewer
+///       resource: document:readme
+///       description: bob was never shared the readme
+///   - accessible_by:
+///       subject: user:alice
+///       permission: viewer
+///       resource_type: document
+///       expected: [document:readme, document:roadmap]
+///   - subjects:
+///       permission: viewer
+///       resource: document:readme
+///       expected: [user:alice]
+/// ```
+///
+/// YAML files require the `yaml` feature; JSON files use the same structure.
+///
+/// The schema is parsed when the suite is loaded, but the runner does not
+/// deploy it: push it to a real vault before running the suite there.
+/// [`InMemoryClient`] evaluates direct relationships only (see
+/// [`AuthorizationClient::evaluates_schema`]), so suites run in memory should
+/// assert on relations. An assertion on a permission the schema computes
+/// fails with an explanation when run against such a client, rather than
+/// reporting a misleading denial.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::testing::PolicySuite;
+///
+/// let suite = PolicySuite::from_json(r#"{
+///     "relationships": ["doc:1#viewer@user:alice"],
+///     "assertions": [
+///         { "assert_allowed": { "subject": "user:alice", "permission": "viewer", "resource": "doc:1" } },
+///         { "assert_denied": { "subject": "user:bob", "permission": "viewer", "resource": "doc:1" } }
+///     ]
+/// }"#).unwrap();
+///
+/// # tokio_test::block_on(async {
+/// let report = suite.run_in_memory().await.unwrap();
+/// assert!(report.passed());
+/// # });
+/// ```
+///
+/// In a `#[test]`, [`PolicySuite::verify`] loads a file, runs it in memory
+/// and panics with the report if any assertion fails:
+///
+/// ```rust,ignore
+/// #[test]
+/// fn sharing_policy() {
+///     inferadb::testing::PolicySuite::verify("tests/policies/sharing.yaml");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicySuite {
+    name: Option<String>,
+    schema: Option<String>,
+    parsed_schema: Option<Schema>,
+    schema_warning: Option<String>,
+    relationships: Vec<Relationship<'static>>,
+    assertions: Vec<PolicyAssertion>,
+}
+
+/// The on-disk representation of a [`PolicySuite`].
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SuiteFile {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    schema: Option<String>,
+    #[serde(default)]
+    relationships: Vec<String>,
+    #[serde(default)]
+    assertions: Vec<PolicyAssertion>,
+}
+
+/// One assertion of a [`PolicySuite`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAssertion {
+    /// The subject has the permission on the resource.
+    AssertAllowed(CheckAssertion),
+    /// The subject does not have the permission on the resource.
+    AssertDenied(CheckAssertion),
+    /// The subject has the permission on exactly the expected resources.
+    AccessibleBy(ResourcesAssertion),
+    /// Exactly the expected subjects have the permission on the resource.
+    Subjects(SubjectsAssertion),
+}
+
+/// A single permission check expected to be allowed or denied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckAssertion {
+    /// The subject, e.g. `user:alice`.
+    pub subject: String,
+    /// The permission to check.
+    pub permission: String,
+    /// The resource, e.g. `document:readme`.
+    pub resource: String,
+    /// ABAC context for the check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<Context>,
+    /// Describes the assertion in reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// The exact set of resources a subject is expected to access.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourcesAssertion {
+    /// The subject, e.g. `user:alice`.
+    pub subject: String,
+    /// The permission to look up.
+    pub permission: String,
+    /// Limits the lookup to one resource type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+    /// The resources expected, in any order.
+    pub expected: Vec<String>,
+    /// Describes the assertion in reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// The exact set of subjects expected to have a permission on a resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubjectsAssertion {
+    /// The permission to look up.
+    pub permission: String,
+    /// The resource, e.g. `document:readme`.
+    pub resource: String,
+    /// Limits the lookup to one subject type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_type: Option<String>,
+    /// The subjects expected, in any order.
+    pub expected: Vec<String>,
+    /// Describes the assertion in reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl PolicyAssertion {
+    /// Returns a one-line description of the assertion for reports.
+    pub fn describe(&self) -> String {
+        match self {
+            PolicyAssertion::AssertAllowed(a) => a.description.clone().unwrap_or_else(|| {
+                format!("{} is allowed {} on {}", a.subject, a.permission, a.resource)
+            }),
+            PolicyAssertion::AssertDenied(a) => a.description.clone().unwrap_or_else(|| {
+                format!("{} is denied {} on {}", a.subject, a.permission, a.resource)
+            }),
+            PolicyAssertion::AccessibleBy(a) => a.description.clone().unwrap_or_else(|| {
+                let scope =
+                    a.resource_type.as_deref().map(|t| format!(" {}", t)).unwrap_or_default();
+                format!("{} has {} on the expected{} resources", a.subject, a.permission, scope)
+            }),
+            PolicyAssertion::Subjects(a) => a.description.clone().unwrap_or_else(|| {
+                format!("the expected subjects have {} on {}", a.permission, a.resource)
+            }),
+        }
+    }
+
+    /// Returns the permission the assertion looks up and the resource type
+    /// it applies to, if known.
+    fn permission_scope(&self) -> (&str, Option<&str>) {
+        fn resource_type(resource: &str) -> Option<&str> {
+            resource.split_once(':').map(|(t, _)| t)
+        }
+        match self {
+            PolicyAssertion::AssertAllowed(a) | PolicyAssertion::AssertDenied(a) => {
+                (&a.permission, resource_type(&a.resource))
+            },
+            PolicyAssertion::AccessibleBy(a) => (&a.permission, a.resource_type.as_deref()),
+            PolicyAssertion::Subjects(a) => (&a.permission, resource_type(&a.resource)),
+        }
+    }
+
+    /// Runs the assertion, returning `Ok` with an explanation when it holds
+    /// and `Err` with an explanation when it does not.
+    async fn evaluate(&self, client: &dyn AuthorizationClient) -> Result<String, String> {
+        match self {
+            PolicyAssertion::AssertAllowed(a) | PolicyAssertion::AssertDenied(a) => {
+                let expected = matches!(self, PolicyAssertion::AssertAllowed(_));
+                let result = match &a.context {
+                    Some(context) => {
+                        client.check_with_context(&a.subject, &a.permission, &a.resource, context)
+                    },
+                    None => client.check(&a.subject, &a.permission, &a.resource),
+                };
+                let allowed = result.await.map_err(|e| format!("check failed: {}", e))?;
+                let outcome = |allowed| if allowed { "allowed" } else { "denied" };
+                let explanation = format!(
+                    "{} was {} {} on {}",
+                    a.subject,
+                    outcome(allowed),
+                    a.permission,
+                    a.resource
+                );
+                if allowed == expected {
+                    Ok(explanation)
+                } else {
+                    Err(format!("expected {}, but {}", outcome(expected), explanation))
+                }
+            },
+            PolicyAssertion::AccessibleBy(a) => {
+                let actual = client
+                    .accessible_resources(&a.subject, &a.permission, a.resource_type.as_deref())
+                    .await
+                    .map_err(|e| format!("resource lookup failed: {}", e))?;
+                compare_sets("resources", &a.expected, actual)
+            },
+            PolicyAssertion::Subjects(a) => {
+                let actual = client
+                    .subjects_with_permission(&a.permission, &a.resource, a.subject_type.as_deref())
+                    .await
+                    .map_err(|e| format!("subject lookup failed: {}", e))?;
+                compare_sets("subjects", &a.expected, actual)
+            },
+        }
+    }
+}
+
+/// Compares an expected and actual set, explaining any difference.
+fn compare_sets(noun: &str, expected: &[String], actual: Vec<String>) -> Result<String, String> {
+    let expected: BTreeSet<_> = expected.iter().cloned().collect();
+    let actual: BTreeSet<_> = actual.into_iter().collect();
+    if expected == actual {
+        return Ok(format!("found {} {}", actual.len(), noun));
+    }
+
+    let list =
+        |items: Vec<&String>| items.into_iter().map(String::as_str).collect::<Vec<_>>().join(", ");
+    let mut problems = Vec::new();
+    let missing: Vec<_> = expected.difference(&actual).collect();
+    if !missing.is_empty() {
+        problems.push(format!("missing {}: {}", noun, list(missing)));
+    }
+    let unexpected: Vec<_> = actual.difference(&expected).collect();
+    if !unexpected.is_empty() {
+        problems.push(format!("unexpected {}: {}", noun, list(unexpected)));
+    }
+    Err(problems.join("; "))
+}
+
+impl PolicySuite {
+    /// Parses a suite from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if the document is malformed, the
+    /// schema does not parse, or a relationship is not in
+    /// `resource#relation@subject` form.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let file: SuiteFile = serde_json::from_str(json)
+            .map_err(|e| Error::invalid_argument(format!("invalid policy suite: {}", e)))?;
+        Self::from_file(file)
+    }
+
+    /// Parses a suite from YAML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if the document is malformed, the
+    /// schema does not parse, or a relationship is not in
+    /// `resource#relation@subject` form.
+    #[cfg(feature = "yaml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+    pub fn from_yaml(yaml: &str) -> Result<Self, Error> {
+        // Going through JSON values keeps the `{ assert_allowed: ... }` form
+        // YAML would otherwise expect as `!assert_allowed` tags
+        let value: serde_json::Value = serde_yaml_ng::from_str(yaml)
+            .map_err(|e| Error::invalid_argument(format!("invalid policy suite: {}", e)))?;
+        let file: SuiteFile = serde_json::from_value(value)
+            .map_err(|e| Error::invalid_argument(format!("invalid policy suite: {}", e)))?;
+        Self::from_file(file)
+    }
+
+    /// Loads a suite from a `.json`, `.yaml` or `.yml` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Configuration` error if the file cannot be read, has an
+    /// unsupported extension, or YAML support is not enabled, and an
+    /// `InvalidArgument` error if its contents are invalid.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            Error::configuration(format!("cannot read policy suite {}: {}", path.display(), e))
+        })?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+        let suite = match extension {
+            "json" => Self::from_json(&contents),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Self::from_yaml(&contents),
+            #[cfg(not(feature = "yaml"))]
+            "yaml" | "yml" => {
+                Err(Error::configuration("YAML policy suites require the `yaml` feature"))
+            },
+            _ => Err(Error::configuration(format!(
+                "unsupported policy suite extension '{}', expected json, yaml or yml",
+                extension
+            ))),
+        };
+        suite.map(|suite| {
+            let name = suite.name.clone().unwrap_or_else(|| path.display().to_string());
+            Self { name: Some(name), ..suite }
+        })
+    }
+
+    fn from_file(file: SuiteFile) -> Result<Self, Error> {
+        // The schema is only used to spot computed permissions, so one the
+        // local parser cannot read is reported with the results instead
+        let (parsed_schema, schema_warning) = match file.schema.as_deref().map(Schema::parse) {
+            Some(Ok(schema)) => (Some(schema), None),
+            Some(Err(e)) => (None, Some(format!("cannot parse policy suite schema: {}", e))),
+            None => (None, None),
+        };
+        let relationships =
+            file.relationships.iter().map(|r| r.parse()).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            name: file.name,
+            schema: file.schema,
+            parsed_schema,
+            schema_warning,
+            relationships,
+            assertions: file.assertions,
+        })
+    }
+
+    /// Returns the suite name, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the schema the suite was written against, if any.
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// Returns the `type#permission` the schema computes for an assertion,
+    /// if the assertion looks up a schema permission rather than a relation.
+    fn computed_permission(&self, assertion: &PolicyAssertion) -> Option<String> {
+        let (permission, resource_type) = assertion.permission_scope();
+        self.parsed_schema
+            .as_ref()?
+            .types
+            .iter()
+            .filter(|t| resource_type.is_none_or(|name| t.name == name))
+            .find(|t| t.permission(permission).is_some())
+            .map(|t| format!("{}#{}", t.name, permission))
+    }
+
+    /// Returns the relationships written before the assertions run.
+    pub fn relationships(&self) -> &[Relationship<'static>] {
+        &self.relationships
+    }
+
+    /// Returns the assertions.
+    pub fn assertions(&self) -> &[PolicyAssertion] {
+        &self.assertions
+    }
+
+    /// Writes the seed relationships to `client`, then runs every assertion.
+    ///
+    /// A failing assertion is recorded in the report and does not stop the
+    /// run. If `client` does not evaluate schemas, assertions on permissions
+    /// the suite's schema computes fail without being run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the seed relationships cannot be written.
+    pub async fn run(&self, client: &dyn AuthorizationClient) -> Result<PolicyReport, Error> {
+        if !self.relationships.is_empty() {
+            client.write_batch(self.relationships.clone()).await?;
+        }
+
+        let mut results = Vec::with_capacity(self.assertions.len());
+        let evaluates_schema = client.evaluates_schema();
+        for assertion in &self.assertions {
+            let computed = self.computed_permission(assertion).filter(|_| !evaluates_schema);
+            let outcome = match computed {
+                Some(permission) => Err(format!(
+                    "{} is computed by the schema, but the client only evaluates direct \
+                     relationships; run the suite against a vault with the schema deployed",
+                    permission
+                )),
+                None => assertion.evaluate(client).await,
+            };
+            results.push(AssertionResult {
+                description: assertion.describe(),
+                passed: outcome.is_ok(),
+                explanation: outcome.unwrap_or_else(|explanation| explanation),
+            });
+        }
+        Ok(PolicyReport {
+            suite: self.name.clone(),
+            results,
+            warnings: self.schema_warning.iter().cloned().collect(),
+        })
+    }
+
+    /// Runs the suite against a fresh [`InMemoryClient`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the seed relationships cannot be written.
+    pub async fn run_in_memory(&self) -> Result<PolicyReport, Error> {
+        self.run(&InMemoryClient::new()).await
+    }
+
+    /// Loads the suite at `path`, runs it in memory and panics with the
+    /// report if it cannot be loaded or any assertion fails.
+    ///
+    /// Assertions on permissions computed by the suite's schema always fail
+    /// in memory; run such suites against a vault with [`PolicySuite::run`].
+    ///
+    /// Intended to be called from a synchronous `#[test]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the suite cannot be loaded or run, or if it fails.
+    pub fn verify(path: impl AsRef<Path>) -> PolicyReport {
+        let suite = match Self::load(path) {
+            Ok(suite) => suite,
+            Err(e) => panic!("{}", e),
+        };
+        let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(runtime) => runtime,
+            Err(e) => panic!("cannot start a runtime for the policy suite: {}", e),
+        };
+        let report = match runtime.block_on(suite.run_in_memory()) {
+            Ok(report) => report,
+            Err(e) => panic!("cannot seed the policy suite: {}", e),
+        };
+        report.assert_passed();
+        report
+    }
+}
+
+/// The outcome of one assertion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssertionResult {
+    /// What the assertion checks.
+    pub description: String,
+    /// Whether the assertion held.
+    pub passed: bool,
+    /// What was observed, and why the assertion failed if it did.
+    pub explanation: String,
+}
+
+/// The outcome of running a [`PolicySuite`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyReport {
+    /// The name of the suite, if any.
+    pub suite: Option<String>,
+    /// One result per assertion, in order.
+    pub results: Vec<AssertionResult>,
+    /// Problems that did not fail the run, such as a schema that could not
+    /// be parsed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl PolicyReport {
+    /// Returns `true` if every assertion held.
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.passed)
+    }
+
+    /// Returns the assertions that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &AssertionResult> {
+        self.results.iter().filter(|r| !r.passed)
+    }
+
+    /// Panics with the full report if any assertion failed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any assertion failed.
+    pub fn assert_passed(&self) {
+        if !self.passed() {
+            panic!("{}", self);
+        }
+    }
+}
+
+impl fmt::Display for PolicyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failed = self.failures().count();
+        writeln!(
+            f,
+            "policy suite {}: {} passed, {} failed",
+            self.suite.as_deref().unwrap_or("<unnamed>"),
+            self.results.len() - failed,
+            failed
+        )?;
+        for result in &self.results {
+            let status = if result.passed { "PASS" } else { "FAIL" };
+            writeln!(f, "  {} {}: {}", status, result.description, result.explanation)?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "  WARN {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    const SUITE: &str = r#"{
+        "name": "sharing",
+        "schema": "entity user {}",
+        "relationships": ["doc:1#viewer@user:alice", "doc:2#viewer@user:alice"],
+        "assertions": [
+            { "assert_allowed": { "subject": "user:alice", "permission": "viewer", "resource": "doc:1" } },
+            { "assert_denied": { "subject": "user:bob", "permission": "viewer", "resource": "doc:1" } },
+            { "accessible_by": { "subject": "user:alice", "permission": "viewer", "resource_type": "doc", "expected": ["doc:2", "doc:1"] } },
+            { "subjects": { "permission": "viewer", "resource": "doc:1", "expected": ["user:alice"] } }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn test_passing_suite() {
+        let suite = PolicySuite::from_json(SUITE).unwrap();
+        assert_eq!(suite.name(), Some("sharing"));
+        assert_eq!(suite.schema(), Some("entity user {}"));
+        assert_eq!(suite.relationships().len(), 2);
+
+        let report = suite.run_in_memory().await.unwrap();
+        assert!(report.passed(), "{}", report);
+        assert_eq!(report.results.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_explained() {
+        let suite = PolicySuite::from_json(
+            r#"{
+                "relationships": ["doc:1#viewer@user:alice", "doc:3#viewer@user:alice"],
+                "assertions": [
+                    { "assert_denied": { "subject": "user:alice", "permission": "viewer", "resource": "doc:1", "description": "alice is blocked" } },
+                    { "accessible_by": { "subject": "user:alice", "permission": "viewer", "expected": ["doc:1", "doc:2"] } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let report = suite.run_in_memory().await.unwrap();
+        assert!(!report.passed());
+        assert_eq!(report.failures().count(), 2);
+        assert_eq!(report.results[0].description, "alice is blocked");
+        assert_eq!(
+            report.results[0].explanation,
+            "expected denied, but user:alice was allowed viewer on doc:1"
+        );
+        assert_eq!(
+            report.results[1].explanation,
+            "missing resources: doc:2; unexpected resources: doc:3"
+        );
+        assert!(report.to_string().contains("0 passed, 2 failed"));
+    }
+
+    #[tokio::test]
+    async fn test_schema_permissions_fail_without_schema_evaluation() {
+        let suite = PolicySuite::from_json(
+            r#"{
+                "schema": "type user {}\ntype doc {\n  relation viewer: user\n  permission view = viewer\n}",
+                "relationships": ["doc:1#viewer@user:alice"],
+                "assertions": [
+                    { "assert_allowed": { "subject": "user:alice", "permission": "viewer", "resource": "doc:1" } },
+                    { "assert_allowed": { "subject": "user:alice", "permission": "view", "resource": "doc:1" } },
+                    { "accessible_by": { "subject": "user:alice", "permission": "view", "expected": ["doc:1"] } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let report = suite.run_in_memory().await.unwrap();
+        assert!(report.results[0].passed, "{}", report);
+        assert_eq!(report.failures().count(), 2);
+        assert!(report.results[1].explanation.starts_with("doc#view is computed by the schema"));
+
+        // A client that evaluates schemas runs the same assertion
+        let report = suite.run(&crate::testing::MockClient::allow_all()).await.unwrap();
+        assert!(report.results[1].passed, "{}", report);
+    }
+
+    #[test]
+    fn test_entity_schema_with_dot_arrows_loads() {
+        let suite = PolicySuite::from_json(
+            r#"{
+                "schema": "entity User {} entity Document { relations { parent: Folder, viewer: User } permissions { view: viewer | parent.view } }",
+                "assertions": [
+                    { "assert_allowed": { "subject": "user:alice", "permission": "view", "resource": "Document:1" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            suite.computed_permission(&suite.assertions()[0]).as_deref(),
+            Some("Document#view")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unparsable_schema_is_a_warning() {
+        let suite = PolicySuite::from_json(
+            r#"{
+                "schema": "type doc {",
+                "relationships": ["doc:1#viewer@user:alice"],
+                "assertions": [
+                    { "assert_allowed": { "subject": "user:alice", "permission": "viewer", "resource": "doc:1" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let report = suite.run_in_memory().await.unwrap();
+        assert!(report.passed(), "{}", report);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].starts_with("cannot parse policy suite schema"));
+        assert!(report.to_string().contains("WARN cannot parse policy suite schema"));
+    }
+
+    #[test]
+    fn test_invalid_relationship_is_rejected() {
+        let err = PolicySuite::from_json(r#"{ "relationships": ["doc:1-viewer-user:alice"] }"#)
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidArgument);
+    }
+
+    #[test]
+    fn test_unknown_assertion_is_rejected() {
+        let err =
+            PolicySuite::from_json(r#"{ "assertions": [{ "assert_maybe": {} }] }"#).unwrap_err();
+        assert!(err.to_string().contains("invalid policy suite"));
+    }
+
+    #[test]
+    fn test_verify_loads_and_runs_file() {
+        let dir = std::env::temp_dir().join(format!("inferadb-policy-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sharing.json");
+        std::fs::write(&path, SUITE).unwrap();
+
+        let report = PolicySuite::verify(&path);
+        assert_eq!(report.suite.as_deref(), Some("sharing"));
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    #[should_panic(expected = "FAIL user:bob is allowed viewer on doc:1")]
+    fn test_verify_panics_with_report() {
+        let dir = std::env::temp_dir().join(format!("inferadb-policy-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("failing.json");
+        std::fs::write(
+            &path,
+            r#"{ "assertions": [{ "assert_allowed": { "subject": "user:bob", "permission": "viewer", "resource": "doc:1" } }] }"#,
+        )
+        .unwrap();
+
+        PolicySuite::verify(&path);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[tokio::test]
+    async fn test_yaml_suite() {
+        let suite = PolicySuite::from_yaml(
+            r#"
+name: sharing
+relationships:
+  - doc:1#viewer@user:alice
+assertions:
+  - assert_allowed:
+      subject: user:alice
+      permission: viewer
+      resource: doc:1
+  - subjects:
+      permission: viewer
+      resource: doc:1
+      subject_type: user
+      expected: [user:alice]
+"#,
+        )
+        .unwrap();
+
+        let report = suite.run_in_memory().await.unwrap();
+        assert!(report.passed(), "{}", report);
+    }
+}
@@ -3,12 +3,13 @@
 //! `TestVault` provides an in-memory implementation that behaves like a real vault
 //! but uses the MockTransport internally for testing without network calls.
 
-use std::sync::Arc;
+use std::{future::Future, pin::Pin, sync::Arc};
 
 use crate::{
     Error,
+    testing::{AuthorizationClient, authorization_client::require_delete_filter},
     transport::{mock::MockTransport, traits::TransportClient},
-    types::{ConsistencyToken, Context, Relationship},
+    types::{ConsistencyRequirement, ConsistencyToken, Context, Relationship},
 };
 
 /// An in-memory vault for testing.
@@ -108,7 +109,7 @@ impl TestVault {
             permission: permission.into(),
             resource: resource.into(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
         let response = self.transport.check(request).await?;
@@ -128,7 +129,7 @@ impl TestVault {
             permission: permission.into(),
             resource: resource.into(),
             context: Some(context),
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
         let response = self.transport.check(request).await?;
@@ -163,6 +164,159 @@ impl std::fmt::Debug for TestVault {
     }
 }
 
+impl AuthorizationClient for TestVault {
+    fn check(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, Error>> + Send + '_>> {
+        Box::pin(TestVault::check(
+            self,
+            subject.to_string(),
+            permission.to_string(),
+            resource.to_string(),
+        ))
+    }
+
+    fn check_with_context(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource: &str,
+        context: &Context,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, Error>> + Send + '_>> {
+        Box::pin(TestVault::check_with_context(
+            self,
+            subject.to_string(),
+            permission.to_string(),
+            resource.to_string(),
+            context.clone(),
+        ))
+    }
+
+    fn check_batch(
+        &self,
+        checks: &[(&str, &str, &str)],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<bool>, Error>> + Send + '_>> {
+        let requests: Vec<_> = checks
+            .iter()
+            .map(|&(subject, permission, resource)| crate::transport::traits::CheckRequest {
+                subject: subject.to_string(),
+                permission: permission.to_string(),
+                resource: resource.to_string(),
+                context: None,
+                consistency: ConsistencyRequirement::Eventual,
+                trace: false,
+            })
+            .collect();
+        Box::pin(async move {
+            let responses = self.transport.check_batch(requests).await?;
+            Ok(responses.into_iter().map(|r| r.allowed).collect())
+        })
+    }
+
+    fn write(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+        let relationship = relationship.into_owned();
+        Box::pin(async move { self.relationships().write(relationship).await })
+    }
+
+    fn write_batch(
+        &self,
+        relationships: Vec<Relationship<'_>>,
+    ) -> Pin<Box<dyn Future<Output = Result<ConsistencyToken, Error>> + Send + '_>> {
+        let relationships: Vec<_> =
+            relationships.into_iter().map(Relationship::into_owned).collect();
+        Box::pin(async move { self.relationships().write_batch(relationships).await })
+    }
+
+    fn delete(
+        &self,
+        relationship: Relationship<'_>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send + '_>> {
+        let relationship = relationship.into_owned();
+        Box::pin(async move { self.relationships().delete(relationship).await })
+    }
+
+    fn delete_where(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, Error>> + Send + '_>> {
+        let filter = require_delete_filter(resource, relation, subject);
+        let (resource, relation, subject) = (
+            resource.map(str::to_string),
+            relation.map(str::to_string),
+            subject.map(str::to_string),
+        );
+        Box::pin(async move {
+            filter?;
+            let matching = self
+                .relationships()
+                .list(resource.as_deref(), relation.as_deref(), subject.as_deref())
+                .await?;
+            let mut deleted = 0;
+            for relationship in matching {
+                self.transport.delete(relationship).await?;
+                deleted += 1;
+            }
+            Ok(deleted)
+        })
+    }
+
+    fn list_relationships(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Relationship<'static>>, Error>> + Send + '_>> {
+        let (resource, relation, subject) = (
+            resource.map(str::to_string),
+            relation.map(str::to_string),
+            subject.map(str::to_string),
+        );
+        Box::pin(async move {
+            self.relationships()
+                .list(resource.as_deref(), relation.as_deref(), subject.as_deref())
+                .await
+        })
+    }
+
+    fn accessible_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+        let (subject, permission, resource_type) =
+            (subject.to_string(), permission.to_string(), resource_type.map(str::to_string));
+        Box::pin(async move {
+            self.resources().accessible_by(&subject, &permission, resource_type.as_deref()).await
+        })
+    }
+
+    fn subjects_with_permission(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + '_>> {
+        let (permission, resource, subject_type) =
+            (permission.to_string(), resource.to_string(), subject_type.map(str::to_string));
+        Box::pin(async move {
+            self.subjects().with_permission(&permission, &resource, subject_type.as_deref()).await
+        })
+    }
+
+    fn evaluates_schema(&self) -> bool {
+        false
+    }
+}
+
 /// Client for managing relationships in a test vault.
 pub struct TestRelationshipsClient {
     transport: Arc<MockTransport>,
@@ -200,7 +354,8 @@ impl TestRelationshipsClient {
 
     /// Deletes a relationship from the vault.
     pub async fn delete<'a>(&self, relationship: Relationship<'a>) -> Result<(), Error> {
-        self.transport.delete(relationship.into_owned()).await
+        self.transport.delete(relationship.into_owned()).await?;
+        Ok(())
     }
 
     /// Lists relationships matching the given filters.
@@ -210,8 +365,17 @@ impl TestRelationshipsClient {
         relation: Option<&str>,
         subject: Option<&str>,
     ) -> Result<Vec<Relationship<'static>>, Error> {
-        let response =
-            self.transport.list_relationships(resource, relation, subject, None, None).await?;
+        let response = self
+            .transport
+            .list_relationships(
+                resource,
+                relation,
+                subject,
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await?;
         Ok(response.relationships)
     }
 }
@@ -229,8 +393,17 @@ impl TestResourcesClient {
         permission: &str,
         resource_type: Option<&str>,
     ) -> Result<Vec<String>, Error> {
-        let response =
-            self.transport.list_resources(subject, permission, resource_type, None, None).await?;
+        let response = self
+            .transport
+            .list_resources(
+                subject,
+                permission,
+                resource_type,
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await?;
         Ok(response.resources)
     }
 }
@@ -248,8 +421,17 @@ impl TestSubjectsClient {
         resource: &str,
         subject_type: Option<&str>,
     ) -> Result<Vec<String>, Error> {
-        let response =
-            self.transport.list_subjects(permission, resource, subject_type, None, None).await?;
+        let response = self
+            .transport
+            .list_subjects(
+                permission,
+                resource,
+                subject_type,
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await?;
         Ok(response.subjects)
     }
 }
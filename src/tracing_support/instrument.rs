@@ -0,0 +1,397 @@
+//! Automatic spans and trace-context propagation for SDK calls.
+//!
+//! Every engine and control-plane call made by the SDK is wrapped in a
+//! `tracing` span carrying [OpenTelemetry semantic convention][semconv]
+//! attribute names, so a `tracing-opentelemetry` layer exports them without
+//! any mapping.
+//!
+//! A caller-supplied [`TraceContext`] is held in a task-local for the
+//! duration of one SDK call. Transports read it when building request
+//! headers or gRPC metadata and inject it with the client's configured
+//! [`TracePropagation`] format.
+//!
+//! [semconv]: https://opentelemetry.io/docs/specs/semconv/
+
+use std::future::Future;
+
+use tracing::{Instrument, Span, field::Empty};
+
+use crate::{
+    Error,
+    tracing_support::{
+        B3Propagator, HeaderExtractor, HeaderInjector, Propagator, TraceContext, W3CTraceContext,
+        context::TraceContextError,
+    },
+    transport::Transport,
+};
+
+/// Wire format used to propagate trace context to InferaDB.
+///
+/// Configured once per client with
+/// [`ClientBuilder::trace_propagation`](crate::ClientBuilder::trace_propagation).
+///
+/// ## Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use inferadb::tracing_support::{Propagator, TraceContext, TracePropagation};
+///
+/// let ctx = TraceContext::new_root();
+/// let mut headers = HashMap::new();
+/// TracePropagation::B3Multi.inject(&ctx, &mut headers);
+///
+/// assert!(headers.contains_key("x-b3-traceid"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TracePropagation {
+    /// W3C `traceparent` / `tracestate` headers.
+    #[default]
+    W3C,
+    /// Single `b3` header.
+    B3,
+    /// Multiple `X-B3-*` headers.
+    B3Multi,
+    /// Spans are still recorded, but no context is sent to the server.
+    Disabled,
+}
+
+impl Propagator for TracePropagation {
+    fn extract<E: HeaderExtractor>(
+        &self,
+        extractor: &E,
+    ) -> Result<TraceContext, TraceContextError> {
+        match self {
+            TracePropagation::W3C => W3CTraceContext.extract(extractor),
+            TracePropagation::B3 => B3Propagator::single().extract(extractor),
+            TracePropagation::B3Multi => B3Propagator::multi().extract(extractor),
+            TracePropagation::Disabled => Err(TraceContextError::InvalidFormat),
+        }
+    }
+
+    fn inject<I: HeaderInjector>(&self, context: &TraceContext, injector: &mut I) {
+        match self {
+            TracePropagation::W3C => W3CTraceContext.inject(context, injector),
+            TracePropagation::B3 => B3Propagator::single().inject(context, injector),
+            TracePropagation::B3Multi => B3Propagator::multi().inject(context, injector),
+            TracePropagation::Disabled => {},
+        }
+    }
+}
+
+#[cfg(feature = "rest")]
+impl HeaderInjector for reqwest::header::HeaderMap {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            reqwest::header::HeaderName::from_bytes(key.as_bytes()),
+            reqwest::header::HeaderValue::from_str(&value),
+        ) {
+            self.insert(name, value);
+        }
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl HeaderInjector for tonic::metadata::MetadataMap {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(key), Ok(value)) = (
+            tonic::metadata::MetadataKey::from_bytes(key.as_bytes()),
+            tonic::metadata::MetadataValue::try_from(value.as_str()),
+        ) {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// The trace context of the SDK call in flight on the current task.
+#[derive(Debug, Clone)]
+struct ActiveTrace {
+    context: TraceContext,
+    propagation: TracePropagation,
+}
+
+tokio::task_local! {
+    static ACTIVE: ActiveTrace;
+}
+
+/// Runs `future` with `context` as the parent of every request it sends.
+pub(crate) async fn with_context<F: Future>(
+    context: TraceContext,
+    propagation: TracePropagation,
+    future: F,
+) -> F::Output {
+    ACTIVE.scope(ActiveTrace { context, propagation }, future).await
+}
+
+/// Returns the trace context of the SDK call in flight, if one was supplied.
+pub(crate) fn current() -> Option<TraceContext> {
+    ACTIVE.try_with(|active| active.context.clone()).ok()
+}
+
+/// Injects the active trace context, if any, into outgoing request headers.
+pub(crate) fn inject_current<I: HeaderInjector>(injector: &mut I) {
+    let _ = ACTIVE.try_with(|active| active.propagation.inject(&active.context, injector));
+}
+
+/// Creates the span for one engine RPC.
+///
+/// Resource-specific attributes start empty and are filled in with
+/// [`Span::record`] by the caller.
+pub(crate) fn engine_span(method: &'static str, transport: Transport) -> Span {
+    let span = tracing::info_span!(
+        "inferadb.request",
+        otel.name = format!("inferadb/{method}"),
+        otel.kind = "client",
+        otel.status_code = Empty,
+        rpc.system = "inferadb",
+        rpc.service = "inferadb.authorization.v1.AuthorizationService",
+        rpc.method = method,
+        network.protocol.name = transport_protocol(transport),
+        inferadb.subject = Empty,
+        inferadb.permission = Empty,
+        inferadb.resource = Empty,
+        inferadb.batch.size = Empty,
+        inferadb.decision = Empty,
+        error.type = Empty,
+        trace_id = Empty,
+        span_id = Empty,
+    );
+    record_context(&span);
+    span
+}
+
+/// Creates the span for one control-plane HTTP call.
+pub(crate) fn control_span(method: &'static str, path: &str) -> Span {
+    let span = tracing::info_span!(
+        "inferadb.control",
+        otel.name = format!("{method} {}", route_template(path)),
+        otel.kind = "client",
+        otel.status_code = Empty,
+        http.request.method = method,
+        url.path = path,
+        http.response.status_code = Empty,
+        error.type = Empty,
+        trace_id = Empty,
+        span_id = Empty,
+    );
+    record_context(&span);
+    span
+}
+
+/// Runs `future` inside `span`, recording its outcome.
+pub(crate) async fn instrument<T, F>(span: Span, future: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let result = future.instrument(span.clone()).await;
+    match &result {
+        Ok(_) => {
+            span.record("otel.status_code", "OK");
+        },
+        Err(error) => {
+            span.record("otel.status_code", "ERROR");
+            span.record("error.type", tracing::field::debug(error.kind()));
+        },
+    }
+    result
+}
+
+/// Links `span` to the caller's trace context so logs can be correlated.
+fn record_context(span: &Span) {
+    if let Some(context) = current() {
+        span.record("trace_id", tracing::field::display(context.trace_id()));
+        span.record("span_id", tracing::field::display(context.span_id()));
+    }
+}
+
+fn transport_protocol(transport: Transport) -> &'static str {
+    match transport {
+        Transport::Grpc => "grpc",
+        Transport::Http => "http",
+        Transport::Mock => "mock",
+    }
+}
+
+/// Replaces identifier path segments with placeholders to keep span names
+/// low-cardinality, e.g. `/control/v1/organizations/{id}/vaults`.
+fn route_template(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or(path);
+    path.split('/')
+        .map(|segment| if is_identifier(segment) { "{id}" } else { segment })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Returns `true` for path segments that name an entity (`org_123`, `42`)
+/// rather than a collection, action or API version (`vaults`, `v1`).
+fn is_identifier(segment: &str) -> bool {
+    let is_version = segment
+        .strip_prefix('v')
+        .is_some_and(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()));
+    !is_version && segment.chars().any(|c| c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_propagation_formats() {
+        let ctx = TraceContext::new_root();
+
+        let mut w3c = HashMap::new();
+        TracePropagation::W3C.inject(&ctx, &mut w3c);
+        assert_eq!(w3c.get("traceparent"), Some(&ctx.to_traceparent()));
+
+        let mut b3 = HashMap::new();
+        TracePropagation::B3.inject(&ctx, &mut b3);
+        assert!(b3.get("b3").unwrap().starts_with(&ctx.trace_id().to_string()));
+
+        let mut multi = HashMap::new();
+        TracePropagation::B3Multi.inject(&ctx, &mut multi);
+        assert_eq!(multi.get("x-b3-spanid"), Some(&ctx.span_id().to_string()));
+
+        let mut disabled = HashMap::new();
+        TracePropagation::Disabled.inject(&ctx, &mut disabled);
+        assert!(disabled.is_empty());
+    }
+
+    #[test]
+    fn test_propagation_extract_roundtrip() {
+        let ctx = TraceContext::new_root();
+        for propagation in [TracePropagation::W3C, TracePropagation::B3, TracePropagation::B3Multi]
+        {
+            let mut headers = HashMap::new();
+            propagation.inject(&ctx, &mut headers);
+            let extracted = propagation.extract(&headers).unwrap();
+            assert_eq!(extracted.trace_id(), ctx.trace_id());
+        }
+        assert!(TracePropagation::Disabled.extract(&HashMap::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_active_context_is_task_scoped() {
+        assert!(current().is_none());
+
+        let ctx = TraceContext::new_root();
+        let seen = with_context(ctx.clone(), TracePropagation::W3C, async {
+            let mut headers = HashMap::new();
+            inject_current(&mut headers);
+            (current(), headers)
+        })
+        .await;
+
+        assert_eq!(seen.0.unwrap().span_id(), ctx.span_id());
+        assert_eq!(seen.1.get("traceparent"), Some(&ctx.to_traceparent()));
+        assert!(current().is_none());
+    }
+
+    #[test]
+    fn test_inject_without_context_is_noop() {
+        let mut headers = HashMap::new();
+        inject_current(&mut headers);
+        assert!(headers.is_empty());
+    }
+
+    #[test]
+    fn test_header_map_injector() {
+        let ctx = TraceContext::new_root();
+
+        #[cfg(feature = "rest")]
+        {
+            let mut headers = reqwest::header::HeaderMap::new();
+            TracePropagation::W3C.inject(&ctx, &mut headers);
+            assert_eq!(headers["traceparent"], ctx.to_traceparent().as_str());
+        }
+
+        #[cfg(feature = "grpc")]
+        {
+            let mut metadata = tonic::metadata::MetadataMap::new();
+            TracePropagation::B3.inject(&ctx, &mut metadata);
+            assert!(metadata.get("b3").is_some());
+        }
+    }
+
+    /// Collects the fields recorded on every span, keyed by field name.
+    #[derive(Clone, Default)]
+    struct FieldCapture(std::sync::Arc<parking_lot::Mutex<HashMap<String, String>>>);
+
+    impl tracing::field::Visit for FieldCapture {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.lock().insert(field.name().to_string(), format!("{value:?}"));
+        }
+
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.lock().insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for FieldCapture {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            attrs.record(&mut self.clone());
+        }
+
+        fn on_record(
+            &self,
+            _id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            values.record(&mut self.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_engine_call_records_semantic_attributes() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        use crate::transport::{TransportCheckRequest, mock::MockTransport};
+
+        let capture = FieldCapture::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+        let transport = MockTransport::new().into_any();
+        let parent = TraceContext::new_root();
+        let request = TransportCheckRequest {
+            subject: "user:alice".to_string(),
+            permission: "view".to_string(),
+            resource: "doc:1".to_string(),
+            context: None,
+            consistency: crate::ConsistencyRequirement::Eventual,
+            trace: false,
+        };
+        with_context(parent.clone(), TracePropagation::W3C, async {
+            transport.check(request).await
+        })
+        .await
+        .unwrap();
+
+        let fields = capture.0.lock();
+        assert_eq!(fields["rpc.system"], "inferadb");
+        assert_eq!(fields["rpc.method"], "Check");
+        assert_eq!(fields["otel.kind"], "client");
+        assert_eq!(fields["inferadb.subject"], "user:alice");
+        assert_eq!(fields["inferadb.permission"], "view");
+        assert_eq!(fields["inferadb.resource"], "doc:1");
+        assert_eq!(fields["inferadb.decision"], "deny");
+        assert_eq!(fields["otel.status_code"], "OK");
+        assert_eq!(fields["trace_id"], parent.trace_id().to_string());
+    }
+
+    #[test]
+    fn test_route_template() {
+        assert_eq!(
+            route_template("/control/v1/organizations/org_abc/vaults/456?limit=10"),
+            "/control/v1/organizations/{id}/vaults/{id}"
+        );
+        assert_eq!(route_template("/control/v1/users/me"), "/control/v1/users/me");
+    }
+}
@@ -1,7 +1,13 @@
 //! Metrics collection for observability.
+//!
+//! [`Metrics`] keeps every series in process and renders them with
+//! [`Metrics::render_prometheus`]. With the `metrics` feature enabled each
+//! sample is also forwarded to the [`metrics`](https://docs.rs/metrics)
+//! facade, so any installed exporter receives it.
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
     sync::{
         Arc,
         atomic::{AtomicU64, Ordering},
@@ -11,6 +17,8 @@ use std::{
 
 use parking_lot::RwLock;
 
+use crate::{Transport, config::CircuitState};
+
 /// Default latency histogram buckets for metrics collection.
 fn default_latency_buckets() -> Vec<f64> {
     vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
@@ -98,15 +106,86 @@ struct MetricsInner {
     write_latency_count: AtomicU64,
     // Connection metrics
     connection_pool_size: AtomicU64,
+    connection_pool_limit: AtomicU64,
     connection_errors: AtomicU64,
     // Custom counters
     custom_counters: RwLock<HashMap<String, AtomicU64>>,
     custom_gauges: RwLock<HashMap<String, AtomicU64>>,
+    // Labelled series
+    latency_bounds: Vec<f64>,
+    requests: RwLock<BTreeMap<RequestKey, RequestSeries>>,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    retries: RwLock<BTreeMap<&'static str, AtomicU64>>,
+    circuit_transitions: RwLock<BTreeMap<(String, &'static str), AtomicU64>>,
+}
+
+/// Labels identifying one request series.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestKey {
+    operation: String,
+    transport: &'static str,
+    outcome: &'static str,
+}
+
+/// Count and latency distribution of one request series.
+#[derive(Debug)]
+struct RequestSeries {
+    // Per-bucket (non-cumulative) observation counts
+    buckets: Vec<AtomicU64>,
+    latency_sum_ns: AtomicU64,
+    count: AtomicU64,
+}
+
+impl RequestSeries {
+    fn new(bounds: usize) -> Self {
+        Self {
+            buckets: (0..bounds).map(|_| AtomicU64::new(0)).collect(),
+            latency_sum_ns: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Outcome label of a recorded request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestOutcome {
+    /// An authorization check returned allow.
+    Allow,
+    /// An authorization check returned deny.
+    Deny,
+    /// Any other operation completed successfully.
+    Success,
+    /// The operation failed.
+    Error,
+}
+
+impl RequestOutcome {
+    /// Returns the value of the `outcome` label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RequestOutcome::Allow => "allow",
+            RequestOutcome::Deny => "deny",
+            RequestOutcome::Success => "success",
+            RequestOutcome::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for RequestOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 impl Metrics {
     /// Creates a new metrics collector with the given configuration.
     pub fn new(config: MetricsConfig) -> Self {
+        let mut latency_bounds: Vec<f64> =
+            config.latency_buckets.iter().copied().filter(|bound| bound.is_finite()).collect();
+        latency_bounds.sort_by(f64::total_cmp);
+        latency_bounds.dedup();
+
         Self {
             inner: Arc::new(MetricsInner {
                 config,
@@ -121,9 +200,16 @@ impl Metrics {
                 write_latency_sum_ns: AtomicU64::new(0),
                 write_latency_count: AtomicU64::new(0),
                 connection_pool_size: AtomicU64::new(0),
+                connection_pool_limit: AtomicU64::new(0),
                 connection_errors: AtomicU64::new(0),
                 custom_counters: RwLock::new(HashMap::new()),
                 custom_gauges: RwLock::new(HashMap::new()),
+                latency_bounds,
+                requests: RwLock::new(BTreeMap::new()),
+                cache_hits: AtomicU64::new(0),
+                cache_misses: AtomicU64::new(0),
+                retries: RwLock::new(BTreeMap::new()),
+                circuit_transitions: RwLock::new(BTreeMap::new()),
             }),
         }
     }
@@ -175,11 +261,299 @@ impl Metrics {
     /// Sets the connection pool size gauge.
     pub fn set_connection_pool_size(&self, size: u64) {
         self.inner.connection_pool_size.store(size, Ordering::Relaxed);
+
+        #[cfg(feature = "metrics")]
+        ::metrics::gauge!(self.metric_name("connection_pool_size"), self.facade_labels(&[]))
+            .set(size as f64);
+    }
+
+    /// Sets the connection pool limit gauge: the most connections the
+    /// client's pools may open, across all endpoints.
+    pub fn set_connection_pool_limit(&self, limit: u64) {
+        self.inner.connection_pool_limit.store(limit, Ordering::Relaxed);
+
+        #[cfg(feature = "metrics")]
+        ::metrics::gauge!(self.metric_name("connection_pool_limit"), self.facade_labels(&[]))
+            .set(limit as f64);
     }
 
     /// Increments the connection error counter.
     pub fn increment_connection_errors(&self) {
         self.inner.connection_errors.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!(self.metric_name("connection_errors_total"), self.facade_labels(&[]))
+            .increment(1);
+    }
+
+    /// Records one completed SDK request.
+    ///
+    /// Feeds the `requests_total` counter and, when histograms are enabled,
+    /// the `request_duration_seconds` histogram, both labelled by
+    /// `operation`, `transport` and `outcome`.
+    pub fn record_request(
+        &self,
+        operation: &str,
+        transport: Transport,
+        outcome: RequestOutcome,
+        duration: Duration,
+    ) {
+        let key = RequestKey {
+            operation: operation.to_string(),
+            transport: transport_label(transport),
+            outcome: outcome.as_str(),
+        };
+        let seconds = duration.as_secs_f64();
+        let bucket = self.inner.latency_bounds.iter().position(|bound| seconds <= *bound);
+
+        let observe = |series: &RequestSeries| {
+            series.count.fetch_add(1, Ordering::Relaxed);
+            series.latency_sum_ns.fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+            if let Some(index) = bucket {
+                series.buckets[index].fetch_add(1, Ordering::Relaxed);
+            }
+        };
+
+        let requests = self.inner.requests.read();
+        if let Some(series) = requests.get(&key) {
+            observe(series);
+        } else {
+            drop(requests);
+            let bounds = self.inner.latency_bounds.len();
+            let mut requests = self.inner.requests.write();
+            observe(requests.entry(key.clone()).or_insert_with(|| RequestSeries::new(bounds)));
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            let labels = self.facade_labels(&[
+                ("operation", &key.operation),
+                ("transport", key.transport),
+                ("outcome", key.outcome),
+            ]);
+            ::metrics::counter!(self.metric_name("requests_total"), labels.clone()).increment(1);
+            if self.inner.config.histograms_enabled {
+                ::metrics::histogram!(self.metric_name("request_duration_seconds"), labels)
+                    .record(seconds);
+            }
+        }
+    }
+
+    /// Records a lookup in the client's decision cache.
+    pub fn record_cache_lookup(&self, hit: bool) {
+        let (counter, _name) = if hit {
+            (&self.inner.cache_hits, "cache_hit_total")
+        } else {
+            (&self.inner.cache_misses, "cache_miss_total")
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!(self.metric_name(_name), self.facade_labels(&[])).increment(1);
+    }
+
+    /// Increments the retry counter for the given transport.
+    pub fn increment_retries(&self, transport: Transport) {
+        let label = transport_label(transport);
+        increment_series(&self.inner.retries, label);
+
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!(
+            self.metric_name("retry_total"),
+            self.facade_labels(&[("transport", label)])
+        )
+        .increment(1);
+    }
+
+    /// Records a circuit breaker state transition for an endpoint.
+    ///
+    /// Load-balanced clients report an endpoint ejection as a transition to
+    /// [`CircuitState::Open`] and its readmission as a transition to
+    /// [`CircuitState::Closed`].
+    pub fn record_circuit_transition(&self, endpoint: &str, state: CircuitState) {
+        let label = circuit_state_label(state);
+        increment_series(&self.inner.circuit_transitions, (endpoint.to_string(), label));
+
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!(
+            self.metric_name("circuit_breaker_transitions_total"),
+            self.facade_labels(&[("endpoint", endpoint), ("state", label)])
+        )
+        .increment(1);
+    }
+
+    /// Renders every collected series in the OpenMetrics text format.
+    ///
+    /// The output is suitable for serving from a `/metrics` endpoint scraped
+    /// by Prometheus.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use inferadb::Transport;
+    /// use inferadb::tracing_support::{Metrics, RequestOutcome};
+    ///
+    /// let metrics = Metrics::default();
+    /// metrics.record_request("check", Transport::Grpc, RequestOutcome::Allow, Duration::from_millis(3));
+    ///
+    /// let text = metrics.render_prometheus();
+    /// assert!(text.contains(
+    ///     r#"inferadb_requests_total{operation="check",outcome="allow",transport="grpc"} 1"#
+    /// ));
+    /// assert!(text.ends_with("# EOF\n"));
+    /// ```
+    pub fn render_prometheus(&self) -> String {
+        let inner = &*self.inner;
+        let mut global: Vec<(&str, &str)> = inner
+            .config
+            .global_labels
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        global.sort_unstable();
+
+        let mut out = String::new();
+        let sample = |out: &mut String, name: &str, labels: &[(&str, &str)], value: &str| {
+            let _ = write!(out, "{}", name);
+            write_labels(out, global.iter().chain(labels));
+            let _ = writeln!(out, " {}", value);
+        };
+
+        let requests = inner.requests.read();
+        let name = self.metric_name("requests");
+        write_family(
+            &mut out,
+            &name,
+            "counter",
+            "SDK requests by operation, transport and outcome.",
+        );
+        for (key, series) in requests.iter() {
+            let labels = key.labels();
+            let count = series.count.load(Ordering::Relaxed).to_string();
+            sample(&mut out, &format!("{}_total", name), &labels, &count);
+        }
+
+        if inner.config.histograms_enabled {
+            let name = self.metric_name("request_duration_seconds");
+            write_family(&mut out, &name, "histogram", "SDK request latency in seconds.");
+            for (key, series) in requests.iter() {
+                let labels = key.labels();
+                let mut cumulative = 0;
+                for (bound, bucket) in inner.latency_bounds.iter().zip(&series.buckets) {
+                    cumulative += bucket.load(Ordering::Relaxed);
+                    let le = format_float(*bound);
+                    let mut bucket_labels = labels.to_vec();
+                    bucket_labels.push(("le", &le));
+                    sample(
+                        &mut out,
+                        &format!("{}_bucket", name),
+                        &bucket_labels,
+                        &cumulative.to_string(),
+                    );
+                }
+                let count = series.count.load(Ordering::Relaxed).to_string();
+                let mut inf_labels = labels.to_vec();
+                inf_labels.push(("le", "+Inf"));
+                sample(&mut out, &format!("{}_bucket", name), &inf_labels, &count);
+                let sum = series.latency_sum_ns.load(Ordering::Relaxed) as f64 / 1e9;
+                sample(&mut out, &format!("{}_sum", name), &labels, &format_float(sum));
+                sample(&mut out, &format!("{}_count", name), &labels, &count);
+            }
+        }
+        drop(requests);
+
+        for (family, help, counter) in [
+            ("cache_hit", "Decision cache hits.", &inner.cache_hits),
+            ("cache_miss", "Decision cache misses.", &inner.cache_misses),
+            ("connection_errors", "Connection errors.", &inner.connection_errors),
+        ] {
+            let name = self.metric_name(family);
+            write_family(&mut out, &name, "counter", help);
+            let value = counter.load(Ordering::Relaxed).to_string();
+            sample(&mut out, &format!("{}_total", name), &[], &value);
+        }
+
+        let name = self.metric_name("retry");
+        write_family(&mut out, &name, "counter", "Request retries by transport.");
+        for (transport, counter) in inner.retries.read().iter() {
+            let value = counter.load(Ordering::Relaxed).to_string();
+            sample(&mut out, &format!("{}_total", name), &[("transport", transport)], &value);
+        }
+
+        let name = self.metric_name("circuit_breaker_transitions");
+        write_family(&mut out, &name, "counter", "Endpoint circuit breaker state transitions.");
+        for ((endpoint, state), counter) in inner.circuit_transitions.read().iter() {
+            let value = counter.load(Ordering::Relaxed).to_string();
+            let labels = [("endpoint", endpoint.as_str()), ("state", *state)];
+            sample(&mut out, &format!("{}_total", name), &labels, &value);
+        }
+
+        for (family, help, gauge) in [
+            ("connection_pool_size", "Connection pool size.", &inner.connection_pool_size),
+            (
+                "connection_pool_limit",
+                "Configured maximum connections across endpoints.",
+                &inner.connection_pool_limit,
+            ),
+        ] {
+            let name = self.metric_name(family);
+            write_family(&mut out, &name, "gauge", help);
+            let value = gauge.load(Ordering::Relaxed).to_string();
+            sample(&mut out, &name, &[], &value);
+        }
+
+        let mut counters: Vec<_> = inner
+            .custom_counters
+            .read()
+            .iter()
+            .map(|(key, value)| (sanitize_name(key), value.load(Ordering::Relaxed)))
+            .collect();
+        counters.sort_unstable();
+        for (key, value) in counters {
+            let name = self.metric_name(&key);
+            write_family(&mut out, &name, "counter", "Application-defined counter.");
+            sample(&mut out, &format!("{}_total", name), &[], &value.to_string());
+        }
+
+        let mut gauges: Vec<_> = inner
+            .custom_gauges
+            .read()
+            .iter()
+            .map(|(key, value)| (sanitize_name(key), value.load(Ordering::Relaxed)))
+            .collect();
+        gauges.sort_unstable();
+        for (key, value) in gauges {
+            let name = self.metric_name(&key);
+            write_family(&mut out, &name, "gauge", "Application-defined gauge.");
+            sample(&mut out, &name, &[], &value.to_string());
+        }
+
+        out.push_str("# EOF\n");
+        out
+    }
+
+    /// Returns the full metric name for `suffix`, applying the configured prefix.
+    fn metric_name(&self, suffix: &str) -> String {
+        let prefix = &self.inner.config.prefix;
+        if prefix.is_empty() { suffix.to_string() } else { format!("{}_{}", prefix, suffix) }
+    }
+
+    /// Builds facade labels from the global labels followed by `labels`.
+    #[cfg(feature = "metrics")]
+    fn facade_labels(&self, labels: &[(&str, &str)]) -> Vec<::metrics::Label> {
+        self.inner
+            .config
+            .global_labels
+            .iter()
+            .map(|(key, value)| ::metrics::Label::new(key.clone(), value.clone()))
+            .chain(
+                labels
+                    .iter()
+                    .map(|(key, value)| ::metrics::Label::new(key.to_string(), value.to_string())),
+            )
+            .collect()
     }
 
     /// Returns a custom counter, creating it if it doesn't exist.
@@ -234,7 +608,17 @@ impl Metrics {
             check_latency_avg_ns: if check_count > 0 { check_sum_ns / check_count } else { 0 },
             write_latency_avg_ns: if write_count > 0 { write_sum_ns / write_count } else { 0 },
             connection_pool_size: self.inner.connection_pool_size.load(Ordering::Relaxed),
+            connection_pool_limit: self.inner.connection_pool_limit.load(Ordering::Relaxed),
             connection_errors: self.inner.connection_errors.load(Ordering::Relaxed),
+            cache_hits: self.inner.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.inner.cache_misses.load(Ordering::Relaxed),
+            retries: self
+                .inner
+                .retries
+                .read()
+                .values()
+                .map(|counter| counter.load(Ordering::Relaxed))
+                .sum(),
         }
     }
 
@@ -251,9 +635,91 @@ impl Metrics {
         self.inner.write_latency_sum_ns.store(0, Ordering::Relaxed);
         self.inner.write_latency_count.store(0, Ordering::Relaxed);
         self.inner.connection_errors.store(0, Ordering::Relaxed);
+        self.inner.requests.write().clear();
+        self.inner.cache_hits.store(0, Ordering::Relaxed);
+        self.inner.cache_misses.store(0, Ordering::Relaxed);
+        self.inner.retries.write().clear();
+        self.inner.circuit_transitions.write().clear();
     }
 }
 
+impl RequestKey {
+    fn labels(&self) -> [(&str, &str); 3] {
+        [("operation", &self.operation), ("outcome", self.outcome), ("transport", self.transport)]
+    }
+}
+
+/// Increments the counter stored under `key`, creating it if needed.
+fn increment_series<K: Ord>(series: &RwLock<BTreeMap<K, AtomicU64>>, key: K) {
+    let read = series.read();
+    if let Some(counter) = read.get(&key) {
+        counter.fetch_add(1, Ordering::Relaxed);
+        return;
+    }
+    drop(read);
+    series.write().entry(key).or_insert_with(|| AtomicU64::new(0)).fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns the `transport` label value for a transport.
+fn transport_label(transport: Transport) -> &'static str {
+    match transport {
+        Transport::Grpc => "grpc",
+        Transport::Http => "rest",
+        Transport::Mock => "mock",
+    }
+}
+
+/// Returns the `state` label value for a circuit state.
+fn circuit_state_label(state: CircuitState) -> &'static str {
+    match state {
+        CircuitState::Closed => "closed",
+        CircuitState::Open => "open",
+        CircuitState::HalfOpen => "half_open",
+    }
+}
+
+/// Writes the `# TYPE` and `# HELP` lines introducing a metric family.
+fn write_family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+}
+
+/// Writes a label set, escaping values as OpenMetrics requires.
+fn write_labels<'a>(out: &mut String, labels: impl Iterator<Item = &'a (&'a str, &'a str)>) {
+    let mut first = true;
+    for (key, value) in labels {
+        out.push(if first { '{' } else { ',' });
+        first = false;
+        let _ = write!(out, "{}=\"", key);
+        for c in value.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+    if !first {
+        out.push('}');
+    }
+}
+
+/// Formats a float so that whole numbers keep a decimal point (`1.0`).
+fn format_float(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{:.1}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Replaces characters that are not valid in a metric name with `_`.
+fn sanitize_name(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }).collect()
+}
+
 impl Default for Metrics {
     fn default() -> Self {
         Self::new(MetricsConfig::default())
@@ -281,8 +747,16 @@ pub struct MetricsSnapshot {
     pub write_latency_avg_ns: u64,
     /// Current connection pool size.
     pub connection_pool_size: u64,
+    /// Configured maximum connections across endpoints.
+    pub connection_pool_limit: u64,
     /// Total connection errors.
     pub connection_errors: u64,
+    /// Total decision cache hits.
+    pub cache_hits: u64,
+    /// Total decision cache misses.
+    pub cache_misses: u64,
+    /// Total request retries across transports.
+    pub retries: u64,
 }
 
 impl MetricsSnapshot {
@@ -312,6 +786,15 @@ impl MetricsSnapshot {
         }
         self.check_errors as f64 / total as f64
     }
+
+    /// Returns the decision cache hit rate (0.0 - 1.0).
+    pub fn cache_hit_rate(&self) -> f64 {
+        let total = self.cache_hits + self.cache_misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.cache_hits as f64 / total as f64
+    }
 }
 
 /// A counter metric that can only be incremented.
@@ -819,6 +1302,99 @@ mod tests {
         assert!(debug.contains("MetricsSnapshot"));
     }
 
+    #[test]
+    fn test_record_request_series() {
+        let metrics = Metrics::default();
+        metrics.record_request(
+            "check",
+            Transport::Grpc,
+            RequestOutcome::Allow,
+            Duration::from_millis(3),
+        );
+        metrics.record_request(
+            "check",
+            Transport::Grpc,
+            RequestOutcome::Allow,
+            Duration::from_millis(30),
+        );
+        metrics.record_request("write", Transport::Http, RequestOutcome::Error, Duration::ZERO);
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains(
+            r#"inferadb_requests_total{operation="check",outcome="allow",transport="grpc"} 2"#
+        ));
+        assert!(text.contains(
+            r#"inferadb_requests_total{operation="write",outcome="error",transport="rest"} 1"#
+        ));
+        // Buckets are cumulative.
+        assert!(text.contains(
+            r#"inferadb_request_duration_seconds_bucket{operation="check",outcome="allow",transport="grpc",le="0.005"} 1"#
+        ));
+        assert!(text.contains(
+            r#"inferadb_request_duration_seconds_bucket{operation="check",outcome="allow",transport="grpc",le="0.05"} 2"#
+        ));
+        assert!(text.contains(
+            r#"inferadb_request_duration_seconds_bucket{operation="check",outcome="allow",transport="grpc",le="+Inf"} 2"#
+        ));
+        assert!(text.contains(
+            r#"inferadb_request_duration_seconds_sum{operation="check",outcome="allow",transport="grpc"} 0.033"#
+        ));
+    }
+
+    #[test]
+    fn test_render_prometheus_format() {
+        let config = MetricsConfig::builder()
+            .prefix("app")
+            .histograms_enabled(false)
+            .build()
+            .with_label("region", "us\"east");
+        let metrics = Metrics::new(config);
+        metrics.record_request("check", Transport::Mock, RequestOutcome::Deny, Duration::ZERO);
+        metrics.record_cache_lookup(true);
+        metrics.increment_retries(Transport::Http);
+        metrics.set_connection_pool_size(100);
+        metrics.set_connection_pool_limit(400);
+        metrics.counter("jobs.run").increment();
+
+        let text = metrics.render_prometheus();
+        assert!(text.starts_with("# TYPE app_requests counter\n"));
+        assert!(text.ends_with("# EOF\n"));
+        assert!(!text.contains("request_duration_seconds"));
+        assert!(text.contains(r#"app_cache_hit_total{region="us\"east"} 1"#));
+        assert!(text.contains(r#"app_cache_miss_total{region="us\"east"} 0"#));
+        assert!(text.contains(r#"app_retry_total{region="us\"east",transport="rest"} 1"#));
+        assert!(text.contains("# TYPE app_connection_pool_size gauge\n"));
+        assert!(text.contains(r#"app_connection_pool_size{region="us\"east"} 100"#));
+        assert!(text.contains("# TYPE app_connection_pool_limit gauge\n"));
+        assert!(text.contains(r#"app_connection_pool_limit{region="us\"east"} 400"#));
+        assert!(text.contains(r#"app_jobs_run_total{region="us\"east"} 1"#));
+    }
+
+    #[test]
+    fn test_circuit_transitions_and_reset() {
+        let metrics = Metrics::default();
+        metrics.record_circuit_transition("https://a.example.com", CircuitState::Open);
+        metrics.record_circuit_transition("https://a.example.com", CircuitState::HalfOpen);
+        metrics.record_cache_lookup(false);
+        metrics.increment_retries(Transport::Grpc);
+        metrics.increment_retries(Transport::Http);
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains(
+            r#"inferadb_circuit_breaker_transitions_total{endpoint="https://a.example.com",state="half_open"} 1"#
+        ));
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.retries, 2);
+        assert_eq!(snapshot.cache_misses, 1);
+        assert_eq!(snapshot.cache_hit_rate(), 0.0);
+
+        metrics.reset();
+        let text = metrics.render_prometheus();
+        assert!(!text.contains("a.example.com"));
+        assert!(!text.contains("inferadb_retry_total{"));
+        assert_eq!(metrics.snapshot().retries, 0);
+    }
+
     #[test]
     fn test_metrics_snapshot_clone() {
         let metrics = Metrics::default();
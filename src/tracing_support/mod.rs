@@ -5,16 +5,17 @@
 //!
 //! ## Features
 //!
-//! - Request/response logging
-//! - Span propagation (W3C Trace Context)
-//! - OpenTelemetry integration
+//! - A span for every engine and control-plane call, using OpenTelemetry
+//!   semantic attribute names (`rpc.method`, `http.request.method`, ...)
+//! - Trace context propagation (W3C Trace Context or B3) into REST headers and
+//!   gRPC metadata
 //! - Metrics collection
 //!
 //! ## Example
 //!
 //! ```rust,ignore
 //! use tracing_subscriber::prelude::*;
-//! use inferadb::tracing_support::TraceContext;
+//! use inferadb::tracing_support::{Propagator, W3CTraceContext};
 //!
 //! // Set up tracing subscriber
 //! tracing_subscriber::registry()
@@ -22,7 +23,7 @@
 //!     .init();
 //!
 //! // Propagate trace context from incoming request
-//! let trace_ctx = TraceContext::from_headers(&headers)?;
+//! let trace_ctx = W3CTraceContext.extract(&headers)?;
 //!
 //! // SDK automatically creates spans for operations
 //! let allowed = vault.check("user:alice", "view", "doc:1")
@@ -34,12 +35,21 @@
 #![allow(dead_code)]
 
 mod context;
+mod instrument;
 mod metrics;
 mod propagator;
 mod span;
 
 pub use context::{SpanId, TraceContext, TraceFlags, TraceId};
-pub use metrics::{Counter, Gauge, Histogram, Metrics, MetricsConfig};
+pub use instrument::TracePropagation;
+#[cfg(feature = "rest")]
+pub(crate) use instrument::control_span;
+#[cfg(any(feature = "grpc", feature = "rest"))]
+pub(crate) use instrument::inject_current;
+pub(crate) use instrument::{engine_span, instrument, with_context};
+pub use metrics::{
+    Counter, Gauge, Histogram, Metrics, MetricsConfig, MetricsSnapshot, RequestOutcome,
+};
 pub use propagator::{B3Propagator, HeaderExtractor, HeaderInjector, Propagator, W3CTraceContext};
 pub use span::{InferaDbSpan, SpanKind, SpanStatus};
 
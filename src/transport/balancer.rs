@@ -0,0 +1,802 @@
+//! Client-side load balancing across multiple endpoints.
+//!
+//! [`BalancedTransport`] wraps one transport per endpoint and distributes
+//! requests across them according to a [`LoadBalancingStrategy`]. Failed
+//! requests are retried on the next available endpoint, and endpoints that
+//! keep failing are ejected from rotation according to the configured
+//! [`OutlierDetectionConfig`].
+
+use std::{
+    sync::atomic::{AtomicU32, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use super::traits::{
+    AnyTransport, BoxFuture, CheckRequest, CheckResponse, EndpointStats, GrpcStats,
+    ListRelationshipsResponse, ListResourcesResponse, ListSubjectsResponse, RestStats,
+    SimulateBatchRequest, SimulateRequest, SimulateResponse, Transport, TransportClient,
+    TransportStats, WatchEventStream, WatchRequest, WriteRequest, WriteResponse,
+};
+use crate::{
+    Error, ErrorKind,
+    config::{CircuitState, LoadBalancingStrategy, OutlierDetectionConfig},
+    types::{ConsistencyRequirement, ConsistencyToken, Relationship},
+};
+
+/// Smoothing factor for the latency EWMA (weight of the newest sample).
+const EWMA_ALPHA: f64 = 0.3;
+
+/// Mutable per-endpoint bookkeeping.
+#[derive(Debug, Default)]
+struct EndpointState {
+    requests_sent: u64,
+    requests_failed: u64,
+    consecutive_failures: u32,
+    latency_ewma: Option<Duration>,
+    ejections: u32,
+    ejected_until: Option<Instant>,
+}
+
+impl EndpointState {
+    fn is_ejected(&self, now: Instant) -> bool {
+        self.ejected_until.is_some_and(|until| until > now)
+    }
+
+    fn observe_latency(&mut self, latency: Duration) {
+        self.latency_ewma = Some(match self.latency_ewma {
+            Some(previous) => previous.mul_f64(1.0 - EWMA_ALPHA) + latency.mul_f64(EWMA_ALPHA),
+            None => latency,
+        });
+    }
+}
+
+/// A single endpoint and its transport.
+struct Endpoint {
+    url: String,
+    transport: AnyTransport,
+    outstanding: AtomicU32,
+    state: Mutex<EndpointState>,
+}
+
+/// Decrements an endpoint's in-flight counter when the request finishes or
+/// is cancelled.
+struct OutstandingGuard<'a>(&'a AtomicU32);
+
+impl<'a> OutstandingGuard<'a> {
+    fn new(counter: &'a AtomicU32) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for OutstandingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Result of actively probing one endpoint.
+#[derive(Debug, Clone)]
+pub struct EndpointProbe {
+    /// Endpoint URL.
+    pub url: String,
+    /// Round-trip time of the probe.
+    pub latency: Duration,
+    /// Error returned by the probe, if it failed.
+    pub error: Option<String>,
+}
+
+/// Transport that load balances requests across multiple endpoints.
+pub struct BalancedTransport {
+    endpoints: Vec<Endpoint>,
+    strategy: LoadBalancingStrategy,
+    outlier_detection: OutlierDetectionConfig,
+    next: AtomicUsize,
+    #[cfg(feature = "tracing")]
+    metrics: Option<crate::tracing_support::Metrics>,
+}
+
+impl BalancedTransport {
+    /// Creates a balanced transport from `(url, transport)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if no endpoints are provided.
+    pub fn new(
+        endpoints: Vec<(String, AnyTransport)>,
+        strategy: LoadBalancingStrategy,
+        outlier_detection: OutlierDetectionConfig,
+    ) -> Result<Self, Error> {
+        if endpoints.is_empty() {
+            return Err(Error::configuration("at least one endpoint is required"));
+        }
+
+        let endpoints = endpoints
+            .into_iter()
+            .map(|(url, transport)| Endpoint {
+                url,
+                transport,
+                outstanding: AtomicU32::new(0),
+                state: Mutex::new(EndpointState::default()),
+            })
+            .collect();
+
+        Ok(Self {
+            endpoints,
+            strategy,
+            outlier_detection,
+            next: AtomicUsize::new(0),
+            #[cfg(feature = "tracing")]
+            metrics: None,
+        })
+    }
+
+    /// Reports endpoint ejections and readmissions, and the retries of
+    /// every endpoint transport, to `metrics`.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn attach_metrics(&mut self, metrics: &crate::tracing_support::Metrics) {
+        for endpoint in &mut self.endpoints {
+            endpoint.transport.attach_metrics(metrics);
+        }
+        self.metrics = Some(metrics.clone());
+    }
+
+    /// Returns the number of endpoints.
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// Returns the load balancing strategy.
+    pub fn strategy(&self) -> LoadBalancingStrategy {
+        self.strategy
+    }
+
+    /// Returns the indices of endpoints currently in rotation.
+    ///
+    /// Endpoints whose ejection has expired are readmitted here.
+    fn available(&self, now: Instant) -> Vec<usize> {
+        self.endpoints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, endpoint)| {
+                let mut state = endpoint.state.lock();
+                if state.ejected_until.is_some_and(|until| until <= now) {
+                    state.ejected_until = None;
+                    state.consecutive_failures = 0;
+                    self.record_transition(index, CircuitState::Closed);
+                }
+                (!state.is_ejected(now)).then_some(index)
+            })
+            .collect()
+    }
+
+    /// Selects the endpoint for the next attempt, skipping `tried`.
+    ///
+    /// Falls back to ejected endpoints when no healthy endpoint remains, so
+    /// that a fully ejected pool still gets a chance to serve traffic.
+    fn select(&self, tried: &[usize]) -> Option<usize> {
+        let now = Instant::now();
+        let mut candidates: Vec<usize> =
+            self.available(now).into_iter().filter(|index| !tried.contains(index)).collect();
+        if candidates.is_empty() {
+            candidates = (0..self.endpoints.len()).filter(|index| !tried.contains(index)).collect();
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+
+        // Rotate the starting point so ties are broken fairly.
+        let offset = self.next.fetch_add(1, Ordering::Relaxed) % candidates.len();
+        candidates.rotate_left(offset);
+
+        match self.strategy {
+            LoadBalancingStrategy::RoundRobin => candidates.first().copied(),
+            LoadBalancingStrategy::LeastOutstanding => candidates
+                .into_iter()
+                .min_by_key(|&index| self.endpoints[index].outstanding.load(Ordering::Relaxed)),
+            LoadBalancingStrategy::LatencyWeighted => {
+                candidates.into_iter().min_by_key(|&index| self.latency_score(index))
+            },
+        }
+    }
+
+    /// Returns the latency-weighted cost of sending a request to an endpoint.
+    ///
+    /// Endpoints without latency samples score zero so they are explored.
+    fn latency_score(&self, index: usize) -> u128 {
+        let endpoint = &self.endpoints[index];
+        let ewma = endpoint.state.lock().latency_ewma.unwrap_or_default();
+        let outstanding = u128::from(endpoint.outstanding.load(Ordering::Relaxed)) + 1;
+        ewma.as_nanos().saturating_mul(outstanding)
+    }
+
+    /// Records the outcome of a request against an endpoint.
+    fn record(&self, index: usize, latency: Duration, error: Option<&Error>) {
+        let endpoint_failure =
+            error.is_some_and(|e| self.outlier_detection.is_endpoint_failure(e.kind()));
+
+        let mut state = self.endpoints[index].state.lock();
+        state.requests_sent += 1;
+
+        if !endpoint_failure {
+            // Application-level errors still prove the endpoint is reachable.
+            state.consecutive_failures = 0;
+            state.observe_latency(latency);
+            return;
+        }
+
+        state.requests_failed += 1;
+        state.consecutive_failures += 1;
+
+        let threshold = self.outlier_detection.get_consecutive_failures();
+        if self.outlier_detection.is_enabled() && state.consecutive_failures >= threshold {
+            drop(state);
+            self.eject(index);
+        }
+    }
+
+    /// Ejects an endpoint unless doing so would exceed the ejection budget.
+    fn eject(&self, index: usize) {
+        let now = Instant::now();
+        let total = self.endpoints.len();
+        let ejected = self
+            .endpoints
+            .iter()
+            .enumerate()
+            .filter(|(i, endpoint)| *i != index && endpoint.state.lock().is_ejected(now))
+            .count();
+
+        let percent = usize::from(self.outlier_detection.get_max_ejection_percent());
+        let budget = (total * percent / 100).min(total.saturating_sub(1));
+        if ejected >= budget {
+            return;
+        }
+
+        let mut state = self.endpoints[index].state.lock();
+        if state.is_ejected(now) {
+            return;
+        }
+        state.ejections += 1;
+        state.ejected_until = Some(now + self.outlier_detection.ejection_time(state.ejections));
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            endpoint = %self.endpoints[index].url,
+            ejections = state.ejections,
+            "endpoint ejected from load balancer rotation"
+        );
+        self.record_transition(index, CircuitState::Open);
+    }
+
+    /// Readmits an endpoint into rotation.
+    fn readmit(&self, index: usize) {
+        let mut state = self.endpoints[index].state.lock();
+        if state.ejected_until.take().is_some() {
+            self.record_transition(index, CircuitState::Closed);
+        }
+        state.consecutive_failures = 0;
+    }
+
+    /// Reports an endpoint entering or leaving rotation to the metrics
+    /// collector, if one is attached.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn record_transition(&self, index: usize, state: CircuitState) {
+        #[cfg(feature = "tracing")]
+        if let Some(metrics) = &self.metrics {
+            metrics.record_circuit_transition(&self.endpoints[index].url, state);
+        }
+    }
+
+    /// Runs an operation against the selected endpoint, failing over to the
+    /// next endpoint when the error indicates the endpoint is unhealthy.
+    async fn execute<'a, T, F>(&'a self, op: F) -> Result<T, Error>
+    where
+        F: Fn(&'a AnyTransport) -> BoxFuture<'a, Result<T, Error>>,
+    {
+        self.execute_with(true, op).await
+    }
+
+    /// Runs a mutation. Unless `idempotent`, it only fails over on errors
+    /// raised before the request reached the endpoint: after a timeout or
+    /// server error the first endpoint may already have applied it.
+    async fn execute_mutation<'a, T, F>(&'a self, idempotent: bool, op: F) -> Result<T, Error>
+    where
+        F: Fn(&'a AnyTransport) -> BoxFuture<'a, Result<T, Error>>,
+    {
+        self.execute_with(idempotent, op).await
+    }
+
+    async fn execute_with<'a, T, F>(&'a self, retry_after_send: bool, op: F) -> Result<T, Error>
+    where
+        F: Fn(&'a AnyTransport) -> BoxFuture<'a, Result<T, Error>>,
+    {
+        let mut tried = Vec::with_capacity(self.endpoints.len());
+        let mut last_error = None;
+
+        while let Some(index) = self.select(&tried) {
+            let endpoint = &self.endpoints[index];
+            let start = Instant::now();
+            let result = {
+                let _guard = OutstandingGuard::new(&endpoint.outstanding);
+                op(&endpoint.transport).await
+            };
+            self.record(index, start.elapsed(), result.as_ref().err());
+
+            match result {
+                Err(error)
+                    if self.outlier_detection.is_endpoint_failure(error.kind())
+                        && (retry_after_send
+                            || matches!(
+                                error.kind(),
+                                ErrorKind::Connection | ErrorKind::Unavailable
+                            )) =>
+                {
+                    tried.push(index);
+                    last_error = Some(error);
+                },
+                other => return other,
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| Error::unavailable("no endpoints available")))
+    }
+
+    /// Opens a watch stream on the first endpoint that accepts it.
+    ///
+    /// Only establishing the stream fails over; once open, the stream stays
+    /// on its endpoint until it ends.
+    pub async fn watch(&self, request: WatchRequest) -> Result<WatchEventStream, Error> {
+        self.execute(|t| t.watch(request.clone())).await
+    }
+
+    /// Actively health checks every endpoint.
+    ///
+    /// Healthy endpoints are readmitted immediately; failing endpoints are
+    /// ejected (subject to the ejection budget).
+    pub async fn probe(&self) -> Vec<EndpointProbe> {
+        let probes = self.endpoints.iter().map(|endpoint| async move {
+            let start = Instant::now();
+            let result = endpoint.transport.health_check().await;
+            (start.elapsed(), result)
+        });
+        let results = futures::future::join_all(probes).await;
+
+        results
+            .into_iter()
+            .enumerate()
+            .map(|(index, (latency, result))| {
+                match &result {
+                    Ok(()) => {
+                        self.readmit(index);
+                        self.endpoints[index].state.lock().observe_latency(latency);
+                    },
+                    Err(_) => self.eject(index),
+                }
+                EndpointProbe {
+                    url: self.endpoints[index].url.clone(),
+                    latency,
+                    error: result.err().map(|e| e.to_string()),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns per-endpoint statistics.
+    pub fn endpoint_stats(&self) -> Vec<EndpointStats> {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .map(|endpoint| {
+                let state = endpoint.state.lock();
+                EndpointStats {
+                    url: endpoint.url.clone(),
+                    healthy: !state.is_ejected(now),
+                    outstanding: endpoint.outstanding.load(Ordering::Relaxed),
+                    requests_sent: state.requests_sent,
+                    requests_failed: state.requests_failed,
+                    consecutive_failures: state.consecutive_failures,
+                    latency_ewma: state.latency_ewma,
+                    ejections: state.ejections,
+                    ejected_until: state.ejected_until.filter(|until| *until > now),
+                }
+            })
+            .collect()
+    }
+}
+
+impl TransportClient for BalancedTransport {
+    async fn check(&self, request: CheckRequest) -> Result<CheckResponse, Error> {
+        self.execute(|t| t.check(request.clone())).await
+    }
+
+    async fn check_batch(&self, requests: Vec<CheckRequest>) -> Result<Vec<CheckResponse>, Error> {
+        self.execute(|t| t.check_batch(requests.clone())).await
+    }
+
+    async fn write(&self, request: WriteRequest) -> Result<WriteResponse, Error> {
+        self.execute_mutation(request.idempotency_key.is_some(), |t| t.write(request.clone())).await
+    }
+
+    async fn write_batch(&self, requests: Vec<WriteRequest>) -> Result<WriteResponse, Error> {
+        let idempotent = requests.iter().all(|r| r.idempotency_key.is_some());
+        self.execute_mutation(idempotent, |t| t.write_batch(requests.clone())).await
+    }
+
+    async fn delete(
+        &self,
+        relationship: Relationship<'static>,
+    ) -> Result<Option<ConsistencyToken>, Error> {
+        self.execute_mutation(false, |t| t.delete(relationship.clone())).await
+    }
+
+    async fn list_relationships(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> Result<ListRelationshipsResponse, Error> {
+        self.execute(|t| {
+            t.list_relationships(resource, relation, subject, limit, cursor, consistency)
+        })
+        .await
+    }
+
+    async fn list_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> Result<ListResourcesResponse, Error> {
+        self.execute(|t| {
+            t.list_resources(subject, permission, resource_type, limit, cursor, consistency)
+        })
+        .await
+    }
+
+    async fn list_subjects(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> Result<ListSubjectsResponse, Error> {
+        self.execute(|t| {
+            t.list_subjects(permission, resource, subject_type, limit, cursor, consistency)
+        })
+        .await
+    }
+
+    fn transport_type(&self) -> Transport {
+        self.endpoints.first().map(|e| e.transport.transport_type()).unwrap_or_default()
+    }
+
+    fn stats(&self) -> TransportStats {
+        let mut stats =
+            TransportStats { active_transport: self.transport_type(), ..Default::default() };
+
+        for endpoint in &self.endpoints {
+            let endpoint_stats = endpoint.transport.stats();
+            stats.fallback_count += endpoint_stats.fallback_count;
+            if let Some(grpc) = endpoint_stats.grpc {
+                let total = stats.grpc.get_or_insert_with(GrpcStats::default);
+                total.requests_sent += grpc.requests_sent;
+                total.requests_failed += grpc.requests_failed;
+                total.streams_opened += grpc.streams_opened;
+                total.streams_active += grpc.streams_active;
+            }
+            if let Some(rest) = endpoint_stats.rest {
+                let total = stats.rest.get_or_insert_with(RestStats::default);
+                total.requests_sent += rest.requests_sent;
+                total.requests_failed += rest.requests_failed;
+                total.sse_connections += rest.sse_connections;
+                total.sse_active += rest.sse_active;
+            }
+        }
+
+        stats.endpoints = self.endpoint_stats();
+        stats
+    }
+
+    async fn health_check(&self) -> Result<(), Error> {
+        let probes = self.probe().await;
+        if probes.iter().any(|probe| probe.error.is_none()) {
+            return Ok(());
+        }
+        let details: Vec<String> = probes
+            .into_iter()
+            .map(|probe| format!("{}: {}", probe.url, probe.error.unwrap_or_default()))
+            .collect();
+        Err(Error::unavailable(format!("all endpoints unhealthy ({})", details.join("; "))))
+    }
+
+    async fn simulate(&self, request: SimulateRequest) -> Result<SimulateResponse, Error> {
+        self.execute(|t| t.simulate(request.clone())).await
+    }
+
+    async fn simulate_batch(
+        &self,
+        request: SimulateBatchRequest,
+    ) -> Result<Vec<SimulateResponse>, Error> {
+        self.execute(|t| t.simulate_batch(request.clone())).await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::transport::mock::MockTransport;
+
+    fn balanced(
+        count: usize,
+        strategy: LoadBalancingStrategy,
+        outlier_detection: OutlierDetectionConfig,
+    ) -> BalancedTransport {
+        let endpoints = (0..count)
+            .map(|i| (format!("https://region-{}.example.com", i), MockTransport::new().into_any()))
+            .collect();
+        BalancedTransport::new(endpoints, strategy, outlier_detection).unwrap()
+    }
+
+    fn mock(transport: &BalancedTransport, index: usize) -> &MockTransport {
+        match &transport.endpoints[index].transport {
+            AnyTransport::Mock(mock) => mock,
+            #[allow(unreachable_patterns)]
+            _ => panic!("expected mock transport"),
+        }
+    }
+
+    fn check_request() -> CheckRequest {
+        CheckRequest {
+            subject: "user:alice".to_string(),
+            permission: "view".to_string(),
+            resource: "doc:1".to_string(),
+            context: None,
+            consistency: ConsistencyRequirement::Eventual,
+            trace: false,
+        }
+    }
+
+    #[test]
+    fn test_requires_endpoint() {
+        let result = BalancedTransport::new(
+            Vec::new(),
+            LoadBalancingStrategy::RoundRobin,
+            OutlierDetectionConfig::default(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_distributes_requests() {
+        let transport =
+            balanced(3, LoadBalancingStrategy::RoundRobin, OutlierDetectionConfig::default());
+
+        for _ in 0..6 {
+            transport.check(check_request()).await.unwrap();
+        }
+
+        for index in 0..3 {
+            assert_eq!(mock(&transport, index).request_count(), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_next_endpoint() {
+        let transport =
+            balanced(2, LoadBalancingStrategy::RoundRobin, OutlierDetectionConfig::default());
+        mock(&transport, 0).set_failure(Error::unavailable("down"));
+        mock(&transport, 1).set_failure(Error::unavailable("down"));
+
+        // First attempt fails on one endpoint, second fails on the other.
+        let result = transport.check(check_request()).await;
+        assert!(result.is_err());
+
+        // Failures are one-shot, so the next request succeeds.
+        assert!(transport.check(check_request()).await.is_ok());
+
+        let stats = transport.endpoint_stats();
+        assert_eq!(stats.iter().map(|s| s.requests_failed).sum::<u64>(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_application_errors_do_not_fail_over() {
+        let transport =
+            balanced(2, LoadBalancingStrategy::RoundRobin, OutlierDetectionConfig::default());
+        mock(&transport, 0).set_failure(Error::forbidden("nope"));
+        mock(&transport, 1).set_failure(Error::forbidden("nope"));
+
+        let error = transport.check(check_request()).await.unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Forbidden);
+
+        let total: u64 = (0..2).map(|i| mock(&transport, i).request_count()).sum();
+        assert_eq!(total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mutations_fail_over_only_before_send() {
+        let write = |key: Option<&str>| WriteRequest {
+            relationship: Relationship::new("doc:1", "viewer", "user:alice"),
+            idempotency_key: key.map(str::to_owned),
+        };
+        // Fails the first endpoint tried and returns how many endpoints saw the write
+        let attempt = |failure: Error, key: Option<&'static str>| async move {
+            let transport =
+                balanced(2, LoadBalancingStrategy::RoundRobin, OutlierDetectionConfig::default());
+            mock(&transport, 0).set_failure(failure);
+            let result = transport.write(write(key)).await;
+            let sent: u64 = (0..2).map(|i| mock(&transport, i).request_count()).sum();
+            (result, sent)
+        };
+
+        // The first endpoint may have applied the write before timing out
+        let (result, sent) = attempt(Error::timeout("slow"), None).await;
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::Timeout);
+        assert_eq!(sent, 1);
+
+        // A connection failure means the write was never sent
+        let (result, sent) = attempt(Error::connection("refused"), None).await;
+        assert!(result.is_ok());
+        assert_eq!(sent, 2);
+
+        // An idempotency key makes resending safe
+        let (result, sent) = attempt(Error::timeout("slow"), Some("idem_1")).await;
+        assert!(result.is_ok());
+        assert_eq!(sent, 2);
+    }
+
+    #[test]
+    fn test_ejection_after_consecutive_failures() {
+        let outliers = OutlierDetectionConfig::builder().consecutive_failures(2).build();
+        let transport = balanced(2, LoadBalancingStrategy::RoundRobin, outliers);
+        let error = Error::connection("refused");
+
+        transport.record(0, Duration::from_millis(1), Some(&error));
+        assert!(transport.endpoint_stats()[0].healthy);
+
+        transport.record(0, Duration::from_millis(1), Some(&error));
+        let stats = transport.endpoint_stats();
+        assert!(!stats[0].healthy);
+        assert_eq!(stats[0].ejections, 1);
+        assert!(stats[0].ejected_until.is_some());
+
+        // Ejected endpoints are skipped by selection.
+        for _ in 0..4 {
+            assert_eq!(transport.select(&[]), Some(1));
+        }
+    }
+
+    #[test]
+    fn test_ejection_budget_keeps_one_endpoint() {
+        let outliers = OutlierDetectionConfig::builder()
+            .consecutive_failures(1)
+            .max_ejection_percent(100)
+            .build();
+        let transport = balanced(2, LoadBalancingStrategy::RoundRobin, outliers);
+        let error = Error::unavailable("down");
+
+        transport.record(0, Duration::from_millis(1), Some(&error));
+        transport.record(1, Duration::from_millis(1), Some(&error));
+
+        let healthy = transport.endpoint_stats().iter().filter(|s| s.healthy).count();
+        assert_eq!(healthy, 1);
+    }
+
+    #[test]
+    fn test_readmission_after_ejection_expires() {
+        let outliers = OutlierDetectionConfig::builder()
+            .consecutive_failures(1)
+            .base_ejection_time(Duration::ZERO)
+            .build();
+        let transport = balanced(2, LoadBalancingStrategy::RoundRobin, outliers);
+
+        transport.record(0, Duration::from_millis(1), Some(&Error::unavailable("down")));
+        assert_eq!(transport.available(Instant::now()), vec![0, 1]);
+        assert_eq!(transport.endpoint_stats()[0].consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_probe_readmits_and_ejects() {
+        let outliers = OutlierDetectionConfig::builder().consecutive_failures(1).build();
+        let transport = balanced(2, LoadBalancingStrategy::RoundRobin, outliers);
+        transport.record(0, Duration::from_millis(1), Some(&Error::unavailable("down")));
+        assert!(!transport.endpoint_stats()[0].healthy);
+
+        mock(&transport, 1).set_failure(Error::unavailable("down"));
+        let probes = transport.probe().await;
+
+        assert!(probes[0].error.is_none());
+        assert!(probes[1].error.is_some());
+        let stats = transport.endpoint_stats();
+        assert!(stats[0].healthy);
+        assert!(!stats[1].healthy);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_ejection_and_readmission_record_transitions() {
+        let metrics = crate::tracing_support::Metrics::default();
+        let outliers = OutlierDetectionConfig::builder().consecutive_failures(1).build();
+        let mut transport = balanced(2, LoadBalancingStrategy::RoundRobin, outliers);
+        transport.attach_metrics(&metrics);
+
+        transport.record(0, Duration::from_millis(1), Some(&Error::unavailable("down")));
+        transport.probe().await;
+
+        let text = metrics.render_prometheus();
+        for state in ["open", "closed"] {
+            assert!(text.contains(&format!(
+                r#"inferadb_circuit_breaker_transitions_total{{endpoint="https://region-0.example.com",state="{}"}} 1"#,
+                state
+            )));
+        }
+        assert!(!text.contains("region-1"));
+    }
+
+    #[test]
+    fn test_least_outstanding_selection() {
+        let transport =
+            balanced(3, LoadBalancingStrategy::LeastOutstanding, OutlierDetectionConfig::default());
+        transport.endpoints[0].outstanding.store(5, Ordering::Relaxed);
+        transport.endpoints[1].outstanding.store(1, Ordering::Relaxed);
+        transport.endpoints[2].outstanding.store(3, Ordering::Relaxed);
+
+        for _ in 0..3 {
+            assert_eq!(transport.select(&[]), Some(1));
+        }
+    }
+
+    #[test]
+    fn test_latency_weighted_selection() {
+        let transport =
+            balanced(2, LoadBalancingStrategy::LatencyWeighted, OutlierDetectionConfig::default());
+        transport.record(0, Duration::from_millis(80), None);
+        transport.record(1, Duration::from_millis(5), None);
+
+        for _ in 0..3 {
+            assert_eq!(transport.select(&[]), Some(1));
+        }
+    }
+
+    #[test]
+    fn test_latency_ewma_smoothing() {
+        let mut state = EndpointState::default();
+        state.observe_latency(Duration::from_millis(100));
+        assert_eq!(state.latency_ewma, Some(Duration::from_millis(100)));
+
+        state.observe_latency(Duration::from_millis(0));
+        let ewma = state.latency_ewma.unwrap().as_micros();
+        assert!((69_999..=70_000).contains(&ewma));
+    }
+
+    #[test]
+    fn test_select_skips_tried_endpoints() {
+        let transport =
+            balanced(2, LoadBalancingStrategy::RoundRobin, OutlierDetectionConfig::default());
+        assert_eq!(transport.select(&[0]), Some(1));
+        assert_eq!(transport.select(&[0, 1]), None);
+    }
+
+    #[tokio::test]
+    async fn test_stats_include_endpoints() {
+        let transport =
+            balanced(2, LoadBalancingStrategy::RoundRobin, OutlierDetectionConfig::default());
+        transport.check(check_request()).await.unwrap();
+
+        let stats = transport.stats();
+        assert_eq!(stats.active_transport, Transport::Mock);
+        assert_eq!(stats.endpoints.len(), 2);
+        assert_eq!(stats.endpoints.iter().map(|s| s.requests_sent).sum::<u64>(), 1);
+        assert!(stats.endpoints.iter().all(|s| s.url.starts_with("https://region-")));
+    }
+}
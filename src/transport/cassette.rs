@@ -0,0 +1,1266 @@
+//! Record/replay transport for deterministic tests.
+//!
+//! A [`Cassette`] names a JSON file of recorded engine calls. In record mode,
+//! [`CassetteTransport`] forwards every call to the real transport and records
+//! the request and its outcome. In replay mode it answers calls from the file
+//! without touching the network, so a session captured once against a live
+//! server can be replayed in CI.
+//!
+//! Each watch connection is recorded as one interaction holding the frames
+//! received over it, events and errors alike. Replaying it yields the same
+//! frames and then ends the stream, as if the connection had closed.
+
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use futures::StreamExt;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+use super::traits::{
+    AnyTransport, BoxFuture, CheckRequest, CheckResponse, DecisionTrace, ListRelationshipsResponse,
+    ListResourcesResponse, ListSubjectsResponse, SimulateBatchRequest, SimulateRequest,
+    SimulateResponse, Transport, TransportStats, WatchEventStream, WatchRequest, WriteRequest,
+    WriteResponse,
+};
+use crate::{
+    Error, ErrorKind,
+    types::{ConsistencyRequirement, ConsistencyToken, Decision, Relationship},
+    vault::watch::WatchEvent,
+};
+
+/// Version of the cassette file format.
+const FORMAT_VERSION: u32 = 1;
+
+/// Every error kind, used to restore recorded errors by name.
+const ERROR_KINDS: [ErrorKind; 18] = [
+    ErrorKind::Unauthorized,
+    ErrorKind::Forbidden,
+    ErrorKind::NotFound,
+    ErrorKind::InvalidArgument,
+    ErrorKind::SchemaViolation,
+    ErrorKind::RateLimited,
+    ErrorKind::Unavailable,
+    ErrorKind::Timeout,
+    ErrorKind::Internal,
+    ErrorKind::Cancelled,
+    ErrorKind::CircuitOpen,
+    ErrorKind::Connection,
+    ErrorKind::Protocol,
+    ErrorKind::Configuration,
+    ErrorKind::Unknown,
+    ErrorKind::Conflict,
+    ErrorKind::Transport,
+    ErrorKind::InvalidResponse,
+];
+
+/// Whether a cassette is being recorded or replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Forward calls to the real transport and record them.
+    Record,
+    /// Answer calls from the recording without network access.
+    Replay,
+}
+
+/// A recorded session of engine calls stored as a JSON file.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use inferadb::testing::{Cassette, MatchRules};
+///
+/// // Capture a session against staging once...
+/// let cassette = Cassette::record("tests/cassettes/sharing.json");
+///
+/// // ...then replay it in CI, ignoring per-run idempotency keys
+/// let cassette = Cassette::replay("tests/cassettes/sharing.json")
+///     .with_rules(MatchRules::new().ignore_idempotency_keys());
+///
+/// let client = Client::builder()
+///     .url("https://staging.inferadb.com")
+///     .credentials(credentials)
+///     .cassette(cassette)
+///     .build()
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    rules: MatchRules,
+}
+
+impl Cassette {
+    /// Records calls to `path`, replacing any existing recording.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), mode: CassetteMode::Record, rules: MatchRules::default() }
+    }
+
+    /// Replays calls recorded at `path`.
+    pub fn replay(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), mode: CassetteMode::Replay, rules: MatchRules::default() }
+    }
+
+    /// Sets the rules used to match replayed requests to recorded ones.
+    #[must_use]
+    pub fn with_rules(mut self, rules: MatchRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Returns the path of the cassette file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns whether the cassette records or replays.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Returns the request matching rules.
+    pub fn rules(&self) -> &MatchRules {
+        &self.rules
+    }
+}
+
+/// Rules for matching a replayed request to a recorded one.
+///
+/// By default requests must match exactly. Fields that legitimately differ
+/// between runs can be ignored; ignored fields are removed at any depth,
+/// including inside ABAC context, before requests are compared.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::testing::MatchRules;
+///
+/// let rules = MatchRules::new()
+///     .ignore_request_ids()
+///     .ignore_idempotency_keys()
+///     .ignore_timestamps()
+///     .ignore_field("trace_id");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchRules {
+    ignored_fields: BTreeSet<String>,
+    ignore_timestamps: bool,
+}
+
+impl MatchRules {
+    /// Creates rules that require requests to match exactly.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ignores `request_id` fields.
+    #[must_use]
+    pub fn ignore_request_ids(self) -> Self {
+        self.ignore_field("request_id")
+    }
+
+    /// Ignores the idempotency keys of writes.
+    #[must_use]
+    pub fn ignore_idempotency_keys(self) -> Self {
+        self.ignore_field("idempotency_key")
+    }
+
+    /// Ignores timestamps: fields named `timestamp`, `time` or `now`, and
+    /// fields ending in `_at`.
+    #[must_use]
+    pub fn ignore_timestamps(mut self) -> Self {
+        self.ignore_timestamps = true;
+        self
+    }
+
+    /// Ignores every field named `name`.
+    #[must_use]
+    pub fn ignore_field(mut self, name: impl Into<String>) -> Self {
+        self.ignored_fields.insert(name.into());
+        self
+    }
+
+    fn is_ignored(&self, field: &str) -> bool {
+        self.ignored_fields.contains(field)
+            || (self.ignore_timestamps
+                && (matches!(field, "timestamp" | "time" | "now") || field.ends_with("_at")))
+    }
+
+    /// Removes ignored fields from `value`, recursively.
+    fn normalize(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                map.retain(|field, _| !self.is_ignored(field));
+                map.values_mut().for_each(|value| self.normalize(value));
+            },
+            Value::Array(items) => items.iter_mut().for_each(|value| self.normalize(value)),
+            _ => {},
+        }
+    }
+}
+
+/// The on-disk cassette format.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Recording {
+    version: u32,
+    interactions: Vec<Interaction>,
+}
+
+/// One recorded call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    request: Value,
+    #[serde(flatten)]
+    outcome: Outcome,
+}
+
+/// The recorded result of a call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Outcome {
+    Response(Value),
+    Error(RecordedError),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedError {
+    kind: String,
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry_after_ms: Option<u64>,
+}
+
+impl From<&Error> for RecordedError {
+    fn from(error: &Error) -> Self {
+        Self {
+            kind: format!("{:?}", error.kind()),
+            message: error.message().to_string(),
+            request_id: error.request_id().map(str::to_string),
+            retry_after_ms: error.retry_after().map(|d| d.as_millis() as u64),
+        }
+    }
+}
+
+impl From<RecordedError> for Error {
+    fn from(recorded: RecordedError) -> Self {
+        let kind = ERROR_KINDS
+            .into_iter()
+            .find(|kind| format!("{:?}", kind) == recorded.kind)
+            .unwrap_or(ErrorKind::Unknown);
+        let mut error = Error::new(kind, recorded.message);
+        if let Some(request_id) = recorded.request_id {
+            error = error.with_request_id(request_id);
+        }
+        if let Some(ms) = recorded.retry_after_ms {
+            error = error.with_retry_after(Duration::from_millis(ms));
+        }
+        error
+    }
+}
+
+/// A response that can be written to and read back from a cassette.
+trait Recordable: Sized {
+    fn to_record(&self) -> Value;
+    fn from_record(value: Value) -> Result<Self, serde_json::Error>;
+}
+
+/// Owned form of a relationship, used when reading recordings.
+#[derive(Deserialize)]
+struct RelationshipRecord {
+    resource: String,
+    relation: String,
+    subject: String,
+}
+
+impl From<RelationshipRecord> for Relationship<'static> {
+    fn from(record: RelationshipRecord) -> Self {
+        Relationship::new(record.resource, record.relation, record.subject)
+    }
+}
+
+fn relationship_json(relationship: &Relationship<'_>) -> Value {
+    json!({
+        "resource": relationship.resource(),
+        "relation": relationship.relation(),
+        "subject": relationship.subject(),
+    })
+}
+
+fn consistency_json(consistency: &ConsistencyRequirement) -> Value {
+    match consistency {
+        ConsistencyRequirement::Eventual => json!("eventual"),
+        ConsistencyRequirement::Full => json!("full"),
+        ConsistencyRequirement::AtLeastAsFresh(token) => {
+            json!({ "at_least_as_fresh": token.value() })
+        },
+    }
+}
+
+fn parse<T: DeserializeOwned>(value: Value) -> Result<T, serde_json::Error> {
+    serde_json::from_value(value)
+}
+
+impl Recordable for CheckResponse {
+    fn to_record(&self) -> Value {
+        json!({ "allowed": self.allowed, "decision": self.decision, "trace": self.trace })
+    }
+
+    fn from_record(value: Value) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct Record {
+            allowed: bool,
+            decision: Decision,
+            trace: Option<DecisionTrace>,
+        }
+        let record: Record = parse(value)?;
+        Ok(Self { allowed: record.allowed, decision: record.decision, trace: record.trace })
+    }
+}
+
+impl Recordable for WriteResponse {
+    fn to_record(&self) -> Value {
+        json!({ "consistency_token": self.consistency_token })
+    }
+
+    fn from_record(value: Value) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct Record {
+            consistency_token: ConsistencyToken,
+        }
+        let record: Record = parse(value)?;
+        Ok(Self { consistency_token: record.consistency_token })
+    }
+}
+
+impl Recordable for Option<ConsistencyToken> {
+    fn to_record(&self) -> Value {
+        json!(self)
+    }
+
+    fn from_record(value: Value) -> Result<Self, serde_json::Error> {
+        parse(value)
+    }
+}
+
+impl Recordable for ListRelationshipsResponse {
+    fn to_record(&self) -> Value {
+        let relationships: Vec<_> = self.relationships.iter().map(relationship_json).collect();
+        json!({ "relationships": relationships, "next_cursor": self.next_cursor })
+    }
+
+    fn from_record(value: Value) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct Record {
+            relationships: Vec<RelationshipRecord>,
+            next_cursor: Option<String>,
+        }
+        let record: Record = parse(value)?;
+        Ok(Self {
+            relationships: record.relationships.into_iter().map(Relationship::from).collect(),
+            next_cursor: record.next_cursor,
+        })
+    }
+}
+
+impl Recordable for ListResourcesResponse {
+    fn to_record(&self) -> Value {
+        json!({ "resources": self.resources, "next_cursor": self.next_cursor })
+    }
+
+    fn from_record(value: Value) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct Record {
+            resources: Vec<String>,
+            next_cursor: Option<String>,
+        }
+        let record: Record = parse(value)?;
+        Ok(Self { resources: record.resources, next_cursor: record.next_cursor })
+    }
+}
+
+impl Recordable for ListSubjectsResponse {
+    fn to_record(&self) -> Value {
+        json!({ "subjects": self.subjects, "next_cursor": self.next_cursor })
+    }
+
+    fn from_record(value: Value) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct Record {
+            subjects: Vec<String>,
+            next_cursor: Option<String>,
+        }
+        let record: Record = parse(value)?;
+        Ok(Self { subjects: record.subjects, next_cursor: record.next_cursor })
+    }
+}
+
+impl Recordable for SimulateResponse {
+    fn to_record(&self) -> Value {
+        json!({ "allowed": self.allowed, "decision": self.decision })
+    }
+
+    fn from_record(value: Value) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct Record {
+            allowed: bool,
+            decision: Decision,
+        }
+        let record: Record = parse(value)?;
+        Ok(Self { allowed: record.allowed, decision: record.decision })
+    }
+}
+
+impl Recordable for () {
+    fn to_record(&self) -> Value {
+        Value::Null
+    }
+
+    fn from_record(_: Value) -> Result<Self, serde_json::Error> {
+        Ok(())
+    }
+}
+
+/// The frames received over one watch connection.
+struct WatchFrames(Vec<Result<WatchEvent, Error>>);
+
+/// One recorded frame of a watch connection.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WatchFrame {
+    Event(WatchEvent),
+    Error(RecordedError),
+}
+
+fn watch_frame_json(frame: &Result<WatchEvent, Error>) -> Value {
+    match frame {
+        Ok(event) => json!({ "event": event }),
+        Err(error) => json!({ "error": RecordedError::from(error) }),
+    }
+}
+
+impl Recordable for WatchFrames {
+    fn to_record(&self) -> Value {
+        Value::Array(self.0.iter().map(watch_frame_json).collect())
+    }
+
+    fn from_record(value: Value) -> Result<Self, serde_json::Error> {
+        let frames: Vec<WatchFrame> = parse(value)?;
+        Ok(Self(
+            frames
+                .into_iter()
+                .map(|frame| match frame {
+                    WatchFrame::Event(event) => Ok(event),
+                    WatchFrame::Error(error) => Err(error.into()),
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl<T: Recordable> Recordable for Vec<T> {
+    fn to_record(&self) -> Value {
+        Value::Array(self.iter().map(Recordable::to_record).collect())
+    }
+
+    fn from_record(value: Value) -> Result<Self, serde_json::Error> {
+        let items: Vec<Value> = parse(value)?;
+        items.into_iter().map(T::from_record).collect()
+    }
+}
+
+fn check_json(request: &CheckRequest) -> Value {
+    json!({
+        "subject": request.subject,
+        "permission": request.permission,
+        "resource": request.resource,
+        "context": request.context,
+        "consistency": consistency_json(&request.consistency),
+        "trace": request.trace,
+    })
+}
+
+fn write_json(request: &WriteRequest) -> Value {
+    json!({
+        "relationship": relationship_json(&request.relationship),
+        "idempotency_key": request.idempotency_key,
+    })
+}
+
+fn simulate_json(request: &SimulateRequest) -> Value {
+    json!({
+        "subject": request.subject,
+        "permission": request.permission,
+        "resource": request.resource,
+        "context": request.context,
+        "additions": request.additions.iter().map(relationship_json).collect::<Vec<_>>(),
+        "removals": request.removals.iter().map(relationship_json).collect::<Vec<_>>(),
+        "schema": request.schema,
+        "consistency": consistency_json(&request.consistency),
+    })
+}
+
+fn watch_json(request: &WatchRequest) -> Value {
+    json!({
+        "organization_id": request.organization_id,
+        "vault_id": request.vault_id,
+        "filters": request.filters.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        "from_revision": request.from_revision,
+    })
+}
+
+fn simulate_batch_json(request: &SimulateBatchRequest) -> Value {
+    let checks: Vec<_> = request
+        .checks
+        .iter()
+        .map(|check| {
+            json!({
+                "subject": check.subject,
+                "permission": check.permission,
+                "resource": check.resource,
+                "context": check.context,
+            })
+        })
+        .collect();
+    json!({
+        "checks": checks,
+        "additions": request.additions.iter().map(relationship_json).collect::<Vec<_>>(),
+        "removals": request.removals.iter().map(relationship_json).collect::<Vec<_>>(),
+        "schema": request.schema,
+        "consistency": consistency_json(&request.consistency),
+    })
+}
+
+/// Recorded interactions and which of them have been replayed.
+struct Tape {
+    interactions: Vec<Interaction>,
+    replayed: Vec<bool>,
+    /// Whether the tape changed since it was last written.
+    dirty: bool,
+}
+
+impl Tape {
+    fn new(interactions: Vec<Interaction>) -> Self {
+        let replayed = vec![false; interactions.len()];
+        Self { interactions, replayed, dirty: false }
+    }
+}
+
+/// Transport that records calls to an inner transport, or replays them.
+pub struct CassetteTransport {
+    cassette: Cassette,
+    inner: Option<Arc<AnyTransport>>,
+    tape: Arc<Mutex<Tape>>,
+}
+
+impl CassetteTransport {
+    /// Records every call made through `inner` to the cassette file.
+    ///
+    /// Interactions are kept in memory and written by [`flush`](Self::flush)
+    /// and when the transport is dropped.
+    pub fn record(cassette: Cassette, inner: Arc<AnyTransport>) -> Self {
+        Self { cassette, inner: Some(inner), tape: Arc::new(Mutex::new(Tape::new(Vec::new()))) }
+    }
+
+    /// Loads the cassette file for replay.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the file cannot be read or parsed.
+    pub fn replay(cassette: Cassette) -> Result<Self, Error> {
+        let path = cassette.path.display().to_string();
+        let content = std::fs::read_to_string(&cassette.path).map_err(|e| {
+            Error::configuration(format!("failed to read cassette '{}': {}", path, e))
+        })?;
+        let recording: Recording = serde_json::from_str(&content).map_err(|e| {
+            Error::configuration(format!("failed to parse cassette '{}': {}", path, e))
+        })?;
+        if recording.version != FORMAT_VERSION {
+            return Err(Error::configuration(format!(
+                "cassette '{}' has unsupported format version {}",
+                path, recording.version
+            )));
+        }
+
+        Ok(Self {
+            cassette,
+            inner: None,
+            tape: Arc::new(Mutex::new(Tape::new(recording.interactions))),
+        })
+    }
+
+    /// Wraps the transport in an [`AnyTransport`].
+    pub fn into_any(self) -> AnyTransport {
+        AnyTransport::Cassette(self)
+    }
+
+    /// Returns the transport being recorded, or `None` when replaying.
+    pub fn inner(&self) -> Option<&AnyTransport> {
+        self.inner.as_deref()
+    }
+
+    /// Returns the cassette this transport records or replays.
+    pub fn cassette(&self) -> &Cassette {
+        &self.cassette
+    }
+
+    /// Returns the number of recorded interactions.
+    pub fn len(&self) -> usize {
+        self.tape.lock().interactions.len()
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of recorded interactions not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.tape.lock().replayed.iter().filter(|replayed| !**replayed).count()
+    }
+
+    /// Returns the transport type of the recorded transport, or
+    /// [`Transport::Mock`] when replaying.
+    pub fn transport_type(&self) -> Transport {
+        self.inner.as_ref().map_or(Transport::Mock, |inner| inner.transport_type())
+    }
+
+    /// Returns the statistics of the recorded transport.
+    pub fn stats(&self) -> TransportStats {
+        self.inner.as_ref().map(|inner| inner.stats()).unwrap_or_default()
+    }
+
+    /// Performs an authorization check.
+    pub fn check(&self, request: CheckRequest) -> BoxFuture<'_, Result<CheckResponse, Error>> {
+        let recorded = check_json(&request);
+        self.call("check", recorded, move |inner| inner.check(request))
+    }
+
+    /// Performs a batch of authorization checks.
+    pub fn check_batch(
+        &self,
+        requests: Vec<CheckRequest>,
+    ) -> BoxFuture<'_, Result<Vec<CheckResponse>, Error>> {
+        let recorded = Value::Array(requests.iter().map(check_json).collect());
+        self.call("check_batch", recorded, move |inner| inner.check_batch(requests))
+    }
+
+    /// Writes a relationship.
+    pub fn write(&self, request: WriteRequest) -> BoxFuture<'_, Result<WriteResponse, Error>> {
+        let recorded = write_json(&request);
+        self.call("write", recorded, move |inner| inner.write(request))
+    }
+
+    /// Writes a batch of relationships.
+    pub fn write_batch(
+        &self,
+        requests: Vec<WriteRequest>,
+    ) -> BoxFuture<'_, Result<WriteResponse, Error>> {
+        let recorded = Value::Array(requests.iter().map(write_json).collect());
+        self.call("write_batch", recorded, move |inner| inner.write_batch(requests))
+    }
+
+    /// Deletes a relationship.
+    pub fn delete(
+        &self,
+        relationship: Relationship<'static>,
+    ) -> BoxFuture<'_, Result<Option<ConsistencyToken>, Error>> {
+        let recorded = relationship_json(&relationship);
+        self.call("delete", recorded, move |inner| inner.delete(relationship))
+    }
+
+    /// Lists relationships matching a filter.
+    pub fn list_relationships(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> BoxFuture<'_, Result<ListRelationshipsResponse, Error>> {
+        let recorded = json!({
+            "resource": resource,
+            "relation": relation,
+            "subject": subject,
+            "limit": limit,
+            "cursor": cursor,
+            "consistency": consistency_json(consistency),
+        });
+        let live = self.inner.as_ref().map(|inner| {
+            inner.list_relationships(resource, relation, subject, limit, cursor, consistency)
+        });
+        self.call("list_relationships", recorded, move |_| live.unwrap_or_else(unreachable))
+    }
+
+    /// Lists resources accessible by a subject with a permission.
+    pub fn list_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> BoxFuture<'_, Result<ListResourcesResponse, Error>> {
+        let recorded = json!({
+            "subject": subject,
+            "permission": permission,
+            "resource_type": resource_type,
+            "limit": limit,
+            "cursor": cursor,
+            "consistency": consistency_json(consistency),
+        });
+        let live = self.inner.as_ref().map(|inner| {
+            inner.list_resources(subject, permission, resource_type, limit, cursor, consistency)
+        });
+        self.call("list_resources", recorded, move |_| live.unwrap_or_else(unreachable))
+    }
+
+    /// Lists subjects with a permission on a resource.
+    pub fn list_subjects(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> BoxFuture<'_, Result<ListSubjectsResponse, Error>> {
+        let recorded = json!({
+            "permission": permission,
+            "resource": resource,
+            "subject_type": subject_type,
+            "limit": limit,
+            "cursor": cursor,
+            "consistency": consistency_json(consistency),
+        });
+        let live = self.inner.as_ref().map(|inner| {
+            inner.list_subjects(permission, resource, subject_type, limit, cursor, consistency)
+        });
+        self.call("list_subjects", recorded, move |_| live.unwrap_or_else(unreachable))
+    }
+
+    /// Checks if the transport is healthy.
+    pub fn health_check(&self) -> BoxFuture<'_, Result<(), Error>> {
+        self.call("health_check", Value::Null, |inner| inner.health_check())
+    }
+
+    /// Performs a simulated authorization check with hypothetical changes.
+    pub fn simulate(
+        &self,
+        request: SimulateRequest,
+    ) -> BoxFuture<'_, Result<SimulateResponse, Error>> {
+        let recorded = simulate_json(&request);
+        self.call("simulate", recorded, move |inner| inner.simulate(request))
+    }
+
+    /// Performs many simulated checks against the same hypothetical changes.
+    pub fn simulate_batch(
+        &self,
+        request: SimulateBatchRequest,
+    ) -> BoxFuture<'_, Result<Vec<SimulateResponse>, Error>> {
+        let recorded = simulate_batch_json(&request);
+        self.call("simulate_batch", recorded, move |inner| inner.simulate_batch(request))
+    }
+
+    /// Opens a watch stream, recording every frame received over it.
+    pub fn watch(&self, request: WatchRequest) -> BoxFuture<'_, Result<WatchEventStream, Error>> {
+        let recorded = watch_json(&request);
+        Box::pin(async move {
+            let Some(inner) = &self.inner else {
+                let WatchFrames(frames) = self.replay_call("watch", recorded)?;
+                let stream: WatchEventStream = Box::pin(futures::stream::iter(frames));
+                return Ok(stream);
+            };
+
+            let stream = match inner.watch(request).await {
+                Ok(stream) => stream,
+                Err(error) => {
+                    let outcome = Outcome::Error(RecordedError::from(&error));
+                    self.append(Interaction {
+                        method: "watch".to_string(),
+                        request: recorded,
+                        outcome,
+                    });
+                    return Err(error);
+                },
+            };
+
+            let outcome = Outcome::Response(Value::Array(Vec::new()));
+            let index = self.append(Interaction {
+                method: "watch".to_string(),
+                request: recorded,
+                outcome,
+            });
+            let tape = Arc::clone(&self.tape);
+            let stream: WatchEventStream = Box::pin(stream.inspect(move |frame| {
+                let mut tape = tape.lock();
+                if let Outcome::Response(Value::Array(frames)) =
+                    &mut tape.interactions[index].outcome
+                {
+                    frames.push(watch_frame_json(frame));
+                }
+                tape.dirty = true;
+            }));
+            Ok(stream)
+        })
+    }
+
+    /// Writes the recorded interactions to the cassette file.
+    ///
+    /// Recordings are also written when the transport is dropped, but
+    /// errors can only be reported from here. Does nothing in replay mode
+    /// or when nothing changed since the last write.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the file cannot be written.
+    pub async fn flush(&self) -> Result<(), Error> {
+        let Some(json) = self.take_snapshot()? else {
+            return Ok(());
+        };
+        let path = self.cassette.path.clone();
+        let written = tokio::task::spawn_blocking(move || write_cassette(&path, &json))
+            .await
+            .map_err(|e| Error::internal(format!("cassette writer failed: {}", e)))
+            .and_then(|result| result);
+        if written.is_err() {
+            self.tape.lock().dirty = true;
+        }
+        written
+    }
+
+    /// Serializes the tape if it changed since it was last written, marking
+    /// it clean.
+    fn take_snapshot(&self) -> Result<Option<String>, Error> {
+        let mut tape = self.tape.lock();
+        if self.inner.is_none() || !tape.dirty {
+            return Ok(None);
+        }
+        let recording = RecordingRef { version: FORMAT_VERSION, interactions: &tape.interactions };
+        let json = serde_json::to_string_pretty(&recording)
+            .map_err(|e| Error::internal(format!("failed to serialize cassette: {}", e)))?;
+        tape.dirty = false;
+        Ok(Some(json))
+    }
+
+    /// Records the live call in record mode, or answers it from the tape in
+    /// replay mode.
+    fn call<'a, T, F>(
+        &'a self,
+        method: &'static str,
+        request: Value,
+        live: F,
+    ) -> BoxFuture<'a, Result<T, Error>>
+    where
+        T: Recordable + Send + 'a,
+        F: FnOnce(&'a AnyTransport) -> BoxFuture<'a, Result<T, Error>> + Send + 'a,
+    {
+        Box::pin(async move {
+            let Some(inner) = &self.inner else {
+                return self.replay_call(method, request);
+            };
+
+            let result = live(inner).await;
+            let outcome = match &result {
+                Ok(response) => Outcome::Response(response.to_record()),
+                Err(error) => Outcome::Error(RecordedError::from(error)),
+            };
+            self.append(Interaction { method: method.to_string(), request, outcome });
+            result
+        })
+    }
+
+    /// Returns the first unreplayed interaction matching the request.
+    fn replay_call<T: Recordable>(&self, method: &str, mut request: Value) -> Result<T, Error> {
+        let rules = &self.cassette.rules;
+        rules.normalize(&mut request);
+
+        let mut tape = self.tape.lock();
+        let Tape { interactions, replayed, .. } = &mut *tape;
+        let position = interactions.iter().zip(replayed.iter()).position(|(interaction, used)| {
+            if *used || interaction.method != method {
+                return false;
+            }
+            let mut recorded = interaction.request.clone();
+            rules.normalize(&mut recorded);
+            recorded == request
+        });
+        let Some(position) = position else {
+            return Err(Error::configuration(format!(
+                "cassette '{}' has no unreplayed {} interaction matching {}",
+                self.cassette.path.display(),
+                method,
+                request
+            )));
+        };
+
+        replayed[position] = true;
+        match interactions[position].outcome.clone() {
+            Outcome::Response(value) => T::from_record(value).map_err(|e| {
+                Error::configuration(format!("invalid recorded {} response: {}", method, e))
+            }),
+            Outcome::Error(error) => Err(error.into()),
+        }
+    }
+
+    /// Appends an interaction to the tape, returning its index.
+    fn append(&self, interaction: Interaction) -> usize {
+        let mut tape = self.tape.lock();
+        tape.interactions.push(interaction);
+        tape.replayed.push(true);
+        tape.dirty = true;
+        tape.interactions.len() - 1
+    }
+}
+
+/// Writes serialized recordings, creating the cassette directory if needed.
+fn write_cassette(path: &Path, json: &str) -> Result<(), Error> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| {
+            Error::configuration(format!("failed to create cassette directory: {}", e))
+        })?;
+    }
+    std::fs::write(path, json).map_err(|e| {
+        Error::configuration(format!("failed to write cassette '{}': {}", path.display(), e))
+    })
+}
+
+impl Drop for CassetteTransport {
+    fn drop(&mut self) {
+        let written = self.take_snapshot().and_then(|json| {
+            json.map_or(Ok(()), |json| write_cassette(&self.cassette.path, &json))
+        });
+        #[cfg(feature = "tracing")]
+        if let Err(error) = written {
+            tracing::warn!(error = %error, "failed to write cassette");
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = written;
+    }
+}
+
+/// Borrowed form of [`Recording`] for writing.
+#[derive(Serialize)]
+struct RecordingRef<'a> {
+    version: u32,
+    interactions: &'a [Interaction],
+}
+
+/// Placeholder for the live call of a replaying transport, which is never
+/// made.
+fn unreachable<'a, T: Send + 'a>() -> BoxFuture<'a, Result<T, Error>> {
+    Box::pin(async { Err(Error::internal("replaying cassette made a live call")) })
+}
+
+impl std::fmt::Debug for CassetteTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CassetteTransport")
+            .field("path", &self.cassette.path)
+            .field("mode", &self.cassette.mode)
+            .field("interactions", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::transport::mock::MockTransport;
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("inferadb-cassette-{}-{}", std::process::id(), name))
+            .join("session.json")
+    }
+
+    fn write(relationship: Relationship<'static>, key: &str) -> WriteRequest {
+        WriteRequest { relationship, idempotency_key: Some(key.to_string()) }
+    }
+
+    fn check(subject: &str, permission: &str, resource: &str) -> CheckRequest {
+        CheckRequest {
+            subject: subject.to_string(),
+            permission: permission.to_string(),
+            resource: resource.to_string(),
+            context: None,
+            consistency: ConsistencyRequirement::default(),
+            trace: false,
+        }
+    }
+
+    async fn record_session(path: &Path) {
+        let inner = Arc::new(MockTransport::new().into_any());
+        let recorder = CassetteTransport::record(Cassette::record(path), inner);
+
+        recorder
+            .write(write(Relationship::new("doc:1", "viewer", "user:alice"), "key-1"))
+            .await
+            .unwrap();
+        assert!(recorder.check(check("user:alice", "viewer", "doc:1")).await.unwrap().allowed);
+        let listed = recorder
+            .list_relationships(Some("doc:1"), None, None, None, None, &Default::default())
+            .await
+            .unwrap();
+        assert_eq!(listed.relationships.len(), 1);
+        recorder.delete(Relationship::new("doc:1", "viewer", "user:alice")).await.unwrap();
+        assert!(!recorder.check(check("user:alice", "viewer", "doc:1")).await.unwrap().allowed);
+        assert_eq!(recorder.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_replays_recorded_session_in_order() {
+        let path = cassette_path("order");
+        record_session(&path).await;
+
+        let player = CassetteTransport::replay(Cassette::replay(&path)).unwrap();
+        assert_eq!(player.transport_type(), Transport::Mock);
+        player
+            .write(write(Relationship::new("doc:1", "viewer", "user:alice"), "key-1"))
+            .await
+            .unwrap();
+        // The same check replays different outcomes in recorded order
+        assert!(player.check(check("user:alice", "viewer", "doc:1")).await.unwrap().allowed);
+        let listed = player
+            .list_relationships(Some("doc:1"), None, None, None, None, &Default::default())
+            .await
+            .unwrap();
+        assert_eq!(listed.relationships, vec![Relationship::new("doc:1", "viewer", "user:alice")]);
+        player.delete(Relationship::new("doc:1", "viewer", "user:alice")).await.unwrap();
+        assert!(!player.check(check("user:alice", "viewer", "doc:1")).await.unwrap().allowed);
+        assert_eq!(player.remaining(), 0);
+
+        let err = player.check(check("user:alice", "viewer", "doc:1")).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Configuration);
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn test_match_rules_ignore_idempotency_keys() {
+        let path = cassette_path("rules");
+        record_session(&path).await;
+
+        let strict = CassetteTransport::replay(Cassette::replay(&path)).unwrap();
+        let request = write(Relationship::new("doc:1", "viewer", "user:alice"), "key-2");
+        assert!(strict.write(request.clone()).await.is_err());
+
+        let lenient = CassetteTransport::replay(
+            Cassette::replay(&path).with_rules(MatchRules::new().ignore_idempotency_keys()),
+        )
+        .unwrap();
+        assert!(lenient.write(request).await.is_ok());
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn test_records_and_replays_errors() {
+        let path = cassette_path("errors");
+        let mock = MockTransport::new();
+        mock.set_failure(
+            Error::rate_limited(Some(Duration::from_secs(2))).with_request_id("req-1"),
+        );
+        let recorder =
+            CassetteTransport::record(Cassette::record(&path), Arc::new(mock.into_any()));
+        assert!(recorder.check(check("user:alice", "viewer", "doc:1")).await.is_err());
+        recorder.flush().await.unwrap();
+
+        let player = CassetteTransport::replay(Cassette::replay(&path)).unwrap();
+        let err = player.check(check("user:alice", "viewer", "doc:1")).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert_eq!(err.request_id(), Some("req-1"));
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(2)));
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn test_client_replays_without_a_server() {
+        use crate::{BearerCredentialsConfig, Client};
+
+        let path = cassette_path("client");
+        let recording = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .cassette(Cassette::record(&path))
+            .build_with_transport(Arc::new(MockTransport::new().into_any()))
+            .await
+            .unwrap();
+        let vault = recording.organization("org").vault("vault");
+        vault
+            .relationships()
+            .write(Relationship::new("doc:1", "viewer", "user:alice"))
+            .await
+            .unwrap();
+        assert!(vault.check("user:alice", "viewer", "doc:1").await.unwrap());
+        recording.flush_cassette().await.unwrap();
+
+        // Nothing listens on this address, so every answer comes from the file
+        let replaying = Client::builder()
+            .url("http://127.0.0.1:9")
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test"))
+            .cassette(
+                Cassette::replay(&path).with_rules(MatchRules::new().ignore_idempotency_keys()),
+            )
+            .build()
+            .await
+            .unwrap();
+        let vault = replaying.organization("org").vault("vault");
+        vault
+            .relationships()
+            .write(Relationship::new("doc:1", "viewer", "user:alice"))
+            .await
+            .unwrap();
+        assert!(vault.check("user:alice", "viewer", "doc:1").await.unwrap());
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn test_recording_failure_does_not_fail_live_calls() {
+        let path = PathBuf::from("/dev/null/session.json");
+        let inner = Arc::new(MockTransport::new().into_any());
+        let recorder = CassetteTransport::record(Cassette::record(&path), inner);
+
+        recorder.check(check("user:alice", "viewer", "doc:1")).await.unwrap();
+        let err = recorder.flush().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Configuration);
+        // The interaction stays buffered so a later flush can retry
+        assert_eq!(recorder.len(), 1);
+        assert!(recorder.flush().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_flush_writes_only_when_tape_changed() {
+        let path = cassette_path("flush");
+        let inner = Arc::new(MockTransport::new().into_any());
+        let recorder = CassetteTransport::record(Cassette::record(&path), inner);
+
+        recorder.flush().await.unwrap();
+        assert!(!path.exists());
+        recorder.check(check("user:alice", "viewer", "doc:1")).await.unwrap();
+        recorder.flush().await.unwrap();
+        assert_eq!(CassetteTransport::replay(Cassette::replay(&path)).unwrap().remaining(), 1);
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_timestamps_are_normalized_at_any_depth() {
+        let rules = MatchRules::new().ignore_timestamps().ignore_request_ids();
+        let mut value = json!({
+            "subject": "user:alice",
+            "request_id": "abc",
+            "context": { "now": "2026-01-01T00:00:00Z", "ip": "10.0.0.1", "created_at": 1 },
+        });
+        rules.normalize(&mut value);
+        assert_eq!(value, json!({ "subject": "user:alice", "context": { "ip": "10.0.0.1" } }));
+    }
+
+    #[test]
+    fn test_replay_missing_file_is_configuration_error() {
+        let err =
+            CassetteTransport::replay(Cassette::replay(cassette_path("missing"))).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Configuration);
+    }
+}
+
+#[cfg(all(test, feature = "rest"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod wiremock_tests {
+    use futures::StreamExt;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    use super::*;
+    use crate::{
+        BearerCredentialsConfig, Client,
+        vault::watch::{Operation, WatchFilter},
+    };
+
+    #[tokio::test]
+    async fn test_records_and_replays_watch_frames() {
+        let file = std::env::temp_dir()
+            .join(format!("inferadb-cassette-{}-watch", std::process::id()))
+            .join("session.json");
+        let recorded: Vec<WatchEvent> = (1..=2)
+            .map(|revision| {
+                WatchEvent::new(
+                    Operation::Create,
+                    Relationship::new(format!("doc:{}", revision), "viewer", "user:alice"),
+                    revision,
+                    chrono::Utc::now(),
+                )
+            })
+            .collect();
+        let body: String = recorded
+            .iter()
+            .map(|event| format!("data: {}\n\n", serde_json::to_string(event).unwrap()))
+            .collect();
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/organizations/org/vaults/vault/watch"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let recording = Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test"))
+            .transport_strategy(crate::TransportStrategy::RestOnly)
+            .cassette(Cassette::record(&file))
+            .build()
+            .await
+            .unwrap();
+        let vault = recording.organization("org").vault("vault");
+        let live: Vec<u64> = vault
+            .watch()
+            .filter(WatchFilter::resource_type("doc"))
+            .run()
+            .await
+            .unwrap()
+            .map(|event| event.unwrap().revision)
+            .collect()
+            .await;
+        assert_eq!(live, vec![1, 2]);
+        recording.flush_cassette().await.unwrap();
+
+        // Nothing listens on this address, so the frames come from the file
+        let replaying = Client::builder()
+            .url("http://127.0.0.1:9")
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test"))
+            .cassette(Cassette::replay(&file))
+            .build()
+            .await
+            .unwrap();
+        let vault = replaying.organization("org").vault("vault");
+        let replayed: Vec<u64> = vault
+            .watch()
+            .filter(WatchFilter::resource_type("doc"))
+            .run()
+            .await
+            .unwrap()
+            .map(|event| event.unwrap().revision)
+            .collect()
+            .await;
+        assert_eq!(replayed, vec![1, 2]);
+        std::fs::remove_dir_all(file.parent().unwrap()).ok();
+    }
+}
@@ -0,0 +1,871 @@
+//! Fault-injecting transport wrapper for chaos testing.
+//!
+//! A [`FaultInjector`] describes the faults to inject: latency, errors by
+//! [`ErrorKind`], rate limiting with a retry-after hint, connection resets
+//! part way through streamed responses, watch streams cut off after a number
+//! of events, and scripted sequences such as "fail three times, then
+//! succeed". [`FaultTransport`] applies it to every call made through an inner
+//! transport.
+//!
+//! All randomness comes from an RNG seeded by the injector, so a failing run
+//! can be reproduced by reusing its seed.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use futures::StreamExt;
+use parking_lot::Mutex;
+
+use super::traits::{
+    AnyTransport, BoxFuture, CheckRequest, CheckResponse, ListRelationshipsResponse,
+    ListResourcesResponse, ListSubjectsResponse, SimulateBatchRequest, SimulateRequest,
+    SimulateResponse, Transport, TransportStats, WatchEventStream, WatchRequest, WriteRequest,
+    WriteResponse,
+};
+use crate::{
+    Error, ErrorKind,
+    types::{ConsistencyRequirement, ConsistencyToken, Relationship},
+};
+
+/// An engine operation that faults can be scoped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EngineOperation {
+    /// A single authorization check.
+    Check,
+    /// A batch of authorization checks (streamed).
+    CheckBatch,
+    /// A single relationship write.
+    Write,
+    /// A batch of relationship writes.
+    WriteBatch,
+    /// A relationship delete.
+    Delete,
+    /// Listing relationships (streamed).
+    ListRelationships,
+    /// Listing accessible resources (streamed).
+    ListResources,
+    /// Listing subjects with a permission (streamed).
+    ListSubjects,
+    /// A transport health check.
+    HealthCheck,
+    /// A simulated check.
+    Simulate,
+    /// A batch of simulated checks.
+    SimulateBatch,
+    /// A watch connection (streamed).
+    Watch,
+}
+
+impl EngineOperation {
+    /// Returns `true` if the server streams the response to this operation,
+    /// so that the connection can be reset part way through.
+    pub fn is_streaming(&self) -> bool {
+        matches!(
+            self,
+            EngineOperation::CheckBatch
+                | EngineOperation::ListRelationships
+                | EngineOperation::ListResources
+                | EngineOperation::ListSubjects
+                | EngineOperation::Watch
+        )
+    }
+}
+
+/// Distribution of injected latency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LatencyDistribution {
+    /// Always the same delay.
+    Fixed(Duration),
+    /// A delay drawn uniformly between `min` and `max`.
+    Uniform {
+        /// Shortest delay.
+        min: Duration,
+        /// Longest delay.
+        max: Duration,
+    },
+    /// An exponentially distributed delay with the given mean, modelling
+    /// a long tail of slow calls.
+    Exponential {
+        /// Mean delay.
+        mean: Duration,
+    },
+}
+
+impl LatencyDistribution {
+    fn sample(&self, rng: &mut fastrand::Rng) -> Duration {
+        match *self {
+            LatencyDistribution::Fixed(delay) => delay,
+            LatencyDistribution::Uniform { min, max } => {
+                let (min, max) = if min <= max { (min, max) } else { (max, min) };
+                min + (max - min).mul_f64(rng.f64())
+            },
+            LatencyDistribution::Exponential { mean } => mean.mul_f64(-(1.0 - rng.f64()).ln()),
+        }
+    }
+}
+
+/// One step of a scripted fault sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FaultStep {
+    /// Let the call through.
+    Pass,
+    /// Fail the call with an error of this kind.
+    Fail(ErrorKind),
+    /// Fail the call as rate limited, asking to retry after the delay.
+    RateLimit(Duration),
+    /// Reset the connection part way through a streamed response.
+    ///
+    /// A watch connection is reset as soon as it is established. Calls that
+    /// are not streamed are let through.
+    Reset,
+    /// Cut a watch connection off with a connection error after it has
+    /// delivered this many events.
+    ///
+    /// Calls other than watches are let through.
+    Cut(usize),
+    /// Delay the call, then let it through.
+    Delay(Duration),
+}
+
+/// What a [`Fault`] does.
+#[derive(Debug, Clone, PartialEq)]
+enum FaultKind {
+    Latency(LatencyDistribution),
+    Error { kind: ErrorKind, rate: f64 },
+    RateLimit { rate: f64, retry_after: Duration },
+    StreamReset { rate: f64 },
+    WatchCut { events: usize, rate: f64 },
+    Script(Vec<FaultStep>),
+}
+
+/// A fault to inject, optionally scoped to some operations.
+///
+/// Rates are probabilities between `0.0` and `1.0`; values outside that
+/// range are clamped.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use inferadb::ErrorKind;
+/// use inferadb::testing::{EngineOperation, Fault, LatencyDistribution};
+///
+/// // 20% of checks fail as unavailable
+/// let flaky = Fault::error(ErrorKind::Unavailable, 0.2).on(EngineOperation::Check);
+///
+/// // Every call takes 5-50ms
+/// let slow = Fault::latency(LatencyDistribution::Uniform {
+///     min: Duration::from_millis(5),
+///     max: Duration::from_millis(50),
+/// });
+///
+/// // Writes fail three times, then succeed
+/// let outage = Fault::fail_times(ErrorKind::Unavailable, 3).on(EngineOperation::Write);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fault {
+    kind: FaultKind,
+    operations: Vec<EngineOperation>,
+}
+
+impl Fault {
+    fn new(kind: FaultKind) -> Self {
+        Self { kind, operations: Vec::new() }
+    }
+
+    /// Delays calls by a latency drawn from `distribution`.
+    pub fn latency(distribution: LatencyDistribution) -> Self {
+        Self::new(FaultKind::Latency(distribution))
+    }
+
+    /// Fails calls with an error of `kind` at the given rate.
+    pub fn error(kind: ErrorKind, rate: f64) -> Self {
+        Self::new(FaultKind::Error { kind, rate: rate.clamp(0.0, 1.0) })
+    }
+
+    /// Fails calls as rate limited at the given rate, with a retry-after
+    /// hint of `retry_after`.
+    pub fn rate_limit(rate: f64, retry_after: Duration) -> Self {
+        Self::new(FaultKind::RateLimit { rate: rate.clamp(0.0, 1.0), retry_after })
+    }
+
+    /// Resets the connection part way through streamed responses at the
+    /// given rate.
+    ///
+    /// The server still processes the request; the caller sees a connection
+    /// error instead of the response. Calls that are not streamed (see
+    /// [`EngineOperation::is_streaming`]) are unaffected. Watch connections
+    /// are reset as soon as they are established.
+    pub fn stream_reset(rate: f64) -> Self {
+        Self::new(FaultKind::StreamReset { rate: rate.clamp(0.0, 1.0) })
+    }
+
+    /// Cuts watch connections off with a connection error after `events`
+    /// events, at the given rate.
+    ///
+    /// Use it to exercise reconnecting and resuming watches. Calls other
+    /// than watches are unaffected.
+    pub fn watch_cut(events: usize, rate: f64) -> Self {
+        Self::new(FaultKind::WatchCut { events, rate: rate.clamp(0.0, 1.0) })
+    }
+
+    /// Applies `steps` to successive calls, then lets calls through once
+    /// the script is exhausted.
+    pub fn script(steps: impl IntoIterator<Item = FaultStep>) -> Self {
+        Self::new(FaultKind::Script(steps.into_iter().collect()))
+    }
+
+    /// Fails the next `times` calls with an error of `kind`, then succeeds.
+    pub fn fail_times(kind: ErrorKind, times: usize) -> Self {
+        Self::script(std::iter::repeat_n(FaultStep::Fail(kind), times))
+    }
+
+    /// Scopes the fault to `operation`.
+    ///
+    /// May be called repeatedly; a fault without any operations applies to
+    /// every call.
+    #[must_use]
+    pub fn on(mut self, operation: EngineOperation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    fn applies_to(&self, operation: EngineOperation) -> bool {
+        self.operations.is_empty() || self.operations.contains(&operation)
+    }
+}
+
+/// A seeded set of faults to inject into a transport.
+///
+/// Faults are evaluated in the order they were added. Latency from every
+/// matching fault is added up; the first matching fault that fires decides
+/// the failure.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use inferadb::ErrorKind;
+/// use inferadb::testing::{Fault, FaultInjector};
+///
+/// let faults = FaultInjector::new(42)
+///     .with(Fault::error(ErrorKind::Unavailable, 0.1))
+///     .with(Fault::rate_limit(0.05, Duration::from_secs(1)))
+///     .with(Fault::stream_reset(0.05));
+///
+/// let client = Client::builder()
+///     .url("https://staging.inferadb.com")
+///     .credentials(credentials)
+///     .fault_injection(faults)
+///     .build()
+///     .await?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaultInjector {
+    seed: u64,
+    faults: Vec<Fault>,
+}
+
+impl FaultInjector {
+    /// Creates an injector without faults whose randomness is seeded by
+    /// `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { seed, faults: Vec::new() }
+    }
+
+    /// Adds a fault.
+    #[must_use]
+    pub fn with(mut self, fault: Fault) -> Self {
+        self.faults.push(fault);
+        self
+    }
+
+    /// Returns the RNG seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the faults, in evaluation order.
+    pub fn faults(&self) -> &[Fault] {
+        &self.faults
+    }
+
+    /// Returns a copy seeded for the endpoint at `index`, so that endpoints
+    /// draw independent but reproducible sequences.
+    pub(crate) fn for_endpoint(&self, index: usize) -> Self {
+        Self { seed: self.seed.wrapping_add(index as u64), faults: self.faults.clone() }
+    }
+}
+
+/// The faults chosen for one call.
+#[derive(Default)]
+struct Plan {
+    delay: Duration,
+    failure: Option<Failure>,
+}
+
+enum Failure {
+    Error(Error),
+    Reset,
+    Cut(usize),
+}
+
+/// The RNG and script positions of a fault transport.
+struct FaultState {
+    rng: fastrand::Rng,
+    positions: Vec<usize>,
+}
+
+/// Transport that injects faults into calls to an inner transport.
+pub struct FaultTransport {
+    inner: Arc<AnyTransport>,
+    injector: FaultInjector,
+    state: Mutex<FaultState>,
+    injected: AtomicU64,
+}
+
+impl FaultTransport {
+    /// Wraps `inner`, injecting the faults described by `injector`.
+    pub fn new(injector: FaultInjector, inner: Arc<AnyTransport>) -> Self {
+        let state = FaultState {
+            rng: fastrand::Rng::with_seed(injector.seed),
+            positions: vec![0; injector.faults.len()],
+        };
+        Self { inner, injector, state: Mutex::new(state), injected: AtomicU64::new(0) }
+    }
+
+    /// Wraps the transport in an [`AnyTransport`].
+    pub fn into_any(self) -> AnyTransport {
+        AnyTransport::Faulty(self)
+    }
+
+    /// Returns the wrapped transport.
+    pub fn inner(&self) -> &AnyTransport {
+        &self.inner
+    }
+
+    /// Returns the injected faults.
+    pub fn injector(&self) -> &FaultInjector {
+        &self.injector
+    }
+
+    /// Reports retries of the wrapped transport to `metrics`.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn attach_metrics(&mut self, metrics: &crate::tracing_support::Metrics) {
+        if let Some(inner) = Arc::get_mut(&mut self.inner) {
+            inner.attach_metrics(metrics);
+        }
+    }
+
+    /// Returns the number of calls that were failed, reset or cut off.
+    pub fn injected_count(&self) -> u64 {
+        self.injected.load(Ordering::Relaxed)
+    }
+
+    /// Performs an authorization check.
+    pub fn check(&self, request: CheckRequest) -> BoxFuture<'_, Result<CheckResponse, Error>> {
+        self.call(EngineOperation::Check, self.inner.check(request))
+    }
+
+    /// Performs a batch of authorization checks.
+    pub fn check_batch(
+        &self,
+        requests: Vec<CheckRequest>,
+    ) -> BoxFuture<'_, Result<Vec<CheckResponse>, Error>> {
+        self.call(EngineOperation::CheckBatch, self.inner.check_batch(requests))
+    }
+
+    /// Writes a relationship.
+    pub fn write(&self, request: WriteRequest) -> BoxFuture<'_, Result<WriteResponse, Error>> {
+        self.call(EngineOperation::Write, self.inner.write(request))
+    }
+
+    /// Writes a batch of relationships.
+    pub fn write_batch(
+        &self,
+        requests: Vec<WriteRequest>,
+    ) -> BoxFuture<'_, Result<WriteResponse, Error>> {
+        self.call(EngineOperation::WriteBatch, self.inner.write_batch(requests))
+    }
+
+    /// Deletes a relationship.
+    pub fn delete(
+        &self,
+        relationship: Relationship<'static>,
+    ) -> BoxFuture<'_, Result<Option<ConsistencyToken>, Error>> {
+        self.call(EngineOperation::Delete, self.inner.delete(relationship))
+    }
+
+    /// Lists relationships matching a filter.
+    pub fn list_relationships(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> BoxFuture<'_, Result<ListRelationshipsResponse, Error>> {
+        let future =
+            self.inner.list_relationships(resource, relation, subject, limit, cursor, consistency);
+        self.call(EngineOperation::ListRelationships, future)
+    }
+
+    /// Lists resources accessible by a subject with a permission.
+    pub fn list_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> BoxFuture<'_, Result<ListResourcesResponse, Error>> {
+        let future = self.inner.list_resources(
+            subject,
+            permission,
+            resource_type,
+            limit,
+            cursor,
+            consistency,
+        );
+        self.call(EngineOperation::ListResources, future)
+    }
+
+    /// Lists subjects with a permission on a resource.
+    pub fn list_subjects(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> BoxFuture<'_, Result<ListSubjectsResponse, Error>> {
+        let future = self.inner.list_subjects(
+            permission,
+            resource,
+            subject_type,
+            limit,
+            cursor,
+            consistency,
+        );
+        self.call(EngineOperation::ListSubjects, future)
+    }
+
+    /// Checks if the transport is healthy.
+    pub fn health_check(&self) -> BoxFuture<'_, Result<(), Error>> {
+        self.call(EngineOperation::HealthCheck, self.inner.health_check())
+    }
+
+    /// Performs a simulated authorization check with hypothetical changes.
+    pub fn simulate(
+        &self,
+        request: SimulateRequest,
+    ) -> BoxFuture<'_, Result<SimulateResponse, Error>> {
+        self.call(EngineOperation::Simulate, self.inner.simulate(request))
+    }
+
+    /// Performs many simulated checks against the same hypothetical changes.
+    pub fn simulate_batch(
+        &self,
+        request: SimulateBatchRequest,
+    ) -> BoxFuture<'_, Result<Vec<SimulateResponse>, Error>> {
+        self.call(EngineOperation::SimulateBatch, self.inner.simulate_batch(request))
+    }
+
+    /// Opens a watch connection.
+    ///
+    /// A cut connection delivers the planned number of events from the inner
+    /// stream and then fails with a connection error, as if the server had
+    /// dropped it.
+    pub fn watch(&self, request: WatchRequest) -> BoxFuture<'_, Result<WatchEventStream, Error>> {
+        let plan = self.plan(EngineOperation::Watch);
+        Box::pin(async move {
+            if !plan.delay.is_zero() {
+                tokio::time::sleep(plan.delay).await;
+            }
+            let events = match plan.failure {
+                None => return self.inner.watch(request).await,
+                Some(Failure::Error(error)) => {
+                    self.injected.fetch_add(1, Ordering::Relaxed);
+                    return Err(error);
+                },
+                Some(Failure::Reset) => 0,
+                Some(Failure::Cut(events)) => events,
+            };
+            let stream = self.inner.watch(request).await?;
+            self.injected.fetch_add(1, Ordering::Relaxed);
+            let cut = futures::stream::once(async move {
+                Err(Error::connection(format!(
+                    "watch stream cut after {} events (injected fault)",
+                    events
+                )))
+            });
+            Ok(Box::pin(stream.take(events).chain(cut)) as WatchEventStream)
+        })
+    }
+
+    /// Returns the transport type of the wrapped transport.
+    pub fn transport_type(&self) -> Transport {
+        self.inner.transport_type()
+    }
+
+    /// Returns the statistics of the wrapped transport.
+    pub fn stats(&self) -> TransportStats {
+        self.inner.stats()
+    }
+
+    /// Applies the planned faults around the `live` call.
+    ///
+    /// A failed call never starts `live`; a reset call runs it to
+    /// completion and then discards the response.
+    fn call<'a, T: Send + 'a>(
+        &'a self,
+        operation: EngineOperation,
+        live: BoxFuture<'a, Result<T, Error>>,
+    ) -> BoxFuture<'a, Result<T, Error>> {
+        let plan = self.plan(operation);
+        Box::pin(async move {
+            if !plan.delay.is_zero() {
+                tokio::time::sleep(plan.delay).await;
+            }
+            match plan.failure {
+                None | Some(Failure::Cut(_)) => live.await,
+                Some(Failure::Error(error)) => {
+                    self.injected.fetch_add(1, Ordering::Relaxed);
+                    Err(error)
+                },
+                Some(Failure::Reset) => {
+                    self.injected.fetch_add(1, Ordering::Relaxed);
+                    let _ = live.await;
+                    Err(Error::connection("connection reset mid-stream (injected fault)"))
+                },
+            }
+        })
+    }
+
+    /// Decides the faults for one call.
+    ///
+    /// Every matching probabilistic fault draws from the RNG even after
+    /// another fault has fired, so the sequence of draws depends only on
+    /// the sequence of calls.
+    fn plan(&self, operation: EngineOperation) -> Plan {
+        let mut state = self.state.lock();
+        let FaultState { rng, positions } = &mut *state;
+        let mut plan = Plan::default();
+
+        for (fault, position) in self.injector.faults.iter().zip(positions.iter_mut()) {
+            if !fault.applies_to(operation) {
+                continue;
+            }
+            let failure =
+                match &fault.kind {
+                    FaultKind::Latency(distribution) => {
+                        plan.delay += distribution.sample(rng);
+                        None
+                    },
+                    FaultKind::Error { kind, rate } => (rng.f64() < *rate)
+                        .then(|| Failure::Error(Error::new(*kind, "injected fault"))),
+                    FaultKind::RateLimit { rate, retry_after } => (rng.f64() < *rate)
+                        .then(|| Failure::Error(Error::rate_limited(Some(*retry_after)))),
+                    FaultKind::StreamReset { rate } => {
+                        (rng.f64() < *rate && operation.is_streaming()).then_some(Failure::Reset)
+                    },
+                    FaultKind::WatchCut { events, rate } => (rng.f64() < *rate
+                        && operation == EngineOperation::Watch)
+                        .then_some(Failure::Cut(*events)),
+                    FaultKind::Script(steps) => {
+                        let step = steps.get(*position).cloned().unwrap_or(FaultStep::Pass);
+                        *position += 1;
+                        match step {
+                            FaultStep::Pass => None,
+                            FaultStep::Fail(kind) => {
+                                Some(Failure::Error(Error::new(kind, "injected fault")))
+                            },
+                            FaultStep::RateLimit(retry_after) => {
+                                Some(Failure::Error(Error::rate_limited(Some(retry_after))))
+                            },
+                            FaultStep::Reset => operation.is_streaming().then_some(Failure::Reset),
+                            FaultStep::Cut(events) => (operation == EngineOperation::Watch)
+                                .then_some(Failure::Cut(events)),
+                            FaultStep::Delay(delay) => {
+                                plan.delay += delay;
+                                None
+                            },
+                        }
+                    },
+                };
+            if plan.failure.is_none() {
+                plan.failure = failure;
+            }
+        }
+        plan
+    }
+}
+
+impl std::fmt::Debug for FaultTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FaultTransport")
+            .field("inner", &self.inner)
+            .field("injector", &self.injector)
+            .field("injected", &self.injected_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::transport::mock::MockTransport;
+
+    fn check() -> CheckRequest {
+        CheckRequest {
+            subject: "user:alice".to_string(),
+            permission: "viewer".to_string(),
+            resource: "doc:1".to_string(),
+            context: None,
+            consistency: ConsistencyRequirement::default(),
+            trace: false,
+        }
+    }
+
+    fn faulty(injector: FaultInjector) -> FaultTransport {
+        FaultTransport::new(injector, Arc::new(MockTransport::new().into_any()))
+    }
+
+    async fn outcomes(transport: &FaultTransport, calls: usize) -> Vec<Option<ErrorKind>> {
+        let mut outcomes = Vec::new();
+        for _ in 0..calls {
+            outcomes.push(transport.check(check()).await.err().map(|e| e.kind()));
+        }
+        outcomes
+    }
+
+    #[tokio::test]
+    async fn test_same_seed_reproduces_faults() {
+        let injector = FaultInjector::new(7)
+            .with(Fault::error(ErrorKind::Unavailable, 0.3))
+            .with(Fault::error(ErrorKind::Timeout, 0.3));
+
+        let first = outcomes(&faulty(injector.clone()), 50).await;
+        let second = outcomes(&faulty(injector), 50).await;
+        assert_eq!(first, second);
+        assert!(first.contains(&Some(ErrorKind::Unavailable)));
+        assert!(first.contains(&Some(ErrorKind::Timeout)));
+        assert!(first.contains(&None));
+    }
+
+    #[tokio::test]
+    async fn test_fail_times_then_succeed() {
+        let transport = faulty(
+            FaultInjector::new(0)
+                .with(Fault::fail_times(ErrorKind::Unavailable, 3).on(EngineOperation::Check)),
+        );
+
+        let outcomes = outcomes(&transport, 5).await;
+        let unavailable = Some(ErrorKind::Unavailable);
+        assert_eq!(outcomes, vec![unavailable, unavailable, unavailable, None, None]);
+        assert_eq!(transport.injected_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_faults_are_scoped_to_operations() {
+        let transport = faulty(
+            FaultInjector::new(0)
+                .with(Fault::error(ErrorKind::Internal, 1.0).on(EngineOperation::Write)),
+        );
+
+        assert!(transport.check(check()).await.is_ok());
+        let write = WriteRequest {
+            relationship: Relationship::new("doc:1", "viewer", "user:alice"),
+            idempotency_key: None,
+        };
+        assert_eq!(transport.write(write).await.unwrap_err().kind(), ErrorKind::Internal);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_carries_retry_after() {
+        let transport =
+            faulty(FaultInjector::new(0).with(Fault::rate_limit(1.0, Duration::from_secs(3))));
+
+        let err = transport.check(check()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(3)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_reset_only_affects_streamed_calls() {
+        let mock = Arc::new(MockTransport::new().into_any());
+        let transport = FaultTransport::new(
+            FaultInjector::new(0).with(Fault::stream_reset(1.0)),
+            Arc::clone(&mock),
+        );
+
+        assert!(transport.check(check()).await.is_ok());
+        let err = transport
+            .list_relationships(None, None, None, None, None, &Default::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Connection);
+
+        // The reset happens after the server has handled the request
+        let AnyTransport::Mock(mock) = &*mock else { panic!("expected mock transport") };
+        assert_eq!(mock.request_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_watch_cut_only_affects_watches() {
+        let transport = faulty(
+            FaultInjector::new(0)
+                .with(Fault::watch_cut(0, 1.0))
+                .with(Fault::script([FaultStep::Cut(0), FaultStep::Pass])),
+        );
+
+        assert!(transport.check(check()).await.is_ok());
+        let request = WatchRequest {
+            organization_id: "org".to_string(),
+            vault_id: "vault".to_string(),
+            filters: Vec::new(),
+            from_revision: None,
+        };
+        let events: Vec<_> = transport.watch(request).await.unwrap().collect().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_ref().unwrap_err().kind(), ErrorKind::Connection);
+        assert_eq!(transport.injected_count(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_latency_is_injected() {
+        let transport = faulty(
+            FaultInjector::new(0)
+                .with(Fault::latency(LatencyDistribution::Fixed(Duration::from_millis(200))))
+                .with(Fault::script([FaultStep::Delay(Duration::from_millis(100))])),
+        );
+
+        let start = tokio::time::Instant::now();
+        transport.check(check()).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn test_client_injects_faults() {
+        use crate::{BearerCredentialsConfig, Client};
+
+        let client = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .fault_injection(
+                FaultInjector::new(0)
+                    .with(Fault::fail_times(ErrorKind::Unavailable, 2).on(EngineOperation::Check)),
+            )
+            .build_with_transport(Arc::new(MockTransport::new().into_any()))
+            .await
+            .unwrap();
+        let vault = client.organization("org").vault("vault");
+
+        for _ in 0..2 {
+            let err = vault.check("user:alice", "viewer", "doc:1").await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Unavailable);
+        }
+        assert!(vault.check("user:alice", "viewer", "doc:1").await.is_ok());
+    }
+
+    #[test]
+    fn test_latency_distributions_stay_in_range() {
+        let mut rng = fastrand::Rng::with_seed(1);
+        let uniform = LatencyDistribution::Uniform {
+            min: Duration::from_millis(10),
+            max: Duration::from_millis(20),
+        };
+        for _ in 0..100 {
+            let delay = uniform.sample(&mut rng);
+            assert!(delay >= Duration::from_millis(10) && delay <= Duration::from_millis(20));
+        }
+        let exponential = LatencyDistribution::Exponential { mean: Duration::from_millis(10) };
+        let total: Duration = (0..1000).map(|_| exponential.sample(&mut rng)).sum();
+        assert!(total > Duration::from_secs(5) && total < Duration::from_secs(15));
+    }
+}
+
+#[cfg(all(test, feature = "rest"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod wiremock_tests {
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path, query_param},
+    };
+
+    use super::*;
+    use crate::{
+        BearerCredentialsConfig, Client,
+        vault::watch::{Operation, ReconnectConfig, WatchEvent},
+    };
+
+    fn events(revisions: impl IntoIterator<Item = u64>) -> ResponseTemplate {
+        let body: String = revisions
+            .into_iter()
+            .map(|revision| {
+                let relationship =
+                    Relationship::new(format!("doc:{}", revision), "viewer", "user:alice");
+                let event =
+                    WatchEvent::new(Operation::Create, relationship, revision, chrono::Utc::now());
+                format!("data: {}\n\n", serde_json::to_string(&event).unwrap())
+            })
+            .collect();
+        ResponseTemplate::new(200)
+            .insert_header("content-type", "text/event-stream")
+            .set_body_string(body)
+    }
+
+    #[tokio::test]
+    async fn test_cut_watch_resumes_from_last_event() {
+        let server = MockServer::start().await;
+        let watch_path = "/v1/organizations/org/vaults/vault/watch";
+        Mock::given(method("GET"))
+            .and(path(watch_path))
+            .and(query_param("from_revision", "0"))
+            .respond_with(events([1, 2, 3]))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(watch_path))
+            .and(query_param("from_revision", "2"))
+            .respond_with(events([3, 4]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test"))
+            .transport_strategy(crate::TransportStrategy::RestOnly)
+            .fault_injection(
+                FaultInjector::new(0)
+                    .with(Fault::script([FaultStep::Cut(2)]).on(EngineOperation::Watch)),
+            )
+            .build()
+            .await
+            .unwrap();
+        let vault = client.organization("org").vault("vault");
+        let stream = vault
+            .watch()
+            .from_revision(0)
+            .reconnect(ReconnectConfig::builder().initial_backoff(Duration::from_millis(1)).build())
+            .run()
+            .await
+            .unwrap();
+
+        let revisions: Vec<u64> =
+            stream.take(4).map(|event| event.unwrap().revision).collect().await;
+        assert_eq!(revisions, vec![1, 2, 3, 4]);
+    }
+}
@@ -8,9 +8,9 @@
 //! The gRPC transport is the preferred transport for production use due to
 //! its performance benefits and native support for bidirectional streaming.
 
-use std::{sync::Arc, time::Duration};
+use std::{future::Future, sync::Arc, time::Duration};
 
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
 use parking_lot::RwLock;
 use tonic::{
     service::interceptor::InterceptedService,
@@ -23,18 +23,25 @@ use super::proto::{
     {self as pb},
 };
 use crate::{
-    Error,
+    Error, ErrorKind,
     config::{RetryConfig, TlsConfig},
     transport::traits::{
         CheckRequest, CheckResponse, GrpcStats, ListRelationshipsResponse, ListResourcesResponse,
-        ListSubjectsResponse, PoolConfig, SimulateRequest, SimulateResponse, Transport,
-        TransportClient, TransportStats, WriteRequest, WriteResponse,
+        ListSubjectsResponse, PoolConfig, SimulateBatchRequest, SimulateRequest, SimulateResponse,
+        Transport, TransportClient, TransportStats, WatchEventStream, WatchRequest, WriteRequest,
+        WriteResponse,
     },
-    types::{ConsistencyToken, Decision, Relationship},
+    types::{ConsistencyRequirement, ConsistencyToken, Decision, Relationship},
     user_agent,
+    vault::watch::{Operation, WatchEvent, WatchFilter},
 };
 
+/// Most simulate RPCs a [`SimulateBatchRequest`] keeps in flight at once.
+const SIMULATE_BATCH_CONCURRENCY: usize = 16;
+
 /// Interceptor that adds user-agent metadata to all gRPC requests.
+///
+/// With the `tracing` feature, the caller's trace context is injected too.
 #[allow(clippy::result_large_err)] // tonic::Status is the required error type for interceptors
 fn user_agent_interceptor(
     mut req: tonic::Request<()>,
@@ -45,6 +52,8 @@ fn user_agent_interceptor(
             .parse()
             .unwrap_or_else(|_| tonic::metadata::MetadataValue::from_static("inferadb-rust")),
     );
+    #[cfg(feature = "tracing")]
+    crate::tracing_support::inject_current(req.metadata_mut());
     Ok(req)
 }
 
@@ -63,7 +72,14 @@ type InterceptedClient = AuthorizationServiceClient<
 #[derive(Clone)]
 pub struct GrpcTransport {
     client: InterceptedClient,
+    retry_config: RetryConfig,
     stats: Arc<RwLock<GrpcStats>>,
+    #[cfg(feature = "tracing")]
+    metrics: Option<crate::tracing_support::Metrics>,
+    /// Transport for the same endpoint that carries the simulations the
+    /// gRPC API cannot express.
+    #[cfg(feature = "rest")]
+    rest: Option<Box<super::rest::RestTransport>>,
 }
 
 #[bon::bon]
@@ -81,7 +97,7 @@ impl GrpcTransport {
         base_url: Url,
         tls_config: &TlsConfig,
         pool_config: &PoolConfig,
-        _retry_config: RetryConfig,
+        retry_config: RetryConfig,
         timeout: Duration,
     ) -> Result<Self, Error> {
         let endpoint = Endpoint::from_shared(base_url.to_string())
@@ -132,7 +148,45 @@ impl GrpcTransport {
             user_agent_interceptor;
         let client = AuthorizationServiceClient::with_interceptor(channel, interceptor);
 
-        Ok(Self { client, stats: Arc::new(RwLock::new(GrpcStats::default())) })
+        Ok(Self {
+            client,
+            retry_config,
+            stats: Arc::new(RwLock::new(GrpcStats::default())),
+            #[cfg(feature = "tracing")]
+            metrics: None,
+            #[cfg(feature = "rest")]
+            rest: None,
+        })
+    }
+
+    /// Sends simulations with a schema or removals, which the gRPC API
+    /// cannot express, over `rest` instead of failing them.
+    #[cfg(feature = "rest")]
+    pub(crate) fn with_rest(mut self, rest: super::rest::RestTransport) -> Self {
+        self.rest = Some(Box::new(rest));
+        self
+    }
+
+    /// Returns what a simulation asks for that the gRPC API cannot express,
+    /// if anything.
+    fn unsupported_simulation(
+        schema: Option<&String>,
+        removals: &[Relationship<'static>],
+    ) -> Option<&'static str> {
+        if schema.is_some() {
+            Some("schema simulation")
+        } else if !removals.is_empty() {
+            Some("simulating relationship removals")
+        } else {
+            None
+        }
+    }
+
+    fn unsupported_simulation_error(unsupported: &str) -> Error {
+        Error::invalid_argument(format!(
+            "{} is not supported over gRPC; use a transport strategy that allows REST",
+            unsupported
+        ))
     }
 
     /// Returns a builder for configuring the gRPC transport.
@@ -199,6 +253,60 @@ impl GrpcTransport {
         }
     }
 
+    /// Sets the metrics collector that retries are reported to.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn set_metrics(&mut self, metrics: crate::tracing_support::Metrics) {
+        #[cfg(feature = "rest")]
+        if let Some(rest) = &mut self.rest {
+            rest.set_metrics(metrics.clone());
+        }
+        self.metrics = Some(metrics);
+    }
+
+    /// Reports a retry to the metrics collector, if one is set.
+    fn record_retry(&self) {
+        #[cfg(feature = "tracing")]
+        if let Some(metrics) = &self.metrics {
+            metrics.increment_retries(Transport::Grpc);
+        }
+    }
+
+    /// Issues an RPC with retry logic.
+    ///
+    /// `call` builds and sends a fresh request on each attempt. Unavailable
+    /// and rate-limited calls are retried with exponential backoff, and timed
+    /// out calls too when [`RetryConfig::retry_on_timeout`] is set.
+    async fn with_retry<T, F, Fut>(&self, mut call: F) -> Result<T, Error>
+    where
+        F: FnMut(InterceptedClient) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<T>, Error>>,
+    {
+        let mut retries = 0;
+        let mut delay = self.retry_config.initial_delay;
+
+        loop {
+            let error = match call(self.client.clone()).await {
+                Ok(response) => return Ok(response.into_inner()),
+                Err(error) => error,
+            };
+
+            let retriable = match error.kind() {
+                ErrorKind::Unavailable | ErrorKind::RateLimited => true,
+                ErrorKind::Timeout => self.retry_config.retry_on_timeout,
+                _ => false,
+            };
+            if !retriable || retries >= self.retry_config.max_retries {
+                self.increment_failures();
+                return Err(error);
+            }
+
+            retries += 1;
+            self.record_retry();
+            tokio::time::sleep(error.retry_after().unwrap_or(delay)).await;
+            delay = std::cmp::min(delay * 2, self.retry_config.max_delay);
+        }
+    }
+
     /// Increments the requests_sent counter.
     fn increment_requests(&self) {
         self.stats.write().requests_sent += 1;
@@ -268,6 +376,74 @@ impl GrpcTransport {
     fn convert_relationship(rel: pb::Relationship) -> Relationship<'static> {
         Relationship::new(rel.resource, rel.relation, rel.subject).into_owned()
     }
+
+    /// Converts a proto WatchResponse to a watch event.
+    fn convert_watch_event(message: pb::WatchResponse) -> Result<WatchEvent, Error> {
+        let invalid =
+            |what: &str| Error::new(ErrorKind::InvalidResponse, format!("watch event {}", what));
+        let operation = match pb::ChangeOperation::try_from(message.operation) {
+            Ok(pb::ChangeOperation::Create) => Operation::Create,
+            Ok(pb::ChangeOperation::Delete) => Operation::Delete,
+            _ => return Err(invalid("has no operation")),
+        };
+        let relationship = message
+            .relationship
+            .map(Self::convert_relationship)
+            .ok_or_else(|| invalid("has no relationship"))?;
+        let revision = message.revision.parse().map_err(|_| invalid("has an invalid revision"))?;
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&message.timestamp)
+            .map_err(|_| invalid("has an invalid timestamp"))?
+            .with_timezone(&chrono::Utc);
+        Ok(WatchEvent::new(operation, relationship, revision, timestamp))
+    }
+
+    /// Opens a watch stream.
+    ///
+    /// The RPC can only filter by resource type, so other filters are left
+    /// to the caller.
+    pub async fn watch(&self, request: WatchRequest) -> Result<WatchEventStream, Error> {
+        self.increment_requests();
+
+        let pb_request = pb::WatchRequest {
+            resource_types: request
+                .filters
+                .into_iter()
+                .filter_map(|filter| match filter {
+                    WatchFilter::ResourceType(type_name) => Some(type_name),
+                    _ => None,
+                })
+                .collect(),
+            cursor: request.from_revision.map(|revision| revision.to_string()),
+        };
+
+        let stream = self
+            .with_retry(|mut client| {
+                let request = pb_request.clone();
+                async move { client.watch(request).await.map_err(Self::convert_error) }
+            })
+            .await?;
+        self.stats.write().streams_opened += 1;
+
+        Ok(Box::pin(
+            stream.map(|message| Self::convert_watch_event(message.map_err(Self::convert_error)?)),
+        ))
+    }
+
+    /// Wraps a message in a request whose metadata carries a read's
+    /// consistency requirement.
+    fn consistent_request<T>(
+        message: T,
+        consistency: &ConsistencyRequirement,
+    ) -> Result<tonic::Request<T>, Error> {
+        let mut request = tonic::Request::new(message);
+        for (key, value) in consistency.wire_headers() {
+            let value = value.parse().map_err(|_| {
+                Error::invalid_argument("consistency token is not a valid metadata value")
+            })?;
+            request.metadata_mut().insert(key, value);
+        }
+        Ok(request)
+    }
 }
 
 impl TransportClient for GrpcTransport {
@@ -283,12 +459,13 @@ impl TransportClient for GrpcTransport {
         };
 
         // Use streaming API with a single request
-        let stream = futures::stream::once(async { pb_request });
-        let mut client = self.client.clone();
-
-        let response = client.evaluate(stream).await.map_err(Self::convert_error)?;
-
-        let mut stream = response.into_inner();
+        let mut stream = self
+            .with_retry(|mut client| {
+                let stream = futures::stream::iter([pb_request.clone()]);
+                let request = Self::consistent_request(stream, &request.consistency);
+                async move { client.evaluate(request?).await.map_err(Self::convert_error) }
+            })
+            .await?;
 
         if let Some(result) = stream.next().await {
             let eval_response = result.map_err(Self::convert_error)?;
@@ -314,6 +491,8 @@ impl TransportClient for GrpcTransport {
     async fn check_batch(&self, requests: Vec<CheckRequest>) -> Result<Vec<CheckResponse>, Error> {
         self.increment_requests();
 
+        let consistency =
+            ConsistencyRequirement::strictest(requests.iter().map(|r| &r.consistency)).clone();
         let pb_requests: Vec<pb::EvaluateRequest> = requests
             .into_iter()
             .map(|r| pb::EvaluateRequest {
@@ -325,12 +504,13 @@ impl TransportClient for GrpcTransport {
             })
             .collect();
 
-        let stream = futures::stream::iter(pb_requests);
-        let mut client = self.client.clone();
-
-        let response = client.evaluate(stream).await.map_err(Self::convert_error)?;
-
-        let mut stream = response.into_inner();
+        let mut stream = self
+            .with_retry(|mut client| {
+                let stream = futures::stream::iter(pb_requests.clone());
+                let request = Self::consistent_request(stream, &consistency);
+                async move { client.evaluate(request?).await.map_err(Self::convert_error) }
+            })
+            .await?;
         let mut results = Vec::new();
 
         while let Some(result) = stream.next().await {
@@ -363,12 +543,12 @@ impl TransportClient for GrpcTransport {
             }],
         };
 
-        let stream = futures::stream::once(async { pb_request });
-        let mut client = self.client.clone();
-
-        let response = client.write_relationships(stream).await.map_err(Self::convert_error)?;
-
-        let write_response = response.into_inner();
+        let write_response = self
+            .with_retry(|mut client| {
+                let stream = futures::stream::iter([pb_request.clone()]);
+                async move { client.write_relationships(stream).await.map_err(Self::convert_error) }
+            })
+            .await?;
 
         Ok(WriteResponse { consistency_token: ConsistencyToken::new(&write_response.revision) })
     }
@@ -387,17 +567,20 @@ impl TransportClient for GrpcTransport {
 
         let pb_request = pb::WriteRelationshipsRequest { relationships };
 
-        let stream = futures::stream::once(async { pb_request });
-        let mut client = self.client.clone();
-
-        let response = client.write_relationships(stream).await.map_err(Self::convert_error)?;
-
-        let write_response = response.into_inner();
+        let write_response = self
+            .with_retry(|mut client| {
+                let stream = futures::stream::iter([pb_request.clone()]);
+                async move { client.write_relationships(stream).await.map_err(Self::convert_error) }
+            })
+            .await?;
 
         Ok(WriteResponse { consistency_token: ConsistencyToken::new(&write_response.revision) })
     }
 
-    async fn delete(&self, relationship: Relationship<'static>) -> Result<(), Error> {
+    async fn delete(
+        &self,
+        relationship: Relationship<'static>,
+    ) -> Result<Option<ConsistencyToken>, Error> {
         self.increment_requests();
 
         let pb_request = pb::DeleteRelationshipsRequest {
@@ -410,12 +593,16 @@ impl TransportClient for GrpcTransport {
             limit: None,
         };
 
-        let stream = futures::stream::once(async { pb_request });
-        let mut client = self.client.clone();
-
-        client.delete_relationships(stream).await.map_err(Self::convert_error)?;
+        let response = self
+            .with_retry(|mut client| {
+                let stream = futures::stream::iter([pb_request.clone()]);
+                async move { client.delete_relationships(stream).await.map_err(Self::convert_error) }
+            })
+            .await?;
 
-        Ok(())
+        Ok(Some(response.revision)
+            .filter(|revision| !revision.is_empty())
+            .map(ConsistencyToken::new))
     }
 
     async fn list_relationships(
@@ -425,6 +612,7 @@ impl TransportClient for GrpcTransport {
         subject: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> Result<ListRelationshipsResponse, Error> {
         self.increment_requests();
 
@@ -436,10 +624,12 @@ impl TransportClient for GrpcTransport {
             cursor: cursor.map(String::from),
         };
 
-        let mut client = self.client.clone();
-        let response = client.list_relationships(pb_request).await.map_err(Self::convert_error)?;
-
-        let mut stream = response.into_inner();
+        let mut stream = self
+            .with_retry(|mut client| {
+                let request = Self::consistent_request(pb_request.clone(), consistency);
+                async move { client.list_relationships(request?).await.map_err(Self::convert_error) }
+            })
+            .await?;
         let mut relationships = Vec::new();
         let mut next_cursor = None;
 
@@ -464,6 +654,7 @@ impl TransportClient for GrpcTransport {
         resource_type: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> Result<ListResourcesResponse, Error> {
         self.increment_requests();
 
@@ -476,10 +667,12 @@ impl TransportClient for GrpcTransport {
             resource_id_pattern: None,
         };
 
-        let mut client = self.client.clone();
-        let response = client.list_resources(pb_request).await.map_err(Self::convert_error)?;
-
-        let mut stream = response.into_inner();
+        let mut stream = self
+            .with_retry(|mut client| {
+                let request = Self::consistent_request(pb_request.clone(), consistency);
+                async move { client.list_resources(request?).await.map_err(Self::convert_error) }
+            })
+            .await?;
         let mut resources = Vec::new();
         let mut next_cursor = None;
 
@@ -504,6 +697,7 @@ impl TransportClient for GrpcTransport {
         subject_type: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> Result<ListSubjectsResponse, Error> {
         self.increment_requests();
 
@@ -515,10 +709,12 @@ impl TransportClient for GrpcTransport {
             cursor: cursor.map(String::from),
         };
 
-        let mut client = self.client.clone();
-        let response = client.list_subjects(pb_request).await.map_err(Self::convert_error)?;
-
-        let mut stream = response.into_inner();
+        let mut stream = self
+            .with_retry(|mut client| {
+                let request = Self::consistent_request(pb_request.clone(), consistency);
+                async move { client.list_subjects(request?).await.map_err(Self::convert_error) }
+            })
+            .await?;
         let mut subjects = Vec::new();
         let mut next_cursor = None;
 
@@ -549,18 +745,30 @@ impl TransportClient for GrpcTransport {
             last_fallback_at: None,
             grpc: Some(grpc),
             rest: None,
+            endpoints: Vec::new(),
         }
     }
 
     async fn health_check(&self) -> Result<(), Error> {
-        let mut client = self.client.clone();
-
-        client.health(pb::HealthRequest {}).await.map_err(Self::convert_error)?;
+        self.with_retry(|mut client| async move {
+            client.health(pb::HealthRequest {}).await.map_err(Self::convert_error)
+        })
+        .await?;
 
         Ok(())
     }
 
     async fn simulate(&self, request: SimulateRequest) -> Result<SimulateResponse, Error> {
+        if let Some(unsupported) =
+            Self::unsupported_simulation(request.schema.as_ref(), &request.removals)
+        {
+            #[cfg(feature = "rest")]
+            if let Some(rest) = &self.rest {
+                return rest.simulate(request).await;
+            }
+            return Err(Self::unsupported_simulation_error(unsupported));
+        }
+
         self.increment_requests();
 
         let context_relationships: Vec<pb::Relationship> = request
@@ -583,14 +791,38 @@ impl TransportClient for GrpcTransport {
             }),
         };
 
-        let mut client = self.client.clone();
-        let response = client.simulate(pb_request).await.map_err(Self::convert_error)?;
-
-        let sim_response = response.into_inner();
+        let sim_response = self
+            .with_retry(|mut client| {
+                let request = Self::consistent_request(pb_request.clone(), &request.consistency);
+                async move { client.simulate(request?).await.map_err(Self::convert_error) }
+            })
+            .await?;
         let allowed = Self::convert_decision(sim_response.decision);
 
         Ok(SimulateResponse { allowed, decision: Decision::new(allowed) })
     }
+
+    async fn simulate_batch(
+        &self,
+        request: SimulateBatchRequest,
+    ) -> Result<Vec<SimulateResponse>, Error> {
+        if let Some(unsupported) =
+            Self::unsupported_simulation(request.schema.as_ref(), &request.removals)
+        {
+            #[cfg(feature = "rest")]
+            if let Some(rest) = &self.rest {
+                return rest.simulate_batch(request).await;
+            }
+            return Err(Self::unsupported_simulation_error(unsupported));
+        }
+
+        // The gRPC API has no batch simulate RPC, so issue the checks concurrently
+        // over the multiplexed HTTP/2 channel, a bounded number at a time.
+        futures::stream::iter(request.into_requests().into_iter().map(|r| self.simulate(r)))
+            .buffered(SIMULATE_BATCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
 }
 
 #[cfg(test)]
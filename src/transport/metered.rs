@@ -0,0 +1,226 @@
+//! Transport wrapper that records SDK metrics.
+//!
+//! [`MeteredTransport`] wraps the client's transport when metrics are
+//! configured and records every engine call in a [`Metrics`] collector,
+//! labelled by operation, transport and outcome.
+
+use std::{sync::Arc, time::Instant};
+
+use super::traits::{
+    AnyTransport, BoxFuture, CheckRequest, CheckResponse, ListRelationshipsResponse,
+    ListResourcesResponse, ListSubjectsResponse, SimulateBatchRequest, SimulateRequest,
+    SimulateResponse, WriteRequest, WriteResponse,
+};
+use crate::{
+    Error, ErrorKind,
+    tracing_support::{Metrics, RequestOutcome},
+    types::{ConsistencyRequirement, ConsistencyToken, Relationship},
+};
+
+/// Transport that records metrics for every call to an inner transport.
+pub struct MeteredTransport {
+    inner: Arc<AnyTransport>,
+    metrics: Metrics,
+}
+
+impl MeteredTransport {
+    /// Wraps `inner`, recording its calls in `metrics`.
+    pub fn new(inner: Arc<AnyTransport>, metrics: Metrics) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// Returns the wrapped transport.
+    pub fn inner(&self) -> &AnyTransport {
+        &self.inner
+    }
+
+    /// Returns the metrics collector.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Performs an authorization check.
+    pub fn check(&self, request: CheckRequest) -> BoxFuture<'_, Result<CheckResponse, Error>> {
+        Box::pin(async move {
+            let start = Instant::now();
+            let result = self
+                .observe("check", self.inner.check(request), |response: &CheckResponse| {
+                    decision(response.allowed)
+                })
+                .await;
+            match &result {
+                Ok(response) => {
+                    self.metrics.increment_check_count(response.allowed);
+                    self.metrics.record_check_latency(start.elapsed(), response.allowed);
+                },
+                Err(_) => self.metrics.increment_check_errors(),
+            }
+            result
+        })
+    }
+
+    /// Performs a batch of authorization checks.
+    pub fn check_batch(
+        &self,
+        requests: Vec<CheckRequest>,
+    ) -> BoxFuture<'_, Result<Vec<CheckResponse>, Error>> {
+        Box::pin(self.observe("check_batch", self.inner.check_batch(requests), success))
+    }
+
+    /// Writes a relationship.
+    pub fn write(&self, request: WriteRequest) -> BoxFuture<'_, Result<WriteResponse, Error>> {
+        Box::pin(async move {
+            let start = Instant::now();
+            let result = self.observe("write", self.inner.write(request), success).await;
+            if result.is_ok() {
+                self.metrics.increment_relationship_writes(1);
+                self.metrics.record_write_latency(start.elapsed());
+            }
+            result
+        })
+    }
+
+    /// Writes a batch of relationships.
+    pub fn write_batch(
+        &self,
+        requests: Vec<WriteRequest>,
+    ) -> BoxFuture<'_, Result<WriteResponse, Error>> {
+        let count = requests.len() as u64;
+        Box::pin(async move {
+            let start = Instant::now();
+            let result =
+                self.observe("write_batch", self.inner.write_batch(requests), success).await;
+            if result.is_ok() {
+                self.metrics.increment_relationship_writes(count);
+                self.metrics.record_write_latency(start.elapsed());
+            }
+            result
+        })
+    }
+
+    /// Deletes a relationship.
+    pub fn delete(
+        &self,
+        relationship: Relationship<'static>,
+    ) -> BoxFuture<'_, Result<Option<ConsistencyToken>, Error>> {
+        Box::pin(async move {
+            let result = self.observe("delete", self.inner.delete(relationship), success).await;
+            if result.is_ok() {
+                self.metrics.increment_relationship_deletes(1);
+            }
+            result
+        })
+    }
+
+    /// Lists relationships matching a filter.
+    pub fn list_relationships(
+        &self,
+        resource: Option<&str>,
+        relation: Option<&str>,
+        subject: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> BoxFuture<'_, Result<ListRelationshipsResponse, Error>> {
+        let future =
+            self.inner.list_relationships(resource, relation, subject, limit, cursor, consistency);
+        Box::pin(self.observe("list_relationships", future, success))
+    }
+
+    /// Lists resources accessible by a subject with a permission.
+    pub fn list_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        resource_type: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> BoxFuture<'_, Result<ListResourcesResponse, Error>> {
+        let future = self.inner.list_resources(
+            subject,
+            permission,
+            resource_type,
+            limit,
+            cursor,
+            consistency,
+        );
+        Box::pin(self.observe("list_resources", future, success))
+    }
+
+    /// Lists subjects with a permission on a resource.
+    pub fn list_subjects(
+        &self,
+        permission: &str,
+        resource: &str,
+        subject_type: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
+    ) -> BoxFuture<'_, Result<ListSubjectsResponse, Error>> {
+        let future = self.inner.list_subjects(
+            permission,
+            resource,
+            subject_type,
+            limit,
+            cursor,
+            consistency,
+        );
+        Box::pin(self.observe("list_subjects", future, success))
+    }
+
+    /// Performs a simulated authorization check with hypothetical changes.
+    pub fn simulate(
+        &self,
+        request: SimulateRequest,
+    ) -> BoxFuture<'_, Result<SimulateResponse, Error>> {
+        Box::pin(self.observe("simulate", self.inner.simulate(request), |response| {
+            decision(response.allowed)
+        }))
+    }
+
+    /// Performs many simulated checks against the same hypothetical changes.
+    pub fn simulate_batch(
+        &self,
+        request: SimulateBatchRequest,
+    ) -> BoxFuture<'_, Result<Vec<SimulateResponse>, Error>> {
+        Box::pin(self.observe("simulate_batch", self.inner.simulate_batch(request), success))
+    }
+
+    /// Awaits `future`, recording its latency and outcome under `operation`.
+    async fn observe<T>(
+        &self,
+        operation: &'static str,
+        future: BoxFuture<'_, Result<T, Error>>,
+        outcome: impl FnOnce(&T) -> RequestOutcome,
+    ) -> Result<T, Error> {
+        let start = Instant::now();
+        let result = future.await;
+        let outcome = match &result {
+            Ok(value) => outcome(value),
+            Err(error) => {
+                if error.kind() == ErrorKind::Connection {
+                    self.metrics.increment_connection_errors();
+                }
+                RequestOutcome::Error
+            },
+        };
+        self.metrics.record_request(
+            operation,
+            self.inner.transport_type(),
+            outcome,
+            start.elapsed(),
+        );
+        result
+    }
+}
+
+/// Outcome of an authorization decision.
+fn decision(allowed: bool) -> RequestOutcome {
+    if allowed { RequestOutcome::Allow } else { RequestOutcome::Deny }
+}
+
+/// Outcome of any successful non-decision call.
+fn success<T>(_: &T) -> RequestOutcome {
+    RequestOutcome::Success
+}
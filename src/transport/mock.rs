@@ -12,12 +12,12 @@ use parking_lot::RwLock;
 
 use super::traits::{
     CheckRequest, CheckResponse, ListRelationshipsResponse, ListResourcesResponse,
-    ListSubjectsResponse, SimulateRequest, SimulateResponse, Transport, TransportClient,
-    TransportStats, WriteRequest, WriteResponse,
+    ListSubjectsResponse, SimulateBatchRequest, SimulateRequest, SimulateResponse, Transport,
+    TransportClient, TransportStats, WatchEventStream, WatchRequest, WriteRequest, WriteResponse,
 };
 use crate::{
     Error,
-    types::{ConsistencyToken, Decision, Relationship},
+    types::{ConsistencyRequirement, ConsistencyToken, Decision, Relationship},
 };
 
 /// Mock transport for testing.
@@ -82,6 +82,14 @@ impl MockTransport {
         self.request_count.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Opens a watch stream. The mock transport publishes no changes, so
+    /// the stream ends immediately.
+    pub async fn watch(&self, _request: WatchRequest) -> Result<WatchEventStream, Error> {
+        self.increment_requests();
+        self.check_failure()?;
+        Ok(Box::pin(futures::stream::empty()))
+    }
+
     /// Wraps this transport in an `AnyTransport` enum for use with the client.
     ///
     /// This is a convenience method for testing. It consumes the transport.
@@ -171,7 +179,10 @@ impl TransportClient for MockTransport {
         Ok(WriteResponse { consistency_token: ConsistencyToken::new("mock_token") })
     }
 
-    async fn delete(&self, relationship: Relationship<'static>) -> Result<(), Error> {
+    async fn delete(
+        &self,
+        relationship: Relationship<'static>,
+    ) -> Result<Option<ConsistencyToken>, Error> {
         self.increment_requests();
         self.check_failure()?;
 
@@ -182,7 +193,7 @@ impl TransportClient for MockTransport {
                 && rel.subject() == relationship.subject())
         });
 
-        Ok(())
+        Ok(Some(ConsistencyToken::new("mock_token")))
     }
 
     async fn list_relationships(
@@ -192,6 +203,7 @@ impl TransportClient for MockTransport {
         subject: Option<&str>,
         limit: Option<u32>,
         _cursor: Option<&str>,
+        _consistency: &ConsistencyRequirement,
     ) -> Result<ListRelationshipsResponse, Error> {
         self.increment_requests();
         self.check_failure()?;
@@ -219,6 +231,7 @@ impl TransportClient for MockTransport {
         resource_type: Option<&str>,
         limit: Option<u32>,
         _cursor: Option<&str>,
+        _consistency: &ConsistencyRequirement,
     ) -> Result<ListResourcesResponse, Error> {
         self.increment_requests();
         self.check_failure()?;
@@ -246,6 +259,7 @@ impl TransportClient for MockTransport {
         subject_type: Option<&str>,
         limit: Option<u32>,
         _cursor: Option<&str>,
+        _consistency: &ConsistencyRequirement,
     ) -> Result<ListSubjectsResponse, Error> {
         self.increment_requests();
         self.check_failure()?;
@@ -277,6 +291,7 @@ impl TransportClient for MockTransport {
             last_fallback_at: None,
             grpc: None,
             rest: None,
+            endpoints: Vec::new(),
         }
     }
 
@@ -307,6 +322,17 @@ impl TransportClient for MockTransport {
 
         Ok(SimulateResponse { allowed, decision: Decision::new(allowed) })
     }
+
+    async fn simulate_batch(
+        &self,
+        request: SimulateBatchRequest,
+    ) -> Result<Vec<SimulateResponse>, Error> {
+        let mut responses = Vec::with_capacity(request.checks.len());
+        for request in request.into_requests() {
+            responses.push(self.simulate(request).await?);
+        }
+        Ok(responses)
+    }
 }
 
 /// Shared mock transport for use across async contexts.
@@ -328,7 +354,7 @@ mod tests {
                 permission: "view".to_string(),
                 resource: "doc:1".to_string(),
                 context: None,
-                consistency: None,
+                consistency: ConsistencyRequirement::Eventual,
                 trace: false,
             })
             .await
@@ -345,7 +371,7 @@ mod tests {
                 permission: "view".to_string(),
                 resource: "doc:1".to_string(),
                 context: None,
-                consistency: None,
+                consistency: ConsistencyRequirement::Eventual,
                 trace: false,
             })
             .await
@@ -379,8 +405,17 @@ mod tests {
             .await
             .unwrap();
 
-        let list =
-            transport.list_relationships(Some("doc:1"), None, None, None, None).await.unwrap();
+        let list = transport
+            .list_relationships(
+                Some("doc:1"),
+                None,
+                None,
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await
+            .unwrap();
         assert!(list.relationships.is_empty());
     }
 
@@ -391,12 +426,30 @@ mod tests {
         transport.add_relationship(Relationship::new("doc:1", "editor", "user:bob").into_owned());
         transport.add_relationship(Relationship::new("doc:2", "viewer", "user:alice").into_owned());
 
-        let list =
-            transport.list_relationships(Some("doc:1"), None, None, None, None).await.unwrap();
+        let list = transport
+            .list_relationships(
+                Some("doc:1"),
+                None,
+                None,
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await
+            .unwrap();
         assert_eq!(list.relationships.len(), 2);
 
-        let list =
-            transport.list_relationships(None, Some("viewer"), None, None, None).await.unwrap();
+        let list = transport
+            .list_relationships(
+                None,
+                Some("viewer"),
+                None,
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await
+            .unwrap();
         assert_eq!(list.relationships.len(), 2);
     }
 
@@ -411,7 +464,7 @@ mod tests {
                 permission: "view".to_string(),
                 resource: "doc:1".to_string(),
                 context: None,
-                consistency: None,
+                consistency: ConsistencyRequirement::Eventual,
                 trace: false,
             })
             .await;
@@ -425,7 +478,7 @@ mod tests {
                 permission: "view".to_string(),
                 resource: "doc:1".to_string(),
                 context: None,
-                consistency: None,
+                consistency: ConsistencyRequirement::Eventual,
                 trace: false,
             })
             .await;
@@ -489,6 +542,8 @@ mod tests {
                 context: None,
                 additions: vec![Relationship::new("doc:1", "viewer", "user:bob").into_owned()],
                 removals: vec![],
+                schema: None,
+                consistency: ConsistencyRequirement::Eventual,
             })
             .await
             .unwrap();
@@ -512,6 +567,8 @@ mod tests {
                 context: None,
                 additions: vec![],
                 removals: vec![Relationship::new("doc:1", "viewer", "user:alice").into_owned()],
+                schema: None,
+                consistency: ConsistencyRequirement::Eventual,
             })
             .await
             .unwrap();
@@ -527,8 +584,17 @@ mod tests {
         transport
             .add_relationship(Relationship::new("folder:1", "viewer", "user:alice").into_owned());
 
-        let result =
-            transport.list_resources("user:alice", "viewer", None, None, None).await.unwrap();
+        let result = transport
+            .list_resources(
+                "user:alice",
+                "viewer",
+                None,
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await
+            .unwrap();
 
         assert_eq!(result.resources.len(), 3);
         assert!(result.resources.contains(&"doc:1".to_string()));
@@ -545,7 +611,14 @@ mod tests {
             .add_relationship(Relationship::new("folder:1", "viewer", "user:alice").into_owned());
 
         let result = transport
-            .list_resources("user:alice", "viewer", Some("doc"), None, None)
+            .list_resources(
+                "user:alice",
+                "viewer",
+                Some("doc"),
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
             .await
             .unwrap();
 
@@ -562,7 +635,10 @@ mod tests {
         transport
             .add_relationship(Relationship::new("doc:1", "viewer", "group:admins").into_owned());
 
-        let result = transport.list_subjects("viewer", "doc:1", None, None, None).await.unwrap();
+        let result = transport
+            .list_subjects("viewer", "doc:1", None, None, None, &ConsistencyRequirement::Eventual)
+            .await
+            .unwrap();
 
         assert_eq!(result.subjects.len(), 3);
         assert!(result.subjects.contains(&"user:alice".to_string()));
@@ -578,8 +654,17 @@ mod tests {
         transport
             .add_relationship(Relationship::new("doc:1", "viewer", "group:admins").into_owned());
 
-        let result =
-            transport.list_subjects("viewer", "doc:1", Some("user"), None, None).await.unwrap();
+        let result = transport
+            .list_subjects(
+                "viewer",
+                "doc:1",
+                Some("user"),
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await
+            .unwrap();
 
         assert_eq!(result.subjects.len(), 2);
         assert!(result.subjects.contains(&"user:alice".to_string()));
@@ -594,7 +679,10 @@ mod tests {
 
         transport.clear_relationships();
 
-        let list = transport.list_relationships(None, None, None, None, None).await.unwrap();
+        let list = transport
+            .list_relationships(None, None, None, None, None, &ConsistencyRequirement::Eventual)
+            .await
+            .unwrap();
         assert!(list.relationships.is_empty());
     }
 
@@ -607,8 +695,17 @@ mod tests {
             );
         }
 
-        let result =
-            transport.list_resources("user:alice", "viewer", None, Some(5), None).await.unwrap();
+        let result = transport
+            .list_resources(
+                "user:alice",
+                "viewer",
+                None,
+                Some(5),
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await
+            .unwrap();
 
         assert_eq!(result.resources.len(), 5);
     }
@@ -622,7 +719,17 @@ mod tests {
             );
         }
 
-        let result = transport.list_subjects("viewer", "doc:1", None, Some(5), None).await.unwrap();
+        let result = transport
+            .list_subjects(
+                "viewer",
+                "doc:1",
+                None,
+                Some(5),
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await
+            .unwrap();
 
         assert_eq!(result.subjects.len(), 5);
     }
@@ -5,6 +5,9 @@
 //! - gRPC transport (via tonic) - default, high performance
 //! - REST transport (via reqwest) - for environments without gRPC support
 //! - Mock transport - for testing without network
+//! - Balanced transport - client-side load balancing across endpoints
+//! - Cassette transport - records calls to a file and replays them
+//! - Fault transport - injects latency and failures for chaos testing
 //!
 //! The transport layer is internal to the SDK. Users interact with
 //! the higher-level [`Client`](crate::Client) and [`VaultClient`](crate::VaultClient) APIs.
@@ -41,7 +44,18 @@ pub(crate) mod rest;
 
 pub(crate) mod mock;
 
+pub(crate) mod balancer;
+
+pub(crate) mod cassette;
+
+pub(crate) mod faults;
+
+#[cfg(feature = "tracing")]
+pub(crate) mod metered;
+
 // Re-export public types
+pub use cassette::{Cassette, CassetteMode, MatchRules};
+pub use faults::{EngineOperation, Fault, FaultInjector, FaultStep, LatencyDistribution};
 // Re-export gRPC transport
 #[cfg(feature = "grpc")]
 pub use grpc::{GrpcTransport, GrpcTransportBuilder};
@@ -51,12 +65,18 @@ pub use rest::{RestTransport, RestTransportBuilder};
 // Internal re-exports (used when transport is integrated with client)
 #[cfg(any(feature = "grpc", feature = "rest"))]
 pub(crate) use traits::AnyTransport;
-#[cfg(feature = "rest")]
+#[cfg(any(feature = "grpc", feature = "rest"))]
 pub(crate) use traits::{
     CheckRequest as TransportCheckRequest, SimulateRequest as TransportSimulateRequest,
-    WriteRequest as TransportWriteRequest,
+    WatchRequest,
+};
+#[cfg(feature = "rest")]
+pub(crate) use traits::{
+    CheckResponse as TransportCheckResponse, SimulateBatchRequest as TransportSimulateBatchRequest,
+    SimulateCheck as TransportSimulateCheck, WriteRequest as TransportWriteRequest,
 };
 pub use traits::{
-    FallbackReason, FallbackTrigger, GrpcStats, PoolConfig, RestStats, Transport, TransportEvent,
-    TransportStats, TransportStrategy,
+    DecisionTrace, EndpointStats, EvaluationNode, EvaluationNodeType, FallbackReason,
+    FallbackTrigger, GrpcStats, PoolConfig, RestStats, Transport, TransportEvent, TransportStats,
+    TransportStrategy,
 };
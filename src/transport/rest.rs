@@ -18,11 +18,13 @@ use crate::{
     error::ErrorKind,
     transport::traits::{
         CheckRequest, CheckResponse, ListRelationshipsResponse, ListResourcesResponse,
-        ListSubjectsResponse, PoolConfig, RestStats, SimulateRequest, SimulateResponse, Transport,
-        TransportClient, TransportStats, WriteRequest, WriteResponse,
+        ListSubjectsResponse, PoolConfig, RestStats, SimulateBatchRequest, SimulateRequest,
+        SimulateResponse, Transport, TransportClient, TransportStats, WatchEventStream,
+        WatchRequest, WriteRequest, WriteResponse,
     },
-    types::{ConsistencyToken, Context, Decision, Relationship},
+    types::{ConsistencyRequirement, ConsistencyToken, Context, Decision, Relationship},
     user_agent,
+    vault::watch::{WatchEvent, WatchFilter},
 };
 
 // ============================================================================
@@ -40,6 +42,8 @@ pub struct RestTransport {
     auth_token: Arc<RwLock<Option<String>>>,
     retry_config: RetryConfig,
     stats: Arc<RwLock<RestStats>>,
+    #[cfg(feature = "tracing")]
+    metrics: Option<crate::tracing_support::Metrics>,
 }
 
 impl std::fmt::Debug for RestTransport {
@@ -158,6 +162,8 @@ impl RestTransport {
             auth_token: Arc::new(RwLock::new(None)),
             retry_config,
             stats: Arc::new(RwLock::new(RestStats::default())),
+            #[cfg(feature = "tracing")]
+            metrics: None,
         })
     }
 
@@ -171,6 +177,20 @@ impl RestTransport {
         *self.auth_token.write() = None;
     }
 
+    /// Sets the metrics collector that retries are reported to.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn set_metrics(&mut self, metrics: crate::tracing_support::Metrics) {
+        self.metrics = Some(metrics);
+    }
+
+    /// Reports a retry to the metrics collector, if one is set.
+    fn record_retry(&self) {
+        #[cfg(feature = "tracing")]
+        if let Some(metrics) = &self.metrics {
+            metrics.increment_retries(Transport::Http);
+        }
+    }
+
     /// Builds default headers for requests.
     fn build_headers(&self) -> Result<HeaderMap, Error> {
         let mut headers = HeaderMap::new();
@@ -188,11 +208,33 @@ impl RestTransport {
             );
         }
 
+        #[cfg(feature = "tracing")]
+        crate::tracing_support::inject_current(&mut headers);
+
         Ok(headers)
     }
 
+    /// Adds the headers carrying a read's consistency requirement.
+    fn apply_consistency(
+        headers: &mut HeaderMap,
+        consistency: &ConsistencyRequirement,
+    ) -> Result<(), Error> {
+        for (name, value) in consistency.wire_headers() {
+            let value = HeaderValue::from_str(value).map_err(|_| {
+                Error::invalid_argument("consistency token is not a valid header value")
+            })?;
+            headers.insert(name, value);
+        }
+        Ok(())
+    }
+
     /// Makes a POST request with JSON body.
-    async fn post<T, R>(&self, path: &str, body: &T) -> Result<R, Error>
+    async fn post<T, R>(
+        &self,
+        path: &str,
+        body: &T,
+        consistency: &ConsistencyRequirement,
+    ) -> Result<R, Error>
     where
         T: Serialize,
         R: DeserializeOwned,
@@ -201,7 +243,8 @@ impl RestTransport {
             Error::new(ErrorKind::Configuration, format!("Invalid URL path: {}", e))
         })?;
 
-        let headers = self.build_headers()?;
+        let mut headers = self.build_headers()?;
+        Self::apply_consistency(&mut headers, consistency)?;
 
         let response = self
             .execute_with_retry(|| async {
@@ -213,7 +256,9 @@ impl RestTransport {
     }
 
     /// Makes a DELETE request.
-    async fn delete_request(&self, path: &str) -> Result<(), Error> {
+    /// Makes a DELETE request, returning the revision the server reported,
+    /// if any.
+    async fn delete_request(&self, path: &str) -> Result<Option<ConsistencyToken>, Error> {
         let url = self.base_url.join(path).map_err(|e| {
             Error::new(ErrorKind::Configuration, format!("Invalid URL path: {}", e))
         })?;
@@ -226,7 +271,19 @@ impl RestTransport {
             })
             .await?;
 
-        self.handle_error_response(response).await
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(map_status_error(status.as_u16(), &error_text));
+        }
+
+        // Older servers answer 204 with no body, so the revision is optional
+        let body = response.bytes().await.unwrap_or_default();
+        Ok(serde_json::from_slice::<DeleteRelationshipsResponse>(&body)
+            .ok()
+            .and_then(|response| response.revision)
+            .filter(|revision| !revision.is_empty())
+            .map(ConsistencyToken::new))
     }
 
     /// Makes a POST request that returns SSE stream.
@@ -234,6 +291,7 @@ impl RestTransport {
         &self,
         path: &str,
         body: &T,
+        consistency: &ConsistencyRequirement,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<R, Error>> + Send>>, Error>
     where
         T: Serialize,
@@ -245,6 +303,7 @@ impl RestTransport {
 
         let mut headers = self.build_headers()?;
         headers.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+        Self::apply_consistency(&mut headers, consistency)?;
 
         let response = self
             .client
@@ -284,6 +343,66 @@ impl RestTransport {
         Ok(Box::pin(sse_stream))
     }
 
+    /// Opens a watch stream as a long-lived SSE response.
+    pub async fn watch(&self, request: WatchRequest) -> Result<WatchEventStream, Error> {
+        let path = format!(
+            "/v1/organizations/{}/vaults/{}/watch",
+            request.organization_id, request.vault_id
+        );
+        let mut url = self.base_url.join(&path).map_err(|e| {
+            Error::new(ErrorKind::Configuration, format!("Invalid URL path: {}", e))
+        })?;
+
+        let mut params = Vec::new();
+        if let Some(revision) = request.from_revision {
+            params.push(("from_revision", revision.to_string()));
+        }
+        for filter in request.filters {
+            match filter {
+                WatchFilter::ResourceType(t) => params.push(("resource_type", t)),
+                WatchFilter::SubjectType(t) => params.push(("subject_type", t)),
+                WatchFilter::Resource(r) => params.push(("resource", r)),
+                WatchFilter::Subject(s) => params.push(("subject", s)),
+                WatchFilter::Relation(r) => params.push(("relation", r)),
+                WatchFilter::Operations(ops) => {
+                    params.extend(ops.into_iter().map(|op| ("operation", op.to_string())));
+                },
+                WatchFilter::Custom(expr) => params.push(("filter", expr)),
+            }
+        }
+        if !params.is_empty() {
+            url.query_pairs_mut().extend_pairs(params);
+        }
+
+        let mut headers = self.build_headers()?;
+        headers.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+
+        let response =
+            self.client.get(url).headers(headers).send().await.map_err(map_reqwest_error)?;
+
+        {
+            let mut stats = self.stats.write();
+            stats.requests_sent += 1;
+            stats.sse_connections += 1;
+            stats.sse_active += 1;
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            {
+                let mut stats = self.stats.write();
+                stats.sse_active = stats.sse_active.saturating_sub(1);
+                stats.requests_failed += 1;
+            }
+
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(map_status_error(status.as_u16(), &error_text));
+        }
+
+        let stats = Arc::clone(&self.stats);
+        Ok(Box::pin(parse_sse_stream::<WatchEvent>(response.bytes_stream(), stats)))
+    }
+
     /// Executes a request with retry logic.
     async fn execute_with_retry<F, Fut>(&self, make_request: F) -> Result<reqwest::Response, Error>
     where
@@ -323,6 +442,7 @@ impl RestTransport {
                             .map(Duration::from_secs)
                             .unwrap_or(delay);
 
+                        self.record_retry();
                         tokio::time::sleep(retry_after).await;
                         delay = std::cmp::min(delay * 2, self.retry_config.max_delay);
                         continue;
@@ -346,6 +466,7 @@ impl RestTransport {
                         return Ok(response);
                     }
 
+                    self.record_retry();
                     tokio::time::sleep(delay).await;
                     delay = std::cmp::min(delay * 2, self.retry_config.max_delay);
                 },
@@ -359,6 +480,7 @@ impl RestTransport {
 
                     // Only retry on connection/timeout errors
                     if e.is_connect() || e.is_timeout() {
+                        self.record_retry();
                         tokio::time::sleep(delay).await;
                         delay = std::cmp::min(delay * 2, self.retry_config.max_delay);
                         continue;
@@ -525,6 +647,12 @@ struct WriteRelationshipsResponse {
     _relationships_written: usize,
 }
 
+#[derive(Debug, Deserialize)]
+struct DeleteRelationshipsResponse {
+    #[serde(default)]
+    revision: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct ListRelationshipsApiRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -564,6 +692,98 @@ struct ListSubjectsApiRequest {
     cursor: Option<String>,
 }
 
+/// Body of a batch simulation request.
+#[derive(Debug, Serialize)]
+struct SimulateBatchApiRequest {
+    evaluations: Vec<SimulateItem>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    additions: Vec<RelationshipDto>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    removals: Vec<RelationshipDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SimulateItem {
+    subject: String,
+    permission: String,
+    resource: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<Context>,
+}
+
+impl SimulateBatchApiRequest {
+    /// Path of the batch simulation endpoint.
+    const PATH: &'static str = "/access/v1/simulate/batch";
+
+    fn new(request: SimulateBatchRequest) -> Self {
+        let to_dto = |r: &Relationship<'static>| RelationshipDto {
+            resource: r.resource().to_string(),
+            relation: r.relation().to_string(),
+            subject: r.subject().to_string(),
+        };
+        Self {
+            additions: request.additions.iter().map(to_dto).collect(),
+            removals: request.removals.iter().map(to_dto).collect(),
+            schema: request.schema,
+            evaluations: request
+                .checks
+                .into_iter()
+                .map(|c| SimulateItem {
+                    subject: c.subject,
+                    permission: c.permission,
+                    resource: c.resource,
+                    context: c.context,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Response to a batch simulation request.
+#[derive(Debug, Deserialize)]
+struct SimulateBatchApiResponse {
+    results: Vec<SimulateBatchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimulateBatchResult {
+    #[serde(default)]
+    index: Option<usize>,
+    allowed: bool,
+}
+
+impl SimulateBatchApiResponse {
+    /// Orders the results by index, failing if any of the `count`
+    /// simulations is missing.
+    fn into_responses(self, count: usize) -> Result<Vec<SimulateResponse>, Error> {
+        let mut results = vec![None; count];
+        for (position, result) in self.results.into_iter().enumerate() {
+            let index = result.index.unwrap_or(position);
+            if index < count {
+                results[index] = Some(SimulateResponse {
+                    allowed: result.allowed,
+                    decision: Decision::new(result.allowed),
+                });
+            }
+        }
+
+        results
+            .into_iter()
+            .enumerate()
+            .map(|(i, r)| {
+                r.ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidResponse,
+                        format!("Missing result for simulation at index {}", i),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
 // ============================================================================
 // Trace Conversion Helpers
 // ============================================================================
@@ -630,10 +850,12 @@ impl TransportClient for RestTransport {
                 trace: if request.trace { Some(true) } else { None },
             }],
         };
+        let consistency = request.consistency;
 
         // Use SSE endpoint for streaming
-        let mut stream =
-            self.post_sse::<_, EvaluateResponse>("/access/v1/evaluate", &api_request).await?;
+        let mut stream = self
+            .post_sse::<_, EvaluateResponse>("/access/v1/evaluate", &api_request, &consistency)
+            .await?;
 
         // Get the first result
         if let Some(result) = stream.next().await {
@@ -667,8 +889,11 @@ impl TransportClient for RestTransport {
                 .collect(),
         };
 
-        let mut stream =
-            self.post_sse::<_, EvaluateResponse>("/access/v1/evaluate", &api_request).await?;
+        let consistency =
+            ConsistencyRequirement::strictest(requests.iter().map(|r| &r.consistency));
+        let mut stream = self
+            .post_sse::<_, EvaluateResponse>("/access/v1/evaluate", &api_request, consistency)
+            .await?;
 
         let mut results = vec![None; requests.len()];
 
@@ -709,8 +934,9 @@ impl TransportClient for RestTransport {
             expected_revision: None,
         };
 
-        let response: WriteRelationshipsResponse =
-            self.post("/access/v1/relationships/write", &api_request).await?;
+        let response: WriteRelationshipsResponse = self
+            .post("/access/v1/relationships/write", &api_request, &ConsistencyRequirement::Eventual)
+            .await?;
 
         Ok(WriteResponse { consistency_token: ConsistencyToken::new(response.revision) })
     }
@@ -732,13 +958,17 @@ impl TransportClient for RestTransport {
             expected_revision: None,
         };
 
-        let response: WriteRelationshipsResponse =
-            self.post("/access/v1/relationships/write", &api_request).await?;
+        let response: WriteRelationshipsResponse = self
+            .post("/access/v1/relationships/write", &api_request, &ConsistencyRequirement::Eventual)
+            .await?;
 
         Ok(WriteResponse { consistency_token: ConsistencyToken::new(response.revision) })
     }
 
-    async fn delete(&self, relationship: Relationship<'static>) -> Result<(), Error> {
+    async fn delete(
+        &self,
+        relationship: Relationship<'static>,
+    ) -> Result<Option<ConsistencyToken>, Error> {
         let path = format!(
             "/access/v1/relationships/{}/{}/{}",
             urlencoding::encode(relationship.resource()),
@@ -755,6 +985,7 @@ impl TransportClient for RestTransport {
         subject: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> Result<ListRelationshipsResponse, Error> {
         let api_request = ListRelationshipsApiRequest {
             resource: resource.map(String::from),
@@ -765,7 +996,11 @@ impl TransportClient for RestTransport {
         };
 
         let mut stream = self
-            .post_sse::<_, RelationshipDto>("/access/v1/relationships/list", &api_request)
+            .post_sse::<_, RelationshipDto>(
+                "/access/v1/relationships/list",
+                &api_request,
+                consistency,
+            )
             .await?;
 
         let mut relationships = Vec::new();
@@ -795,6 +1030,7 @@ impl TransportClient for RestTransport {
         resource_type: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> Result<ListResourcesResponse, Error> {
         let api_request = ListResourcesApiRequest {
             subject: subject.to_string(),
@@ -805,8 +1041,9 @@ impl TransportClient for RestTransport {
             resource_id_pattern: None,
         };
 
-        let mut stream =
-            self.post_sse::<_, String>("/access/v1/resources/list", &api_request).await?;
+        let mut stream = self
+            .post_sse::<_, String>("/access/v1/resources/list", &api_request, consistency)
+            .await?;
 
         let mut resources = Vec::new();
 
@@ -831,6 +1068,7 @@ impl TransportClient for RestTransport {
         subject_type: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> Result<ListSubjectsResponse, Error> {
         let api_request = ListSubjectsApiRequest {
             resource: resource.to_string(),
@@ -840,8 +1078,9 @@ impl TransportClient for RestTransport {
             cursor: cursor.map(String::from),
         };
 
-        let mut stream =
-            self.post_sse::<_, String>("/access/v1/subjects/list", &api_request).await?;
+        let mut stream = self
+            .post_sse::<_, String>("/access/v1/subjects/list", &api_request, consistency)
+            .await?;
 
         let mut subjects = Vec::new();
 
@@ -871,6 +1110,7 @@ impl TransportClient for RestTransport {
             last_fallback_at: None,
             grpc: None,
             rest: Some(self.stats.read().clone()),
+            endpoints: Vec::new(),
         }
     }
 
@@ -904,6 +1144,8 @@ impl TransportClient for RestTransport {
             additions: Vec<RelationshipDto>,
             #[serde(skip_serializing_if = "Vec::is_empty")]
             removals: Vec<RelationshipDto>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            schema: Option<String>,
         }
 
         #[derive(Deserialize)]
@@ -936,16 +1178,37 @@ impl TransportClient for RestTransport {
                     subject: r.subject().to_string(),
                 })
                 .collect(),
+            schema: request.schema,
         };
 
         let api_response: SimulateApiResponse =
-            self.post("/access/v1/simulate", &api_request).await?;
+            self.post("/access/v1/simulate", &api_request, &request.consistency).await?;
 
         Ok(SimulateResponse {
             allowed: api_response.allowed,
             decision: Decision::new(api_response.allowed),
         })
     }
+
+    async fn simulate_batch(
+        &self,
+        request: SimulateBatchRequest,
+    ) -> Result<Vec<SimulateResponse>, Error> {
+        if request.checks.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let count = request.checks.len();
+        let consistency = request.consistency.clone();
+        let api_response: SimulateBatchApiResponse = self
+            .post(
+                SimulateBatchApiRequest::PATH,
+                &SimulateBatchApiRequest::new(request),
+                &consistency,
+            )
+            .await?;
+        api_response.into_responses(count)
+    }
 }
 
 // ============================================================================
@@ -1377,11 +1640,11 @@ mod tests {
 mod wiremock_tests {
     use wiremock::{
         Mock, MockServer, ResponseTemplate,
-        matchers::{method, path},
+        matchers::{body_string_contains, method, path},
     };
 
     use super::*;
-    use crate::Context;
+    use crate::{Context, transport::traits::SimulateCheck};
 
     async fn create_test_transport(server: &MockServer) -> RestTransport {
         RestTransport::builder()
@@ -1446,7 +1709,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
 
@@ -1476,7 +1739,7 @@ mod wiremock_tests {
             permission: "edit".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
 
@@ -1504,7 +1767,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
 
@@ -1582,7 +1845,28 @@ mod wiremock_tests {
             Relationship::new("document:readme", "viewer", "user:alice").into_owned();
 
         let result = transport.delete(relationship).await;
-        assert!(result.is_ok());
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_returns_revision() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/access/v1/relationships/document%3Areadme/viewer/user%3Aalice"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "revision": "rev_del123",
+                "relationships_deleted": 1
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = create_test_transport(&server).await;
+        let relationship =
+            Relationship::new("document:readme", "viewer", "user:alice").into_owned();
+
+        let token = transport.delete(relationship).await.unwrap();
+        assert_eq!(token, Some(ConsistencyToken::new("rev_del123")));
     }
 
     #[tokio::test]
@@ -1629,6 +1913,8 @@ mod wiremock_tests {
                 Relationship::new("document:readme", "viewer", "user:alice").into_owned(),
             ],
             removals: vec![],
+            schema: None,
+            consistency: ConsistencyRequirement::Eventual,
         };
 
         let result = transport.simulate(request).await;
@@ -1660,7 +1946,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: Some(context),
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
 
@@ -1686,7 +1972,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
 
@@ -1713,7 +1999,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
 
@@ -1741,7 +2027,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
 
@@ -1809,7 +2095,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
 
@@ -1840,6 +2126,8 @@ mod wiremock_tests {
             context: None,
             additions: vec![],
             removals: vec![],
+            schema: None,
+            consistency: ConsistencyRequirement::Eventual,
         };
 
         let result = transport.simulate(request).await;
@@ -1869,7 +2157,7 @@ mod wiremock_tests {
                 permission: "view".to_string(),
                 resource: "doc:1".to_string(),
                 context: None,
-                consistency: None,
+                consistency: ConsistencyRequirement::Eventual,
                 trace: false,
             },
             CheckRequest {
@@ -1877,7 +2165,7 @@ mod wiremock_tests {
                 permission: "edit".to_string(),
                 resource: "doc:2".to_string(),
                 context: None,
-                consistency: None,
+                consistency: ConsistencyRequirement::Eventual,
                 trace: false,
             },
         ];
@@ -1908,7 +2196,9 @@ mod wiremock_tests {
             .await;
 
         let transport = create_test_transport(&server).await;
-        let result = transport.list_relationships(None, None, None, Some(10), None).await;
+        let result = transport
+            .list_relationships(None, None, None, Some(10), None, &ConsistencyRequirement::Eventual)
+            .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1933,8 +2223,16 @@ mod wiremock_tests {
             .await;
 
         let transport = create_test_transport(&server).await;
-        let result =
-            transport.list_resources("user:alice", "view", Some("doc"), Some(10), None).await;
+        let result = transport
+            .list_resources(
+                "user:alice",
+                "view",
+                Some("doc"),
+                Some(10),
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1959,8 +2257,16 @@ mod wiremock_tests {
             .await;
 
         let transport = create_test_transport(&server).await;
-        let result =
-            transport.list_subjects("view", "doc:readme", Some("user"), Some(10), None).await;
+        let result = transport
+            .list_subjects(
+                "view",
+                "doc:readme",
+                Some("user"),
+                Some(10),
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1990,7 +2296,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: true,
         };
 
@@ -2027,7 +2333,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "doc:1".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: true,
         };
 
@@ -2056,7 +2362,7 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: None,
+            consistency: ConsistencyRequirement::Eventual,
             trace: false,
         };
 
@@ -2098,6 +2404,8 @@ mod wiremock_tests {
             removals: vec![
                 Relationship::new("document:readme", "viewer", "user:alice").into_owned(),
             ],
+            schema: None,
+            consistency: ConsistencyRequirement::Eventual,
         };
 
         let result = transport.simulate(request).await;
@@ -2106,6 +2414,80 @@ mod wiremock_tests {
         assert!(response.allowed);
     }
 
+    #[tokio::test]
+    async fn test_simulate_batch_with_schema() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/access/v1/simulate/batch"))
+            .and(body_string_contains("\"schema\":\"type user {}\""))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [
+                    { "index": 1, "allowed": false },
+                    { "index": 0, "allowed": true }
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = create_test_transport(&server).await;
+        let request = SimulateBatchRequest {
+            checks: vec![
+                SimulateCheck {
+                    subject: "user:alice".to_string(),
+                    permission: "view".to_string(),
+                    resource: "doc:1".to_string(),
+                    context: None,
+                },
+                SimulateCheck {
+                    subject: "user:bob".to_string(),
+                    permission: "view".to_string(),
+                    resource: "doc:1".to_string(),
+                    context: None,
+                },
+            ],
+            additions: vec![],
+            removals: vec![],
+            schema: Some("type user {}".to_string()),
+            consistency: ConsistencyRequirement::Eventual,
+        };
+
+        let responses = transport.simulate_batch(request).await.unwrap();
+        assert_eq!(responses.len(), 2);
+        assert!(responses[0].allowed);
+        assert!(!responses[1].allowed);
+    }
+
+    #[tokio::test]
+    async fn test_simulate_batch_missing_result() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/access/v1/simulate/batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": []
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = create_test_transport(&server).await;
+        let request = SimulateBatchRequest {
+            checks: vec![SimulateCheck {
+                subject: "user:alice".to_string(),
+                permission: "view".to_string(),
+                resource: "doc:1".to_string(),
+                context: None,
+            }],
+            additions: vec![],
+            removals: vec![],
+            schema: None,
+            consistency: ConsistencyRequirement::Eventual,
+        };
+
+        let err = transport.simulate_batch(request).await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidResponse);
+    }
+
     #[tokio::test]
     async fn test_list_subjects_with_no_limit() {
         let server = MockServer::start().await;
@@ -2121,7 +2503,16 @@ mod wiremock_tests {
             .await;
 
         let transport = create_test_transport(&server).await;
-        let result = transport.list_subjects("view", "doc:readme", None, None, None).await;
+        let result = transport
+            .list_subjects(
+                "view",
+                "doc:readme",
+                None,
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -2154,10 +2545,14 @@ mod wiremock_tests {
 
     #[tokio::test]
     async fn test_check_with_consistency() {
+        use wiremock::matchers::header;
+
         let server = MockServer::start().await;
 
         Mock::given(method("POST"))
             .and(path("/access/v1/evaluate"))
+            .and(header("x-inferadb-consistency", "at_least_as_fresh"))
+            .and(header("x-inferadb-consistency-token", "token_abc"))
             .respond_with(
                 ResponseTemplate::new(200)
                     .insert_header("content-type", "text/event-stream")
@@ -2172,7 +2567,9 @@ mod wiremock_tests {
             permission: "view".to_string(),
             resource: "document:readme".to_string(),
             context: None,
-            consistency: Some(crate::ConsistencyToken::new("token_abc")),
+            consistency: ConsistencyRequirement::AtLeastAsFresh(crate::ConsistencyToken::new(
+                "token_abc",
+            )),
             trace: false,
         };
 
@@ -2181,6 +2578,44 @@ mod wiremock_tests {
         assert!(result.unwrap().allowed);
     }
 
+    #[tokio::test]
+    async fn test_full_consistency_header_on_reads() {
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/access/v1/resources/list"))
+            .and(header("x-inferadb-consistency", "full"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string("data: \"doc:1\"\n\n"),
+            )
+            .mount(&server)
+            .await;
+
+        let transport = create_test_transport(&server).await;
+        let result = transport
+            .list_resources("user:alice", "view", None, None, None, &ConsistencyRequirement::Full)
+            .await
+            .unwrap();
+        assert_eq!(result.resources, vec!["doc:1"]);
+
+        // Eventual reads send no consistency header and miss the mock
+        let result = transport
+            .list_resources(
+                "user:alice",
+                "view",
+                None,
+                None,
+                None,
+                &ConsistencyRequirement::Eventual,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_user_agent_header_is_sent() {
         use wiremock::matchers::header_exists;
@@ -9,14 +9,20 @@ use std::{
     time::{Duration, Instant},
 };
 
+use futures::Stream;
+
 use crate::{
     Error,
-    types::{ConsistencyToken, Context, Decision, Relationship},
+    types::{ConsistencyRequirement, ConsistencyToken, Context, Decision, Relationship},
+    vault::watch::{WatchEvent, WatchFilter},
 };
 
 /// Boxed future type alias for async methods.
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
+/// Boxed stream of the events received over one watch connection.
+pub type WatchEventStream = Pin<Box<dyn Stream<Item = Result<WatchEvent, Error>> + Send>>;
+
 // ============================================================================
 // Transport Enum
 // ============================================================================
@@ -239,6 +245,8 @@ pub struct TransportStats {
     pub grpc: Option<GrpcStats>,
     /// REST-specific stats (if REST enabled).
     pub rest: Option<RestStats>,
+    /// Per-endpoint stats (populated when multiple endpoints are configured).
+    pub endpoints: Vec<EndpointStats>,
 }
 
 /// Reason for transport fallback.
@@ -291,6 +299,29 @@ pub struct RestStats {
     pub sse_active: u32,
 }
 
+/// Statistics for a single endpoint of a multi-endpoint client.
+#[derive(Debug, Clone, Default)]
+pub struct EndpointStats {
+    /// Endpoint URL.
+    pub url: String,
+    /// Whether the endpoint is currently in rotation.
+    pub healthy: bool,
+    /// Requests currently in flight.
+    pub outstanding: u32,
+    /// Total requests sent.
+    pub requests_sent: u64,
+    /// Failed requests.
+    pub requests_failed: u64,
+    /// Current run of consecutive failures.
+    pub consecutive_failures: u32,
+    /// Exponentially weighted moving average of observed latency.
+    pub latency_ewma: Option<Duration>,
+    /// Number of times the endpoint has been ejected.
+    pub ejections: u32,
+    /// When the current ejection ends, if the endpoint is ejected.
+    pub ejected_until: Option<Instant>,
+}
+
 // ============================================================================
 // Transport Events
 // ============================================================================
@@ -342,8 +373,8 @@ pub struct CheckRequest {
     pub resource: String,
     /// Optional ABAC context.
     pub context: Option<Context>,
-    /// Optional consistency requirement.
-    pub consistency: Option<ConsistencyToken>,
+    /// Consistency required of the read.
+    pub consistency: ConsistencyRequirement,
     /// Whether to include detailed evaluation trace (for explain).
     pub trace: bool,
 }
@@ -360,7 +391,10 @@ pub struct CheckResponse {
 }
 
 /// Detailed trace of an authorization decision.
-#[derive(Debug, Clone)]
+///
+/// Returned by [`explain_permission()`](crate::VaultClient::explain_permission)
+/// and serialized in a stable JSON form for attaching to tickets.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct DecisionTrace {
     /// Time taken to evaluate in microseconds.
     pub duration_micros: u64,
@@ -369,29 +403,54 @@ pub struct DecisionTrace {
     /// Number of relations evaluated.
     pub relations_evaluated: u64,
     /// Root node of the evaluation tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub root: Option<EvaluationNode>,
 }
 
 /// A node in the evaluation tree.
-#[derive(Debug, Clone)]
+///
+/// The tree includes failed branches, so a denied decision can be traced to
+/// the missing relationship, failed intersection arm, or exclusion that
+/// caused it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct EvaluationNode {
     /// Type of this node.
     pub node_type: EvaluationNodeType,
     /// Result at this node.
     pub result: bool,
     /// Child nodes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub children: Vec<EvaluationNode>,
 }
 
 /// Type of evaluation node.
-#[derive(Debug, Clone)]
+///
+/// For [`Exclusion`](Self::Exclusion) nodes, the first child is the base
+/// set and the remaining children are the excluded sets.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum EvaluationNodeType {
     /// Direct relationship check.
-    DirectCheck { resource: String, relation: String, subject: String },
+    DirectCheck {
+        /// Resource of the checked relationship.
+        resource: String,
+        /// Relation of the checked relationship.
+        relation: String,
+        /// Subject of the checked relationship.
+        subject: String,
+    },
     /// Computed userset.
-    ComputedUserset { relation: String },
+    ComputedUserset {
+        /// The relation being computed.
+        relation: String,
+    },
     /// Related object userset (tupleset rewrite).
-    RelatedObjectUserset { relationship: String, computed: String },
+    RelatedObjectUserset {
+        /// The relation linking to the related object.
+        relationship: String,
+        /// The relation computed on the related object.
+        computed: String,
+    },
     /// Union of child nodes.
     Union,
     /// Intersection of child nodes.
@@ -399,7 +458,28 @@ pub enum EvaluationNodeType {
     /// Exclusion (difference) of child nodes.
     Exclusion,
     /// WASM module evaluation.
-    WasmModule { module_name: String },
+    WasmModule {
+        /// Name of the evaluated module.
+        module_name: String,
+    },
+}
+
+impl std::fmt::Display for EvaluationNodeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvaluationNodeType::DirectCheck { resource, relation, subject } => {
+                write!(f, "{}#{}@{}", resource, relation, subject)
+            },
+            EvaluationNodeType::ComputedUserset { relation } => write!(f, "computed {}", relation),
+            EvaluationNodeType::RelatedObjectUserset { relationship, computed } => {
+                write!(f, "{}->{}", relationship, computed)
+            },
+            EvaluationNodeType::Union => write!(f, "union"),
+            EvaluationNodeType::Intersection => write!(f, "intersection"),
+            EvaluationNodeType::Exclusion => write!(f, "exclusion"),
+            EvaluationNodeType::WasmModule { module_name } => write!(f, "wasm:{}", module_name),
+        }
+    }
 }
 
 // ============================================================================
@@ -441,6 +521,58 @@ pub struct SimulateRequest {
     pub additions: Vec<Relationship<'static>>,
     /// Hypothetical relationships to remove for the simulation.
     pub removals: Vec<Relationship<'static>>,
+    /// Proposed schema (IPL) to evaluate against instead of the active schema.
+    pub schema: Option<String>,
+    /// Consistency required of the base state the simulation starts from.
+    pub consistency: ConsistencyRequirement,
+}
+
+/// A single check within a batched simulation.
+#[derive(Debug, Clone)]
+pub struct SimulateCheck {
+    /// Subject to check (e.g., "user:alice").
+    pub subject: String,
+    /// Permission to check (e.g., "view").
+    pub permission: String,
+    /// Resource to check (e.g., "document:readme").
+    pub resource: String,
+    /// Optional ABAC context.
+    pub context: Option<Context>,
+}
+
+/// Request for many simulated checks sharing the same hypothetical state.
+#[derive(Debug, Clone)]
+pub struct SimulateBatchRequest {
+    /// Checks to evaluate.
+    pub checks: Vec<SimulateCheck>,
+    /// Hypothetical relationships to add for the simulation.
+    pub additions: Vec<Relationship<'static>>,
+    /// Hypothetical relationships to remove for the simulation.
+    pub removals: Vec<Relationship<'static>>,
+    /// Proposed schema (IPL) to evaluate against instead of the active schema.
+    pub schema: Option<String>,
+    /// Consistency required of the base state the simulation starts from.
+    pub consistency: ConsistencyRequirement,
+}
+
+impl SimulateBatchRequest {
+    /// Splits the batch into one single-check request per check.
+    pub fn into_requests(self) -> Vec<SimulateRequest> {
+        let Self { checks, additions, removals, schema, consistency } = self;
+        checks
+            .into_iter()
+            .map(|check| SimulateRequest {
+                subject: check.subject,
+                permission: check.permission,
+                resource: check.resource,
+                context: check.context,
+                additions: additions.clone(),
+                removals: removals.clone(),
+                schema: schema.clone(),
+                consistency: consistency.clone(),
+            })
+            .collect()
+    }
 }
 
 /// Response from a simulated authorization check.
@@ -452,6 +584,25 @@ pub struct SimulateResponse {
     pub decision: Decision,
 }
 
+// ============================================================================
+// Watch Request
+// ============================================================================
+
+/// Request to stream relationship changes in a vault.
+#[derive(Debug, Clone)]
+pub struct WatchRequest {
+    /// Organization owning the vault.
+    pub organization_id: String,
+    /// Vault to watch.
+    pub vault_id: String,
+    /// Filters the server should apply. Transports that cannot express a
+    /// filter send a broader request; callers filter events again.
+    pub filters: Vec<WatchFilter>,
+    /// Only changes after this revision are streamed. Without it, only
+    /// changes made after connecting are.
+    pub from_revision: Option<u64>,
+}
+
 // ============================================================================
 // Transport Trait
 // ============================================================================
@@ -488,11 +639,12 @@ pub trait TransportClient: Send + Sync {
         requests: Vec<WriteRequest>,
     ) -> impl Future<Output = Result<WriteResponse, Error>> + Send;
 
-    /// Deletes a relationship.
+    /// Deletes a relationship, returning the revision token if the server
+    /// reported one.
     fn delete(
         &self,
         relationship: Relationship<'static>,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<Option<ConsistencyToken>, Error>> + Send;
 
     /// Lists relationships matching a filter.
     fn list_relationships(
@@ -502,6 +654,7 @@ pub trait TransportClient: Send + Sync {
         subject: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> impl Future<Output = Result<ListRelationshipsResponse, Error>> + Send;
 
     /// Lists resources accessible by a subject with a permission.
@@ -512,6 +665,7 @@ pub trait TransportClient: Send + Sync {
         resource_type: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> impl Future<Output = Result<ListResourcesResponse, Error>> + Send;
 
     /// Lists subjects with a permission on a resource.
@@ -522,6 +676,7 @@ pub trait TransportClient: Send + Sync {
         subject_type: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> impl Future<Output = Result<ListSubjectsResponse, Error>> + Send;
 
     /// Returns the transport type.
@@ -538,6 +693,14 @@ pub trait TransportClient: Send + Sync {
         &self,
         request: SimulateRequest,
     ) -> impl Future<Output = Result<SimulateResponse, Error>> + Send;
+
+    /// Performs many simulated checks against the same hypothetical changes.
+    ///
+    /// Responses are returned in the same order as the checks.
+    fn simulate_batch(
+        &self,
+        request: SimulateBatchRequest,
+    ) -> impl Future<Output = Result<Vec<SimulateResponse>, Error>> + Send;
 }
 
 // ============================================================================
@@ -557,6 +720,15 @@ pub enum AnyTransport {
     Rest(super::rest::RestTransport),
     /// Mock transport for testing.
     Mock(super::mock::MockTransport),
+    /// Load-balanced transport spanning multiple endpoints.
+    Balanced(super::balancer::BalancedTransport),
+    /// Transport recording metrics for an inner transport.
+    #[cfg(feature = "tracing")]
+    Metered(super::metered::MeteredTransport),
+    /// Transport recording calls to, or replaying them from, a cassette.
+    Cassette(super::cassette::CassetteTransport),
+    /// Transport injecting faults into calls to an inner transport.
+    Faulty(super::faults::FaultTransport),
 }
 
 impl std::fmt::Debug for AnyTransport {
@@ -567,6 +739,17 @@ impl std::fmt::Debug for AnyTransport {
             #[cfg(feature = "rest")]
             AnyTransport::Rest(_) => f.debug_struct("AnyTransport::Rest").finish(),
             AnyTransport::Mock(_) => f.debug_struct("AnyTransport::Mock").finish(),
+            AnyTransport::Balanced(t) => f
+                .debug_struct("AnyTransport::Balanced")
+                .field("endpoints", &t.len())
+                .field("strategy", &t.strategy())
+                .finish(),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => {
+                f.debug_tuple("AnyTransport::Metered").field(t.inner()).finish()
+            },
+            AnyTransport::Cassette(t) => f.debug_tuple("AnyTransport::Cassette").field(t).finish(),
+            AnyTransport::Faulty(t) => f.debug_tuple("AnyTransport::Faulty").field(t).finish(),
         }
     }
 }
@@ -574,13 +757,29 @@ impl std::fmt::Debug for AnyTransport {
 impl AnyTransport {
     /// Performs an authorization check.
     pub fn check(&self, request: CheckRequest) -> BoxFuture<'_, Result<CheckResponse, Error>> {
-        match self {
+        let span = self.call_span("Check", CallAttributes::check(&request));
+        let future: BoxFuture<'_, _> = match self {
             #[cfg(feature = "grpc")]
             AnyTransport::Grpc(t) => Box::pin(t.check(request)),
             #[cfg(feature = "rest")]
             AnyTransport::Rest(t) => Box::pin(t.check(request)),
             AnyTransport::Mock(t) => Box::pin(t.check(request)),
-        }
+            AnyTransport::Balanced(t) => Box::pin(t.check(request)),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.check(request),
+            AnyTransport::Cassette(t) => t.check(request),
+            AnyTransport::Faulty(t) => t.check(request),
+        };
+        #[cfg(feature = "tracing")]
+        let future: BoxFuture<'_, _> = Box::pin(async move {
+            let response = future.await;
+            if let Ok(response) = &response {
+                let decision = if response.allowed { "allow" } else { "deny" };
+                tracing::Span::current().record("inferadb.decision", decision);
+            }
+            response
+        });
+        span.instrument(future)
     }
 
     /// Performs a batch of authorization checks.
@@ -588,24 +787,38 @@ impl AnyTransport {
         &self,
         requests: Vec<CheckRequest>,
     ) -> BoxFuture<'_, Result<Vec<CheckResponse>, Error>> {
-        match self {
+        let span = self.call_span("CheckBatch", CallAttributes::batch(requests.len()));
+        let future: BoxFuture<'_, _> = match self {
             #[cfg(feature = "grpc")]
             AnyTransport::Grpc(t) => Box::pin(t.check_batch(requests)),
             #[cfg(feature = "rest")]
             AnyTransport::Rest(t) => Box::pin(t.check_batch(requests)),
             AnyTransport::Mock(t) => Box::pin(t.check_batch(requests)),
-        }
+            AnyTransport::Balanced(t) => Box::pin(t.check_batch(requests)),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.check_batch(requests),
+            AnyTransport::Cassette(t) => t.check_batch(requests),
+            AnyTransport::Faulty(t) => t.check_batch(requests),
+        };
+        span.instrument(future)
     }
 
     /// Writes a relationship.
     pub fn write(&self, request: WriteRequest) -> BoxFuture<'_, Result<WriteResponse, Error>> {
-        match self {
+        let span = self.call_span("Write", CallAttributes::relationship(&request.relationship));
+        let future: BoxFuture<'_, _> = match self {
             #[cfg(feature = "grpc")]
             AnyTransport::Grpc(t) => Box::pin(t.write(request)),
             #[cfg(feature = "rest")]
             AnyTransport::Rest(t) => Box::pin(t.write(request)),
             AnyTransport::Mock(t) => Box::pin(t.write(request)),
-        }
+            AnyTransport::Balanced(t) => Box::pin(t.write(request)),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.write(request),
+            AnyTransport::Cassette(t) => t.write(request),
+            AnyTransport::Faulty(t) => t.write(request),
+        };
+        span.instrument(future)
     }
 
     /// Writes a batch of relationships.
@@ -613,24 +826,42 @@ impl AnyTransport {
         &self,
         requests: Vec<WriteRequest>,
     ) -> BoxFuture<'_, Result<WriteResponse, Error>> {
-        match self {
+        let span = self.call_span("WriteBatch", CallAttributes::batch(requests.len()));
+        let future: BoxFuture<'_, _> = match self {
             #[cfg(feature = "grpc")]
             AnyTransport::Grpc(t) => Box::pin(t.write_batch(requests)),
             #[cfg(feature = "rest")]
             AnyTransport::Rest(t) => Box::pin(t.write_batch(requests)),
             AnyTransport::Mock(t) => Box::pin(t.write_batch(requests)),
-        }
+            AnyTransport::Balanced(t) => Box::pin(t.write_batch(requests)),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.write_batch(requests),
+            AnyTransport::Cassette(t) => t.write_batch(requests),
+            AnyTransport::Faulty(t) => t.write_batch(requests),
+        };
+        span.instrument(future)
     }
 
-    /// Deletes a relationship.
-    pub fn delete(&self, relationship: Relationship<'static>) -> BoxFuture<'_, Result<(), Error>> {
-        match self {
+    /// Deletes a relationship, returning the revision token if the server
+    /// reported one.
+    pub fn delete(
+        &self,
+        relationship: Relationship<'static>,
+    ) -> BoxFuture<'_, Result<Option<ConsistencyToken>, Error>> {
+        let span = self.call_span("Delete", CallAttributes::relationship(&relationship));
+        let future: BoxFuture<'_, _> = match self {
             #[cfg(feature = "grpc")]
             AnyTransport::Grpc(t) => Box::pin(t.delete(relationship)),
             #[cfg(feature = "rest")]
             AnyTransport::Rest(t) => Box::pin(t.delete(relationship)),
             AnyTransport::Mock(t) => Box::pin(t.delete(relationship)),
-        }
+            AnyTransport::Balanced(t) => Box::pin(t.delete(relationship)),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.delete(relationship),
+            AnyTransport::Cassette(t) => t.delete(relationship),
+            AnyTransport::Faulty(t) => t.delete(relationship),
+        };
+        span.instrument(future)
     }
 
     /// Lists relationships matching a filter.
@@ -643,14 +874,25 @@ impl AnyTransport {
         subject: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> BoxFuture<'_, Result<ListRelationshipsResponse, Error>> {
         // Convert to owned to move into async block
         let resource = resource.map(str::to_owned);
         let relation = relation.map(str::to_owned);
         let subject = subject.map(str::to_owned);
         let cursor = cursor.map(str::to_owned);
-
-        match self {
+        let consistency = consistency.clone();
+
+        let span = self.call_span(
+            "ListRelationships",
+            CallAttributes {
+                subject: subject.as_deref(),
+                permission: relation.as_deref(),
+                resource: resource.as_deref(),
+                batch_size: None,
+            },
+        );
+        let future: BoxFuture<'_, _> = match self {
             #[cfg(feature = "grpc")]
             AnyTransport::Grpc(t) => Box::pin(async move {
                 t.list_relationships(
@@ -659,6 +901,7 @@ impl AnyTransport {
                     subject.as_deref(),
                     limit,
                     cursor.as_deref(),
+                    &consistency,
                 )
                 .await
             }),
@@ -670,6 +913,7 @@ impl AnyTransport {
                     subject.as_deref(),
                     limit,
                     cursor.as_deref(),
+                    &consistency,
                 )
                 .await
             }),
@@ -680,10 +924,48 @@ impl AnyTransport {
                     subject.as_deref(),
                     limit,
                     cursor.as_deref(),
+                    &consistency,
                 )
                 .await
             }),
-        }
+            AnyTransport::Balanced(t) => Box::pin(async move {
+                t.list_relationships(
+                    resource.as_deref(),
+                    relation.as_deref(),
+                    subject.as_deref(),
+                    limit,
+                    cursor.as_deref(),
+                    &consistency,
+                )
+                .await
+            }),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.list_relationships(
+                resource.as_deref(),
+                relation.as_deref(),
+                subject.as_deref(),
+                limit,
+                cursor.as_deref(),
+                &consistency,
+            ),
+            AnyTransport::Cassette(t) => t.list_relationships(
+                resource.as_deref(),
+                relation.as_deref(),
+                subject.as_deref(),
+                limit,
+                cursor.as_deref(),
+                &consistency,
+            ),
+            AnyTransport::Faulty(t) => t.list_relationships(
+                resource.as_deref(),
+                relation.as_deref(),
+                subject.as_deref(),
+                limit,
+                cursor.as_deref(),
+                &consistency,
+            ),
+        };
+        span.instrument(future)
     }
 
     /// Lists resources accessible by a subject with a permission.
@@ -696,14 +978,24 @@ impl AnyTransport {
         resource_type: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> BoxFuture<'_, Result<ListResourcesResponse, Error>> {
         // Convert to owned to move into async block
         let subject = subject.to_owned();
         let permission = permission.to_owned();
         let resource_type = resource_type.map(str::to_owned);
         let cursor = cursor.map(str::to_owned);
-
-        match self {
+        let consistency = consistency.clone();
+
+        let span = self.call_span(
+            "ListResources",
+            CallAttributes {
+                subject: Some(&subject),
+                permission: Some(&permission),
+                ..Default::default()
+            },
+        );
+        let future: BoxFuture<'_, _> = match self {
             #[cfg(feature = "grpc")]
             AnyTransport::Grpc(t) => Box::pin(async move {
                 t.list_resources(
@@ -712,6 +1004,7 @@ impl AnyTransport {
                     resource_type.as_deref(),
                     limit,
                     cursor.as_deref(),
+                    &consistency,
                 )
                 .await
             }),
@@ -723,6 +1016,7 @@ impl AnyTransport {
                     resource_type.as_deref(),
                     limit,
                     cursor.as_deref(),
+                    &consistency,
                 )
                 .await
             }),
@@ -733,10 +1027,48 @@ impl AnyTransport {
                     resource_type.as_deref(),
                     limit,
                     cursor.as_deref(),
+                    &consistency,
                 )
                 .await
             }),
-        }
+            AnyTransport::Balanced(t) => Box::pin(async move {
+                t.list_resources(
+                    &subject,
+                    &permission,
+                    resource_type.as_deref(),
+                    limit,
+                    cursor.as_deref(),
+                    &consistency,
+                )
+                .await
+            }),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.list_resources(
+                &subject,
+                &permission,
+                resource_type.as_deref(),
+                limit,
+                cursor.as_deref(),
+                &consistency,
+            ),
+            AnyTransport::Cassette(t) => t.list_resources(
+                &subject,
+                &permission,
+                resource_type.as_deref(),
+                limit,
+                cursor.as_deref(),
+                &consistency,
+            ),
+            AnyTransport::Faulty(t) => t.list_resources(
+                &subject,
+                &permission,
+                resource_type.as_deref(),
+                limit,
+                cursor.as_deref(),
+                &consistency,
+            ),
+        };
+        span.instrument(future)
     }
 
     /// Lists subjects with a permission on a resource.
@@ -749,14 +1081,24 @@ impl AnyTransport {
         subject_type: Option<&str>,
         limit: Option<u32>,
         cursor: Option<&str>,
+        consistency: &ConsistencyRequirement,
     ) -> BoxFuture<'_, Result<ListSubjectsResponse, Error>> {
         // Convert to owned to move into async block
         let permission = permission.to_owned();
         let resource = resource.to_owned();
         let subject_type = subject_type.map(str::to_owned);
         let cursor = cursor.map(str::to_owned);
-
-        match self {
+        let consistency = consistency.clone();
+
+        let span = self.call_span(
+            "ListSubjects",
+            CallAttributes {
+                permission: Some(&permission),
+                resource: Some(&resource),
+                ..Default::default()
+            },
+        );
+        let future: BoxFuture<'_, _> = match self {
             #[cfg(feature = "grpc")]
             AnyTransport::Grpc(t) => Box::pin(async move {
                 t.list_subjects(
@@ -765,6 +1107,7 @@ impl AnyTransport {
                     subject_type.as_deref(),
                     limit,
                     cursor.as_deref(),
+                    &consistency,
                 )
                 .await
             }),
@@ -776,6 +1119,7 @@ impl AnyTransport {
                     subject_type.as_deref(),
                     limit,
                     cursor.as_deref(),
+                    &consistency,
                 )
                 .await
             }),
@@ -786,10 +1130,48 @@ impl AnyTransport {
                     subject_type.as_deref(),
                     limit,
                     cursor.as_deref(),
+                    &consistency,
                 )
                 .await
             }),
-        }
+            AnyTransport::Balanced(t) => Box::pin(async move {
+                t.list_subjects(
+                    &permission,
+                    &resource,
+                    subject_type.as_deref(),
+                    limit,
+                    cursor.as_deref(),
+                    &consistency,
+                )
+                .await
+            }),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.list_subjects(
+                &permission,
+                &resource,
+                subject_type.as_deref(),
+                limit,
+                cursor.as_deref(),
+                &consistency,
+            ),
+            AnyTransport::Cassette(t) => t.list_subjects(
+                &permission,
+                &resource,
+                subject_type.as_deref(),
+                limit,
+                cursor.as_deref(),
+                &consistency,
+            ),
+            AnyTransport::Faulty(t) => t.list_subjects(
+                &permission,
+                &resource,
+                subject_type.as_deref(),
+                limit,
+                cursor.as_deref(),
+                &consistency,
+            ),
+        };
+        span.instrument(future)
     }
 
     /// Returns the transport type.
@@ -800,6 +1182,11 @@ impl AnyTransport {
             #[cfg(feature = "rest")]
             AnyTransport::Rest(t) => t.transport_type(),
             AnyTransport::Mock(t) => t.transport_type(),
+            AnyTransport::Balanced(t) => t.transport_type(),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.inner().transport_type(),
+            AnyTransport::Cassette(t) => t.transport_type(),
+            AnyTransport::Faulty(t) => t.transport_type(),
         }
     }
 
@@ -811,6 +1198,11 @@ impl AnyTransport {
             #[cfg(feature = "rest")]
             AnyTransport::Rest(t) => t.stats(),
             AnyTransport::Mock(t) => t.stats(),
+            AnyTransport::Balanced(t) => t.stats(),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.inner().stats(),
+            AnyTransport::Cassette(t) => t.stats(),
+            AnyTransport::Faulty(t) => t.stats(),
         }
     }
 
@@ -822,6 +1214,11 @@ impl AnyTransport {
             #[cfg(feature = "rest")]
             AnyTransport::Rest(t) => Box::pin(t.health_check()),
             AnyTransport::Mock(t) => Box::pin(t.health_check()),
+            AnyTransport::Balanced(t) => Box::pin(t.health_check()),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.inner().health_check(),
+            AnyTransport::Cassette(t) => t.health_check(),
+            AnyTransport::Faulty(t) => t.health_check(),
         }
     }
 
@@ -830,13 +1227,193 @@ impl AnyTransport {
         &self,
         request: SimulateRequest,
     ) -> BoxFuture<'_, Result<SimulateResponse, Error>> {
-        match self {
+        let span = self.call_span(
+            "Simulate",
+            CallAttributes {
+                subject: Some(&request.subject),
+                permission: Some(&request.permission),
+                resource: Some(&request.resource),
+                ..Default::default()
+            },
+        );
+        let future: BoxFuture<'_, _> = match self {
             #[cfg(feature = "grpc")]
             AnyTransport::Grpc(t) => Box::pin(t.simulate(request)),
             #[cfg(feature = "rest")]
             AnyTransport::Rest(t) => Box::pin(t.simulate(request)),
             AnyTransport::Mock(t) => Box::pin(t.simulate(request)),
+            AnyTransport::Balanced(t) => Box::pin(t.simulate(request)),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.simulate(request),
+            AnyTransport::Cassette(t) => t.simulate(request),
+            AnyTransport::Faulty(t) => t.simulate(request),
+        };
+        span.instrument(future)
+    }
+
+    /// Performs many simulated checks against the same hypothetical changes.
+    pub fn simulate_batch(
+        &self,
+        request: SimulateBatchRequest,
+    ) -> BoxFuture<'_, Result<Vec<SimulateResponse>, Error>> {
+        let span = self.call_span("SimulateBatch", CallAttributes::batch(request.checks.len()));
+        let future: BoxFuture<'_, _> = match self {
+            #[cfg(feature = "grpc")]
+            AnyTransport::Grpc(t) => Box::pin(t.simulate_batch(request)),
+            #[cfg(feature = "rest")]
+            AnyTransport::Rest(t) => Box::pin(t.simulate_batch(request)),
+            AnyTransport::Mock(t) => Box::pin(t.simulate_batch(request)),
+            AnyTransport::Balanced(t) => Box::pin(t.simulate_batch(request)),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.simulate_batch(request),
+            AnyTransport::Cassette(t) => t.simulate_batch(request),
+            AnyTransport::Faulty(t) => t.simulate_batch(request),
+        };
+        span.instrument(future)
+    }
+
+    /// Opens a stream of relationship changes.
+    ///
+    /// The returned future resolves once the stream is established; the
+    /// stream ends when the connection closes. Reconnecting is up to the
+    /// caller.
+    pub fn watch(&self, request: WatchRequest) -> BoxFuture<'_, Result<WatchEventStream, Error>> {
+        match self {
+            #[cfg(feature = "grpc")]
+            AnyTransport::Grpc(t) => Box::pin(t.watch(request)),
+            #[cfg(feature = "rest")]
+            AnyTransport::Rest(t) => Box::pin(t.watch(request)),
+            AnyTransport::Mock(t) => Box::pin(t.watch(request)),
+            AnyTransport::Balanced(t) => Box::pin(t.watch(request)),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.inner().watch(request),
+            AnyTransport::Cassette(t) => t.watch(request),
+            AnyTransport::Faulty(t) => t.watch(request),
+        }
+    }
+
+    /// Returns the load-balanced transport, seeing through metrics,
+    /// recording and fault-injecting wrappers.
+    pub(crate) fn balanced(&self) -> Option<&super::balancer::BalancedTransport> {
+        match self {
+            AnyTransport::Balanced(t) => Some(t),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.inner().balanced(),
+            AnyTransport::Cassette(t) => t.inner().and_then(AnyTransport::balanced),
+            AnyTransport::Faulty(t) => t.inner().balanced(),
+            _ => None,
+        }
+    }
+
+    /// Returns the cassette transport, seeing through the metrics wrapper.
+    pub(crate) fn cassette(&self) -> Option<&super::cassette::CassetteTransport> {
+        match self {
+            AnyTransport::Cassette(t) => Some(t),
+            #[cfg(feature = "tracing")]
+            AnyTransport::Metered(t) => t.inner().cassette(),
+            _ => None,
+        }
+    }
+
+    /// Reports retries and endpoint ejections to `metrics`.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn attach_metrics(&mut self, metrics: &crate::tracing_support::Metrics) {
+        match self {
+            #[cfg(feature = "grpc")]
+            AnyTransport::Grpc(t) => t.set_metrics(metrics.clone()),
+            #[cfg(feature = "rest")]
+            AnyTransport::Rest(t) => t.set_metrics(metrics.clone()),
+            AnyTransport::Balanced(t) => t.attach_metrics(metrics),
+            AnyTransport::Faulty(t) => t.attach_metrics(metrics),
+            _ => {},
+        }
+    }
+
+    /// Starts the span covering one engine call.
+    ///
+    /// Load-balanced, metered, recording and fault-injecting transports
+    /// record no span of their own; the transport they wrap records one
+    /// instead.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn call_span(&self, method: &'static str, attributes: CallAttributes<'_>) -> CallSpan {
+        #[cfg(feature = "tracing")]
+        if !matches!(
+            self,
+            AnyTransport::Balanced(_) | AnyTransport::Metered(_) | AnyTransport::Faulty(_)
+        ) && !matches!(self, AnyTransport::Cassette(t) if t.inner().is_some())
+        {
+            let span = crate::tracing_support::engine_span(method, self.transport_type());
+            attributes.record(&span);
+            return CallSpan(Some(span));
+        }
+        CallSpan::default()
+    }
+}
+
+/// Semantic attributes describing one engine call, recorded on its span.
+#[derive(Debug, Default, Clone, Copy)]
+struct CallAttributes<'r> {
+    subject: Option<&'r str>,
+    permission: Option<&'r str>,
+    resource: Option<&'r str>,
+    batch_size: Option<usize>,
+}
+
+impl<'r> CallAttributes<'r> {
+    fn check(request: &'r CheckRequest) -> Self {
+        Self {
+            subject: Some(&request.subject),
+            permission: Some(&request.permission),
+            resource: Some(&request.resource),
+            batch_size: None,
+        }
+    }
+
+    fn relationship(relationship: &'r Relationship<'static>) -> Self {
+        Self {
+            subject: Some(relationship.subject()),
+            permission: Some(relationship.relation()),
+            resource: Some(relationship.resource()),
+            batch_size: None,
+        }
+    }
+
+    fn batch(size: usize) -> Self {
+        Self { batch_size: Some(size), ..Default::default() }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn record(&self, span: &tracing::Span) {
+        if let Some(subject) = self.subject {
+            span.record("inferadb.subject", subject);
+        }
+        if let Some(permission) = self.permission {
+            span.record("inferadb.permission", permission);
+        }
+        if let Some(resource) = self.resource {
+            span.record("inferadb.resource", resource);
+        }
+        if let Some(size) = self.batch_size {
+            span.record("inferadb.batch.size", size);
+        }
+    }
+}
+
+/// Span covering one engine call; a no-op without the `tracing` feature.
+#[derive(Default)]
+struct CallSpan(#[cfg(feature = "tracing")] Option<tracing::Span>);
+
+impl CallSpan {
+    /// Runs `future` inside the span, recording its outcome.
+    fn instrument<'a, T: Send + 'a>(
+        self,
+        future: BoxFuture<'a, Result<T, Error>>,
+    ) -> BoxFuture<'a, Result<T, Error>> {
+        #[cfg(feature = "tracing")]
+        if let Some(span) = self.0 {
+            return Box::pin(crate::tracing_support::instrument(span, future));
         }
+        future
     }
 }
 
@@ -101,6 +101,18 @@ impl ConsistencyToken {
     pub fn len(&self) -> usize {
         self.value.len()
     }
+
+    /// Returns `true` if this token should replace `other` as the newest one
+    /// seen.
+    ///
+    /// Tokens are opaque, so when both are numeric revisions the larger one
+    /// is newer, and otherwise the token seen last wins.
+    pub(crate) fn supersedes(&self, other: &ConsistencyToken) -> bool {
+        match (self.value.parse::<u64>(), other.value.parse::<u64>()) {
+            (Ok(this), Ok(other)) => this >= other,
+            _ => true,
+        }
+    }
 }
 
 impl fmt::Display for ConsistencyToken {
@@ -138,9 +150,25 @@ impl AsRef<str> for ConsistencyToken {
     }
 }
 
+/// Header (REST) and metadata key (gRPC) naming the requested consistency.
+pub(crate) const CONSISTENCY_HEADER: &str = "x-inferadb-consistency";
+
+/// Header (REST) and metadata key (gRPC) carrying an at-least-as-fresh token.
+pub(crate) const CONSISTENCY_TOKEN_HEADER: &str = "x-inferadb-consistency-token";
+
 /// Represents the desired consistency level for a read operation.
 ///
-/// This is used internally to specify how fresh the data should be.
+/// Pass one to `.consistency()` on any read builder (checks, lookups,
+/// explain and simulate) to control how fresh the data must be:
+///
+/// ```rust,ignore
+/// use inferadb::ConsistencyRequirement;
+///
+/// // Admin screens that must never show stale access
+/// let allowed = vault.check("user:alice", "view", "doc:1")
+///     .consistency(ConsistencyRequirement::Full)
+///     .await?;
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum ConsistencyRequirement {
     /// Use eventual consistency (fastest, but may see stale data).
@@ -172,6 +200,54 @@ impl ConsistencyRequirement {
     pub fn is_full(&self) -> bool {
         matches!(self, ConsistencyRequirement::Full)
     }
+
+    /// Returns `true` if a locally cached result may satisfy this requirement.
+    ///
+    /// Only eventual reads may be served from a local cache: cached entries
+    /// carry no snapshot, so they cannot prove they are as fresh as a token,
+    /// and `Full` reads must always reach the server.
+    pub fn allows_cached(&self) -> bool {
+        self.is_eventual()
+    }
+
+    /// Returns the strictest of several requirements, for requests that send
+    /// many reads under one set of headers.
+    ///
+    /// `Full` wins over any token, and any `AtLeastAsFresh` wins over
+    /// `Eventual`. Among tokens the newest wins, as in a session: the highest
+    /// numeric revision, or otherwise the last token.
+    pub(crate) fn strictest<'a>(requirements: impl IntoIterator<Item = &'a Self>) -> &'a Self {
+        static EVENTUAL: ConsistencyRequirement = ConsistencyRequirement::Eventual;
+
+        let mut strictest = &EVENTUAL;
+        for requirement in requirements {
+            match (requirement, strictest) {
+                (ConsistencyRequirement::Full, _) => return requirement,
+                (ConsistencyRequirement::AtLeastAsFresh(_), ConsistencyRequirement::Eventual) => {
+                    strictest = requirement;
+                },
+                (
+                    ConsistencyRequirement::AtLeastAsFresh(token),
+                    ConsistencyRequirement::AtLeastAsFresh(current),
+                ) if token.supersedes(current) => strictest = requirement,
+                _ => {},
+            }
+        }
+        strictest
+    }
+
+    /// Returns the headers (REST) or metadata entries (gRPC) that carry this
+    /// requirement. Eventual reads send none and get the server default.
+    pub(crate) fn wire_headers(&self) -> Vec<(&'static str, &str)> {
+        match self {
+            ConsistencyRequirement::Eventual => Vec::new(),
+            ConsistencyRequirement::AtLeastAsFresh(token) => vec![
+                (CONSISTENCY_HEADER, "at_least_as_fresh"),
+                (CONSISTENCY_TOKEN_HEADER, token.value()),
+            ],
+            ConsistencyRequirement::Full => vec![(CONSISTENCY_HEADER, "full")],
+        }
+    }
 }
 
 impl From<ConsistencyToken> for ConsistencyRequirement {
@@ -279,6 +355,51 @@ mod tests {
         assert!(req.token().is_none());
     }
 
+    #[test]
+    fn test_consistency_requirement_cache_and_wire() {
+        let token = ConsistencyToken::new("t");
+        assert!(ConsistencyRequirement::Eventual.allows_cached());
+        assert!(!ConsistencyRequirement::Full.allows_cached());
+        assert!(!ConsistencyRequirement::AtLeastAsFresh(token.clone()).allows_cached());
+
+        assert!(ConsistencyRequirement::Eventual.wire_headers().is_empty());
+        assert_eq!(
+            ConsistencyRequirement::Full.wire_headers(),
+            vec![("x-inferadb-consistency", "full")]
+        );
+        assert_eq!(
+            ConsistencyRequirement::AtLeastAsFresh(token).wire_headers(),
+            vec![
+                ("x-inferadb-consistency", "at_least_as_fresh"),
+                ("x-inferadb-consistency-token", "t"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_consistency_requirement_strictest() {
+        let token = ConsistencyRequirement::AtLeastAsFresh(ConsistencyToken::new("t"));
+        let eventual = ConsistencyRequirement::Eventual;
+        let full = ConsistencyRequirement::Full;
+
+        assert!(ConsistencyRequirement::strictest([]).is_eventual());
+        assert_eq!(ConsistencyRequirement::strictest([&eventual, &token]), &token);
+        assert_eq!(ConsistencyRequirement::strictest([&token, &full, &eventual]), &full);
+    }
+
+    #[test]
+    fn test_consistency_requirement_strictest_picks_newest_token() {
+        let old = ConsistencyRequirement::AtLeastAsFresh(ConsistencyToken::new("9"));
+        let new = ConsistencyRequirement::AtLeastAsFresh(ConsistencyToken::new("10"));
+        let eventual = ConsistencyRequirement::Eventual;
+        assert_eq!(ConsistencyRequirement::strictest([&new, &eventual, &old]), &new);
+        assert_eq!(ConsistencyRequirement::strictest([&old, &new]), &new);
+
+        let opaque = ConsistencyRequirement::AtLeastAsFresh(ConsistencyToken::new("abc"));
+        assert_eq!(ConsistencyRequirement::strictest([&new, &opaque]), &opaque);
+        assert_eq!(ConsistencyRequirement::strictest([&opaque, &old]), &old);
+    }
+
     #[test]
     fn test_consistency_requirement_from_token() {
         let token = ConsistencyToken::new("test");
@@ -5,6 +5,7 @@
 //! - [`Relationship`]: Represents a relationship tuple (resource, relation, subject)
 //! - [`Context`]: ABAC context for attribute-based conditions
 //! - [`ConsistencyToken`]: Snapshot token for read-after-write consistency
+//! - [`ConsistencyRequirement`]: Freshness required of a read
 //! - [`Decision`]: Authorization decision result with metadata
 //! - [`Resource`]: Trait for types that can be used as resources
 //! - [`Subject`]: Trait for types that can be used as subjects
@@ -20,7 +21,7 @@ mod decision;
 mod entity;
 mod relationship;
 
-pub use consistency::ConsistencyToken;
+pub use consistency::{ConsistencyRequirement, ConsistencyToken};
 pub use context::{Context, ContextValue};
 pub use decision::{Decision, DecisionMetadata, DecisionReason};
 pub use entity::{EntityRef, ParseError, Resource, Subject, SubjectRef};
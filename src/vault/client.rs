@@ -3,19 +3,26 @@
 // Allow dead code for request types that aren't fully integrated yet
 #![allow(dead_code)]
 
-#[cfg(feature = "rest")]
-use std::sync::Arc;
-use std::{borrow::Cow, future::Future, pin::Pin};
+use std::{borrow::Cow, future::Future, pin::Pin, sync::Arc};
 
 use futures::Stream;
 
-#[cfg(feature = "rest")]
-use crate::transport::{TransportCheckRequest, TransportWriteRequest};
+use super::session::{ConsistencySession, SessionState};
+#[cfg(feature = "tracing")]
+use crate::tracing_support::TraceContext;
 use crate::{
     AccessDenied, Error,
-    client::Client,
+    client::{Client, TraceScope},
     control::SchemasClient,
-    types::{ConsistencyToken, Context, Decision, Relationship},
+    types::{ConsistencyRequirement, ConsistencyToken, Context, Decision, Relationship},
+};
+#[cfg(feature = "rest")]
+use crate::{
+    client::CacheKey,
+    transport::{
+        AnyTransport, TransportCheckRequest, TransportCheckResponse, TransportWriteRequest,
+    },
+    types::DecisionMetadata,
 };
 
 /// A vault-scoped client for authorization operations.
@@ -43,12 +50,171 @@ pub struct VaultClient {
     client: Client,
     organization_id: String,
     vault_id: String,
+    session: Option<Arc<SessionState>>,
 }
 
 impl VaultClient {
     /// Creates a new VaultClient.
     pub(crate) fn new(client: Client, organization_id: String, vault_id: String) -> Self {
-        Self { client, organization_id, vault_id }
+        Self { client, organization_id, vault_id, session: None }
+    }
+
+    /// Returns a copy of this client bound to a session.
+    pub(super) fn with_session(&self, session: Arc<SessionState>) -> Self {
+        Self { session: Some(session), ..self.clone() }
+    }
+
+    /// Returns the consistency reads from this client default to.
+    pub(super) fn read_consistency(&self) -> ConsistencyRequirement {
+        self.session.as_ref().map_or(ConsistencyRequirement::Eventual, |s| s.read_consistency())
+    }
+
+    /// Records a write made through this client: drops the vault's cached
+    /// decisions, and records the returned token in the session, if any.
+    fn observe_write(&self, token: Option<&ConsistencyToken>) {
+        self.client.inner().decision_cache.invalidate_vault(&self.organization_id, &self.vault_id);
+        if let (Some(session), Some(token)) = (&self.session, token) {
+            session.observe(token);
+        }
+    }
+
+    /// Returns the cache key for a check, if the client's decision cache may
+    /// serve it.
+    #[cfg(feature = "rest")]
+    fn cache_key(&self, request: &TransportCheckRequest) -> Option<CacheKey> {
+        let cacheable = !request.trace
+            && self
+                .client
+                .inner()
+                .decision_cache
+                .accepts(&request.consistency, request.context.is_some());
+        cacheable.then(|| {
+            CacheKey::new(
+                &self.organization_id,
+                &self.vault_id,
+                &request.subject,
+                &request.permission,
+                &request.resource,
+            )
+        })
+    }
+
+    /// Runs a check, serving it from the client's decision cache when the
+    /// cache is enabled and the check may be cached.
+    #[cfg(feature = "rest")]
+    async fn cached_check(
+        &self,
+        transport: &AnyTransport,
+        request: TransportCheckRequest,
+    ) -> Result<TransportCheckResponse, Error> {
+        let Some(key) = self.cache_key(&request) else {
+            return transport.check(request).await;
+        };
+        let cache = &self.client.inner().decision_cache;
+        let cached = cache.get(&key);
+        self.record_cache_lookup(cached.is_some());
+        if let Some(allowed) = cached {
+            return Ok(TransportCheckResponse {
+                allowed,
+                decision: Decision::new(allowed)
+                    .with_metadata(DecisionMetadata::new().with_cached(true)),
+                trace: None,
+            });
+        }
+        let response = transport.check(request).await?;
+        cache.insert(key, response.allowed);
+        Ok(response)
+    }
+
+    /// Reports a decision cache lookup to the client's metrics, if enabled.
+    #[cfg(feature = "rest")]
+    fn record_cache_lookup(&self, _hit: bool) {
+        #[cfg(feature = "tracing")]
+        if let Some(metrics) = &self.client.inner().metrics {
+            metrics.record_cache_lookup(_hit);
+        }
+    }
+
+    /// Runs checks in one batch, serving those it can from the client's
+    /// decision cache and sending the rest.
+    #[cfg(feature = "rest")]
+    async fn cached_check_batch(
+        &self,
+        transport: &AnyTransport,
+        requests: Vec<TransportCheckRequest>,
+    ) -> Result<Vec<bool>, Error> {
+        let cache = &self.client.inner().decision_cache;
+        let mut results = Vec::with_capacity(requests.len());
+        let mut misses = Vec::new();
+        for request in requests {
+            let key = self.cache_key(&request);
+            let cached = key.as_ref().and_then(|key| cache.get(key));
+            if key.is_some() {
+                self.record_cache_lookup(cached.is_some());
+            }
+            if cached.is_none() {
+                misses.push((results.len(), key, request));
+            }
+            results.push(cached.unwrap_or_default());
+        }
+        if misses.is_empty() {
+            return Ok(results);
+        }
+
+        let (slots, misses): (Vec<_>, Vec<_>) =
+            misses.into_iter().map(|(index, key, request)| ((index, key), request)).unzip();
+        let responses = transport.check_batch(misses).await?;
+        if responses.len() != slots.len() {
+            return Err(Error::new(
+                crate::ErrorKind::InvalidResponse,
+                format!("expected {} check results, got {}", slots.len(), responses.len()),
+            ));
+        }
+        for ((index, key), response) in slots.into_iter().zip(responses) {
+            if let Some(key) = key {
+                cache.insert(key, response.allowed);
+            }
+            results[index] = response.allowed;
+        }
+        Ok(results)
+    }
+
+    /// Starts a read-your-writes session.
+    ///
+    /// The session tracks the newest consistency token returned by its own
+    /// writes and deletes, and its reads are at least as fresh as that token.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let session = vault.session();
+    /// session.relationships()
+    ///     .write(Relationship::new("doc:1", "viewer", "user:bob"))
+    ///     .await?;
+    ///
+    /// // Guaranteed to see the write above
+    /// assert!(session.check("user:bob", "view", "doc:1").await?);
+    /// ```
+    pub fn session(&self) -> ConsistencySession {
+        ConsistencySession::new(self, None)
+    }
+
+    /// Resumes a session from a string produced by [`ConsistencySession::encode()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgument` error if the string is not a valid
+    /// encoded session.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let cookie = session.encode();
+    /// // ... in another service ...
+    /// let session = vault.resume_session(&cookie)?;
+    /// ```
+    pub fn resume_session(&self, encoded: &str) -> Result<ConsistencySession, Error> {
+        ConsistencySession::decode(self, encoded)
     }
 
     /// Returns the organization ID.
@@ -67,7 +233,7 @@ impl VaultClient {
     }
 
     /// Returns the transport client, if available.
-    #[cfg(feature = "rest")]
+    #[cfg(any(feature = "grpc", feature = "rest"))]
     pub(super) fn transport(&self) -> Option<&std::sync::Arc<crate::transport::AnyTransport>> {
         self.client.transport()
     }
@@ -125,7 +291,8 @@ impl VaultClient {
             permission: permission.into(),
             resource: resource.into(),
             context: None,
-            consistency: None,
+            consistency: self.read_consistency(),
+            trace: TraceScope::default(),
         }
     }
 
@@ -174,7 +341,13 @@ impl VaultClient {
             })
             .collect();
 
-        BatchCheckRequest { vault: self.clone(), items, context: None, consistency: None }
+        BatchCheckRequest {
+            vault: self.clone(),
+            items,
+            context: None,
+            consistency: self.read_consistency(),
+            trace: TraceScope::default(),
+        }
     }
 
     /// Returns a client for managing relationships in this vault.
@@ -324,6 +497,27 @@ impl VaultClient {
         super::simulate::SimulateBuilder::new(self.clone())
     }
 
+    /// Previews which decisions would flip if relationships were changed.
+    ///
+    /// Enumerates the subjects and resources affected by the proposed
+    /// additions and removals, up to a configurable bound, and reports every
+    /// decision that would become allowed or denied.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let report = vault
+    ///     .impact()
+    ///     .add_relationship(Relationship::new("folder:design", "viewer", "team:engineering#member"))
+    ///     .permission("view")
+    ///     .await?;
+    ///
+    /// println!("{}", report);
+    /// ```
+    pub fn impact(&self) -> super::impact::ImpactBuilder {
+        super::impact::ImpactBuilder::new(self.clone())
+    }
+
     /// Subscribe to real-time relationship changes.
     ///
     /// Returns a [`WatchBuilder`](super::watch::WatchBuilder) for configuring and starting
@@ -407,10 +601,34 @@ pub struct CheckRequest<'a> {
     permission: Cow<'a, str>,
     resource: Cow<'a, str>,
     context: Option<Context>,
-    consistency: Option<ConsistencyToken>,
+    consistency: ConsistencyRequirement,
+    trace: TraceScope,
 }
 
 impl<'a> CheckRequest<'a> {
+    /// Parents this call's span to the caller's trace context.
+    ///
+    /// The context is sent to the server in the client's configured
+    /// [`TracePropagation`](crate::tracing_support::TracePropagation) format,
+    /// so server-side spans join the caller's trace.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::tracing_support::{Propagator, W3CTraceContext};
+    ///
+    /// let trace_ctx = W3CTraceContext.extract(&incoming_headers)?;
+    /// let allowed = vault.check("user:alice", "view", "doc:1")
+    ///     .with_trace_context(trace_ctx)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.vault.client(), context);
+        self
+    }
+
     /// Adds ABAC context to the check.
     ///
     /// Context values are evaluated against conditions in the authorization schema.
@@ -450,7 +668,26 @@ impl<'a> CheckRequest<'a> {
     /// ```
     #[must_use]
     pub fn at_least_as_fresh(mut self, token: ConsistencyToken) -> Self {
-        self.consistency = Some(token);
+        self.consistency = ConsistencyRequirement::AtLeastAsFresh(token);
+        self
+    }
+
+    /// Sets how fresh the data behind the check must be.
+    ///
+    /// Defaults to [`ConsistencyRequirement::Eventual`]. Use
+    /// [`ConsistencyRequirement::Full`] when the check must reflect every
+    /// committed write, such as on admin screens.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let allowed = vault.check("user:alice", "view", "doc:1")
+    ///     .consistency(ConsistencyRequirement::Full)
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn consistency(mut self, consistency: ConsistencyRequirement) -> Self {
+        self.consistency = consistency;
         self
     }
 
@@ -494,6 +731,11 @@ impl<'a> CheckRequest<'a> {
     /// }
     /// ```
     pub async fn detailed(self) -> Result<Decision, Error> {
+        self.trace.clone().run(self.execute_detailed()).await
+    }
+
+    /// Executes the check and returns the full decision.
+    async fn execute_detailed(self) -> Result<Decision, Error> {
         #[cfg(feature = "rest")]
         {
             if let Some(transport) = self.vault.transport() {
@@ -505,7 +747,7 @@ impl<'a> CheckRequest<'a> {
                     consistency: self.consistency,
                     trace: false,
                 };
-                let response = transport.check(request).await?;
+                let response = self.vault.cached_check(transport, request).await?;
                 return Ok(response.decision);
             }
         }
@@ -527,7 +769,7 @@ impl<'a> CheckRequest<'a> {
                     consistency: self.consistency,
                     trace: false,
                 };
-                let response = transport.check(request).await?;
+                let response = self.vault.cached_check(transport, request).await?;
                 return Ok(response.allowed);
             }
         }
@@ -549,7 +791,7 @@ impl<'a> std::future::IntoFuture for CheckRequest<'a> {
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -561,6 +803,14 @@ pub struct RequireCheckRequest<'a> {
 }
 
 impl<'a> RequireCheckRequest<'a> {
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.inner.trace = TraceScope::new(self.inner.vault.client(), context);
+        self
+    }
+
     /// Adds ABAC context to the check.
     #[must_use]
     pub fn with_context(mut self, context: Context) -> Self {
@@ -571,7 +821,14 @@ impl<'a> RequireCheckRequest<'a> {
     /// Specifies a consistency requirement.
     #[must_use]
     pub fn at_least_as_fresh(mut self, token: ConsistencyToken) -> Self {
-        self.inner.consistency = Some(token);
+        self.inner.consistency = ConsistencyRequirement::AtLeastAsFresh(token);
+        self
+    }
+
+    /// Sets how fresh the data behind the check must be.
+    #[must_use]
+    pub fn consistency(mut self, consistency: ConsistencyRequirement) -> Self {
+        self.inner.consistency = consistency;
         self
     }
 
@@ -588,13 +845,14 @@ impl<'a> RequireCheckRequest<'a> {
                     consistency: self.inner.consistency.clone(),
                     trace: false,
                 };
-                let response = transport.check(request).await.map_err(|_| {
-                    AccessDenied::new(
-                        self.inner.subject.clone().into_owned(),
-                        self.inner.permission.clone().into_owned(),
-                        self.inner.resource.clone().into_owned(),
-                    )
-                })?;
+                let response =
+                    self.inner.vault.cached_check(transport, request).await.map_err(|_| {
+                        AccessDenied::new(
+                            self.inner.subject.clone().into_owned(),
+                            self.inner.permission.clone().into_owned(),
+                            self.inner.resource.clone().into_owned(),
+                        )
+                    })?;
                 if response.allowed {
                     return Ok(());
                 } else {
@@ -623,7 +881,7 @@ impl<'a> std::future::IntoFuture for RequireCheckRequest<'a> {
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.inner.trace.clone().run(self.execute()))
     }
 }
 
@@ -669,10 +927,19 @@ pub struct BatchCheckRequest<'a> {
     vault: VaultClient,
     items: Vec<BatchCheckItem<'a>>,
     context: Option<Context>,
-    consistency: Option<ConsistencyToken>,
+    consistency: ConsistencyRequirement,
+    trace: TraceScope,
 }
 
 impl<'a> BatchCheckRequest<'a> {
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.vault.client(), context);
+        self
+    }
+
     /// Adds ABAC context to all checks in the batch.
     ///
     /// The same context is applied to every check in the batch.
@@ -685,7 +952,14 @@ impl<'a> BatchCheckRequest<'a> {
     /// Specifies a consistency requirement for all checks.
     #[must_use]
     pub fn at_least_as_fresh(mut self, token: ConsistencyToken) -> Self {
-        self.consistency = Some(token);
+        self.consistency = ConsistencyRequirement::AtLeastAsFresh(token);
+        self
+    }
+
+    /// Sets how fresh the data behind every check in the batch must be.
+    #[must_use]
+    pub fn consistency(mut self, consistency: ConsistencyRequirement) -> Self {
+        self.consistency = consistency;
         self
     }
 
@@ -716,8 +990,7 @@ impl<'a> BatchCheckRequest<'a> {
                         trace: false,
                     })
                     .collect();
-                let responses = transport.check_batch(requests).await?;
-                return Ok(responses.into_iter().map(|r| r.allowed).collect());
+                return self.vault.cached_check_batch(transport, requests).await;
             }
         }
 
@@ -738,7 +1011,7 @@ impl<'a> std::future::IntoFuture for BatchCheckRequest<'a> {
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -827,7 +1100,11 @@ impl RelationshipsClient {
     ///     .await?;
     /// ```
     pub fn write<'a>(&self, relationship: Relationship<'a>) -> WriteRelationshipRequest<'a> {
-        WriteRelationshipRequest { client: self.clone(), relationship }
+        WriteRelationshipRequest {
+            client: self.clone(),
+            relationship,
+            trace: TraceScope::default(),
+        }
     }
 
     /// Writes multiple relationships in a single batch.
@@ -856,6 +1133,7 @@ impl RelationshipsClient {
         WriteBatchRequest {
             client: self.clone(),
             relationships: relationships.into_iter().collect(),
+            trace: TraceScope::default(),
         }
     }
 
@@ -871,7 +1149,11 @@ impl RelationshipsClient {
     ///     .await?;
     /// ```
     pub fn delete<'a>(&self, relationship: Relationship<'a>) -> DeleteRelationshipRequest<'a> {
-        DeleteRelationshipRequest { client: self.clone(), relationship }
+        DeleteRelationshipRequest {
+            client: self.clone(),
+            relationship,
+            trace: TraceScope::default(),
+        }
     }
 
     /// Lists relationships in the vault with optional filters.
@@ -900,6 +1182,8 @@ impl RelationshipsClient {
             subject: None,
             limit: None,
             cursor: None,
+            consistency: self.vault.read_consistency(),
+            trace: TraceScope::default(),
         }
     }
 
@@ -927,7 +1211,13 @@ impl RelationshipsClient {
     ///     .await?;
     /// ```
     pub fn delete_where(&self) -> DeleteWhereBuilder {
-        DeleteWhereBuilder { client: self.clone(), resource: None, relation: None, subject: None }
+        DeleteWhereBuilder {
+            client: self.clone(),
+            resource: None,
+            relation: None,
+            subject: None,
+            trace: TraceScope::default(),
+        }
     }
 }
 
@@ -943,9 +1233,18 @@ impl std::fmt::Debug for RelationshipsClient {
 pub struct WriteRelationshipRequest<'a> {
     client: RelationshipsClient,
     relationship: Relationship<'a>,
+    trace: TraceScope,
 }
 
 impl<'a> WriteRelationshipRequest<'a> {
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.client.vault.client(), context);
+        self
+    }
+
     async fn execute(self) -> Result<ConsistencyToken, Error> {
         #[cfg(feature = "rest")]
         {
@@ -955,6 +1254,7 @@ impl<'a> WriteRelationshipRequest<'a> {
                     idempotency_key: None,
                 };
                 let response = transport.write(request).await?;
+                self.client.vault.observe_write(Some(&response.consistency_token));
                 return Ok(response.consistency_token);
             }
         }
@@ -975,7 +1275,7 @@ impl<'a> std::future::IntoFuture for WriteRelationshipRequest<'a> {
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -983,9 +1283,18 @@ impl<'a> std::future::IntoFuture for WriteRelationshipRequest<'a> {
 pub struct WriteBatchRequest<'a> {
     client: RelationshipsClient,
     relationships: Vec<Relationship<'a>>,
+    trace: TraceScope,
 }
 
 impl<'a> WriteBatchRequest<'a> {
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.client.vault.client(), context);
+        self
+    }
+
     /// Returns the number of relationships in this batch.
     pub fn len(&self) -> usize {
         self.relationships.len()
@@ -1009,6 +1318,7 @@ impl<'a> WriteBatchRequest<'a> {
                     })
                     .collect();
                 let response = transport.write_batch(requests).await?;
+                self.client.vault.observe_write(Some(&response.consistency_token));
                 return Ok(response.consistency_token);
             }
         }
@@ -1029,7 +1339,7 @@ impl<'a> std::future::IntoFuture for WriteBatchRequest<'a> {
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -1037,14 +1347,24 @@ impl<'a> std::future::IntoFuture for WriteBatchRequest<'a> {
 pub struct DeleteRelationshipRequest<'a> {
     client: RelationshipsClient,
     relationship: Relationship<'a>,
+    trace: TraceScope,
 }
 
 impl<'a> DeleteRelationshipRequest<'a> {
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.client.vault.client(), context);
+        self
+    }
+
     async fn execute(self) -> Result<(), Error> {
         #[cfg(feature = "rest")]
         {
             if let Some(transport) = self.client.vault.transport() {
-                transport.delete(self.relationship.into_owned()).await?;
+                let token = transport.delete(self.relationship.into_owned()).await?;
+                self.client.vault.observe_write(token.as_ref());
                 return Ok(());
             }
         }
@@ -1065,7 +1385,7 @@ impl<'a> std::future::IntoFuture for DeleteRelationshipRequest<'a> {
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -1078,9 +1398,18 @@ pub struct DeleteWhereBuilder {
     resource: Option<String>,
     relation: Option<String>,
     subject: Option<String>,
+    trace: TraceScope,
 }
 
 impl DeleteWhereBuilder {
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.client.vault.client(), context);
+        self
+    }
+
     /// Filters by resource.
     ///
     /// # Example
@@ -1151,12 +1480,15 @@ impl DeleteWhereBuilder {
                         self.subject.as_deref(),
                         None, // No limit - get all
                         None, // No cursor - start from beginning
+                        // Deletes must see every committed match
+                        &ConsistencyRequirement::Full,
                     )
                     .await?;
 
                 let mut deleted = 0;
                 for rel in response.relationships {
-                    transport.delete(rel).await?;
+                    let token = transport.delete(rel).await?;
+                    self.client.vault.observe_write(token.as_ref());
                     deleted += 1;
                 }
 
@@ -1181,7 +1513,7 @@ impl std::future::IntoFuture for DeleteWhereBuilder {
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'static>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -1212,9 +1544,19 @@ pub struct ListRelationshipsRequest {
     subject: Option<String>,
     limit: Option<usize>,
     cursor: Option<String>,
+    consistency: ConsistencyRequirement,
+    trace: TraceScope,
 }
 
 impl ListRelationshipsRequest {
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.client.vault.client(), context);
+        self
+    }
+
     /// Filters by resource.
     #[must_use]
     pub fn resource(mut self, resource: impl Into<String>) -> Self {
@@ -1250,6 +1592,15 @@ impl ListRelationshipsRequest {
         self
     }
 
+    /// Sets how fresh the listed relationships must be.
+    ///
+    /// Defaults to [`ConsistencyRequirement::Eventual`].
+    #[must_use]
+    pub fn consistency(mut self, consistency: ConsistencyRequirement) -> Self {
+        self.consistency = consistency;
+        self
+    }
+
     async fn execute(self) -> Result<ListRelationshipsResponse, Error> {
         #[cfg(feature = "rest")]
         {
@@ -1261,6 +1612,7 @@ impl ListRelationshipsRequest {
                         self.subject.as_deref(),
                         self.limit.map(|l| l as u32),
                         self.cursor.as_deref(),
+                        &self.consistency,
                     )
                     .await?;
                 return Ok(ListRelationshipsResponse {
@@ -1271,7 +1623,8 @@ impl ListRelationshipsRequest {
         }
 
         // Fallback for when no transport is available (e.g., testing)
-        let _ = (self.resource, self.relation, self.subject, self.limit, self.cursor);
+        let _ =
+            (self.resource, self.relation, self.subject, self.limit, self.cursor, self.consistency);
         Ok(ListRelationshipsResponse { relationships: vec![], next_cursor: None })
     }
 }
@@ -1287,7 +1640,7 @@ impl std::future::IntoFuture for ListRelationshipsRequest {
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'static>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -1397,8 +1750,9 @@ impl<'a> ResourcesQueryBuilder<'a> {
             subject: self.subject,
             permission: permission.into(),
             resource_type: None,
-            consistency: None,
+            consistency: self.vault.read_consistency(),
             page_size: None,
+            trace: TraceScope::default(),
         }
     }
 }
@@ -1421,8 +1775,9 @@ pub struct ResourcesListBuilder<'a> {
     subject: Cow<'a, str>,
     permission: Cow<'a, str>,
     resource_type: Option<Cow<'a, str>>,
-    consistency: Option<ConsistencyToken>,
+    consistency: ConsistencyRequirement,
     page_size: Option<u32>,
+    trace: TraceScope,
 }
 
 impl<'a> ResourcesListBuilder<'a> {
@@ -1458,7 +1813,16 @@ impl<'a> ResourcesListBuilder<'a> {
     /// ```
     #[must_use]
     pub fn at_least_as_fresh_as(mut self, token: ConsistencyToken) -> Self {
-        self.consistency = Some(token);
+        self.consistency = ConsistencyRequirement::AtLeastAsFresh(token);
+        self
+    }
+
+    /// Sets how fresh the data behind the lookup must be.
+    ///
+    /// Defaults to [`ConsistencyRequirement::Eventual`].
+    #[must_use]
+    pub fn consistency(mut self, consistency: ConsistencyRequirement) -> Self {
+        self.consistency = consistency;
         self
     }
 
@@ -1469,6 +1833,14 @@ impl<'a> ResourcesListBuilder<'a> {
         self
     }
 
+    /// Parents this query's spans to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.vault.client(), context);
+        self
+    }
+
     /// Returns a stream of resources.
     ///
     /// This is the recommended way to handle large result sets, as it
@@ -1523,6 +1895,11 @@ impl<'a> ResourcesListBuilder<'a> {
     ///     .await?;
     /// ```
     pub async fn collect(self) -> Result<Vec<String>, Error> {
+        self.trace.clone().run(self.collect_pages()).await
+    }
+
+    /// Fetches every page of results.
+    async fn collect_pages(self) -> Result<Vec<String>, Error> {
         #[cfg(feature = "rest")]
         {
             if let Some(transport) = self.vault.transport() {
@@ -1537,6 +1914,7 @@ impl<'a> ResourcesListBuilder<'a> {
                             self.resource_type.as_ref().map(|s| s.as_ref()),
                             self.page_size,
                             cursor.as_deref(),
+                            &self.consistency,
                         )
                         .await?;
 
@@ -1577,6 +1955,11 @@ impl<'a> ResourcesListBuilder<'a> {
     ///     .await?;
     /// ```
     pub async fn cursor(self, cursor: Option<&str>) -> Result<ResourcesPage, Error> {
+        self.trace.clone().run(self.fetch_page(cursor)).await
+    }
+
+    /// Fetches a single page of results.
+    async fn fetch_page(self, cursor: Option<&str>) -> Result<ResourcesPage, Error> {
         #[cfg(feature = "rest")]
         {
             if let Some(transport) = self.vault.transport() {
@@ -1587,6 +1970,7 @@ impl<'a> ResourcesListBuilder<'a> {
                         self.resource_type.as_ref().map(|s| s.as_ref()),
                         self.page_size,
                         cursor,
+                        &self.consistency,
                     )
                     .await?;
                 return Ok(ResourcesPage {
@@ -1659,6 +2043,7 @@ pub struct ResourceStream<'a> {
     permission: String,
     resource_type: Option<String>,
     page_size: Option<u32>,
+    consistency: ConsistencyRequirement,
     cursor: Option<String>,
     buffer: std::collections::VecDeque<String>,
     done: bool,
@@ -1674,6 +2059,7 @@ impl<'a> ResourceStream<'a> {
             permission: builder.permission.into_owned(),
             resource_type: builder.resource_type.map(|s| s.into_owned()),
             page_size: builder.page_size,
+            consistency: builder.consistency,
             cursor: None,
             buffer: std::collections::VecDeque::new(),
             done: false,
@@ -1720,6 +2106,7 @@ impl<'a> Stream for ResourceStream<'a> {
                 let permission = this.permission.clone();
                 let resource_type = this.resource_type.clone();
                 let page_size = this.page_size;
+                let consistency = this.consistency.clone();
                 let cursor = this.cursor.clone();
 
                 // Create a future to fetch the next page
@@ -1731,6 +2118,7 @@ impl<'a> Stream for ResourceStream<'a> {
                             resource_type.as_deref(),
                             page_size,
                             cursor.as_deref(),
+                            &consistency,
                         )
                         .await
                 };
@@ -1850,8 +2238,9 @@ impl<'a> SubjectsQueryBuilder<'a> {
             permission: self.permission,
             resource: resource.into(),
             subject_type: None,
-            consistency: None,
+            consistency: self.vault.read_consistency(),
             page_size: None,
+            trace: TraceScope::default(),
         }
     }
 }
@@ -1874,8 +2263,9 @@ pub struct SubjectsListBuilder<'a> {
     permission: Cow<'a, str>,
     resource: Cow<'a, str>,
     subject_type: Option<Cow<'a, str>>,
-    consistency: Option<ConsistencyToken>,
+    consistency: ConsistencyRequirement,
     page_size: Option<u32>,
+    trace: TraceScope,
 }
 
 impl<'a> SubjectsListBuilder<'a> {
@@ -1911,7 +2301,16 @@ impl<'a> SubjectsListBuilder<'a> {
     /// ```
     #[must_use]
     pub fn at_least_as_fresh_as(mut self, token: ConsistencyToken) -> Self {
-        self.consistency = Some(token);
+        self.consistency = ConsistencyRequirement::AtLeastAsFresh(token);
+        self
+    }
+
+    /// Sets how fresh the data behind the lookup must be.
+    ///
+    /// Defaults to [`ConsistencyRequirement::Eventual`].
+    #[must_use]
+    pub fn consistency(mut self, consistency: ConsistencyRequirement) -> Self {
+        self.consistency = consistency;
         self
     }
 
@@ -1922,6 +2321,14 @@ impl<'a> SubjectsListBuilder<'a> {
         self
     }
 
+    /// Parents this query's spans to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.vault.client(), context);
+        self
+    }
+
     /// Returns a stream of subjects.
     ///
     /// This is the recommended way to handle large result sets, as it
@@ -1976,6 +2383,11 @@ impl<'a> SubjectsListBuilder<'a> {
     ///     .await?;
     /// ```
     pub async fn collect(self) -> Result<Vec<String>, Error> {
+        self.trace.clone().run(self.collect_pages()).await
+    }
+
+    /// Fetches every page of results.
+    async fn collect_pages(self) -> Result<Vec<String>, Error> {
         #[cfg(feature = "rest")]
         {
             if let Some(transport) = self.vault.transport() {
@@ -1990,6 +2402,7 @@ impl<'a> SubjectsListBuilder<'a> {
                             self.subject_type.as_ref().map(|s| s.as_ref()),
                             self.page_size,
                             cursor.as_deref(),
+                            &self.consistency,
                         )
                         .await?;
 
@@ -2030,6 +2443,11 @@ impl<'a> SubjectsListBuilder<'a> {
     ///     .await?;
     /// ```
     pub async fn cursor(self, cursor: Option<&str>) -> Result<SubjectsPage, Error> {
+        self.trace.clone().run(self.fetch_page(cursor)).await
+    }
+
+    /// Fetches a single page of results.
+    async fn fetch_page(self, cursor: Option<&str>) -> Result<SubjectsPage, Error> {
         #[cfg(feature = "rest")]
         {
             if let Some(transport) = self.vault.transport() {
@@ -2040,6 +2458,7 @@ impl<'a> SubjectsListBuilder<'a> {
                         self.subject_type.as_ref().map(|s| s.as_ref()),
                         self.page_size,
                         cursor,
+                        &self.consistency,
                     )
                     .await?;
                 return Ok(SubjectsPage {
@@ -2112,6 +2531,7 @@ pub struct SubjectStream<'a> {
     resource: String,
     subject_type: Option<String>,
     page_size: Option<u32>,
+    consistency: ConsistencyRequirement,
     cursor: Option<String>,
     buffer: std::collections::VecDeque<String>,
     done: bool,
@@ -2127,6 +2547,7 @@ impl<'a> SubjectStream<'a> {
             resource: builder.resource.into_owned(),
             subject_type: builder.subject_type.map(|s| s.into_owned()),
             page_size: builder.page_size,
+            consistency: builder.consistency,
             cursor: None,
             buffer: std::collections::VecDeque::new(),
             done: false,
@@ -2173,6 +2594,7 @@ impl<'a> Stream for SubjectStream<'a> {
                 let resource = this.resource.clone();
                 let subject_type = this.subject_type.clone();
                 let page_size = this.page_size;
+                let consistency = this.consistency.clone();
                 let cursor = this.cursor.clone();
 
                 // Create a future to fetch the next page
@@ -2184,6 +2606,7 @@ impl<'a> Stream for SubjectStream<'a> {
                             subject_type.as_deref(),
                             page_size,
                             cursor.as_deref(),
+                            &consistency,
                         )
                         .await
                 };
@@ -2243,11 +2666,30 @@ pub struct ExplainPermissionRequest {
     permission: Option<String>,
     resource: Option<String>,
     context: Option<Context>,
+    consistency: ConsistencyRequirement,
+    trace: TraceScope,
 }
 
 impl ExplainPermissionRequest {
     fn new(vault: VaultClient) -> Self {
-        Self { vault, subject: None, permission: None, resource: None, context: None }
+        let consistency = vault.read_consistency();
+        Self {
+            vault,
+            subject: None,
+            permission: None,
+            resource: None,
+            context: None,
+            consistency,
+            trace: TraceScope::default(),
+        }
+    }
+
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.vault.client(), context);
+        self
     }
 
     /// Sets the subject to check.
@@ -2278,6 +2720,15 @@ impl ExplainPermissionRequest {
         self
     }
 
+    /// Sets how fresh the data behind the explanation must be.
+    ///
+    /// Defaults to [`ConsistencyRequirement::Eventual`].
+    #[must_use]
+    pub fn consistency(mut self, consistency: ConsistencyRequirement) -> Self {
+        self.consistency = consistency;
+        self
+    }
+
     async fn execute(self) -> Result<PermissionExplanation, Error> {
         let subject = self.subject.ok_or_else(|| Error::invalid_argument("subject is required"))?;
         let permission =
@@ -2285,8 +2736,10 @@ impl ExplainPermissionRequest {
         let resource =
             self.resource.ok_or_else(|| Error::invalid_argument("resource is required"))?;
 
-        #[cfg(feature = "rest")]
+        #[cfg(any(feature = "grpc", feature = "rest"))]
         if let Some(transport) = self.vault.transport() {
+            use crate::transport::TransportCheckRequest;
+
             let start = std::time::Instant::now();
 
             // Request with trace enabled to get detailed explanation
@@ -2295,7 +2748,7 @@ impl ExplainPermissionRequest {
                 permission: permission.clone(),
                 resource: resource.clone(),
                 context: self.context.clone(),
-                consistency: None,
+                consistency: self.consistency.clone(),
                 trace: true, // Enable trace for explain
             };
 
@@ -2306,26 +2759,49 @@ impl ExplainPermissionRequest {
                 PermissionExplanation::allowed(&subject, &permission, &resource)
             } else {
                 PermissionExplanation::denied(&subject, &permission, &resource)
-                    .with_denial_reason(DenialReason::no_path())
             };
 
-            // Add trace information if available
-            if let Some(trace) = response.trace {
-                explanation = explanation
-                    .with_evaluation_time(std::time::Duration::from_micros(trace.duration_micros));
+            let Some(trace) = response.trace else {
+                if !response.allowed {
+                    explanation = explanation.with_denial_reason(DenialReason::no_path());
+                }
+                return Ok(explanation.with_evaluation_time(evaluation_time));
+            };
+
+            explanation = explanation
+                .with_evaluation_time(std::time::Duration::from_micros(trace.duration_micros));
 
-                // Convert evaluation tree to paths
-                if let Some(root) = trace.root {
-                    let paths = Self::extract_paths_from_tree(&root, &subject, &resource);
-                    for path in paths {
+            match trace.root {
+                Some(ref root) if response.allowed => {
+                    for path in Self::extract_paths_from_tree(root, &subject, &resource) {
                         explanation = explanation.with_path(path);
                     }
-                }
-            } else {
-                explanation = explanation.with_evaluation_time(evaluation_time);
+                },
+                Some(ref root) => {
+                    for reason in DenialReason::from_tree(root) {
+                        explanation = explanation.with_denial_reason(reason);
+                    }
+                    for suggestion in Self::verified_suggestions(
+                        transport,
+                        root,
+                        &subject,
+                        &permission,
+                        &resource,
+                        &self.context,
+                        &self.consistency,
+                    )
+                    .await
+                    {
+                        explanation = explanation.with_suggestion(suggestion);
+                    }
+                },
+                None if !response.allowed => {
+                    explanation = explanation.with_denial_reason(DenialReason::no_path());
+                },
+                None => {},
             }
 
-            return Ok(explanation);
+            return Ok(explanation.with_trace(trace));
         }
 
         // Fallback for when transport is not available
@@ -2333,6 +2809,48 @@ impl ExplainPermissionRequest {
             .with_denial_reason(DenialReason::no_path()))
     }
 
+    /// Derives relationship writes that would grant access from a denied
+    /// evaluation tree, keeping only those confirmed by a simulation.
+    ///
+    /// Suggestions are best-effort: if the simulation fails, none are returned
+    /// and the explanation is still reported.
+    #[cfg(any(feature = "grpc", feature = "rest"))]
+    async fn verified_suggestions(
+        transport: &crate::transport::AnyTransport,
+        root: &crate::transport::EvaluationNode,
+        subject: &str,
+        permission: &str,
+        resource: &str,
+        context: &Option<Context>,
+        consistency: &ConsistencyRequirement,
+    ) -> Vec<super::explain::AccessSuggestion> {
+        use crate::transport::TransportSimulateRequest;
+
+        let candidates = super::explain::grant_candidates(root, super::explain::MAX_SUGGESTIONS);
+        let verified = futures::future::join_all(candidates.iter().map(|additions| {
+            transport.simulate(TransportSimulateRequest {
+                subject: subject.to_string(),
+                permission: permission.to_string(),
+                resource: resource.to_string(),
+                context: context.clone(),
+                additions: additions.clone(),
+                removals: vec![],
+                schema: None,
+                consistency: consistency.clone(),
+            })
+        }))
+        .await;
+
+        candidates
+            .iter()
+            .zip(verified)
+            .filter(|(_, result)| result.as_ref().is_ok_and(|response| response.allowed))
+            .filter_map(|(additions, _)| {
+                super::explain::AccessSuggestion::from_relationships(additions)
+            })
+            .collect()
+    }
+
     /// Extracts paths from the evaluation tree.
     fn extract_paths_from_tree(
         node: &crate::transport::traits::EvaluationNode,
@@ -2417,7 +2935,7 @@ impl std::future::IntoFuture for ExplainPermissionRequest {
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -2483,6 +3001,123 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_reads_accept_consistency_requirement() {
+        let vault = create_test_vault_with_relationships().await;
+        let full = ConsistencyRequirement::Full;
+
+        assert!(
+            vault.check("user:alice", "view", "doc:1").consistency(full.clone()).await.unwrap()
+        );
+        let batch = vault
+            .check_batch([("user:alice", "view", "doc:1")])
+            .consistency(full.clone())
+            .await
+            .unwrap();
+        assert_eq!(batch, vec![true]);
+        let listed =
+            vault.relationships().list().resource("doc:1").consistency(full.clone()).await.unwrap();
+        assert!(!listed.relationships.is_empty());
+        let resources = vault
+            .resources()
+            .accessible_by("user:alice")
+            .with_permission("view")
+            .consistency(full.clone())
+            .collect()
+            .await
+            .unwrap();
+        assert!(resources.contains(&"doc:1".to_string()));
+    }
+
+    #[cfg(feature = "rest")]
+    #[tokio::test]
+    async fn test_decision_cache_serves_eventual_checks() {
+        use crate::{config::CacheConfig, transport::AnyTransport};
+
+        let mock = MockTransport::new();
+        mock.add_relationship(Relationship::new("doc:1", "view", "user:alice"));
+        let transport = Arc::new(mock.into_any());
+        let client = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .cache_config(CacheConfig::enabled_config())
+            .build_with_transport(Arc::clone(&transport))
+            .await
+            .unwrap();
+        let vault = client.organization("org_test").vault("vlt_test");
+        let requests = || match transport.as_ref() {
+            AnyTransport::Mock(mock) => mock.request_count(),
+            _ => unreachable!(),
+        };
+
+        assert!(vault.check("user:alice", "view", "doc:1").await.unwrap());
+        let decision = vault.check("user:alice", "view", "doc:1").detailed().await.unwrap();
+        assert!(decision.is_allowed());
+        assert_eq!(decision.metadata().and_then(|m| m.cached), Some(true));
+        assert_eq!(requests(), 1);
+
+        // Batches send only the checks the cache cannot answer.
+        let batch = vault
+            .check_batch([("user:alice", "view", "doc:1"), ("user:bob", "view", "doc:1")])
+            .await
+            .unwrap();
+        assert_eq!(batch, vec![true, false]);
+        assert_eq!(requests(), 2);
+        assert_eq!(vault.check_batch([("user:bob", "view", "doc:1")]).await.unwrap(), vec![false]);
+        assert_eq!(requests(), 2);
+
+        // Full reads and checks with context always reach the server.
+        vault
+            .check("user:alice", "view", "doc:1")
+            .consistency(ConsistencyRequirement::Full)
+            .await
+            .unwrap();
+        vault
+            .check("user:alice", "view", "doc:1")
+            .with_context(Context::new().with("env", "prod"))
+            .await
+            .unwrap();
+        assert_eq!(requests(), 4);
+
+        // Writes through the client drop the vault's cached decisions.
+        vault.relationships().write(Relationship::new("doc:1", "view", "user:bob")).await.unwrap();
+        assert!(vault.check("user:bob", "view", "doc:1").await.unwrap());
+        assert_eq!(requests(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_session_reads_default_to_latest_token() {
+        let vault = create_test_vault().await;
+        let session = vault.session();
+        session
+            .relationships()
+            .write(Relationship::new("doc:1", "viewer", "user:a"))
+            .await
+            .unwrap();
+        let expected = ConsistencyRequirement::AtLeastAsFresh(ConsistencyToken::new("mock_token"));
+
+        assert_eq!(session.check("user:a", "view", "doc:1").consistency, expected);
+        assert_eq!(session.check_batch([("user:a", "view", "doc:1")]).consistency, expected);
+        assert_eq!(session.relationships().list().consistency, expected);
+        assert_eq!(
+            session.resources().accessible_by("user:a").with_permission("view").consistency,
+            expected
+        );
+        assert_eq!(
+            session.subjects().with_permission("view").on_resource("doc:1").consistency,
+            expected
+        );
+        assert_eq!(session.explain_permission().consistency, expected);
+
+        // Explicit requirements win, and plain vault reads are unaffected
+        let full = ConsistencyRequirement::Full;
+        assert_eq!(
+            session.check("user:a", "view", "doc:1").consistency(full.clone()).consistency,
+            full
+        );
+        assert!(vault.check("user:a", "view", "doc:1").consistency.is_eventual());
+    }
+
     #[tokio::test]
     async fn test_require() {
         // require() returns Ok only if access is granted
@@ -2998,6 +3633,158 @@ mod tests {
         assert!(explanation.allowed);
     }
 
+    #[cfg(feature = "rest")]
+    #[tokio::test]
+    async fn test_explain_permission_denied_with_trace() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        use crate::vault::DenialReason;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/access/v1/evaluate"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(concat!(
+                        "data: {\"decision\": \"deny\", \"index\": 0, \"trace\": {",
+                        "\"duration_micros\": 80, \"relationships_read\": 3, \"relations_evaluated\": 2, ",
+                        "\"root\": {\"result\": false, \"node_type\": {\"intersection\": {}}, \"children\": [",
+                        "{\"result\": false, \"node_type\": {\"direct_check\": ",
+                        "{\"resource\": \"doc:1\", \"relation\": \"viewer\", \"subject\": \"user:bob\"}}},",
+                        "{\"result\": true, \"node_type\": {\"wasm_module\": {\"module_name\": \"business_hours\"}}}",
+                        "]}}}\n\n"
+                    )),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/access/v1/simulate"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"allowed": true})),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test"))
+            .transport_strategy(crate::TransportStrategy::RestOnly)
+            .build()
+            .await
+            .unwrap();
+        let vault = client.organization("org_test").vault("vlt_test");
+
+        let explanation = vault
+            .explain_permission()
+            .subject("user:bob")
+            .permission("view")
+            .resource("doc:1")
+            .await
+            .unwrap();
+
+        assert!(!explanation.allowed);
+        assert_eq!(explanation.relationships_read(), Some(3));
+        assert_eq!(explanation.relations_evaluated(), Some(2));
+        assert_eq!(explanation.evaluation_tree().unwrap().children.len(), 2);
+        assert_eq!(
+            explanation.denial_reasons,
+            vec![
+                DenialReason::intersection_failed("doc:1#viewer@user:bob"),
+                DenialReason::missing_relationship("doc:1#viewer@user:bob"),
+            ]
+        );
+        assert_eq!(explanation.suggestions.len(), 1);
+        assert_eq!(explanation.suggestions[0].relationship, "doc:1#viewer@user:bob");
+    }
+
+    #[cfg(all(feature = "rest", feature = "tracing"))]
+    async fn traced_check_server(expected_header: &'static str) -> wiremock::MockServer {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header_exists, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/access/v1/evaluate"))
+            .and(header_exists(expected_header))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string("data: {\"decision\": \"allow\", \"index\": 0}\n\n"),
+            )
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[cfg(all(feature = "rest", feature = "tracing"))]
+    #[tokio::test]
+    async fn test_check_propagates_trace_context() {
+        use crate::tracing_support::TraceContext;
+
+        let server = traced_check_server("traceparent").await;
+        let client = Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test"))
+            .transport_strategy(crate::TransportStrategy::RestOnly)
+            .build()
+            .await
+            .unwrap();
+        let vault = client.organization("org_test").vault("vlt_test");
+
+        let parent = TraceContext::new_root();
+        let allowed = vault
+            .check("user:alice", "view", "doc:1")
+            .with_trace_context(parent.clone())
+            .await
+            .unwrap();
+        assert!(allowed);
+
+        let requests = server.received_requests().await.unwrap();
+        let traceparent = requests[0].headers["traceparent"].to_str().unwrap().to_string();
+        let sent = TraceContext::from_traceparent(&traceparent).unwrap();
+        assert_eq!(sent.trace_id(), parent.trace_id());
+        assert_ne!(sent.span_id(), parent.span_id());
+    }
+
+    #[cfg(all(feature = "rest", feature = "tracing"))]
+    #[tokio::test]
+    async fn test_check_propagates_b3_trace_context() {
+        use crate::tracing_support::{TraceContext, TracePropagation};
+
+        let server = traced_check_server("b3").await;
+        let client = Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test"))
+            .transport_strategy(crate::TransportStrategy::RestOnly)
+            .trace_propagation(TracePropagation::B3)
+            .build()
+            .await
+            .unwrap();
+        let vault = client.organization("org_test").vault("vlt_test");
+
+        let parent = TraceContext::new_root();
+        let allowed =
+            vault.check("user:alice", "view", "doc:1").with_trace_context(parent.clone()).await;
+        assert!(allowed.unwrap());
+
+        // Calls without a context send no propagation headers
+        assert!(vault.check("user:alice", "view", "doc:1").await.is_err());
+
+        let requests = server.received_requests().await.unwrap();
+        assert!(!requests[0].headers.contains_key("traceparent"));
+        let b3 = requests[0].headers["b3"].to_str().unwrap();
+        assert!(b3.starts_with(&parent.trace_id().to_string()));
+    }
+
     #[tokio::test]
     async fn test_explain_permission_with_context() {
         let vault = create_test_vault().await;
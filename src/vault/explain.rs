@@ -8,7 +8,14 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-use crate::types::Context;
+use crate::{
+    transport::{DecisionTrace, EvaluationNode, EvaluationNodeType},
+    types::{Context, Relationship},
+};
+
+/// Maximum number of access suggestions generated per explanation.
+#[cfg_attr(not(any(feature = "grpc", feature = "rest")), allow(dead_code))]
+pub(crate) const MAX_SUGGESTIONS: usize = 5;
 
 /// Explains why a permission check resulted in allow or deny.
 ///
@@ -64,6 +71,9 @@ pub struct PermissionExplanation {
     pub evaluation_time: Duration,
     /// Whether the result was served from cache.
     pub cached: bool,
+    /// Full evaluation trace, including failed branches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<DecisionTrace>,
 }
 
 impl PermissionExplanation {
@@ -83,6 +93,7 @@ impl PermissionExplanation {
             suggestions: vec![],
             evaluation_time: Duration::ZERO,
             cached: false,
+            trace: None,
         }
     }
 
@@ -102,6 +113,7 @@ impl PermissionExplanation {
             suggestions: vec![],
             evaluation_time: Duration::ZERO,
             cached: false,
+            trace: None,
         }
     }
 
@@ -140,6 +152,28 @@ impl PermissionExplanation {
         self
     }
 
+    /// Attaches the evaluation trace.
+    #[must_use]
+    pub fn with_trace(mut self, trace: DecisionTrace) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    /// Returns the root of the evaluation tree, if a trace is available.
+    pub fn evaluation_tree(&self) -> Option<&EvaluationNode> {
+        self.trace.as_ref().and_then(|t| t.root.as_ref())
+    }
+
+    /// Returns the number of relationships read during evaluation.
+    pub fn relationships_read(&self) -> Option<u64> {
+        self.trace.as_ref().map(|t| t.relationships_read)
+    }
+
+    /// Returns the number of relations evaluated.
+    pub fn relations_evaluated(&self) -> Option<u64> {
+        self.trace.as_ref().map(|t| t.relations_evaluated)
+    }
+
     /// Formats a path as a human-readable string.
     ///
     /// ## Example Output
@@ -202,6 +236,13 @@ impl std::fmt::Display for PermissionExplanation {
         writeln!(f, "Result:     {}", if self.allowed { "ALLOWED" } else { "DENIED" })?;
         writeln!(f, "Cached:     {}", self.cached)?;
         writeln!(f, "Time:       {:?}", self.evaluation_time)?;
+        if let Some(ref trace) = self.trace {
+            writeln!(
+                f,
+                "Evaluated:  {} relationship(s) read, {} relation(s) evaluated",
+                trace.relationships_read, trace.relations_evaluated
+            )?;
+        }
 
         if !self.paths.is_empty() {
             writeln!(f, "\nPaths:")?;
@@ -304,6 +345,16 @@ pub enum DenialReason {
         /// What was not found.
         what: String,
     },
+    /// A required relationship does not exist.
+    MissingRelationship {
+        /// The missing relationship (e.g., "doc:1#viewer@user:alice").
+        relationship: String,
+    },
+    /// One arm of an intersection was not satisfied.
+    IntersectionFailed {
+        /// Description of the failed arm.
+        branch: String,
+    },
 }
 
 impl DenialReason {
@@ -344,6 +395,35 @@ impl DenialReason {
     pub fn not_found(what: impl Into<String>) -> Self {
         DenialReason::NotFound { what: what.into() }
     }
+
+    /// Creates a "missing relationship" denial reason.
+    pub fn missing_relationship(relationship: impl Into<String>) -> Self {
+        DenialReason::MissingRelationship { relationship: relationship.into() }
+    }
+
+    /// Creates an "intersection failed" denial reason.
+    pub fn intersection_failed(branch: impl Into<String>) -> Self {
+        DenialReason::IntersectionFailed { branch: branch.into() }
+    }
+
+    /// Classifies why an evaluation tree was denied.
+    ///
+    /// Walks the failed branches of the tree and reports missing direct
+    /// relationships, failed intersection arms, exclusion hits (as
+    /// [`ExplicitDeny`](Self::ExplicitDeny)) and failed conditions. Returns
+    /// an empty list if the tree was allowed.
+    pub fn from_tree(root: &EvaluationNode) -> Vec<DenialReason> {
+        if root.result {
+            return Vec::new();
+        }
+        let mut reasons = Vec::new();
+        collect_denials(root, &mut reasons);
+        if reasons.is_empty() {
+            reasons.push(DenialReason::no_path());
+        }
+        reasons.dedup();
+        reasons
+    }
 }
 
 impl std::fmt::Display for DenialReason {
@@ -380,10 +460,112 @@ impl std::fmt::Display for DenialReason {
             DenialReason::NotFound { what } => {
                 write!(f, "{} not found", what)
             },
+            DenialReason::MissingRelationship { relationship } => {
+                write!(f, "missing relationship {}", relationship)
+            },
+            DenialReason::IntersectionFailed { branch } => {
+                write!(f, "intersection arm '{}' not satisfied", branch)
+            },
         }
     }
 }
 
+/// Collects denial reasons from a failed subtree.
+fn collect_denials(node: &EvaluationNode, reasons: &mut Vec<DenialReason>) {
+    if node.result {
+        return;
+    }
+    match &node.node_type {
+        EvaluationNodeType::DirectCheck { .. } => {
+            reasons.push(DenialReason::missing_relationship(node.node_type.to_string()));
+        },
+        EvaluationNodeType::WasmModule { .. } => {
+            reasons.push(DenialReason::condition_failed(node.node_type.to_string()));
+        },
+        EvaluationNodeType::Intersection => {
+            for arm in node.children.iter().filter(|c| !c.result) {
+                reasons.push(DenialReason::intersection_failed(arm.node_type.to_string()));
+                collect_denials(arm, reasons);
+            }
+        },
+        EvaluationNodeType::Exclusion => match node.children.split_first() {
+            Some((base, _)) if !base.result => collect_denials(base, reasons),
+            Some((_, excluded)) => {
+                for hit in excluded.iter().filter(|c| c.result) {
+                    reasons.push(DenialReason::ExplicitDeny {
+                        relationship: Some(hit.node_type.to_string()),
+                    });
+                }
+            },
+            None => {},
+        },
+        EvaluationNodeType::ComputedUserset { .. }
+        | EvaluationNodeType::RelatedObjectUserset { .. }
+        | EvaluationNodeType::Union => {
+            for child in &node.children {
+                collect_denials(child, reasons);
+            }
+        },
+    }
+}
+
+/// Returns minimal sets of relationship writes that would make `node` pass.
+///
+/// Each inner `Vec` is one alternative; all relationships in it must be
+/// written together. Branches that cannot be fixed by writing relationships
+/// (failed conditions, exclusion hits) yield no alternatives. The result is
+/// sorted by size and capped at `limit` alternatives.
+#[cfg_attr(not(any(feature = "grpc", feature = "rest")), allow(dead_code))]
+pub(crate) fn grant_candidates(
+    node: &EvaluationNode,
+    limit: usize,
+) -> Vec<Vec<Relationship<'static>>> {
+    let mut candidates = grants(node, limit);
+    candidates.retain(|set| !set.is_empty());
+    candidates.sort_by_key(Vec::len);
+    candidates.dedup();
+    candidates.truncate(limit);
+    candidates
+}
+
+#[cfg_attr(not(any(feature = "grpc", feature = "rest")), allow(dead_code))]
+fn grants(node: &EvaluationNode, limit: usize) -> Vec<Vec<Relationship<'static>>> {
+    if node.result {
+        return vec![vec![]];
+    }
+    let mut alternatives: Vec<Vec<Relationship<'static>>> = match &node.node_type {
+        EvaluationNodeType::DirectCheck { resource, relation, subject } => {
+            vec![vec![Relationship::new(resource.clone(), relation.clone(), subject.clone())]]
+        },
+        EvaluationNodeType::WasmModule { .. } => vec![],
+        EvaluationNodeType::ComputedUserset { .. }
+        | EvaluationNodeType::RelatedObjectUserset { .. }
+        | EvaluationNodeType::Union => {
+            node.children.iter().flat_map(|child| grants(child, limit)).collect()
+        },
+        EvaluationNodeType::Intersection => node.children.iter().fold(vec![vec![]], |acc, arm| {
+            let arm_grants = grants(arm, limit);
+            acc.iter()
+                .flat_map(|prefix| {
+                    arm_grants.iter().map(move |set| {
+                        let mut combined = prefix.clone();
+                        combined.extend(set.iter().filter(|r| !prefix.contains(r)).cloned());
+                        combined
+                    })
+                })
+                .collect()
+        }),
+        EvaluationNodeType::Exclusion => match node.children.split_first() {
+            Some((base, excluded)) if excluded.iter().all(|c| !c.result) => grants(base, limit),
+            _ => vec![],
+        },
+    };
+    // Keep the search bounded on wide trees
+    alternatives.sort_by_key(Vec::len);
+    alternatives.truncate(limit);
+    alternatives
+}
+
 /// A suggestion for how to grant access.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AccessSuggestion {
@@ -393,12 +575,38 @@ pub struct AccessSuggestion {
     pub description: String,
     /// Impact of adding this relationship (e.g., "low", "medium", "high").
     pub impact: Option<String>,
+    /// Other relationships that must be written together with this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub also_requires: Vec<String>,
 }
 
 impl AccessSuggestion {
     /// Creates a new access suggestion.
     pub fn new(relationship: impl Into<String>, description: impl Into<String>) -> Self {
-        Self { relationship: relationship.into(), description: description.into(), impact: None }
+        Self {
+            relationship: relationship.into(),
+            description: description.into(),
+            impact: None,
+            also_requires: vec![],
+        }
+    }
+
+    /// Creates a suggestion from a set of relationships that grant access together.
+    ///
+    /// Returns `None` if the set is empty.
+    pub fn from_relationships(relationships: &[Relationship<'_>]) -> Option<Self> {
+        let (first, rest) = relationships.split_first()?;
+        let description = if rest.is_empty() {
+            format!("write {} to grant access", first)
+        } else {
+            format!("write {} and {} other relationship(s) to grant access", first, rest.len())
+        };
+        Some(Self {
+            relationship: first.to_string(),
+            description,
+            impact: None,
+            also_requires: rest.iter().map(|r| r.to_string()).collect(),
+        })
     }
 
     /// Sets the impact level.
@@ -411,11 +619,14 @@ impl AccessSuggestion {
 
 impl std::fmt::Display for AccessSuggestion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.relationship)?;
+        for other in &self.also_requires {
+            write!(f, " + {}", other)?;
+        }
         if let Some(ref impact) = self.impact {
-            write!(f, "{} (impact: {}): {}", self.relationship, impact, self.description)
-        } else {
-            write!(f, "{}: {}", self.relationship, self.description)
+            write!(f, " (impact: {})", impact)?;
         }
+        write!(f, ": {}", self.description)
     }
 }
 
@@ -830,4 +1041,166 @@ mod tests {
         let cloned = sugg.clone();
         assert_eq!(sugg, cloned);
     }
+
+    fn direct(resource: &str, relation: &str, subject: &str, result: bool) -> EvaluationNode {
+        EvaluationNode {
+            node_type: EvaluationNodeType::DirectCheck {
+                resource: resource.to_string(),
+                relation: relation.to_string(),
+                subject: subject.to_string(),
+            },
+            result,
+            children: vec![],
+        }
+    }
+
+    fn branch(node_type: EvaluationNodeType, children: Vec<EvaluationNode>) -> EvaluationNode {
+        let result = match node_type {
+            EvaluationNodeType::Intersection => children.iter().all(|c| c.result),
+            EvaluationNodeType::Exclusion => {
+                children.first().is_some_and(|c| c.result)
+                    && children[1..].iter().all(|c| !c.result)
+            },
+            _ => children.iter().any(|c| c.result),
+        };
+        EvaluationNode { node_type, result, children }
+    }
+
+    #[test]
+    fn test_denial_reasons_from_allowed_tree() {
+        let root = direct("doc:1", "viewer", "user:alice", true);
+        assert!(DenialReason::from_tree(&root).is_empty());
+    }
+
+    #[test]
+    fn test_denial_reasons_missing_relationships() {
+        let root = branch(
+            EvaluationNodeType::Union,
+            vec![
+                direct("doc:1", "viewer", "user:bob", false),
+                branch(
+                    EvaluationNodeType::ComputedUserset { relation: "editor".to_string() },
+                    vec![direct("doc:1", "editor", "user:bob", false)],
+                ),
+            ],
+        );
+
+        assert_eq!(
+            DenialReason::from_tree(&root),
+            vec![
+                DenialReason::missing_relationship("doc:1#viewer@user:bob"),
+                DenialReason::missing_relationship("doc:1#editor@user:bob"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_denial_reasons_exclusion_and_condition() {
+        let excluded = branch(
+            EvaluationNodeType::Exclusion,
+            vec![
+                direct("doc:1", "viewer", "user:bob", true),
+                direct("doc:1", "banned", "user:bob", true),
+            ],
+        );
+        assert_eq!(
+            DenialReason::from_tree(&excluded),
+            vec![DenialReason::ExplicitDeny { relationship: Some("doc:1#banned@user:bob".into()) }]
+        );
+
+        let condition = EvaluationNode {
+            node_type: EvaluationNodeType::WasmModule { module_name: "ip_allowlist".to_string() },
+            result: false,
+            children: vec![],
+        };
+        assert_eq!(
+            DenialReason::from_tree(&condition),
+            vec![DenialReason::condition_failed("wasm:ip_allowlist")]
+        );
+    }
+
+    #[test]
+    fn test_denial_reasons_empty_tree_is_no_path() {
+        let root = branch(EvaluationNodeType::Union, vec![]);
+        assert_eq!(DenialReason::from_tree(&root), vec![DenialReason::no_path()]);
+    }
+
+    #[test]
+    fn test_grant_candidates_minimal_sets() {
+        // view = viewer | (member & approved)
+        let root = branch(
+            EvaluationNodeType::Union,
+            vec![
+                direct("doc:1", "viewer", "user:bob", false),
+                branch(
+                    EvaluationNodeType::Intersection,
+                    vec![
+                        direct("doc:1", "member", "user:bob", false),
+                        direct("doc:1", "approved", "user:bob", false),
+                    ],
+                ),
+            ],
+        );
+
+        let candidates = grant_candidates(&root, MAX_SUGGESTIONS);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0], vec![Relationship::new("doc:1", "viewer", "user:bob")]);
+        assert_eq!(candidates[1].len(), 2);
+    }
+
+    #[test]
+    fn test_grant_candidates_skip_unfixable_branches() {
+        let excluded = branch(
+            EvaluationNodeType::Exclusion,
+            vec![
+                direct("doc:1", "viewer", "user:bob", false),
+                direct("doc:1", "banned", "user:bob", true),
+            ],
+        );
+        assert!(grant_candidates(&excluded, MAX_SUGGESTIONS).is_empty());
+
+        let condition = branch(
+            EvaluationNodeType::Intersection,
+            vec![
+                direct("doc:1", "viewer", "user:bob", false),
+                EvaluationNode {
+                    node_type: EvaluationNodeType::WasmModule { module_name: "hours".to_string() },
+                    result: false,
+                    children: vec![],
+                },
+            ],
+        );
+        assert!(grant_candidates(&condition, MAX_SUGGESTIONS).is_empty());
+    }
+
+    #[test]
+    fn test_access_suggestion_from_relationships() {
+        assert!(AccessSuggestion::from_relationships(&[]).is_none());
+
+        let suggestion = AccessSuggestion::from_relationships(&[
+            Relationship::new("doc:1", "member", "user:bob"),
+            Relationship::new("doc:1", "approved", "user:bob"),
+        ])
+        .unwrap();
+        assert_eq!(suggestion.relationship, "doc:1#member@user:bob");
+        assert_eq!(suggestion.also_requires, vec!["doc:1#approved@user:bob"]);
+        assert!(suggestion.to_string().contains(" + doc:1#approved@user:bob"));
+    }
+
+    #[test]
+    fn test_explanation_trace_stats() {
+        let exp = PermissionExplanation::denied("user:bob", "view", "doc:1");
+        assert!(exp.relationships_read().is_none());
+
+        let exp = exp.with_trace(DecisionTrace {
+            duration_micros: 10,
+            relationships_read: 4,
+            relations_evaluated: 2,
+            root: Some(direct("doc:1", "viewer", "user:bob", false)),
+        });
+        assert_eq!(exp.relationships_read(), Some(4));
+        assert_eq!(exp.relations_evaluated(), Some(2));
+        assert!(exp.evaluation_tree().is_some());
+        assert!(exp.to_string().contains("4 relationship(s) read"));
+    }
 }
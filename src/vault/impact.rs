@@ -0,0 +1,779 @@
+//! Permission impact analysis for relationship changes.
+//!
+//! This module computes the "blast radius" of a set of proposed relationship
+//! additions and removals: every (subject, permission, resource) decision that
+//! would flip if the changes were written.
+
+use std::{collections::BTreeSet, future::Future, pin::Pin};
+
+use serde::{Deserialize, Serialize};
+
+use super::simulate::{SimulationChange, SimulationCheck, SimulationDiff};
+#[cfg(feature = "tracing")]
+use crate::tracing_support::TraceContext;
+use crate::{Error, client::TraceScope, types::Relationship};
+
+/// Default number of subjects and resources enumerated per query.
+const DEFAULT_LIMIT: u32 = 100;
+
+/// Default maximum number of decisions evaluated per analysis.
+const DEFAULT_MAX_CHECKS: usize = 1000;
+
+/// Default number of levels of inheriting objects enumerated.
+const DEFAULT_DEPTH: usize = 3;
+
+/// Builder for permission impact analysis.
+///
+/// Candidate subjects and resources are enumerated from the current state of
+/// the vault, per permission:
+///
+/// - **Subjects**: the subjects of the changed relationships, members of any
+///   changed subject sets (e.g. `team:engineering#member`), and subjects that
+///   currently hold the permission on a changed resource.
+/// - **Resources**: the changed resources, objects that inherit from them
+///   (relationships whose subject is a changed resource, such as the
+///   documents whose `parent` is a changed folder, followed
+///   [`depth`](Self::depth) levels down), and resources the changed subjects
+///   can currently reach with the permission.
+///
+/// Every candidate pair is then evaluated with
+/// [`SimulateBuilder::checks`](super::SimulateBuilder::checks), and only the
+/// decisions that flip are reported. The gRPC API cannot simulate removals,
+/// so reports with removals are simulated over REST when the client prefers
+/// gRPC, and fail with [`TransportStrategy::GrpcOnly`](crate::TransportStrategy::GrpcOnly).
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// let report = vault
+///     .impact()
+///     .add_relationship(Relationship::new("folder:design", "viewer", "team:engineering#member"))
+///     .permissions(["view", "edit"])
+///     .await?;
+///
+/// for change in report.now_allowed() {
+///     println!("{} gains {} on {}", change.subject, change.permission, change.resource);
+/// }
+///
+/// if report.truncated {
+///     println!("Report is partial; raise the bound to see everything");
+/// }
+/// ```
+pub struct ImpactBuilder {
+    vault: super::VaultClient,
+    additions: Vec<Relationship<'static>>,
+    removals: Vec<Relationship<'static>>,
+    permissions: Vec<String>,
+    subject_type: Option<String>,
+    resource_type: Option<String>,
+    limit: u32,
+    max_checks: usize,
+    depth: usize,
+    trace: TraceScope,
+}
+
+impl ImpactBuilder {
+    /// Creates a new impact analysis builder.
+    pub(crate) fn new(vault: super::VaultClient) -> Self {
+        Self {
+            vault,
+            additions: vec![],
+            removals: vec![],
+            permissions: vec![],
+            subject_type: None,
+            resource_type: None,
+            limit: DEFAULT_LIMIT,
+            max_checks: DEFAULT_MAX_CHECKS,
+            depth: DEFAULT_DEPTH,
+            trace: TraceScope::default(),
+        }
+    }
+
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.vault.client(), context);
+        self
+    }
+
+    /// Adds a proposed relationship.
+    #[must_use]
+    pub fn add_relationship(mut self, relationship: Relationship<'_>) -> Self {
+        self.additions.push(relationship.into_owned());
+        self
+    }
+
+    /// Adds multiple proposed relationships.
+    #[must_use]
+    pub fn add_all<'a>(
+        mut self,
+        relationships: impl IntoIterator<Item = Relationship<'a>>,
+    ) -> Self {
+        self.additions.extend(relationships.into_iter().map(|r| r.into_owned()));
+        self
+    }
+
+    /// Removes an existing relationship.
+    #[must_use]
+    pub fn remove_relationship(mut self, relationship: Relationship<'_>) -> Self {
+        self.removals.push(relationship.into_owned());
+        self
+    }
+
+    /// Removes multiple existing relationships.
+    #[must_use]
+    pub fn remove_all<'a>(
+        mut self,
+        relationships: impl IntoIterator<Item = Relationship<'a>>,
+    ) -> Self {
+        self.removals.extend(relationships.into_iter().map(|r| r.into_owned()));
+        self
+    }
+
+    /// Adds a permission to analyze.
+    #[must_use]
+    pub fn permission(mut self, permission: impl Into<String>) -> Self {
+        self.permissions.push(permission.into());
+        self
+    }
+
+    /// Adds multiple permissions to analyze.
+    #[must_use]
+    pub fn permissions<I, P>(mut self, permissions: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        self.permissions.extend(permissions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Only reports subjects of the given type (e.g., "user").
+    #[must_use]
+    pub fn subject_type(mut self, subject_type: impl Into<String>) -> Self {
+        self.subject_type = Some(subject_type.into());
+        self
+    }
+
+    /// Only reports resources of the given type (e.g., "document").
+    #[must_use]
+    pub fn resource_type(mut self, resource_type: impl Into<String>) -> Self {
+        self.resource_type = Some(resource_type.into());
+        self
+    }
+
+    /// Sets the maximum number of subjects or resources enumerated per query.
+    ///
+    /// Defaults to 100. When any query reaches the bound, the report is
+    /// marked as [`truncated`](ImpactReport::truncated).
+    #[must_use]
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = limit.max(1);
+        self
+    }
+
+    /// Sets the maximum number of decisions evaluated.
+    ///
+    /// Defaults to 1000. Candidate pairs beyond the bound are skipped and
+    /// the report is marked as [`truncated`](ImpactReport::truncated).
+    #[must_use]
+    pub fn max_checks(mut self, max_checks: usize) -> Self {
+        self.max_checks = max_checks;
+        self
+    }
+
+    /// Sets how many levels of inheriting objects are enumerated below each
+    /// changed resource.
+    ///
+    /// Defaults to 3. When objects exist below the last level, the report is
+    /// marked as [`truncated`](ImpactReport::truncated).
+    #[must_use]
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    async fn execute(self) -> Result<ImpactReport, Error> {
+        if self.permissions.is_empty() {
+            return Err(Error::invalid_argument(
+                "impact analysis requires at least one permission",
+            ));
+        }
+
+        let mut report = ImpactReport::default();
+        if self.additions.is_empty() && self.removals.is_empty() {
+            return Ok(report);
+        }
+
+        let changed: Vec<&Relationship<'static>> =
+            self.additions.iter().chain(self.removals.iter()).collect();
+
+        let mut checks = Vec::new();
+        for permission in &self.permissions {
+            let (subjects, resources) = self.candidates(&changed, permission, &mut report).await?;
+
+            for subject in &subjects {
+                for resource in &resources {
+                    if checks.len() >= self.max_checks {
+                        report.truncated = true;
+                        break;
+                    }
+                    checks.push(SimulationCheck::new(
+                        subject.clone(),
+                        permission.clone(),
+                        resource.clone(),
+                    ));
+                }
+            }
+        }
+
+        report.evaluated = checks.len();
+        if checks.is_empty() {
+            return Ok(report);
+        }
+
+        let diffs = self
+            .vault
+            .simulate()
+            .add_all(self.additions.iter().cloned())
+            .remove_all(self.removals.iter().cloned())
+            .checks(checks)
+            .await?;
+
+        report.changes = diffs.into_iter().filter(SimulationDiff::has_change).collect();
+        Ok(report)
+    }
+
+    /// Enumerates candidate subjects and resources for one permission.
+    async fn candidates(
+        &self,
+        changed: &[&Relationship<'static>],
+        permission: &str,
+        report: &mut ImpactReport,
+    ) -> Result<(BTreeSet<String>, BTreeSet<String>), Error> {
+        let mut subjects = BTreeSet::new();
+        let mut resources = BTreeSet::new();
+
+        for relationship in changed {
+            let subject = relationship.subject();
+            let resource = relationship.resource();
+
+            match subject.split_once('#') {
+                // Subject sets expand to their current members
+                Some((object, relation)) => {
+                    subjects.extend(self.list_subjects(relation, object, report).await?);
+                },
+                None => {
+                    subjects.insert(subject.to_string());
+                },
+            }
+            subjects.extend(self.list_subjects(permission, resource, report).await?);
+
+            resources.insert(resource.to_string());
+            resources.extend(
+                self.descendants(resource, &[relationship.relation(), permission], report).await?,
+            );
+            resources.extend(self.list_resources(subject, permission, report).await?);
+        }
+
+        subjects.retain(|s| {
+            !s.contains('#') && self.subject_type.as_deref().is_none_or(|t| has_type(s, t))
+        });
+        resources.retain(|r| self.resource_type.as_deref().is_none_or(|t| has_type(r, t)));
+
+        Ok((subjects, resources))
+    }
+
+    /// Returns the objects below `resource`: those related to it directly
+    /// (`document:1#parent@folder:design`) or through one of `relations`
+    /// (`document:1#viewer@folder:design#viewer`), recursively up to
+    /// [`depth`](Self::depth) levels.
+    async fn descendants(
+        &self,
+        resource: &str,
+        relations: &[&str],
+        report: &mut ImpactReport,
+    ) -> Result<BTreeSet<String>, Error> {
+        let mut found = BTreeSet::new();
+        let mut frontier = vec![resource.to_string()];
+        for level in 0..=self.depth {
+            let mut next = Vec::new();
+            for object in &frontier {
+                let subjects = std::iter::once(object.clone())
+                    .chain(relations.iter().map(|relation| format!("{}#{}", object, relation)));
+                for subject in subjects {
+                    for child in self.list_related(&subject, report).await? {
+                        if child != resource && !found.contains(&child) {
+                            next.push(child);
+                        }
+                    }
+                }
+            }
+            next.sort();
+            next.dedup();
+            if next.is_empty() {
+                break;
+            }
+            if level == self.depth {
+                // Objects remain below the last level enumerated
+                report.truncated = true;
+                break;
+            }
+            found.extend(next.iter().cloned());
+            frontier = next;
+        }
+        Ok(found)
+    }
+
+    /// Returns the resources of relationships whose subject is `subject`.
+    async fn list_related(
+        &self,
+        subject: &str,
+        report: &mut ImpactReport,
+    ) -> Result<Vec<String>, Error> {
+        let page =
+            self.vault.relationships().list().subject(subject).limit(self.limit as usize).await?;
+
+        let resources = page.relationships.iter().map(|r| r.resource().to_string()).collect();
+        Ok(self.bounded(resources, page.next_cursor.is_some(), report))
+    }
+
+    async fn list_subjects(
+        &self,
+        permission: &str,
+        resource: &str,
+        report: &mut ImpactReport,
+    ) -> Result<Vec<String>, Error> {
+        let page = self
+            .vault
+            .subjects()
+            .with_permission(permission)
+            .on_resource(resource)
+            .page_size(self.limit)
+            .cursor(None)
+            .await?;
+
+        Ok(self.bounded(page.subjects, page.next_cursor.is_some(), report))
+    }
+
+    async fn list_resources(
+        &self,
+        subject: &str,
+        permission: &str,
+        report: &mut ImpactReport,
+    ) -> Result<Vec<String>, Error> {
+        let page = self
+            .vault
+            .resources()
+            .accessible_by(subject)
+            .with_permission(permission)
+            .page_size(self.limit)
+            .cursor(None)
+            .await?;
+
+        Ok(self.bounded(page.resources, page.next_cursor.is_some(), report))
+    }
+
+    fn bounded(
+        &self,
+        mut items: Vec<String>,
+        has_more: bool,
+        report: &mut ImpactReport,
+    ) -> Vec<String> {
+        if has_more || items.len() > self.limit as usize {
+            report.truncated = true;
+            items.truncate(self.limit as usize);
+        }
+        items
+    }
+}
+
+/// Returns true if `entity` (e.g. `"user:alice"`) has the given type.
+fn has_type(entity: &str, entity_type: &str) -> bool {
+    entity.split_once(':').is_some_and(|(t, _)| t == entity_type)
+}
+
+/// Enables ergonomic `.await` without explicit `.build()`.
+///
+/// This `IntoFuture` implementation is intentionally manual (not derived via `bon`)
+/// to preserve the ergonomic async API: `vault.impact()...await`
+/// instead of `vault.impact()...build().await`.
+impl std::future::IntoFuture for ImpactBuilder {
+    type Output = Result<ImpactReport, Error>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.trace.clone().run(self.execute()))
+    }
+}
+
+/// Decisions that would flip if a set of relationship changes were applied.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImpactReport {
+    /// Decisions that change, in evaluation order.
+    pub changes: Vec<SimulationDiff>,
+    /// Number of (subject, permission, resource) decisions evaluated.
+    pub evaluated: usize,
+    /// Whether enumeration hit a bound, so the report may be incomplete.
+    pub truncated: bool,
+}
+
+impl ImpactReport {
+    /// Returns true if no decision would change.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the number of decisions that would change.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns the decisions that would become allowed.
+    pub fn now_allowed(&self) -> impl Iterator<Item = &SimulationDiff> {
+        self.changes.iter().filter(|d| d.change == SimulationChange::NowAllowed)
+    }
+
+    /// Returns the decisions that would become denied.
+    pub fn now_denied(&self) -> impl Iterator<Item = &SimulationDiff> {
+        self.changes.iter().filter(|d| d.change == SimulationChange::NowDenied)
+    }
+
+    /// Returns the distinct subjects whose access would change.
+    pub fn affected_subjects(&self) -> BTreeSet<&str> {
+        self.changes.iter().map(|d| d.subject.as_str()).collect()
+    }
+
+    /// Returns the distinct resources whose access would change.
+    pub fn affected_resources(&self) -> BTreeSet<&str> {
+        self.changes.iter().map(|d| d.resource.as_str()).collect()
+    }
+}
+
+impl std::fmt::Display for ImpactReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Impact: {} gained, {} lost ({} decisions evaluated{})",
+            self.now_allowed().count(),
+            self.now_denied().count(),
+            self.evaluated,
+            if self.truncated { ", truncated" } else { "" }
+        )?;
+        for diff in &self.changes {
+            writeln!(f, "  {}", diff.summary())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{auth::BearerCredentialsConfig, client::Client, transport::mock::MockTransport};
+
+    async fn vault_with(relationships: &[Relationship<'static>]) -> super::super::VaultClient {
+        let mock = MockTransport::new();
+        for relationship in relationships {
+            mock.add_relationship(relationship.clone());
+        }
+        let client = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .build_with_transport(Arc::new(mock.into_any()))
+            .await
+            .unwrap();
+        client.organization("org_test").vault("vlt_test")
+    }
+
+    fn diff(subject: &str, resource: &str, change: SimulationChange) -> SimulationDiff {
+        SimulationDiff {
+            subject: subject.to_string(),
+            permission: "viewer".to_string(),
+            resource: resource.to_string(),
+            current_allowed: change == SimulationChange::NowDenied,
+            simulated_allowed: change == SimulationChange::NowAllowed,
+            change,
+            hypothetical_additions: vec![],
+            hypothetical_removals: vec![],
+            context: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_impact_requires_permission() {
+        let vault = vault_with(&[]).await;
+        let err = vault
+            .impact()
+            .add_relationship(Relationship::new("doc:1", "viewer", "user:alice"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_impact_no_changes() {
+        let vault = vault_with(&[]).await;
+        let report = vault.impact().permission("viewer").await.unwrap();
+        assert!(report.is_empty());
+        assert_eq!(report.evaluated, 0);
+    }
+
+    #[cfg(feature = "rest")]
+    #[tokio::test]
+    async fn test_impact_reports_flipped_decisions() {
+        let vault = vault_with(&[
+            Relationship::new("doc:1", "viewer", "user:alice").into_owned(),
+            Relationship::new("doc:1", "viewer", "user:bob").into_owned(),
+        ])
+        .await;
+
+        let report = vault
+            .impact()
+            .remove_relationship(Relationship::new("doc:1", "viewer", "user:alice"))
+            .add_relationship(Relationship::new("doc:2", "viewer", "user:carol"))
+            .permission("viewer")
+            .await
+            .unwrap();
+
+        let lost: Vec<_> = report.now_denied().map(|d| (&*d.subject, &*d.resource)).collect();
+        let gained: Vec<_> = report.now_allowed().map(|d| (&*d.subject, &*d.resource)).collect();
+        assert_eq!(lost, vec![("user:alice", "doc:1")]);
+        assert_eq!(gained, vec![("user:carol", "doc:2")]);
+        assert!(!report.affected_subjects().contains("user:bob"));
+        assert!(report.evaluated >= 2);
+        assert!(!report.truncated);
+    }
+
+    #[cfg(feature = "rest")]
+    #[tokio::test]
+    async fn test_impact_max_checks_truncates() {
+        let vault = vault_with(&[
+            Relationship::new("doc:1", "viewer", "user:alice").into_owned(),
+            Relationship::new("doc:1", "viewer", "user:bob").into_owned(),
+        ])
+        .await;
+
+        let report = vault
+            .impact()
+            .remove_relationship(Relationship::new("doc:1", "viewer", "user:alice"))
+            .permission("viewer")
+            .max_checks(1)
+            .await
+            .unwrap();
+
+        assert_eq!(report.evaluated, 1);
+        assert!(report.truncated);
+    }
+
+    #[test]
+    fn test_impact_report_accessors() {
+        let report = ImpactReport {
+            changes: vec![
+                diff("user:alice", "doc:1", SimulationChange::NowDenied),
+                diff("user:bob", "doc:2", SimulationChange::NowAllowed),
+                diff("user:bob", "doc:3", SimulationChange::NowAllowed),
+            ],
+            evaluated: 10,
+            truncated: true,
+        };
+
+        assert_eq!(report.len(), 3);
+        assert_eq!(report.now_allowed().count(), 2);
+        assert_eq!(report.now_denied().count(), 1);
+        assert_eq!(report.affected_subjects().len(), 2);
+        assert_eq!(report.affected_resources().len(), 3);
+
+        let display = report.to_string();
+        assert!(display.contains("2 gained, 1 lost"));
+        assert!(display.contains("truncated"));
+    }
+
+    #[test]
+    fn test_has_type() {
+        assert!(has_type("user:alice", "user"));
+        assert!(!has_type("user:alice", "team"));
+        assert!(!has_type("alice", "user"));
+    }
+}
+
+#[cfg(all(test, feature = "rest"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod wiremock_tests {
+    use wiremock::{
+        Mock, MockServer, Request, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    use super::*;
+    use crate::{Client, auth::BearerCredentialsConfig};
+
+    /// Members of `team:eng`, and the documents filed under `folder:design`.
+    const MEMBERS: [&str; 2] = ["user:alice", "user:bob"];
+    const DOCUMENTS: [&str; 2] = ["document:1", "document:2"];
+
+    fn sse(items: impl IntoIterator<Item = serde_json::Value>) -> ResponseTemplate {
+        let body: String = items.into_iter().map(|item| format!("data: {}\n\n", item)).collect();
+        ResponseTemplate::new(200)
+            .insert_header("content-type", "text/event-stream")
+            .set_body_string(body)
+    }
+
+    fn body(request: &Request) -> serde_json::Value {
+        serde_json::from_slice(&request.body).unwrap()
+    }
+
+    /// Serves a vault where `folder:design` has two documents and `team:eng`
+    /// two members. Nobody can currently view anything; with the team granted
+    /// `viewer` on the folder, members can view the folder and its documents.
+    async fn server() -> MockServer {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/access/v1/relationships/list"))
+            .respond_with(|request: &Request| {
+                let children = if body(request)["subject"] == "folder:design" {
+                    DOCUMENTS.to_vec()
+                } else {
+                    vec![]
+                };
+                sse(children.into_iter().map(|doc| {
+                    serde_json::json!({ "resource": doc, "relation": "parent", "subject": "folder:design" })
+                }))
+            })
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/access/v1/subjects/list"))
+            .respond_with(|request: &Request| {
+                let request = body(request);
+                let members =
+                    if request["resource"] == "team:eng" && request["relation"] == "member" {
+                        MEMBERS.to_vec()
+                    } else {
+                        vec![]
+                    };
+                sse(members.into_iter().map(|m| serde_json::json!(m)))
+            })
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/access/v1/resources/list"))
+            .respond_with(sse([]))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/access/v1/evaluate"))
+            .respond_with(|request: &Request| {
+                let count = body(request)["evaluations"].as_array().unwrap().len();
+                sse((0..count)
+                    .map(|index| serde_json::json!({ "decision": "deny", "index": index })))
+            })
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/access/v1/simulate/batch"))
+            .respond_with(|request: &Request| {
+                let request = body(request);
+                let granted = !request["additions"].is_null();
+                let results: Vec<_> = request["evaluations"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, check)| {
+                        let subject = check["subject"].as_str().unwrap();
+                        let resource = check["resource"].as_str().unwrap();
+                        let allowed = granted
+                            && MEMBERS.contains(&subject)
+                            && (resource == "folder:design" || DOCUMENTS.contains(&resource));
+                        serde_json::json!({ "index": index, "allowed": allowed })
+                    })
+                    .collect();
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "results": results }))
+            })
+            .mount(&server)
+            .await;
+
+        server
+    }
+
+    async fn vault(server: &MockServer) -> super::super::VaultClient {
+        Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test_token"))
+            .transport_strategy(crate::TransportStrategy::RestOnly)
+            .build()
+            .await
+            .unwrap()
+            .organization("org_test")
+            .vault("vlt_test")
+    }
+
+    fn gained(report: &ImpactReport) -> Vec<(&str, &str)> {
+        report.now_allowed().map(|d| (d.subject.as_str(), d.resource.as_str())).collect()
+    }
+
+    #[tokio::test]
+    async fn test_impact_expands_subject_sets() {
+        let server = server().await;
+        let report = vault(&server)
+            .await
+            .impact()
+            .add_relationship(Relationship::new("folder:design", "viewer", "team:eng#member"))
+            .permission("view")
+            .subject_type("user")
+            .resource_type("folder")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            gained(&report),
+            [("user:alice", "folder:design"), ("user:bob", "folder:design")]
+        );
+        assert_eq!(report.now_denied().count(), 0);
+        assert_eq!(report.evaluated, 2);
+    }
+
+    #[tokio::test]
+    async fn test_impact_follows_inheriting_objects() {
+        let server = server().await;
+        let report = vault(&server)
+            .await
+            .impact()
+            .add_relationship(Relationship::new("folder:design", "viewer", "team:eng#member"))
+            .permission("view")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            gained(&report),
+            [
+                ("user:alice", "document:1"),
+                ("user:alice", "document:2"),
+                ("user:alice", "folder:design"),
+                ("user:bob", "document:1"),
+                ("user:bob", "document:2"),
+                ("user:bob", "folder:design"),
+            ]
+        );
+        assert!(!report.truncated);
+
+        // Without descending, the documents are not evaluated
+        let shallow = vault(&server)
+            .await
+            .impact()
+            .add_relationship(Relationship::new("folder:design", "viewer", "team:eng#member"))
+            .permission("view")
+            .depth(0)
+            .await
+            .unwrap();
+        assert_eq!(shallow.affected_resources().into_iter().collect::<Vec<_>>(), ["folder:design"]);
+        assert!(shallow.truncated);
+    }
+}
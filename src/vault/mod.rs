@@ -6,8 +6,10 @@
 //! - [`explain_permission()`](VaultClient::explain_permission): Explain why access is
 //!   allowed/denied
 //! - [`simulate()`](VaultClient::simulate): Test hypothetical changes
+//! - [`impact()`](VaultClient::impact): Preview who gains or loses access from a change
 //! - [`watch()`](VaultClient::watch): Subscribe to relationship changes
 //! - [`relationships()`](VaultClient::relationships): Manage relationships
+//! - [`session()`](VaultClient::session): Read your own writes without threading tokens
 //!
 //! ## Quick Start
 //!
@@ -48,6 +50,9 @@
 
 mod client;
 mod explain;
+mod impact;
+mod render;
+mod session;
 mod simulate;
 pub mod watch;
 
@@ -55,9 +60,11 @@ pub use client::VaultClient;
 pub use explain::{
     AccessSuggestion, DenialReason, ExplainBuilder, PathNode, PermissionExplanation,
 };
+pub use impact::{ImpactBuilder, ImpactReport};
+pub use session::ConsistencySession;
 pub use simulate::{
-    SimulateBuilder, SimulateCheckBuilder, SimulateCompareBuilder, SimulationChange,
-    SimulationDiff, SimulationResult,
+    SimulateBuilder, SimulateCheckBuilder, SimulateCompareBuilder, SimulateMatrixBuilder,
+    SimulationChange, SimulationCheck, SimulationDiff, SimulationResult,
 };
 pub use watch::{
     Operation, ReconnectConfig, WatchBuilder, WatchEvent, WatchFilter, WatchShutdownHandle,
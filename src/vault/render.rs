@@ -0,0 +1,415 @@
+//! Renderers for permission explanations and decision traces.
+//!
+//! Explanations and evaluation trees can be rendered as:
+//!
+//! - an indented Unicode tree for terminals ([`render_tree`](EvaluationNode::render_tree))
+//! - Graphviz DOT, with allowed and denied branches styled differently
+//!   ([`to_dot`](EvaluationNode::to_dot))
+//! - JSON using the types' serde representation ([`to_json`](EvaluationNode::to_json))
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let explanation = vault
+//!     .explain_permission()
+//!     .subject("user:bob")
+//!     .permission("view")
+//!     .resource("doc:readme")
+//!     .await?;
+//!
+//! println!("{}", explanation.render_tree());
+//! std::fs::write("trace.dot", explanation.to_dot())?;
+//! ```
+
+use std::fmt::Write as _;
+
+use super::explain::PermissionExplanation;
+use crate::{
+    Error,
+    transport::{DecisionTrace, EvaluationNode},
+};
+
+/// Style for allowed nodes and edges in DOT output.
+const DOT_ALLOWED: &str = "color=\"darkgreen\", fontcolor=\"darkgreen\"";
+
+/// Style for denied nodes and edges in DOT output.
+const DOT_DENIED: &str = "color=\"red\", fontcolor=\"red\", style=\"dashed\"";
+
+impl EvaluationNode {
+    /// Returns the display label for this node, e.g. `doc:1#viewer@user:bob ✗`.
+    pub fn label(&self) -> String {
+        format!("{} {}", self.node_type, mark(self.result))
+    }
+
+    /// Renders this subtree as an indented Unicode tree.
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// union ✓
+    /// ├── doc:readme#editor@user:alice ✗
+    /// └── doc:readme#owner@user:alice ✓
+    /// ```
+    pub fn render_tree(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&self.label());
+        out.push('\n');
+        write_children(&mut out, self, "");
+        out
+    }
+
+    /// Renders this subtree as a Graphviz DOT digraph.
+    ///
+    /// Allowed branches are drawn solid green; denied branches are drawn
+    /// dashed red.
+    pub fn to_dot(&self) -> String {
+        let mut out = dot_header(None);
+        let mut next_id = 0;
+        write_dot_node(&mut out, self, &mut next_id);
+        out.push_str("}\n");
+        out
+    }
+
+    /// Serializes this subtree as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl DecisionTrace {
+    /// Renders the evaluation tree as an indented Unicode tree.
+    ///
+    /// Returns a placeholder line if the trace has no tree.
+    pub fn render_tree(&self) -> String {
+        match self.root {
+            Some(ref root) => root.render_tree(),
+            None => "(no evaluation tree)\n".to_string(),
+        }
+    }
+
+    /// Renders the evaluation tree as a Graphviz DOT digraph.
+    ///
+    /// The graph label carries the evaluation statistics.
+    pub fn to_dot(&self) -> String {
+        let title = format!(
+            "{}us, {} relationship(s) read, {} relation(s) evaluated",
+            self.duration_micros, self.relationships_read, self.relations_evaluated
+        );
+        let mut out = dot_header(Some(&title));
+        if let Some(ref root) = self.root {
+            let mut next_id = 0;
+            write_dot_node(&mut out, root, &mut next_id);
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Serializes the trace as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl PermissionExplanation {
+    /// Renders the explanation as an indented Unicode tree.
+    ///
+    /// Uses the full evaluation tree when a trace is available, and the
+    /// granting paths otherwise.
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// user:alice edit document:readme (ALLOWED)
+    /// └── union ✓
+    ///     ├── document:readme#editor@user:alice ✗
+    ///     └── document:readme#owner@user:alice ✓
+    /// ```
+    pub fn render_tree(&self) -> String {
+        let mut out = format!("{} ({})\n", self.header(), verdict(self.allowed));
+
+        if let Some(root) = self.evaluation_tree() {
+            let _ = writeln!(out, "└── {}", root.label());
+            write_children(&mut out, root, "    ");
+        } else {
+            let branches: Vec<String> = self
+                .paths
+                .iter()
+                .map(|path| format!("{} ✓", self.format_path(path)))
+                .chain(self.denial_reasons.iter().map(|reason| format!("{} ✗", reason)))
+                .collect();
+            for (i, branch) in branches.iter().enumerate() {
+                let connector = if i + 1 == branches.len() { "└── " } else { "├── " };
+                let _ = writeln!(out, "{}{}", connector, branch);
+            }
+        }
+
+        out
+    }
+
+    /// Renders the explanation as a Graphviz DOT digraph.
+    ///
+    /// Uses the full evaluation tree when a trace is available, and draws
+    /// each granting path as a chain of entities otherwise.
+    pub fn to_dot(&self) -> String {
+        let title = format!("{} ({})", self.header(), verdict(self.allowed));
+        let mut out = dot_header(Some(&title));
+
+        if let Some(root) = self.evaluation_tree() {
+            let mut next_id = 0;
+            write_dot_node(&mut out, root, &mut next_id);
+        } else {
+            let mut next_id = 0;
+            for path in &self.paths {
+                let mut previous: Option<usize> = None;
+                for node in path {
+                    let id = next_id;
+                    next_id += 1;
+                    let _ = writeln!(
+                        out,
+                        "  n{} [label=\"{}\", {}];",
+                        id,
+                        escape_dot(&node.to_string()),
+                        DOT_ALLOWED
+                    );
+                    if let Some(from) = previous {
+                        let _ = writeln!(out, "  n{} -> n{} [{}];", from, id, DOT_ALLOWED);
+                    }
+                    previous = Some(id);
+                }
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Serializes the explanation as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    fn header(&self) -> String {
+        format!("{} {} {}", self.subject, self.permission, self.resource)
+    }
+}
+
+fn mark(result: bool) -> &'static str {
+    if result { "✓" } else { "✗" }
+}
+
+fn verdict(allowed: bool) -> &'static str {
+    if allowed { "ALLOWED" } else { "DENIED" }
+}
+
+/// Writes the children of `node` with box-drawing connectors.
+fn write_children(out: &mut String, node: &EvaluationNode, prefix: &str) {
+    let count = node.children.len();
+    for (i, child) in node.children.iter().enumerate() {
+        let last = i + 1 == count;
+        let connector = if last { "└── " } else { "├── " };
+        let _ = writeln!(out, "{}{}{}", prefix, connector, child.label());
+        let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        write_children(out, child, &child_prefix);
+    }
+}
+
+fn dot_header(title: Option<&str>) -> String {
+    let mut out = String::from("digraph trace {\n");
+    out.push_str("  rankdir=TB;\n");
+    out.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+    if let Some(title) = title {
+        let _ = writeln!(out, "  label=\"{}\";", escape_dot(title));
+        out.push_str("  labelloc=t;\n");
+    }
+    out
+}
+
+/// Writes `node` and its subtree, returning the node's id.
+fn write_dot_node(out: &mut String, node: &EvaluationNode, next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+    let style = if node.result { DOT_ALLOWED } else { DOT_DENIED };
+    let _ = writeln!(out, "  n{} [label=\"{}\", {}];", id, escape_dot(&node.label()), style);
+
+    for child in &node.children {
+        let child_id = write_dot_node(out, child, next_id);
+        let edge_style = if child.result { DOT_ALLOWED } else { DOT_DENIED };
+        let _ = writeln!(out, "  n{} -> n{} [{}];", id, child_id, edge_style);
+    }
+    id
+}
+
+/// Escapes a string for use inside a quoted DOT label.
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::{
+        transport::EvaluationNodeType,
+        vault::{DenialReason, PathNode},
+    };
+
+    fn direct(relation: &str, result: bool) -> EvaluationNode {
+        EvaluationNode {
+            node_type: EvaluationNodeType::DirectCheck {
+                resource: "document:readme".to_string(),
+                relation: relation.to_string(),
+                subject: "user:alice".to_string(),
+            },
+            result,
+            children: vec![],
+        }
+    }
+
+    fn sample_tree() -> EvaluationNode {
+        EvaluationNode {
+            node_type: EvaluationNodeType::Union,
+            result: true,
+            children: vec![
+                EvaluationNode {
+                    node_type: EvaluationNodeType::ComputedUserset {
+                        relation: "editor".to_string(),
+                    },
+                    result: false,
+                    children: vec![direct("editor", false)],
+                },
+                direct("owner", true),
+            ],
+        }
+    }
+
+    fn sample_trace() -> DecisionTrace {
+        DecisionTrace {
+            duration_micros: 120,
+            relationships_read: 2,
+            relations_evaluated: 3,
+            root: Some(sample_tree()),
+        }
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let expected = "\
+union ✓
+├── computed editor ✗
+│   └── document:readme#editor@user:alice ✗
+└── document:readme#owner@user:alice ✓
+";
+        assert_eq!(sample_tree().render_tree(), expected);
+        assert_eq!(sample_trace().render_tree(), expected);
+    }
+
+    #[test]
+    fn test_render_tree_without_root() {
+        let trace = DecisionTrace { root: None, ..sample_trace() };
+        assert_eq!(trace.render_tree(), "(no evaluation tree)\n");
+    }
+
+    #[test]
+    fn test_to_dot_styles_branches() {
+        let dot = sample_trace().to_dot();
+        assert!(dot.starts_with("digraph trace {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("label=\"120us, 2 relationship(s) read, 3 relation(s) evaluated\""));
+        assert!(dot.contains(&format!("n0 [label=\"union ✓\", {}];", DOT_ALLOWED)));
+        assert!(dot.contains(&format!("n1 [label=\"computed editor ✗\", {}];", DOT_DENIED)));
+        assert!(dot.contains(&format!("n0 -> n1 [{}];", DOT_DENIED)));
+        assert!(dot.contains(&format!("n0 -> n3 [{}];", DOT_ALLOWED)));
+    }
+
+    #[test]
+    fn test_escape_dot() {
+        assert_eq!(escape_dot(r#"a"b\c"#), r#"a\"b\\c"#);
+    }
+
+    #[test]
+    fn test_trace_json_schema() {
+        let trace = DecisionTrace {
+            duration_micros: 5,
+            relationships_read: 1,
+            relations_evaluated: 1,
+            root: Some(EvaluationNode {
+                node_type: EvaluationNodeType::Exclusion,
+                result: false,
+                children: vec![direct("viewer", false)],
+            }),
+        };
+
+        let value: serde_json::Value = serde_json::from_str(&trace.to_json().unwrap()).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "duration_micros": 5,
+                "relationships_read": 1,
+                "relations_evaluated": 1,
+                "root": {
+                    "node_type": { "type": "exclusion" },
+                    "result": false,
+                    "children": [{
+                        "node_type": {
+                            "type": "direct_check",
+                            "resource": "document:readme",
+                            "relation": "viewer",
+                            "subject": "user:alice"
+                        },
+                        "result": false
+                    }]
+                }
+            })
+        );
+
+        let roundtrip: DecisionTrace = serde_json::from_value(value).unwrap();
+        assert_eq!(roundtrip, trace);
+    }
+
+    #[test]
+    fn test_explanation_render_tree_with_trace() {
+        let explanation = PermissionExplanation::allowed("user:alice", "edit", "document:readme")
+            .with_trace(sample_trace());
+
+        let rendered = explanation.render_tree();
+        assert!(rendered.starts_with("user:alice edit document:readme (ALLOWED)\n└── union ✓\n"));
+        assert!(rendered.contains("    ├── computed editor ✗\n    │   └── "));
+    }
+
+    #[test]
+    fn test_explanation_render_tree_without_trace() {
+        let explanation = PermissionExplanation::denied("user:bob", "edit", "document:readme")
+            .with_denial_reason(DenialReason::missing_relationship(
+                "document:readme#editor@user:bob",
+            ));
+
+        assert_eq!(
+            explanation.render_tree(),
+            "user:bob edit document:readme (DENIED)\n\
+             └── missing relationship document:readme#editor@user:bob ✗\n"
+        );
+    }
+
+    #[test]
+    fn test_explanation_to_dot_from_paths() {
+        let explanation =
+            PermissionExplanation::allowed("user:alice", "view", "doc:1").with_path(vec![
+                PathNode::new("user:alice").with_relation("viewer"),
+                PathNode::new("doc:1"),
+            ]);
+
+        let dot = explanation.to_dot();
+        assert!(dot.contains("label=\"user:alice view doc:1 (ALLOWED)\""));
+        assert!(dot.contains("n0 [label=\"user:alice#viewer\""));
+        assert!(dot.contains("n0 -> n1"));
+    }
+
+    #[test]
+    fn test_explanation_to_json() {
+        let explanation =
+            PermissionExplanation::denied("user:bob", "view", "doc:1").with_trace(sample_trace());
+        let json = explanation.to_json().unwrap();
+        let parsed: PermissionExplanation = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.trace, Some(sample_trace()));
+    }
+}
@@ -0,0 +1,331 @@
+//! Read-your-writes consistency sessions.
+//!
+//! A [`ConsistencySession`] is a [`VaultClient`] that remembers the newest consistency
+//! token returned by its own writes and deletes, and reads at least as fresh
+//! as that token. It removes the need to thread tokens by hand:
+//!
+//! ```rust,ignore
+//! let session = vault.session();
+//!
+//! session.relationships()
+//!     .write(Relationship::new("doc:1", "viewer", "user:bob"))
+//!     .await?;
+//!
+//! // Sees the share above, even on a lagging replica
+//! assert!(session.check("user:bob", "view", "doc:1").await?);
+//! ```
+//!
+//! Sessions encode to a compact string that can travel in a cookie or header
+//! between services, and resume on the other side with
+//! [`VaultClient::resume_session`].
+
+use std::{borrow::Cow, fmt, sync::Arc};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use parking_lot::RwLock;
+
+use super::{
+    VaultClient,
+    client::{
+        BatchCheckRequest, CheckRequest, ExplainPermissionRequest, RelationshipsClient,
+        ResourcesClient, SubjectsClient,
+    },
+    simulate::SimulateBuilder,
+};
+use crate::{
+    Error,
+    types::{ConsistencyRequirement, ConsistencyToken},
+};
+
+/// Version prefix of the encoded session format.
+const ENCODING_VERSION: &str = "v1";
+
+/// Newest consistency token observed by a session, shared by its clones.
+#[derive(Debug, Default)]
+pub(crate) struct SessionState {
+    latest: RwLock<Option<ConsistencyToken>>,
+}
+
+impl SessionState {
+    /// Creates state that starts at `token`.
+    fn new(token: Option<ConsistencyToken>) -> Self {
+        Self { latest: RwLock::new(token) }
+    }
+
+    /// Returns the newest token observed so far.
+    pub(crate) fn latest(&self) -> Option<ConsistencyToken> {
+        self.latest.read().clone()
+    }
+
+    /// Records a token returned by a write, keeping the newer of it and the
+    /// current one.
+    ///
+    /// Tokens are opaque, so when both are numeric revisions the larger one
+    /// wins, and otherwise the token observed last is kept.
+    pub(crate) fn observe(&self, token: &ConsistencyToken) {
+        if token.is_empty() {
+            return;
+        }
+        let mut latest = self.latest.write();
+        let newer = match latest.as_ref() {
+            Some(current) => token.supersedes(current),
+            None => true,
+        };
+        if newer {
+            *latest = Some(token.clone());
+        }
+    }
+
+    /// Returns the consistency reads in this session require.
+    pub(crate) fn read_consistency(&self) -> ConsistencyRequirement {
+        match self.latest() {
+            Some(token) => ConsistencyRequirement::AtLeastAsFresh(token),
+            None => ConsistencyRequirement::Eventual,
+        }
+    }
+}
+
+/// A vault client with read-your-writes consistency.
+///
+/// Created by [`VaultClient::session()`] or resumed from an encoded string
+/// with [`VaultClient::resume_session()`]. Every write and delete made
+/// through the session records its consistency token, and every check,
+/// lookup, explanation and simulation started from the session reads at
+/// least as fresh as the newest one. An explicit `.consistency()` or
+/// `.at_least_as_fresh()` on a request still takes precedence.
+///
+/// Clones share the same tracked token.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// // Service A: share a document, then hand the session to the client
+/// let session = vault.session();
+/// session.relationships()
+///     .write(Relationship::new("doc:1", "viewer", "user:bob"))
+///     .await?;
+/// response.headers_mut().insert("x-authz-session", session.encode().parse()?);
+///
+/// // Service B: continue the same session from the incoming header
+/// let session = vault.resume_session(request.headers()["x-authz-session"].to_str()?)?;
+/// assert!(session.check("user:bob", "view", "doc:1").await?);
+/// ```
+#[derive(Clone)]
+pub struct ConsistencySession {
+    vault: VaultClient,
+    state: Arc<SessionState>,
+}
+
+impl ConsistencySession {
+    /// Creates a session over `vault` that starts at `token`.
+    pub(crate) fn new(vault: &VaultClient, token: Option<ConsistencyToken>) -> Self {
+        let state = Arc::new(SessionState::new(token));
+        Self { vault: vault.with_session(Arc::clone(&state)), state }
+    }
+
+    /// Resumes a session from a string produced by [`encode`](Self::encode).
+    pub(crate) fn decode(vault: &VaultClient, encoded: &str) -> Result<Self, Error> {
+        let invalid = || Error::invalid_argument(format!("invalid session string: {encoded:?}"));
+
+        let (version, token) = match encoded.split_once('.') {
+            Some((version, token)) => (version, Some(token)),
+            None => (encoded, None),
+        };
+        if version != ENCODING_VERSION {
+            return Err(invalid());
+        }
+        let token = token
+            .map(|token| {
+                let bytes = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+                let value = String::from_utf8(bytes).map_err(|_| invalid())?;
+                value.parse::<ConsistencyToken>().map_err(|_| invalid())
+            })
+            .transpose()?;
+
+        Ok(Self::new(vault, token))
+    }
+
+    /// Encodes the session as a compact string.
+    ///
+    /// The string only uses URL-safe characters, so it can be stored in a
+    /// cookie or header as-is. Resume it with
+    /// [`VaultClient::resume_session()`].
+    pub fn encode(&self) -> String {
+        match self.state.latest() {
+            Some(token) => {
+                format!("{ENCODING_VERSION}.{}", URL_SAFE_NO_PAD.encode(token.value()))
+            },
+            None => ENCODING_VERSION.to_string(),
+        }
+    }
+
+    /// Returns the newest consistency token observed by this session.
+    pub fn latest_token(&self) -> Option<ConsistencyToken> {
+        self.state.latest()
+    }
+
+    /// Returns the consistency this session's reads require.
+    pub fn consistency(&self) -> ConsistencyRequirement {
+        self.state.read_consistency()
+    }
+
+    /// Returns the session-bound vault client.
+    ///
+    /// Every operation on the returned client participates in the session.
+    pub fn vault(&self) -> &VaultClient {
+        &self.vault
+    }
+
+    /// Checks a permission, reading the session's own writes.
+    ///
+    /// See [`VaultClient::check()`].
+    pub fn check<'a>(
+        &self,
+        subject: impl Into<Cow<'a, str>>,
+        permission: impl Into<Cow<'a, str>>,
+        resource: impl Into<Cow<'a, str>>,
+    ) -> CheckRequest<'a> {
+        self.vault.check(subject, permission, resource)
+    }
+
+    /// Checks many permissions, reading the session's own writes.
+    ///
+    /// See [`VaultClient::check_batch()`].
+    pub fn check_batch<'a, I, S, P, R>(&self, checks: I) -> BatchCheckRequest<'a>
+    where
+        I: IntoIterator<Item = (S, P, R)>,
+        S: Into<Cow<'a, str>>,
+        P: Into<Cow<'a, str>>,
+        R: Into<Cow<'a, str>>,
+    {
+        self.vault.check_batch(checks)
+    }
+
+    /// Returns a relationships client whose writes and deletes advance the
+    /// session.
+    pub fn relationships(&self) -> RelationshipsClient {
+        self.vault.relationships()
+    }
+
+    /// Returns a resources client that reads the session's own writes.
+    pub fn resources(&self) -> ResourcesClient<'_> {
+        self.vault.resources()
+    }
+
+    /// Returns a subjects client that reads the session's own writes.
+    pub fn subjects(&self) -> SubjectsClient<'_> {
+        self.vault.subjects()
+    }
+
+    /// Explains a permission, reading the session's own writes.
+    pub fn explain_permission(&self) -> ExplainPermissionRequest {
+        self.vault.explain_permission()
+    }
+
+    /// Simulates changes on top of the session's own writes.
+    pub fn simulate(&self) -> SimulateBuilder {
+        self.vault.simulate()
+    }
+}
+
+impl fmt::Display for ConsistencySession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl fmt::Debug for ConsistencySession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConsistencySession")
+            .field("vault_id", &self.vault.vault_id())
+            .field("latest_token", &self.state.latest())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::{
+        Client, Relationship, auth::BearerCredentialsConfig, transport::mock::MockTransport,
+    };
+
+    async fn create_test_vault() -> VaultClient {
+        let client = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .build_with_transport(Arc::new(MockTransport::new().into_any()))
+            .await
+            .unwrap();
+        client.organization("org").vault("vault")
+    }
+
+    #[test]
+    fn test_state_keeps_newest_revision() {
+        let state = SessionState::default();
+        assert!(state.read_consistency().is_eventual());
+
+        state.observe(&ConsistencyToken::new("42"));
+        state.observe(&ConsistencyToken::new("7"));
+        assert_eq!(state.latest(), Some(ConsistencyToken::new("42")));
+
+        // Opaque tokens can't be ordered, so the last one observed wins
+        state.observe(&ConsistencyToken::new("opaque"));
+        state.observe(&ConsistencyToken::new(""));
+        assert_eq!(
+            state.read_consistency(),
+            ConsistencyRequirement::AtLeastAsFresh(ConsistencyToken::new("opaque"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_tracks_writes_and_deletes() {
+        let vault = create_test_vault().await;
+        let session = vault.session();
+        assert!(session.latest_token().is_none());
+        assert!(session.consistency().is_eventual());
+
+        session
+            .relationships()
+            .write(Relationship::new("doc:1", "viewer", "user:alice"))
+            .await
+            .unwrap();
+        let token = session.latest_token().unwrap();
+        assert_eq!(token, ConsistencyToken::new("mock_token"));
+        assert_eq!(session.consistency(), ConsistencyRequirement::AtLeastAsFresh(token.clone()));
+        assert!(session.check("user:alice", "viewer", "doc:1").await.unwrap());
+
+        // Clones share the tracked token
+        assert_eq!(session.clone().latest_token(), Some(token.clone()));
+
+        let other = vault.session();
+        other
+            .relationships()
+            .delete(Relationship::new("doc:1", "viewer", "user:alice"))
+            .await
+            .unwrap();
+        assert_eq!(other.latest_token(), Some(token));
+    }
+
+    #[tokio::test]
+    async fn test_session_encoding_round_trip() {
+        let vault = create_test_vault().await;
+        let session = vault.session();
+        assert_eq!(session.encode(), "v1");
+        assert!(vault.resume_session("v1").unwrap().latest_token().is_none());
+
+        session.state.observe(&ConsistencyToken::new("rev:123/abc"));
+        let encoded = session.to_string();
+        assert!(encoded.starts_with("v1."));
+        assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c)));
+
+        let resumed = vault.resume_session(&encoded).unwrap();
+        assert_eq!(resumed.latest_token(), Some(ConsistencyToken::new("rev:123/abc")));
+        assert_eq!(resumed.consistency(), session.consistency());
+
+        for bad in ["", "v2", "v2.YWJj", "v1.!!", "v1."] {
+            assert!(vault.resume_session(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+}
@@ -8,7 +8,13 @@ use std::{future::Future, pin::Pin};
 use serde::{Deserialize, Serialize};
 
 use super::explain::PermissionExplanation;
-use crate::{Error, types::Relationship};
+#[cfg(feature = "tracing")]
+use crate::tracing_support::TraceContext;
+use crate::{
+    Error,
+    client::TraceScope,
+    types::{ConsistencyRequirement, Context, Relationship},
+};
 
 /// Builder for what-if/simulation queries.
 ///
@@ -36,12 +42,15 @@ pub struct SimulateBuilder {
     vault: super::VaultClient,
     additions: Vec<Relationship<'static>>,
     removals: Vec<Relationship<'static>>,
+    schema: Option<String>,
+    consistency: ConsistencyRequirement,
 }
 
 impl SimulateBuilder {
     /// Creates a new simulation builder.
     pub(crate) fn new(vault: super::VaultClient) -> Self {
-        Self { vault, additions: vec![], removals: vec![] }
+        let consistency = vault.read_consistency();
+        Self { vault, additions: vec![], removals: vec![], schema: None, consistency }
     }
 
     /// Adds a hypothetical relationship.
@@ -105,6 +114,51 @@ impl SimulateBuilder {
         self
     }
 
+    /// Evaluates the simulation against a proposed schema.
+    ///
+    /// The schema (in IPL) is used in place of the vault's active schema for
+    /// the simulated side of every check, so a migration can be verified
+    /// before calling [`SchemasClient::activate`](crate::control::SchemasClient::activate).
+    /// The current side of a [`compare`](Self::compare) or
+    /// [`checks`](Self::checks) still uses the active schema.
+    ///
+    /// The gRPC API cannot carry a schema, so with
+    /// [`TransportStrategy::PreferGrpc`](crate::TransportStrategy::PreferGrpc)
+    /// schema simulations are sent over REST, and with
+    /// [`TransportStrategy::GrpcOnly`](crate::TransportStrategy::GrpcOnly)
+    /// they fail with an [`ErrorKind::InvalidArgument`](crate::ErrorKind::InvalidArgument)
+    /// error. The same applies to removals.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// let diffs = vault
+    ///     .simulate()
+    ///     .with_schema(include_str!("schema_v2.ipl"))
+    ///     .checks([
+    ///         ("user:admin", "manage", "organization:main"),
+    ///         ("user:alice", "edit", "document:important"),
+    ///     ])
+    ///     .await?;
+    ///
+    /// assert!(diffs.iter().all(|diff| !diff.has_change()));
+    /// ```
+    #[must_use]
+    pub fn with_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Sets how fresh the base state must be, for both the simulated side
+    /// and the current side of a comparison.
+    ///
+    /// Defaults to [`ConsistencyRequirement::Eventual`].
+    #[must_use]
+    pub fn consistency(mut self, consistency: ConsistencyRequirement) -> Self {
+        self.consistency = consistency;
+        self
+    }
+
     /// Performs a simulated permission check.
     ///
     /// Returns a `SimulateCheckBuilder` that can be awaited to get the result.
@@ -128,9 +182,13 @@ impl SimulateBuilder {
             vault: self.vault,
             additions: self.additions,
             removals: self.removals,
+            schema: self.schema,
+            consistency: self.consistency,
             subject: subject.into(),
             permission: permission.into(),
             resource: resource.into(),
+            context: None,
+            trace: TraceScope::default(),
         }
     }
 
@@ -164,11 +222,120 @@ impl SimulateBuilder {
             vault: self.vault,
             additions: self.additions,
             removals: self.removals,
+            schema: self.schema,
+            consistency: self.consistency,
+            subject: subject.into(),
+            permission: permission.into(),
+            resource: resource.into(),
+            context: None,
+            trace: TraceScope::default(),
+        }
+    }
+
+    /// Compares many checks against the same hypothetical changes.
+    ///
+    /// Every row is evaluated both against the current state and against
+    /// the simulated state, returning one [`SimulationDiff`] per row in the
+    /// same order. All rows share a single batched round trip for each side.
+    ///
+    /// Rows can be `(subject, permission, resource)` tuples or
+    /// [`SimulationCheck`] values carrying their own ABAC context.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// use inferadb::vault::SimulationCheck;
+    ///
+    /// let diffs = vault
+    ///     .simulate()
+    ///     .remove_relationship(Relationship::new("folder:eng", "viewer", "team:engineering"))
+    ///     .checks([
+    ///         SimulationCheck::new("user:alice", "view", "doc:design"),
+    ///         SimulationCheck::new("user:bob", "view", "doc:design")
+    ///             .with_context(Context::new().with("ip", "10.0.0.1")),
+    ///     ])
+    ///     .await?;
+    ///
+    /// for diff in diffs.iter().filter(|d| d.has_change()) {
+    ///     println!("{}", diff.summary());
+    /// }
+    /// ```
+    pub fn checks<I, C>(self, checks: I) -> SimulateMatrixBuilder
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<SimulationCheck>,
+    {
+        SimulateMatrixBuilder {
+            vault: self.vault,
+            additions: self.additions,
+            removals: self.removals,
+            schema: self.schema,
+            consistency: self.consistency,
+            checks: checks.into_iter().map(Into::into).collect(),
+            context: None,
+            trace: TraceScope::default(),
+        }
+    }
+}
+
+/// A single row of a simulation matrix.
+///
+/// ## Example
+///
+/// ```rust
+/// use inferadb::vault::SimulationCheck;
+/// use inferadb::Context;
+///
+/// let check = SimulationCheck::new("user:alice", "view", "doc:1")
+///     .with_context(Context::new().with("env", "prod"));
+///
+/// let from_tuple: SimulationCheck = ("user:bob", "edit", "doc:2").into();
+/// assert!(from_tuple.context.is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationCheck {
+    /// The subject to check.
+    pub subject: String,
+    /// The permission to check.
+    pub permission: String,
+    /// The resource to check.
+    pub resource: String,
+    /// Optional ABAC context for this row.
+    pub context: Option<Context>,
+}
+
+impl SimulationCheck {
+    /// Creates a new simulation row.
+    pub fn new(
+        subject: impl Into<String>,
+        permission: impl Into<String>,
+        resource: impl Into<String>,
+    ) -> Self {
+        Self {
             subject: subject.into(),
             permission: permission.into(),
             resource: resource.into(),
+            context: None,
         }
     }
+
+    /// Sets the ABAC context for this row.
+    #[must_use]
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.context = Some(context);
+        self
+    }
+}
+
+impl<S, P, R> From<(S, P, R)> for SimulationCheck
+where
+    S: Into<String>,
+    P: Into<String>,
+    R: Into<String>,
+{
+    fn from((subject, permission, resource): (S, P, R)) -> Self {
+        Self::new(subject, permission, resource)
+    }
 }
 
 /// Builder for simulated permission checks.
@@ -177,12 +344,33 @@ pub struct SimulateCheckBuilder {
     vault: super::VaultClient,
     additions: Vec<Relationship<'static>>,
     removals: Vec<Relationship<'static>>,
+    #[cfg_attr(not(feature = "rest"), allow(dead_code))]
+    schema: Option<String>,
+    #[cfg_attr(not(feature = "rest"), allow(dead_code))]
+    consistency: ConsistencyRequirement,
     subject: String,
     permission: String,
     resource: String,
+    context: Option<Context>,
+    trace: TraceScope,
 }
 
 impl SimulateCheckBuilder {
+    /// Sets the ABAC context for the simulated check.
+    #[must_use]
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.vault.client(), context);
+        self
+    }
+
     async fn execute(self) -> Result<SimulationResult, Error> {
         #[cfg(feature = "rest")]
         if let Some(transport) = self.vault.transport() {
@@ -192,9 +380,11 @@ impl SimulateCheckBuilder {
                 subject: self.subject.clone(),
                 permission: self.permission.clone(),
                 resource: self.resource.clone(),
-                context: None,
+                context: self.context.clone(),
                 additions: self.additions.clone(),
                 removals: self.removals.clone(),
+                schema: self.schema.clone(),
+                consistency: self.consistency.clone(),
             };
 
             let response = transport.simulate(request).await?;
@@ -233,7 +423,7 @@ impl std::future::IntoFuture for SimulateCheckBuilder {
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -243,70 +433,90 @@ pub struct SimulateCompareBuilder {
     vault: super::VaultClient,
     additions: Vec<Relationship<'static>>,
     removals: Vec<Relationship<'static>>,
+    #[cfg_attr(not(feature = "rest"), allow(dead_code))]
+    schema: Option<String>,
+    #[cfg_attr(not(feature = "rest"), allow(dead_code))]
+    consistency: ConsistencyRequirement,
     subject: String,
     permission: String,
     resource: String,
+    context: Option<Context>,
+    trace: TraceScope,
 }
 
 impl SimulateCompareBuilder {
+    /// Sets the ABAC context used for both the current and simulated checks.
+    #[must_use]
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.vault.client(), context);
+        self
+    }
+
     async fn execute(self) -> Result<SimulationDiff, Error> {
         #[cfg(feature = "rest")]
         if let Some(transport) = self.vault.transport() {
             use crate::transport::{TransportCheckRequest, TransportSimulateRequest};
 
-            // Run current check (without hypothetical changes)
+            // Current check (without hypothetical changes)
             let current_request = TransportCheckRequest {
                 subject: self.subject.clone(),
                 permission: self.permission.clone(),
                 resource: self.resource.clone(),
-                context: None,
-                consistency: None,
+                context: self.context.clone(),
+                consistency: self.consistency.clone(),
                 trace: false,
             };
-            let current_response = transport.check(current_request).await?;
-            let current_allowed = current_response.allowed;
 
-            // Run simulated check (with hypothetical changes)
+            // Simulated check (with hypothetical changes)
             let simulate_request = TransportSimulateRequest {
                 subject: self.subject.clone(),
                 permission: self.permission.clone(),
                 resource: self.resource.clone(),
-                context: None,
+                context: self.context.clone(),
                 additions: self.additions.clone(),
                 removals: self.removals.clone(),
+                schema: self.schema.clone(),
+                consistency: self.consistency.clone(),
             };
-            let simulated_response = transport.simulate(simulate_request).await?;
-            let simulated_allowed = simulated_response.allowed;
 
-            let change = match (current_allowed, simulated_allowed) {
-                (true, true) | (false, false) => SimulationChange::NoChange,
-                (false, true) => SimulationChange::NowAllowed,
-                (true, false) => SimulationChange::NowDenied,
-            };
+            // Both sides are independent, so issue them concurrently
+            let (current_response, simulated_response) = futures::future::try_join(
+                transport.check(current_request),
+                transport.simulate(simulate_request),
+            )
+            .await?;
 
-            return Ok(SimulationDiff {
-                subject: self.subject,
-                permission: self.permission,
-                resource: self.resource,
-                current_allowed,
-                simulated_allowed,
-                change,
-                hypothetical_additions: self.additions.iter().map(|r| r.to_string()).collect(),
-                hypothetical_removals: self.removals.iter().map(|r| r.to_string()).collect(),
-            });
+            return Ok(SimulationDiff::new(
+                self.subject,
+                self.permission,
+                self.resource,
+                self.context,
+                current_response.allowed,
+                simulated_response.allowed,
+                &self.additions,
+                &self.removals,
+            ));
         }
 
         // Fallback when transport is not available
-        Ok(SimulationDiff {
-            subject: self.subject,
-            permission: self.permission,
-            resource: self.resource,
-            current_allowed: false,
-            simulated_allowed: false,
-            change: SimulationChange::NoChange,
-            hypothetical_additions: self.additions.iter().map(|r| r.to_string()).collect(),
-            hypothetical_removals: self.removals.iter().map(|r| r.to_string()).collect(),
-        })
+        Ok(SimulationDiff::new(
+            self.subject,
+            self.permission,
+            self.resource,
+            self.context,
+            false,
+            false,
+            &self.additions,
+            &self.removals,
+        ))
     }
 }
 
@@ -320,7 +530,158 @@ impl std::future::IntoFuture for SimulateCompareBuilder {
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(self.execute())
+        Box::pin(self.trace.clone().run(self.execute()))
+    }
+}
+
+/// Builder for comparing many checks against the same simulated state.
+pub struct SimulateMatrixBuilder {
+    #[cfg_attr(not(feature = "rest"), allow(dead_code))]
+    vault: super::VaultClient,
+    additions: Vec<Relationship<'static>>,
+    removals: Vec<Relationship<'static>>,
+    #[cfg_attr(not(feature = "rest"), allow(dead_code))]
+    schema: Option<String>,
+    #[cfg_attr(not(feature = "rest"), allow(dead_code))]
+    consistency: ConsistencyRequirement,
+    checks: Vec<SimulationCheck>,
+    context: Option<Context>,
+    trace: TraceScope,
+}
+
+impl SimulateMatrixBuilder {
+    /// Sets a default ABAC context for rows that don't carry their own.
+    #[must_use]
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Parents this call's span to the caller's trace context.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = TraceScope::new(self.vault.client(), context);
+        self
+    }
+
+    async fn execute(self) -> Result<Vec<SimulationDiff>, Error> {
+        let default_context = self.context;
+        let checks: Vec<SimulationCheck> = self
+            .checks
+            .into_iter()
+            .map(|mut check| {
+                if check.context.is_none() {
+                    check.context = default_context.clone();
+                }
+                check
+            })
+            .collect();
+
+        if checks.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        #[cfg(feature = "rest")]
+        if let Some(transport) = self.vault.transport() {
+            use crate::transport::{
+                TransportCheckRequest, TransportSimulateBatchRequest, TransportSimulateCheck,
+            };
+
+            let current_requests = checks
+                .iter()
+                .map(|check| TransportCheckRequest {
+                    subject: check.subject.clone(),
+                    permission: check.permission.clone(),
+                    resource: check.resource.clone(),
+                    context: check.context.clone(),
+                    consistency: self.consistency.clone(),
+                    trace: false,
+                })
+                .collect();
+
+            let simulate_request = TransportSimulateBatchRequest {
+                checks: checks
+                    .iter()
+                    .map(|check| TransportSimulateCheck {
+                        subject: check.subject.clone(),
+                        permission: check.permission.clone(),
+                        resource: check.resource.clone(),
+                        context: check.context.clone(),
+                    })
+                    .collect(),
+                additions: self.additions.clone(),
+                removals: self.removals.clone(),
+                schema: self.schema.clone(),
+                consistency: self.consistency.clone(),
+            };
+
+            let (current, simulated) = futures::future::try_join(
+                transport.check_batch(current_requests),
+                transport.simulate_batch(simulate_request),
+            )
+            .await?;
+
+            if current.len() != checks.len() || simulated.len() != checks.len() {
+                return Err(Error::new(
+                    crate::ErrorKind::InvalidResponse,
+                    format!(
+                        "expected {} simulation results, got {} current and {} simulated",
+                        checks.len(),
+                        current.len(),
+                        simulated.len()
+                    ),
+                ));
+            }
+
+            return Ok(checks
+                .into_iter()
+                .zip(current.into_iter().zip(simulated))
+                .map(|(check, (current, simulated))| {
+                    SimulationDiff::new(
+                        check.subject,
+                        check.permission,
+                        check.resource,
+                        check.context,
+                        current.allowed,
+                        simulated.allowed,
+                        &self.additions,
+                        &self.removals,
+                    )
+                })
+                .collect());
+        }
+
+        // Fallback when transport is not available
+        Ok(checks
+            .into_iter()
+            .map(|check| {
+                SimulationDiff::new(
+                    check.subject,
+                    check.permission,
+                    check.resource,
+                    check.context,
+                    false,
+                    false,
+                    &self.additions,
+                    &self.removals,
+                )
+            })
+            .collect())
+    }
+}
+
+/// Enables ergonomic `.await` without explicit `.build()`.
+///
+/// This `IntoFuture` implementation is intentionally manual (not derived via `bon`)
+/// to preserve the ergonomic async API: `vault.simulate()...checks(...).await`
+/// instead of `vault.simulate()...checks(...).build().await`.
+impl std::future::IntoFuture for SimulateMatrixBuilder {
+    type Output = Result<Vec<SimulationDiff>, Error>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.trace.clone().run(self.execute()))
     }
 }
 
@@ -420,9 +781,37 @@ pub struct SimulationDiff {
     pub hypothetical_additions: Vec<String>,
     /// Relationships that were hypothetically removed (as strings).
     pub hypothetical_removals: Vec<String>,
+    /// ABAC context the check was evaluated with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<Context>,
 }
 
 impl SimulationDiff {
+    /// Builds a diff from the current and simulated decisions.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        subject: String,
+        permission: String,
+        resource: String,
+        context: Option<Context>,
+        current_allowed: bool,
+        simulated_allowed: bool,
+        additions: &[Relationship<'static>],
+        removals: &[Relationship<'static>],
+    ) -> Self {
+        Self {
+            subject,
+            permission,
+            resource,
+            current_allowed,
+            simulated_allowed,
+            change: SimulationChange::between(current_allowed, simulated_allowed),
+            hypothetical_additions: additions.iter().map(|r| r.to_string()).collect(),
+            hypothetical_removals: removals.iter().map(|r| r.to_string()).collect(),
+            context,
+        }
+    }
+
     /// Returns `true` if the simulation would result in a change.
     pub fn has_change(&self) -> bool {
         !matches!(self.change, SimulationChange::NoChange)
@@ -491,6 +880,17 @@ pub enum SimulationChange {
     NowDenied,
 }
 
+impl SimulationChange {
+    /// Classifies the change between a current and a simulated decision.
+    pub fn between(current_allowed: bool, simulated_allowed: bool) -> Self {
+        match (current_allowed, simulated_allowed) {
+            (true, true) | (false, false) => SimulationChange::NoChange,
+            (false, true) => SimulationChange::NowAllowed,
+            (true, false) => SimulationChange::NowDenied,
+        }
+    }
+}
+
 impl std::fmt::Display for SimulationChange {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -570,6 +970,7 @@ mod tests {
             change: SimulationChange::NoChange,
             hypothetical_additions: vec![],
             hypothetical_removals: vec![],
+            context: None,
         };
         assert!(!no_change.has_change());
 
@@ -582,6 +983,7 @@ mod tests {
             change: SimulationChange::NowAllowed,
             hypothetical_additions: vec![],
             hypothetical_removals: vec![],
+            context: None,
         };
         assert!(now_allowed.has_change());
     }
@@ -597,6 +999,7 @@ mod tests {
             change: SimulationChange::NowAllowed,
             hypothetical_additions: vec!["doc:1#editor@user:alice".to_string()],
             hypothetical_removals: vec![],
+            context: None,
         };
 
         let summary = diff.summary();
@@ -700,6 +1103,7 @@ mod tests {
             change: SimulationChange::NowDenied,
             hypothetical_additions: vec![],
             hypothetical_removals: vec!["doc:1#editor@user:alice".to_string()],
+            context: None,
         };
 
         assert!(diff.has_change());
@@ -718,6 +1122,7 @@ mod tests {
             change: SimulationChange::NowAllowed,
             hypothetical_additions: vec!["doc:1#viewer@user:alice".to_string()],
             hypothetical_removals: vec![],
+            context: None,
         };
 
         let display = format!("{}", diff);
@@ -738,6 +1143,7 @@ mod tests {
             change: SimulationChange::NowDenied,
             hypothetical_additions: vec![],
             hypothetical_removals: vec!["doc:1#editor@user:alice".to_string()],
+            context: None,
         };
 
         let display = format!("{}", diff);
@@ -776,6 +1182,7 @@ mod tests {
             change: SimulationChange::NowAllowed,
             hypothetical_additions: vec![],
             hypothetical_removals: vec![],
+            context: None,
         };
 
         let json = serde_json::to_string(&diff).unwrap();
@@ -1029,6 +1436,100 @@ mod tests {
         assert!(diff.simulated_allowed);
     }
 
+    #[cfg(feature = "rest")]
+    #[tokio::test]
+    async fn test_simulate_checks_matrix() {
+        let mock = MockTransport::new();
+        mock.add_relationship(Relationship::new("doc:1", "viewer", "user:alice").into_owned());
+        let mock_transport = Arc::new(mock.into_any());
+
+        let client = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .build_with_transport(mock_transport)
+            .await
+            .unwrap();
+
+        let vault = client.organization("org_test").vault("vlt_test");
+        let context = Context::new().with("env", "prod");
+        let diffs = vault
+            .simulate()
+            .remove_relationship(Relationship::new("doc:1", "viewer", "user:alice"))
+            .add_relationship(Relationship::new("doc:1", "viewer", "user:bob"))
+            .checks([
+                SimulationCheck::new("user:alice", "viewer", "doc:1"),
+                SimulationCheck::new("user:bob", "viewer", "doc:1").with_context(context.clone()),
+                ("user:carol", "viewer", "doc:1").into(),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(diffs.len(), 3);
+        assert_eq!(diffs[0].subject, "user:alice");
+        assert_eq!(diffs[0].change, SimulationChange::NowDenied);
+        assert_eq!(diffs[1].change, SimulationChange::NowAllowed);
+        assert_eq!(diffs[1].context, Some(context));
+        assert_eq!(diffs[2].change, SimulationChange::NoChange);
+        assert!(diffs.iter().all(|d| d.hypothetical_removals.len() == 1));
+    }
+
+    #[cfg(feature = "rest")]
+    #[tokio::test]
+    async fn test_simulate_checks_default_context() {
+        let mock_transport = Arc::new(MockTransport::new().into_any());
+        let client = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .build_with_transport(mock_transport)
+            .await
+            .unwrap();
+
+        let vault = client.organization("org_test").vault("vlt_test");
+        let default_context = Context::new().with("env", "prod");
+        let row_context = Context::new().with("env", "dev");
+        let diffs = vault
+            .simulate()
+            .checks([
+                SimulationCheck::new("user:alice", "view", "doc:1"),
+                SimulationCheck::new("user:bob", "view", "doc:1").with_context(row_context.clone()),
+            ])
+            .with_context(default_context.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(diffs[0].context, Some(default_context));
+        assert_eq!(diffs[1].context, Some(row_context));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_checks_empty() {
+        let mock_transport = Arc::new(MockTransport::new().into_any());
+        let client = Client::builder()
+            .url("https://api.example.com")
+            .credentials(BearerCredentialsConfig::new("test"))
+            .build_with_transport(mock_transport)
+            .await
+            .unwrap();
+
+        let vault = client.organization("org_test").vault("vlt_test");
+        let diffs = vault
+            .simulate()
+            .with_schema("type user {}")
+            .checks(Vec::<SimulationCheck>::new())
+            .await
+            .unwrap();
+
+        assert!(diffs.is_empty());
+    }
+
+    #[test]
+    fn test_simulation_change_between() {
+        assert_eq!(SimulationChange::between(true, true), SimulationChange::NoChange);
+        assert_eq!(SimulationChange::between(false, false), SimulationChange::NoChange);
+        assert_eq!(SimulationChange::between(false, true), SimulationChange::NowAllowed);
+        assert_eq!(SimulationChange::between(true, false), SimulationChange::NowDenied);
+    }
+
     #[test]
     fn test_simulation_diff_summary_no_change_allowed() {
         // Test NoChange summary when current_allowed is true
@@ -1041,6 +1542,7 @@ mod tests {
             change: SimulationChange::NoChange,
             hypothetical_additions: vec![],
             hypothetical_removals: vec![],
+            context: None,
         };
 
         let summary = diff.summary();
@@ -1061,6 +1563,7 @@ mod tests {
             change: SimulationChange::NoChange,
             hypothetical_additions: vec![],
             hypothetical_removals: vec![],
+            context: None,
         };
 
         let summary = diff.summary();
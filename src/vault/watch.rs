@@ -39,8 +39,10 @@ use std::{fmt, pin::Pin, time::Duration};
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 
-#[cfg(not(feature = "rest"))]
+#[cfg(not(any(feature = "grpc", feature = "rest")))]
 use crate::ErrorKind;
+#[cfg(any(feature = "grpc", feature = "rest"))]
+use crate::transport::{AnyTransport, WatchRequest};
 use crate::{Error, Relationship, client::Client, vault::VaultClient};
 
 /// Type alias for the inner watch stream to reduce complexity.
@@ -453,8 +455,7 @@ impl ReconnectConfig {
 ///     .await?;
 /// ```
 pub struct WatchBuilder {
-    // Client is stored for when SSE transport is fully implemented
-    #[allow(dead_code)]
+    #[cfg_attr(not(any(feature = "grpc", feature = "rest")), allow(dead_code))]
     client: Client,
     organization_id: String,
     vault_id: String,
@@ -590,8 +591,14 @@ impl WatchBuilder {
 
     /// Start the watch stream.
     ///
-    /// Returns a stream of watch events. The stream continues until
-    /// cancelled or an unrecoverable error occurs.
+    /// Returns a stream of watch events once the first connection is
+    /// established. The stream continues until cancelled or an unrecoverable
+    /// error occurs. In resumable mode, dropped connections are reopened from
+    /// the last seen revision, so no change is missed or repeated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the first connection cannot be established.
     ///
     /// ## Example
     ///
@@ -605,52 +612,118 @@ impl WatchBuilder {
     ///     println!("Change: {}", event);
     /// }
     /// ```
-    #[cfg(feature = "rest")]
+    #[cfg(any(feature = "grpc", feature = "rest"))]
     pub async fn run(self) -> Result<WatchStream, Error> {
-        // Build query parameters for the watch endpoint
-        let mut query_params = Vec::new();
-
-        if let Some(rev) = self.from_revision {
-            query_params.push(format!("from_revision={}", rev));
-        }
-
-        // Add filters to query params
-        for filter in &self.filters {
-            match filter {
-                WatchFilter::ResourceType(t) => query_params.push(format!("resource_type={}", t)),
-                WatchFilter::SubjectType(t) => query_params.push(format!("subject_type={}", t)),
-                WatchFilter::Resource(r) => query_params.push(format!("resource={}", r)),
-                WatchFilter::Subject(s) => query_params.push(format!("subject={}", s)),
-                WatchFilter::Relation(r) => query_params.push(format!("relation={}", r)),
-                WatchFilter::Operations(ops) => {
-                    for op in ops {
-                        query_params.push(format!("operation={}", op));
-                    }
-                },
-                WatchFilter::Custom(expr) => query_params.push(format!("filter={}", expr)),
-            }
-        }
+        let Some(transport) = self.client.transport().cloned() else {
+            return Ok(WatchStream::new(self.filters, self.from_revision));
+        };
 
-        let _path = format!(
-            "/v1/organizations/{}/vaults/{}/watch{}",
-            self.organization_id,
-            self.vault_id,
-            if query_params.is_empty() {
-                String::new()
-            } else {
-                format!("?{}", query_params.join("&"))
-            }
+        let request = WatchRequest {
+            organization_id: self.organization_id,
+            vault_id: self.vault_id,
+            filters: self.filters.clone(),
+            from_revision: self.from_revision,
+        };
+        let connect = transport.watch(request.clone());
+        #[cfg(feature = "tracing")]
+        let connect = tracing::Instrument::instrument(
+            connect,
+            crate::tracing_support::engine_span("Watch", transport.transport_type()),
         );
+        let stream = connect.await?;
+
+        let connection = Connection {
+            transport,
+            request,
+            reconnect: self.resumable.then(|| self.reconnect_config.unwrap_or_default()),
+            stream: Some(stream),
+            attempts: 0,
+            done: false,
+        };
+        let events = futures::stream::unfold(connection, |mut connection| async move {
+            connection.next().await.map(|event| (event, connection))
+        });
 
-        // For now, return a placeholder stream since SSE/WebSocket transport
-        // requires additional implementation
-        Ok(WatchStream::new(self.filters, self.from_revision))
+        let mut stream = WatchStream::new(self.filters, self.from_revision);
+        stream.inner = Some(Box::pin(events));
+        Ok(stream)
     }
 
     /// Start the watch stream.
-    #[cfg(not(feature = "rest"))]
+    #[cfg(not(any(feature = "grpc", feature = "rest")))]
     pub async fn run(self) -> Result<WatchStream, Error> {
-        Err(Error::new(ErrorKind::Configuration, "REST feature is required for watch streams"))
+        Err(Error::new(
+            ErrorKind::Configuration,
+            "a transport feature is required for watch streams",
+        ))
+    }
+}
+
+/// One watch subscription, reopened from the last seen revision when its
+/// connection drops.
+#[cfg(any(feature = "grpc", feature = "rest"))]
+struct Connection {
+    transport: std::sync::Arc<AnyTransport>,
+    request: WatchRequest,
+    /// Reconnection settings; `None` unless resumable.
+    reconnect: Option<ReconnectConfig>,
+    stream: Option<InnerWatchStream>,
+    /// Reconnection attempts since the last event.
+    attempts: u32,
+    done: bool,
+}
+
+#[cfg(any(feature = "grpc", feature = "rest"))]
+impl Connection {
+    /// Returns the next event, reconnecting as configured.
+    async fn next(&mut self) -> Option<Result<WatchEvent, Error>> {
+        use futures::StreamExt;
+
+        while !self.done {
+            let error = match &mut self.stream {
+                Some(stream) => match stream.next().await {
+                    Some(Ok(event)) => {
+                        self.request.from_revision = Some(event.revision);
+                        self.attempts = 0;
+                        return Some(Ok(event));
+                    },
+                    Some(Err(error)) => error,
+                    None if self.reconnect.is_some() => {
+                        Error::connection("watch stream closed by the server")
+                    },
+                    None => return None,
+                },
+                None => match self.transport.watch(self.request.clone()).await {
+                    Ok(stream) => {
+                        self.stream = Some(stream);
+                        continue;
+                    },
+                    Err(error) => error,
+                },
+            };
+            self.stream = None;
+
+            match self.backoff(&error) {
+                Some(backoff) => tokio::time::sleep(backoff).await,
+                None => {
+                    self.done = true;
+                    return Some(Err(error));
+                },
+            }
+        }
+        None
+    }
+
+    /// Returns how long to wait before reconnecting after `error`, or `None`
+    /// if the stream should end.
+    fn backoff(&mut self, error: &Error) -> Option<Duration> {
+        let config = self.reconnect.as_ref()?;
+        if !error.is_retriable() || config.max_retries.is_some_and(|max| self.attempts >= max) {
+            return None;
+        }
+        let backoff = config.backoff_for_attempt(self.attempts);
+        self.attempts += 1;
+        Some(error.retry_after().unwrap_or(backoff))
     }
 }
 
@@ -674,7 +747,6 @@ pub struct WatchShutdownHandle {
 
 impl WatchShutdownHandle {
     /// Create a new shutdown handle.
-    #[allow(dead_code)] // Used only when transports are enabled
     fn new() -> (Self, tokio::sync::watch::Receiver<bool>) {
         let (sender, receiver) = tokio::sync::watch::channel(false);
         (Self { sender }, receiver)
@@ -701,12 +773,11 @@ pub struct WatchStream {
     last_revision: Option<u64>,
     shutdown_receiver: tokio::sync::watch::Receiver<bool>,
     shutdown_handle: WatchShutdownHandle,
-    // Internal stream state
+    // Events from the server; `None` when there is no transport to watch
     inner: Option<InnerWatchStream>,
 }
 
 impl WatchStream {
-    #[allow(dead_code)] // Used only when transports are enabled
     fn new(filters: Vec<WatchFilter>, from_revision: Option<u64>) -> Self {
         let (shutdown_handle, shutdown_receiver) = WatchShutdownHandle::new();
 
@@ -749,15 +820,6 @@ impl Stream for WatchStream {
             return std::task::Poll::Ready(None);
         }
 
-        // If we don't have an inner stream, create one (placeholder for real SSE implementation)
-        if self.inner.is_none() {
-            // Create a placeholder stream that returns nothing
-            // Real implementation would connect to SSE/WebSocket endpoint
-            let stream: Pin<Box<dyn Stream<Item = Result<WatchEvent, Error>> + Send>> =
-                Box::pin(futures::stream::empty());
-            self.inner = Some(stream);
-        }
-
         // Poll the inner stream
         if let Some(ref mut inner) = self.inner {
             match inner.as_mut().poll_next(cx) {
@@ -1373,3 +1435,113 @@ mod tests {
         assert!(builder.is_resumable());
     }
 }
+
+#[cfg(all(test, feature = "rest"))]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod wiremock_tests {
+    use futures::StreamExt;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path, query_param},
+    };
+
+    use super::*;
+    use crate::{Client, auth::BearerCredentialsConfig};
+
+    const WATCH_PATH: &str = "/v1/organizations/org/vaults/vault/watch";
+
+    fn events(revisions: impl IntoIterator<Item = u64>) -> ResponseTemplate {
+        let body: String = revisions
+            .into_iter()
+            .map(|revision| {
+                let relationship =
+                    Relationship::new(format!("document:{}", revision), "viewer", "user:alice");
+                let event =
+                    WatchEvent::new(Operation::Create, relationship, revision, chrono::Utc::now());
+                format!("data: {}\n\n", serde_json::to_string(&event).unwrap())
+            })
+            .collect();
+        ResponseTemplate::new(200)
+            .insert_header("content-type", "text/event-stream")
+            .set_body_string(body)
+    }
+
+    async fn client(server: &MockServer) -> Client {
+        Client::builder()
+            .url(server.uri())
+            .insecure()
+            .credentials(BearerCredentialsConfig::new("test"))
+            .transport_strategy(crate::TransportStrategy::RestOnly)
+            .build()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_resumable_watch_reconnects_from_last_revision() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(WATCH_PATH))
+            .and(query_param("from_revision", "0"))
+            .respond_with(events([1, 2]))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(WATCH_PATH))
+            .and(query_param("from_revision", "2"))
+            .respond_with(events([3]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let vault = client(&server).await.organization("org").vault("vault");
+        let mut stream = vault
+            .watch()
+            .from_revision(0)
+            .reconnect(ReconnectConfig::builder().initial_backoff(Duration::from_millis(1)).build())
+            .run()
+            .await
+            .unwrap();
+
+        let mut revisions = Vec::new();
+        for _ in 0..3 {
+            revisions.push(stream.next().await.unwrap().unwrap().revision);
+        }
+        assert_eq!(revisions, vec![1, 2, 3]);
+        assert_eq!(stream.last_revision(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_watch_without_reconnect_ends_with_connection() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(WATCH_PATH))
+            .and(query_param("resource_type", "document"))
+            .respond_with(events([1, 2]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let vault = client(&server).await.organization("org").vault("vault");
+        let stream =
+            vault.watch().filter(WatchFilter::resource_type("document")).run().await.unwrap();
+        let events: Vec<_> = stream.collect().await;
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(Result::is_ok));
+    }
+
+    #[tokio::test]
+    async fn test_watch_reports_rejected_connection() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(WATCH_PATH))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let vault = client(&server).await.organization("org").vault("vault");
+        let err = vault.watch().resumable().run().await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Forbidden);
+    }
+}